@@ -0,0 +1,36 @@
+/* C interface of the `capi` feature, see src/capi.rs. */
+#ifndef RUSTCZT_H
+#define RUSTCZT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Layout-compatible with `double _Complex` and `std::complex<double>`. */
+typedef struct {
+    double re;
+    double im;
+} rustczt_complex;
+
+typedef struct RustCztPlan RustCztPlan;
+
+#define RUSTCZT_OK 0
+#define RUSTCZT_NULL_POINTER (-1)
+#define RUSTCZT_BAD_LENGTH (-2)
+#define RUSTCZT_INVALID_STATE (-3)
+
+RustCztPlan *rustczt_plan_create(size_t n, size_t m, rustczt_complex a, rustczt_complex w);
+size_t rustczt_plan_buffer_len(const RustCztPlan *plan);
+size_t rustczt_plan_scratch_len(const RustCztPlan *plan);
+int32_t rustczt_plan_process(const RustCztPlan *plan, rustczt_complex *buffer, size_t buffer_len,
+                             rustczt_complex *scratch, size_t scratch_len);
+void rustczt_plan_destroy(RustCztPlan *plan);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
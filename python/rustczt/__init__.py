@@ -0,0 +1,10 @@
+"""Chirp Z-transforms and zoom FFTs on NumPy arrays, computed by the rustczt crate.
+
+``czt`` and ``ZoomFFT`` take the arguments of ``scipy.signal.czt`` and ``scipy.signal.ZoomFFT``;
+``CztPlanner`` caches plans for repeated transforms. All of them release the GIL while
+transforming.
+"""
+
+from ._rustczt import Czt, CztPlanner, ZoomFFT, czt
+
+__all__ = ["Czt", "CztPlanner", "ZoomFFT", "czt"]
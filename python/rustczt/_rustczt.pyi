@@ -0,0 +1,38 @@
+from typing import Optional, Tuple, Union
+
+import numpy as np
+import numpy.typing as npt
+
+def czt(
+    x: npt.NDArray[np.complex128],
+    m: Optional[int] = None,
+    w: Optional[complex] = None,
+    a: Optional[complex] = None,
+) -> npt.NDArray[np.complex128]: ...
+
+class ZoomFFT:
+    def __init__(
+        self,
+        n: int,
+        fn: Union[float, Tuple[float, float]],
+        m: Optional[int] = None,
+        fs: float = 2.0,
+        endpoint: bool = False,
+    ) -> None: ...
+    def __call__(self, x: npt.NDArray[np.complex128]) -> npt.NDArray[np.complex128]: ...
+    def points(self) -> npt.NDArray[np.complex128]: ...
+    def freqs(self) -> npt.NDArray[np.float64]: ...
+
+class Czt:
+    @property
+    def input_len(self) -> int: ...
+    @property
+    def output_len(self) -> int: ...
+    def process(self, x: npt.NDArray[np.complex128]) -> npt.NDArray[np.complex128]: ...
+
+class CztPlanner:
+    def __init__(self) -> None: ...
+    def plan_czt_forward(self, n: int, m: int, a: complex, w: complex) -> Czt: ...
+    def plan_zoom_fft_hz(
+        self, n: int, m: int, sample_rate: float, f_start: float, f_end: float
+    ) -> Czt: ...
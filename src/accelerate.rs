@@ -0,0 +1,532 @@
+use std::{
+    os::raw::{c_int, c_long, c_ulong, c_void},
+    sync::Arc,
+};
+
+use crate::fft::{
+    num_complex::Complex, num_traits::Float, Direction, Fft, FftDirection, FftNum, Length,
+};
+use rustfft::FftPlanner;
+
+use crate::{bluesteins::BluesteinsAlgorithm, ConvolutionLength, Czt, CztParams, FftProvider};
+
+type VdspLength = c_ulong;
+type VdspStride = c_long;
+type FftSetup = *mut c_void;
+
+const FFT_RADIX2: c_int = 0;
+const FFT_DIRECTION_FORWARD: c_int = 1;
+const FFT_DIRECTION_INVERSE: c_int = -1;
+
+/// `DSPSplitComplex` / `DSPDoubleSplitComplex`.
+#[repr(C)]
+pub struct SplitComplex<T> {
+    realp: *mut T,
+    imagp: *mut T,
+}
+
+#[link(name = "Accelerate", kind = "framework")]
+extern "C" {
+    fn vDSP_create_fftsetup(log2n: VdspLength, radix: c_int) -> FftSetup;
+    fn vDSP_destroy_fftsetup(setup: FftSetup);
+    fn vDSP_fft_zip(
+        setup: FftSetup,
+        c: *const SplitComplex<f32>,
+        stride: VdspStride,
+        log2n: VdspLength,
+        direction: c_int,
+    );
+    fn vDSP_ctoz(
+        c: *const Complex<f32>,
+        ic: VdspStride,
+        z: *const SplitComplex<f32>,
+        iz: VdspStride,
+        n: VdspLength,
+    );
+    fn vDSP_ztoc(
+        z: *const SplitComplex<f32>,
+        iz: VdspStride,
+        c: *mut Complex<f32>,
+        ic: VdspStride,
+        n: VdspLength,
+    );
+    fn vDSP_zvmul(
+        a: *const SplitComplex<f32>,
+        ia: VdspStride,
+        b: *const SplitComplex<f32>,
+        ib: VdspStride,
+        c: *const SplitComplex<f32>,
+        ic: VdspStride,
+        n: VdspLength,
+        conjugate: c_int,
+    );
+
+    fn vDSP_create_fftsetupD(log2n: VdspLength, radix: c_int) -> FftSetup;
+    fn vDSP_destroy_fftsetupD(setup: FftSetup);
+    fn vDSP_fft_zipD(
+        setup: FftSetup,
+        c: *const SplitComplex<f64>,
+        stride: VdspStride,
+        log2n: VdspLength,
+        direction: c_int,
+    );
+    fn vDSP_ctozD(
+        c: *const Complex<f64>,
+        ic: VdspStride,
+        z: *const SplitComplex<f64>,
+        iz: VdspStride,
+        n: VdspLength,
+    );
+    fn vDSP_ztocD(
+        z: *const SplitComplex<f64>,
+        iz: VdspStride,
+        c: *mut Complex<f64>,
+        ic: VdspStride,
+        n: VdspLength,
+    );
+    fn vDSP_zvmulD(
+        a: *const SplitComplex<f64>,
+        ia: VdspStride,
+        b: *const SplitComplex<f64>,
+        ib: VdspStride,
+        c: *const SplitComplex<f64>,
+        ic: VdspStride,
+        n: VdspLength,
+        conjugate: c_int,
+    );
+}
+
+mod sealed {
+    use std::os::raw::c_int;
+
+    use crate::fft::num_complex::Complex;
+
+    use super::{FftSetup, SplitComplex, VdspLength};
+
+    /// Raw vDSP entry points; every pointer must cover `n` (or `2^log2n`) valid elements.
+    pub trait VdspRoutines: Sized {
+        unsafe fn create_fftsetup(log2n: VdspLength) -> FftSetup;
+        unsafe fn destroy_fftsetup(setup: FftSetup);
+        unsafe fn fft_zip(
+            setup: FftSetup,
+            c: &SplitComplex<Self>,
+            log2n: VdspLength,
+            direction: c_int,
+        );
+        unsafe fn ctoz(c: *const Complex<Self>, z: &SplitComplex<Self>, n: VdspLength);
+        unsafe fn ztoc(z: &SplitComplex<Self>, c: *mut Complex<Self>, n: VdspLength);
+        unsafe fn zvmul(
+            a: &SplitComplex<Self>,
+            b: &SplitComplex<Self>,
+            c: &SplitComplex<Self>,
+            n: VdspLength,
+        );
+    }
+}
+
+/// Floating point types with vDSP entry points.
+pub trait VdspFloat: FftNum + sealed::VdspRoutines {}
+
+impl VdspFloat for f32 {}
+
+impl VdspFloat for f64 {}
+
+impl sealed::VdspRoutines for f32 {
+    unsafe fn create_fftsetup(log2n: VdspLength) -> FftSetup {
+        vDSP_create_fftsetup(log2n, FFT_RADIX2)
+    }
+    unsafe fn destroy_fftsetup(setup: FftSetup) {
+        vDSP_destroy_fftsetup(setup)
+    }
+    unsafe fn fft_zip(
+        setup: FftSetup,
+        c: &SplitComplex<Self>,
+        log2n: VdspLength,
+        direction: c_int,
+    ) {
+        vDSP_fft_zip(setup, c, 1, log2n, direction)
+    }
+    unsafe fn ctoz(c: *const Complex<Self>, z: &SplitComplex<Self>, n: VdspLength) {
+        vDSP_ctoz(c, 2, z, 1, n)
+    }
+    unsafe fn ztoc(z: &SplitComplex<Self>, c: *mut Complex<Self>, n: VdspLength) {
+        vDSP_ztoc(z, 1, c, 2, n)
+    }
+    unsafe fn zvmul(
+        a: &SplitComplex<Self>,
+        b: &SplitComplex<Self>,
+        c: &SplitComplex<Self>,
+        n: VdspLength,
+    ) {
+        vDSP_zvmul(a, 1, b, 1, c, 1, n, 1)
+    }
+}
+
+impl sealed::VdspRoutines for f64 {
+    unsafe fn create_fftsetup(log2n: VdspLength) -> FftSetup {
+        vDSP_create_fftsetupD(log2n, FFT_RADIX2)
+    }
+    unsafe fn destroy_fftsetup(setup: FftSetup) {
+        vDSP_destroy_fftsetupD(setup)
+    }
+    unsafe fn fft_zip(
+        setup: FftSetup,
+        c: &SplitComplex<Self>,
+        log2n: VdspLength,
+        direction: c_int,
+    ) {
+        vDSP_fft_zipD(setup, c, 1, log2n, direction)
+    }
+    unsafe fn ctoz(c: *const Complex<Self>, z: &SplitComplex<Self>, n: VdspLength) {
+        vDSP_ctozD(c, 2, z, 1, n)
+    }
+    unsafe fn ztoc(z: &SplitComplex<Self>, c: *mut Complex<Self>, n: VdspLength) {
+        vDSP_ztocD(z, 1, c, 2, n)
+    }
+    unsafe fn zvmul(
+        a: &SplitComplex<Self>,
+        b: &SplitComplex<Self>,
+        c: &SplitComplex<Self>,
+        n: VdspLength,
+    ) {
+        vDSP_zvmulD(a, 1, b, 1, c, 1, n, 1)
+    }
+}
+
+/// Views complex scratch as planar real and imaginary halves of `scratch.len()` reals each.
+fn planar<T: FftNum>(scratch: &mut [Complex<T>]) -> (&mut [T], &mut [T]) {
+    let len = scratch.len();
+    // Complex<T> is repr(C), so the scratch holds 2 * len contiguous reals.
+    let reals = unsafe { std::slice::from_raw_parts_mut(scratch.as_mut_ptr() as *mut T, 2 * len) };
+    reals.split_at_mut(len)
+}
+
+/// A split-complex operand vDSP may write to.
+fn split_mut<T>(real: &mut [T], imag: &mut [T]) -> SplitComplex<T> {
+    SplitComplex {
+        realp: real.as_mut_ptr(),
+        imagp: imag.as_mut_ptr(),
+    }
+}
+
+/// A split-complex operand vDSP only reads. vDSP takes inputs and outputs through the same
+/// struct, so the pointers are `*mut` but must never be written through.
+fn split<T>(real: &[T], imag: &[T]) -> SplitComplex<T> {
+    SplitComplex {
+        realp: real.as_ptr() as *mut T,
+        imagp: imag.as_ptr() as *mut T,
+    }
+}
+
+/// Owns a radix-2 vDSP FFT setup.
+struct Setup<T: VdspFloat> {
+    setup: FftSetup,
+    log2n: VdspLength,
+    _marker: std::marker::PhantomData<T>,
+}
+
+// vDSP FFT setups are read-only after creation and may be shared between threads.
+unsafe impl<T: VdspFloat> Send for Setup<T> {}
+unsafe impl<T: VdspFloat> Sync for Setup<T> {}
+
+impl<T: VdspFloat> Setup<T> {
+    fn new(len: usize) -> Self {
+        assert!(len.is_power_of_two());
+        let log2n = len.trailing_zeros() as VdspLength;
+        let setup = unsafe { T::create_fftsetup(log2n) };
+        assert!(!setup.is_null(), "vDSP failed to create an FFT setup");
+
+        Self {
+            setup,
+            log2n,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn len(&self) -> usize {
+        1 << self.log2n
+    }
+
+    fn process(&self, data: &SplitComplex<T>, direction: FftDirection) {
+        let direction = match direction {
+            FftDirection::Forward => FFT_DIRECTION_FORWARD,
+            FftDirection::Inverse => FFT_DIRECTION_INVERSE,
+        };
+        unsafe { T::fft_zip(self.setup, data, self.log2n, direction) };
+    }
+}
+
+impl<T: VdspFloat> Drop for Setup<T> {
+    fn drop(&mut self) {
+        unsafe { T::destroy_fftsetup(self.setup) };
+    }
+}
+
+/// Power-of-two FFT executed by `vDSP_fft_zip` on split-complex data.
+pub struct AccelerateFft<T: VdspFloat> {
+    setup: Setup<T>,
+    direction: FftDirection,
+}
+
+impl<T: VdspFloat> AccelerateFft<T> {
+    pub fn new(len: usize, direction: FftDirection) -> Self {
+        Self {
+            setup: Setup::new(len),
+            direction,
+        }
+    }
+}
+
+impl<T: VdspFloat> Length for AccelerateFft<T> {
+    fn len(&self) -> usize {
+        self.setup.len()
+    }
+}
+
+impl<T: VdspFloat> Direction for AccelerateFft<T> {
+    fn fft_direction(&self) -> FftDirection {
+        self.direction
+    }
+}
+
+impl<T: VdspFloat> Fft<T> for AccelerateFft<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let len = self.len();
+        assert_eq!(buffer.len() % len, 0);
+        assert!(scratch.len() >= self.get_inplace_scratch_len());
+
+        let (real, imag) = planar(&mut scratch[..len]);
+        let data = split_mut(real, imag);
+        for chunk in buffer.chunks_exact_mut(len) {
+            unsafe { T::ctoz(chunk.as_ptr(), &data, len as VdspLength) };
+            self.setup.process(&data, self.direction);
+            unsafe { T::ztoc(&data, chunk.as_mut_ptr(), len as VdspLength) };
+        }
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.process_immutable_with_scratch(input, output, scratch);
+    }
+
+    fn process_immutable_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), output.len());
+        output.copy_from_slice(input);
+        self.process_with_scratch(output, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.len()
+    }
+
+    fn get_immutable_scratch_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Coefficient table in split-complex layout.
+struct SplitVec<T> {
+    real: Vec<T>,
+    imag: Vec<T>,
+}
+
+impl<T: FftNum> SplitVec<T> {
+    fn new(values: impl Iterator<Item = Complex<T>>) -> Self {
+        let (real, imag) = values.map(|c| (c.re, c.im)).unzip();
+        Self { real, imag }
+    }
+
+    fn as_split(&self) -> SplitComplex<T> {
+        split(&self.real, &self.imag)
+    }
+}
+
+/// Bluestein's algorithm kept in split-complex layout from input marshaling to output
+/// marshaling, so both the convolution FFTs and the chirp multiplies run through vDSP.
+pub struct AccelerateBluesteins<T: VdspFloat> {
+    y_coefficients: SplitVec<T>,
+    v_coefficients: SplitVec<T>,
+    x_coefficients: SplitVec<T>,
+    setup: Setup<T>,
+    params: CztParams<T>,
+}
+
+impl<T: VdspFloat + Float> AccelerateBluesteins<T> {
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        let reference = BluesteinsAlgorithm::new_segment(
+            n,
+            m,
+            a,
+            w,
+            0,
+            0,
+            ConvolutionLength::PowerOfTwo,
+            &mut FftPlanner::new(),
+        );
+        let (y, v, x) = (
+            reference.y_coefficients(),
+            reference.v_coefficients(),
+            reference.x_coefficients(),
+        );
+        let setup = Setup::new(v.len());
+
+        Self {
+            y_coefficients: SplitVec::new(y.iter().copied()),
+            v_coefficients: SplitVec::new(v.iter().copied()),
+            x_coefficients: SplitVec::new(x.iter().copied()),
+            setup,
+            params: reference.params(),
+        }
+    }
+}
+
+impl<T: VdspFloat> AccelerateBluesteins<T> {
+    fn n(&self) -> usize {
+        self.y_coefficients.real.len()
+    }
+
+    fn m(&self) -> usize {
+        self.x_coefficients.real.len()
+    }
+}
+
+impl<T: VdspFloat> Czt<T> for AccelerateBluesteins<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let (n, m) = (self.n(), self.m());
+        let (real, imag) = planar(scratch);
+        real[n..].fill(T::zero());
+        imag[n..].fill(T::zero());
+        let work = split_mut(real, imag);
+
+        unsafe {
+            T::ctoz(buffer.as_ptr(), &work, n as VdspLength);
+            T::zvmul(
+                &work,
+                &self.y_coefficients.as_split(),
+                &work,
+                n as VdspLength,
+            );
+        }
+        self.setup.process(&work, FftDirection::Forward);
+        unsafe {
+            T::zvmul(
+                &work,
+                &self.v_coefficients.as_split(),
+                &work,
+                self.setup.len() as VdspLength,
+            );
+        }
+        self.setup.process(&work, FftDirection::Inverse);
+        unsafe {
+            T::zvmul(
+                &work,
+                &self.x_coefficients.as_split(),
+                &work,
+                m as VdspLength,
+            );
+            T::ztoc(&work, buffer.as_mut_ptr(), m as VdspLength);
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.setup.len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        // The vDSP setup holds about one twiddle factor per point.
+        let tables = self.y_coefficients.real.len()
+            + self.v_coefficients.real.len()
+            + self.x_coefficients.real.len();
+        (tables + 2 * self.setup.len()) * std::mem::size_of::<Complex<T>>()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        self.params
+    }
+}
+
+/// Elementwise `buffer[i] *= coefficients[i]` through `vDSP_zvmul`.
+///
+/// `scratch` must hold at least `2 * buffer.len()` elements for the split-complex operands.
+pub fn complex_multiply<T: VdspFloat>(
+    buffer: &mut [Complex<T>],
+    coefficients: &[Complex<T>],
+    scratch: &mut [Complex<T>],
+) {
+    let len = buffer.len();
+    assert_eq!(coefficients.len(), len);
+    assert!(scratch.len() >= 2 * len);
+
+    let (lhs, rhs) = scratch[..2 * len].split_at_mut(len);
+    let (lhs_real, lhs_imag) = planar(lhs);
+    let (rhs_real, rhs_imag) = planar(rhs);
+    let lhs = split_mut(lhs_real, lhs_imag);
+    let rhs = split_mut(rhs_real, rhs_imag);
+    unsafe {
+        T::ctoz(buffer.as_ptr(), &lhs, len as VdspLength);
+        T::ctoz(coefficients.as_ptr(), &rhs, len as VdspLength);
+        T::zvmul(&lhs, &rhs, &lhs, len as VdspLength);
+        T::ztoc(&lhs, buffer.as_mut_ptr(), len as VdspLength);
+    }
+}
+
+/// Runs power-of-two convolution FFTs through vDSP and falls back to rustfft for other lengths.
+pub struct AccelerateProvider<T: VdspFloat> {
+    fallback: FftPlanner<T>,
+}
+
+impl<T: VdspFloat> AccelerateProvider<T> {
+    pub fn new() -> Self {
+        Self {
+            fallback: FftPlanner::new(),
+        }
+    }
+}
+
+impl<T: VdspFloat> Default for AccelerateProvider<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: VdspFloat> FftProvider<T> for AccelerateProvider<T> {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        if len.is_power_of_two() {
+            Arc::new(AccelerateFft::new(len, FftDirection::Forward))
+        } else {
+            self.fallback.plan_fft_forward(len)
+        }
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        if len.is_power_of_two() {
+            Arc::new(AccelerateFft::new(len, FftDirection::Inverse))
+        } else {
+            self.fallback.plan_fft_inverse(len)
+        }
+    }
+}
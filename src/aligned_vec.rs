@@ -0,0 +1,91 @@
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    vec::Vec,
+};
+use core::{
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// Alignment of coefficient tables, enough for any vector register in use.
+const ALIGNMENT: usize = 64;
+
+/// A fixed-length heap buffer whose first element is aligned to a cache line.
+pub struct AlignedVec<T: Copy> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+
+unsafe impl<T: Copy + Send> Send for AlignedVec<T> {}
+unsafe impl<T: Copy + Sync> Sync for AlignedVec<T> {}
+
+impl<T: Copy> AlignedVec<T> {
+    pub fn from_slice(values: &[T]) -> Self {
+        let len = values.len();
+        if len == 0 || core::mem::size_of::<T>() == 0 {
+            return Self {
+                ptr: NonNull::dangling(),
+                len,
+            };
+        }
+
+        let layout = Self::layout(len);
+        let ptr = unsafe { alloc(layout) } as *mut T;
+        let Some(ptr) = NonNull::new(ptr) else {
+            handle_alloc_error(layout);
+        };
+        unsafe { core::ptr::copy_nonoverlapping(values.as_ptr(), ptr.as_ptr(), len) };
+        Self { ptr, len }
+    }
+
+    fn layout(len: usize) -> Layout {
+        Layout::from_size_align(
+            len * core::mem::size_of::<T>(),
+            ALIGNMENT.max(core::mem::align_of::<T>()),
+        )
+        .unwrap()
+    }
+}
+
+impl<T: Copy> Drop for AlignedVec<T> {
+    fn drop(&mut self) {
+        if self.len != 0 && core::mem::size_of::<T>() != 0 {
+            unsafe { dealloc(self.ptr.as_ptr() as *mut u8, Self::layout(self.len)) };
+        }
+    }
+}
+
+impl<T: Copy> Clone for AlignedVec<T> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self)
+    }
+}
+
+impl<T: Copy> Deref for AlignedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy> DerefMut for AlignedVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy> FromIterator<T> for AlignedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_slice(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a AlignedVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::fft::num_complex::Complex;
+use astro_float::{BigFloat, Consts, Radix, RoundingMode};
+
+use crate::CztParams;
+
+const RM: RoundingMode = RoundingMode::ToEven;
+
+/// A complex number with [`BigFloat`] parts, all carrying the same precision in bits.
+#[derive(Clone, Debug)]
+pub struct BigComplex {
+    pub re: BigFloat,
+    pub im: BigFloat,
+}
+
+impl BigComplex {
+    pub fn new(re: BigFloat, im: BigFloat) -> Self {
+        Self { re, im }
+    }
+
+    pub fn zero(precision: usize) -> Self {
+        Self::new(BigFloat::new(precision), BigFloat::new(precision))
+    }
+
+    /// Exact conversion; `precision` must be at least 53 bits.
+    pub fn from_complex(z: Complex<f64>, precision: usize) -> Self {
+        Self::new(
+            BigFloat::from_f64(z.re, precision),
+            BigFloat::from_f64(z.im, precision),
+        )
+    }
+
+    /// `e^(2 pi i num / den)`, rounded once from the exact angle, so DFT contours are not
+    /// limited by the f64 rounding of `2 pi / den`.
+    pub fn from_turns(num: i64, den: u64, precision: usize) -> Self {
+        assert!(den > 0);
+        let mut cc = consts();
+        let angle = cc
+            .pi(precision, RM)
+            .mul(&BigFloat::from_i64(2 * num, precision), precision, RM)
+            .div(&BigFloat::from_u64(den, precision), precision, RM);
+        Self::new(
+            angle.cos(precision, RM, &mut cc),
+            angle.sin(precision, RM, &mut cc),
+        )
+    }
+
+    /// Rounds both parts to the nearest f64.
+    pub fn to_complex(&self) -> Complex<f64> {
+        let mut cc = consts();
+        Complex::new(to_f64(&self.re, &mut cc), to_f64(&self.im, &mut cc))
+    }
+
+    pub fn add(&self, other: &Self, precision: usize) -> Self {
+        Self::new(
+            self.re.add(&other.re, precision, RM),
+            self.im.add(&other.im, precision, RM),
+        )
+    }
+
+    pub fn sub(&self, other: &Self, precision: usize) -> Self {
+        Self::new(
+            self.re.sub(&other.re, precision, RM),
+            self.im.sub(&other.im, precision, RM),
+        )
+    }
+
+    pub fn mul(&self, other: &Self, precision: usize) -> Self {
+        let re = self.re.mul(&other.re, precision, RM).sub(
+            &self.im.mul(&other.im, precision, RM),
+            precision,
+            RM,
+        );
+        let im = self.re.mul(&other.im, precision, RM).add(
+            &self.im.mul(&other.re, precision, RM),
+            precision,
+            RM,
+        );
+        Self::new(re, im)
+    }
+
+    pub fn norm_sqr(&self, precision: usize) -> BigFloat {
+        self.re.mul(&self.re, precision, RM).add(
+            &self.im.mul(&self.im, precision, RM),
+            precision,
+            RM,
+        )
+    }
+
+    pub fn inv(&self, precision: usize) -> Self {
+        let norm_sqr = self.norm_sqr(precision);
+        Self::new(
+            self.re.div(&norm_sqr, precision, RM),
+            self.im.neg().div(&norm_sqr, precision, RM),
+        )
+    }
+}
+
+/// Reference CZT evaluated directly in arbitrary precision, for ground-truth spectra and for
+/// measuring the error of the f64 plans.
+///
+/// Bin `k` is `sum_j x_j u_k^j` with `u_k = a^-1 w^k`, evaluated by Horner's rule in
+/// `O(nm)` multiplications at `precision` bits.
+pub struct ArbitraryPrecisionCzt {
+    n: usize,
+    m: usize,
+    a: BigComplex,
+    w: BigComplex,
+    precision: usize,
+}
+
+impl ArbitraryPrecisionCzt {
+    pub fn new(n: usize, m: usize, a: BigComplex, w: BigComplex, precision: usize) -> Self {
+        Self {
+            n,
+            m,
+            a,
+            w,
+            precision,
+        }
+    }
+
+    /// The contour of an f64 plan, taken exactly as the plan was given it.
+    pub fn from_params(params: CztParams<f64>, precision: usize) -> Self {
+        Self::new(
+            params.n,
+            params.m,
+            BigComplex::from_complex(params.a, precision),
+            BigComplex::from_complex(params.w, precision),
+            precision,
+        )
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    pub fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    pub fn process(&self, input: &[Complex<f64>]) -> Vec<BigComplex> {
+        assert_eq!(input.len(), self.n);
+        let p = self.precision;
+        let input: Vec<_> = input
+            .iter()
+            .map(|&x| BigComplex::from_complex(x, p))
+            .collect();
+
+        let mut u = self.a.inv(p);
+        let mut output = Vec::with_capacity(self.m);
+        for _ in 0..self.m {
+            let mut acc = BigComplex::zero(p);
+            for x in input.iter().rev() {
+                acc = acc.mul(&u, p).add(x, p);
+            }
+            output.push(acc);
+            u = u.mul(&self.w, p);
+        }
+        output
+    }
+
+    pub fn process_to_f64(&self, input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        self.process(input)
+            .iter()
+            .map(BigComplex::to_complex)
+            .collect()
+    }
+
+    /// Largest `|X_k - actual_k|` over the bins, with the differences taken before rounding to
+    /// f64.
+    pub fn max_error(&self, input: &[Complex<f64>], actual: &[Complex<f64>]) -> f64 {
+        assert_eq!(actual.len(), self.m);
+        let p = self.precision;
+        self.process(input)
+            .iter()
+            .zip(actual)
+            .map(|(expected, &actual)| {
+                let diff = expected.sub(&BigComplex::from_complex(actual, p), p);
+                BigComplex::to_complex(&diff).norm()
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+fn consts() -> Consts {
+    Consts::new().expect("failed to allocate the astro-float constants cache")
+}
+
+/// Rounds through the decimal expansion, which keeps more digits than an f64 holds.
+fn to_f64(x: &BigFloat, cc: &mut Consts) -> f64 {
+    if x.is_zero() {
+        return 0.0;
+    }
+    let digits: String = x
+        .format(Radix::Dec, RM, cc)
+        .expect("BigFloat is not finite");
+    digits.parse().unwrap()
+}
@@ -0,0 +1,86 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::io::{self, Read};
+
+use crate::fft::{num_complex::Complex, FftNum};
+use futures_core::Stream;
+use tokio::task::JoinHandle;
+
+use crate::{stream::CztStream, Czt};
+
+/// Processes `buffers`, consecutive signals of `get_buffer_len()` samples each, on tokio's
+/// blocking pool, so a large batch does not stall the async workers. Must be awaited within a
+/// tokio runtime; a panic in the plan resumes in the awaiting task.
+pub async fn process_async<T: FftNum>(
+    czt: Arc<dyn Czt<T>>,
+    mut buffers: Vec<Complex<T>>,
+) -> Vec<Complex<T>> {
+    let len = czt.get_buffer_len();
+    assert!(buffers.len().is_multiple_of(len));
+    let count = buffers.len() / len;
+    tokio::task::spawn_blocking(move || {
+        czt.process_batch(&mut buffers, len, count);
+        buffers
+    })
+    .await
+    .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
+
+/// A [`CztStream`] as an async [`Stream`]: each frame is read and transformed on tokio's
+/// blocking pool, so blocking readers such as files and pipes are fine.
+pub struct AsyncCztStream<T: FftNum, R: Read> {
+    stream: Option<CztStream<T, R>>,
+    pending: Option<NextFrame<T, R>>,
+}
+
+/// The blocking read of the next frame, handing the stream back with it.
+type NextFrame<T, R> = JoinHandle<(CztStream<T, R>, Option<io::Result<Vec<Complex<T>>>>)>;
+
+impl<T: FftNum, R: Read + Send + 'static> AsyncCztStream<T, R> {
+    pub fn new(stream: CztStream<T, R>) -> Self {
+        Self {
+            stream: Some(stream),
+            pending: None,
+        }
+    }
+
+    /// The wrapped stream, or `None` while a frame is in flight.
+    pub fn into_inner(self) -> Option<CztStream<T, R>> {
+        self.stream
+    }
+}
+
+impl<T: FftNum, R: Read> Unpin for AsyncCztStream<T, R> {}
+
+impl<T: FftNum, R: Read + Send + 'static> Stream for AsyncCztStream<T, R> {
+    type Item = io::Result<Vec<Complex<T>>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let pending = match &mut this.pending {
+            Some(pending) => pending,
+            None => {
+                let Some(mut stream) = this.stream.take() else {
+                    return Poll::Ready(None);
+                };
+                this.pending.insert(tokio::task::spawn_blocking(move || {
+                    let item = stream.next();
+                    (stream, item)
+                }))
+            }
+        };
+        let (stream, item) = match Pin::new(pending).poll(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(result) => {
+                result.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+            }
+        };
+        this.pending = None;
+        this.stream = Some(stream);
+        Poll::Ready(item)
+    }
+}
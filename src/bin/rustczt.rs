@@ -0,0 +1,384 @@
+//! Zoom spectrum of a WAV, CSV or raw IQ file.
+//!
+//! Two-channel WAV files are read as I/Q, CSV lines as `re` or `re,im`, and raw files as
+//! little-endian interleaved `f32` (`cf32`) or `f64` (`cf64`) pairs, or as SDR captures of
+//! 16-bit (`cs16`, `cs16be`) or 8-bit (`cs8`, `cu8`) integers. The spectrum of the whole
+//! file is written as CSV or JSON with one `frequency, magnitude, phase` record per bin.
+//!
+//! With the `npy` feature, NumPy `.npy` arrays are read as well and the complex bins can be
+//! written as one, for comparing against `scipy.signal.czt`.
+
+use std::{fmt::Write as _, fs, io::Write as _, path::Path, process::ExitCode};
+
+use rustczt::{
+    iq::{decode_iq, Endianness, IqFormat},
+    Czt, CztPlanner, Window,
+};
+use rustfft::{num_complex::Complex, num_traits::Zero};
+
+const USAGE: &str = "\
+usage: rustczt [options] <input>
+
+  --fs <hz>            sample rate; read from WAV files, required otherwise
+  --f-start <hz>       first bin frequency [default: -fs/2 for IQ input, else 0]
+  --f-end <hz>         last bin frequency [default: fs/2]
+  --bins <m>           number of bins [default: number of samples]
+  --window <name>      rect, hann, hamming, blackman-harris, flattop or kaiser:<beta>
+                       [default: rect]
+  --input-format <f>   wav, csv, npy, cf32, cf64, cs16, cs16be, cs8 or cu8
+                       [default: from the extension, else cf32]
+  --format <f>         csv, json or npy [default: csv]
+  -o, --output <path>  write to a file instead of stdout";
+
+struct Options {
+    input: String,
+    sample_rate: Option<f64>,
+    f_start: Option<f64>,
+    f_end: Option<f64>,
+    bins: Option<usize>,
+    window: Window<f64>,
+    input_format: Option<String>,
+    format: String,
+    output: Option<String>,
+}
+
+/// Samples of the input, whether they are complex, and the sample rate the file records.
+struct Signal {
+    samples: Vec<Complex<f64>>,
+    complex: bool,
+    sample_rate: Option<f64>,
+}
+
+fn main() -> ExitCode {
+    if std::env::args().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    match run(std::env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("rustczt: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let options = parse_args(args)?;
+    let bytes = fs::read(&options.input).map_err(|e| format!("{}: {e}", options.input))?;
+    let format = options.input_format.clone().unwrap_or_else(|| {
+        let extension = Path::new(&options.input)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "wav" | "csv" | "npy" | "cf64" | "cs16" | "cs8" | "cu8" => extension,
+            _ => "cf32".into(),
+        }
+    });
+    let signal = match format.as_str() {
+        "wav" => read_wav(&bytes)?,
+        "csv" => read_csv(&bytes)?,
+        "npy" => read_npy(&bytes)?,
+        "cf32" => read_raw(&bytes, 4),
+        "cf64" => read_raw(&bytes, 8),
+        "cs16" => read_iq(&bytes, IqFormat::I16(Endianness::Little)),
+        "cs16be" => read_iq(&bytes, IqFormat::I16(Endianness::Big)),
+        "cs8" => read_iq(&bytes, IqFormat::I8),
+        "cu8" => read_iq(&bytes, IqFormat::U8),
+        _ => return Err(format!("unknown input format {format}")),
+    };
+
+    let n = signal.samples.len();
+    if n == 0 {
+        return Err("input holds no samples".into());
+    }
+    let sample_rate = options
+        .sample_rate
+        .or(signal.sample_rate)
+        .ok_or("--fs is required for this input")?;
+    if !sample_rate.is_finite() || sample_rate <= 0.0 {
+        return Err("--fs must be positive".into());
+    }
+    let nyquist = sample_rate / 2.0;
+    let f_start = options
+        .f_start
+        .unwrap_or(if signal.complex { -nyquist } else { 0.0 });
+    let f_end = options.f_end.unwrap_or(nyquist);
+    if !(-nyquist <= f_start && f_start <= f_end && f_end <= nyquist) {
+        return Err("need -fs/2 <= --f-start <= --f-end <= fs/2".into());
+    }
+    let m = options.bins.unwrap_or(n);
+
+    let zoom = CztPlanner::new().plan_zoom_fft_hz_windowed(
+        n,
+        m,
+        sample_rate,
+        f_start,
+        f_end,
+        options.window,
+    );
+    let mut buffer = signal.samples;
+    buffer.resize(zoom.get_buffer_len(), Complex::zero());
+    zoom.process(&mut buffer);
+
+    let mut text = String::new();
+    if options.format == "npy" {
+        let bytes = write_npy(buffer[..m].to_vec())?;
+        return match &options.output {
+            Some(path) => fs::write(path, bytes).map_err(|e| format!("{path}: {e}")),
+            None => std::io::stdout()
+                .write_all(&bytes)
+                .map_err(|e| e.to_string()),
+        };
+    }
+    if options.format == "json" {
+        write!(
+            text,
+            "{{\"sample_rate\":{sample_rate},\"samples\":{n},\"bins\":["
+        )
+        .unwrap();
+        for (k, bin) in buffer[..m].iter().enumerate() {
+            let separator = if k > 0 { "," } else { "" };
+            write!(
+                text,
+                "{separator}{{\"frequency\":{},\"magnitude\":{},\"phase\":{}}}",
+                zoom.freq_of_bin(k),
+                bin.norm(),
+                bin.arg()
+            )
+            .unwrap();
+        }
+        text.push_str("]}\n");
+    } else {
+        text.push_str("frequency,magnitude,phase\n");
+        for (k, bin) in buffer[..m].iter().enumerate() {
+            writeln!(text, "{},{},{}", zoom.freq_of_bin(k), bin.norm(), bin.arg()).unwrap();
+        }
+    }
+    match &options.output {
+        Some(path) => fs::write(path, text).map_err(|e| format!("{path}: {e}")),
+        None => std::io::stdout()
+            .write_all(text.as_bytes())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        input: String::new(),
+        sample_rate: None,
+        f_start: None,
+        f_end: None,
+        bins: None,
+        window: Window::Rectangular,
+        input_format: None,
+        format: "csv".into(),
+        output: None,
+    };
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        let number = |value: String| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number {value}"))
+        };
+        match arg.as_str() {
+            "--fs" => options.sample_rate = Some(number(value()?)?),
+            "--f-start" => options.f_start = Some(number(value()?)?),
+            "--f-end" => options.f_end = Some(number(value()?)?),
+            "--bins" => {
+                let value = value()?;
+                match value.parse() {
+                    Ok(bins) if bins > 0 => options.bins = Some(bins),
+                    _ => return Err(format!("invalid bin count {value}")),
+                }
+            }
+            "--window" => options.window = parse_window(&value()?)?,
+            "--input-format" => options.input_format = Some(value()?),
+            "--format" => {
+                options.format = value()?;
+                if !matches!(options.format.as_str(), "csv" | "json" | "npy") {
+                    return Err(format!("unknown output format {}", options.format));
+                }
+            }
+            "-o" | "--output" => options.output = Some(value()?),
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                return Err(format!("unknown option {arg}"))
+            }
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+    options.input = input.ok_or("no input file")?;
+    Ok(options)
+}
+
+fn parse_window(name: &str) -> Result<Window<f64>, String> {
+    Ok(match name {
+        "rect" | "rectangular" => Window::Rectangular,
+        "hann" => Window::Hann,
+        "hamming" => Window::Hamming,
+        "blackman-harris" => Window::BlackmanHarris,
+        "flattop" => Window::FlatTop,
+        _ => match name.strip_prefix("kaiser:").map(str::parse) {
+            Some(Ok(beta)) => Window::Kaiser(beta),
+            _ => return Err(format!("unknown window {name}")),
+        },
+    })
+}
+
+/// PCM (8, 16, 24 or 32 bit) or IEEE float (32 or 64 bit) WAV, mono or I/Q stereo.
+fn read_wav(bytes: &[u8]) -> Result<Signal, String> {
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a WAV file".into());
+    }
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+
+    let (mut format, mut data) = (None, None);
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let len = u32_at(offset + 4) as usize;
+        let body = offset + 8..(offset + 8 + len).min(bytes.len());
+        match &bytes[offset..offset + 4] {
+            b"fmt " if len >= 16 => format = Some((body.start, len)),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        offset += 8 + len + len % 2;
+    }
+    let ((format, format_len), data) = format
+        .zip(data)
+        .ok_or("WAV file lacks a fmt or data chunk")?;
+
+    // WAVE_FORMAT_EXTENSIBLE keeps the actual format in the sub-format GUID.
+    let mut tag = u16_at(format);
+    if tag == 0xfffe && format_len >= 26 {
+        tag = u16_at(format + 24);
+    }
+    let channels = u16_at(format + 2) as usize;
+    let sample_rate = u32_at(format + 4) as f64;
+    let bits = u16_at(format + 14);
+    let width = bits as usize / 8;
+    if channels == 0 || channels > 2 {
+        return Err(format!("unsupported channel count {channels}"));
+    }
+    let decode = |b: &[u8]| -> f64 {
+        match (tag, bits) {
+            (1, 8) => (b[0] as f64 - 128.0) / 128.0,
+            (1, 16) => i16::from_le_bytes([b[0], b[1]]) as f64 / 32768.0,
+            (1, 24) => i32::from_le_bytes([0, b[0], b[1], b[2]]) as f64 / 2147483648.0,
+            (1, 32) => i32::from_le_bytes(b.try_into().unwrap()) as f64 / 2147483648.0,
+            (3, 32) => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+            _ => f64::from_le_bytes(b.try_into().unwrap()),
+        }
+    };
+    if !matches!((tag, bits), (1, 8 | 16 | 24 | 32) | (3, 32 | 64)) {
+        return Err(format!("unsupported WAV encoding {tag} with {bits} bits"));
+    }
+    let samples = bytes[data]
+        .chunks_exact(width * channels)
+        .map(|frame| {
+            let re = decode(&frame[..width]);
+            let im = if channels == 2 {
+                decode(&frame[width..])
+            } else {
+                0.0
+            };
+            Complex::new(re, im)
+        })
+        .collect();
+    Ok(Signal {
+        samples,
+        complex: channels == 2,
+        sample_rate: Some(sample_rate),
+    })
+}
+
+/// One `re` or `re,im` sample per line; blank lines and a leading header are skipped.
+fn read_csv(bytes: &[u8]) -> Result<Signal, String> {
+    let text = std::str::from_utf8(bytes).map_err(|_| "CSV file is not UTF-8")?;
+    let mut samples = Vec::new();
+    let mut complex = false;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Result<Vec<f64>, _> = line.split(',').map(|x| x.trim().parse()).collect();
+        match fields.as_deref() {
+            Ok([re]) => samples.push(Complex::new(*re, 0.0)),
+            Ok([re, im]) => {
+                complex = true;
+                samples.push(Complex::new(*re, *im));
+            }
+            Err(_) if samples.is_empty() && i == 0 => {}
+            _ => return Err(format!("line {}: expected re or re,im", i + 1)),
+        }
+    }
+    Ok(Signal {
+        samples,
+        complex,
+        sample_rate: None,
+    })
+}
+
+/// Little-endian `re, im` pairs of `width`-byte floats; a trailing partial sample is dropped.
+fn read_raw(bytes: &[u8], width: usize) -> Signal {
+    let decode = |b: &[u8]| match width {
+        4 => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+        _ => f64::from_le_bytes(b.try_into().unwrap()),
+    };
+    let samples = bytes
+        .chunks_exact(2 * width)
+        .map(|pair| Complex::new(decode(&pair[..width]), decode(&pair[width..])))
+        .collect();
+    Signal {
+        samples,
+        complex: true,
+        sample_rate: None,
+    }
+}
+
+fn read_iq(bytes: &[u8], format: IqFormat) -> Signal {
+    let samples = decode_iq(bytes, format)
+        .into_iter()
+        .map(|x| Complex::new(x.re as f64, x.im as f64))
+        .collect();
+    Signal {
+        samples,
+        complex: true,
+        sample_rate: None,
+    }
+}
+
+#[cfg(feature = "npy")]
+fn read_npy(bytes: &[u8]) -> Result<Signal, String> {
+    let array = rustczt::npy::read_npy(bytes).map_err(|e| e.to_string())?;
+    Ok(Signal {
+        complex: array.data.iter().any(|x| x.im != 0.0),
+        samples: array.data,
+        sample_rate: None,
+    })
+}
+
+#[cfg(not(feature = "npy"))]
+fn read_npy(_: &[u8]) -> Result<Signal, String> {
+    Err("reading .npy files needs the npy feature".into())
+}
+
+#[cfg(feature = "npy")]
+fn write_npy(bins: Vec<Complex<f64>>) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    rustczt::npy::write_npy(&mut bytes, &rustczt::npy::NpyArray::from_vec(bins))
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+#[cfg(not(feature = "npy"))]
+fn write_npy(_: Vec<Complex<f64>>) -> Result<Vec<u8>, String> {
+    Err("writing .npy files needs the npy feature".into())
+}
@@ -0,0 +1,6 @@
+//! uniffi's binding generator, built with the crate's own uniffi version; see
+//! `rustczt::mobile`.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,237 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    Fft, FftNum,
+};
+
+use crate::{
+    bluesteins::polar_pow,
+    cost::{self, CostEstimate},
+    fft_provider::fft_memory_usage,
+    pointwise, ConvolutionLength, Czt, CztParams, FftProvider,
+};
+
+/// Bluestein's algorithm with the convolution split into blocks, so its working set fits a
+/// given scratch size however long the input is.
+///
+/// The chirp tables are kept in the time domain, `O(n + m)` like the buffer itself. Each block
+/// of inputs is transformed once and convolved with the kernel segment of every block of bins,
+/// and the partial convolutions are added up (overlap-add). The chirps and the kernel are the
+/// same as in one long convolution, so only the rounding of the FFTs differs from
+/// [`BluesteinsAlgorithm`](crate::bluesteins::BluesteinsAlgorithm).
+///
+/// [`get_outofplace_scratch_len`](Czt::get_outofplace_scratch_len) stays within the size given
+/// to [`new`](Self::new); processing in place adds an accumulator for the `m` bins. With `p`
+/// blocks of inputs and `q` of bins a transform runs `p + 2 p q` FFTs of the block length.
+pub struct BlockedCzt<T: FftNum> {
+    /// `a^-j w^(j d + (j - c)^2 / 2)`
+    y_coefficients: Vec<Complex<T>>,
+    /// `w^-(t - d + c)^2 / 2` for `t = k - j` from `1 - n` to `m - 1`.
+    kernel: Vec<Complex<T>>,
+    /// `w^(c (k - d) + (k - d)^2 / 2) / l`
+    x_coefficients: Vec<Complex<T>>,
+    fft_forward: Arc<dyn Fft<T>>,
+    fft_inverse: Arc<dyn Fft<T>>,
+    input_block: usize,
+    output_block: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> BlockedCzt<T> {
+    /// Picks the longest blocks whose convolution, with its FFT scratch, fits in `max_scratch`
+    /// elements.
+    ///
+    /// # Panics
+    ///
+    /// If `max_scratch` does not even fit a convolution of one input with one bin.
+    pub fn new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        max_scratch: usize,
+        length: ConvolutionLength,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        assert!(n > 0 && m > 0);
+        let mut len = n + m - 1;
+        let (fft_forward, fft_inverse) = loop {
+            let l = length.for_len(len);
+            let (forward, inverse) = (fft_provider.plan_forward(l), fft_provider.plan_inverse(l));
+            if block_scratch_len(&*forward, &*inverse) <= max_scratch || len == 1 {
+                break (forward, inverse);
+            }
+            len /= 2;
+        };
+        assert!(
+            block_scratch_len(&*fft_forward, &*fft_inverse) <= max_scratch,
+            "max_scratch of {max_scratch} is too small for any block"
+        );
+        let output_block = m.min(len.div_ceil(2));
+        let input_block = n.min(len + 1 - output_block);
+        let output_block = m.min(len + 1 - input_block);
+
+        let (c, d) = ((n as i128 - 1) / 2, (m as i128 - 1) / 2);
+        let y_coefficients = (0..n as i128)
+            .map(|j| polar_pow(a, -2 * j) * polar_pow(w, 2 * j * d + (j - c).pow(2)))
+            .collect();
+        let kernel = (1 - n as i128..m as i128)
+            .map(|t| polar_pow(w, -(t - d + c).pow(2)))
+            .collect();
+        let scale = T::one() / T::from_usize(fft_forward.len()).unwrap();
+        let x_coefficients = (0..m as i128)
+            .map(|k| polar_pow(w, 2 * c * (k - d) + (k - d).pow(2)) * scale)
+            .collect();
+
+        Self {
+            y_coefficients,
+            kernel,
+            x_coefficients,
+            fft_forward,
+            fft_inverse,
+            input_block,
+            output_block,
+            a,
+            w,
+        }
+    }
+}
+
+/// Input spectrum, product and FFT scratch of one block convolution.
+fn block_scratch_len<T: FftNum>(forward: &dyn Fft<T>, inverse: &dyn Fft<T>) -> usize {
+    2 * forward.len()
+        + forward
+            .get_inplace_scratch_len()
+            .max(inverse.get_inplace_scratch_len())
+}
+
+impl<T: FftNum> BlockedCzt<T> {
+    /// Inputs and bins per block.
+    pub fn block_lens(&self) -> (usize, usize) {
+        (self.input_block, self.output_block)
+    }
+
+    /// Adds every pair of blocks' convolution into `output` and applies the output chirp.
+    fn accumulate(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        let (n, l) = (self.y_coefficients.len(), self.fft_forward.len());
+        let (spectrum, scratch) = scratch.split_at_mut(l);
+        let (product, fft_scratch) = scratch.split_at_mut(l);
+        let forward_scratch_len = self.fft_forward.get_inplace_scratch_len();
+        let inverse_scratch_len = self.fft_inverse.get_inplace_scratch_len();
+
+        output.fill(Complex::zero());
+        for (j0, (input, y)) in (0..).step_by(self.input_block).zip(
+            input
+                .chunks(self.input_block)
+                .zip(self.y_coefficients.chunks(self.input_block)),
+        ) {
+            let (head, tail) = spectrum.split_at_mut(input.len());
+            pointwise::multiply(head, input, y);
+            tail.fill(Complex::zero());
+            self.fft_forward
+                .process_with_scratch(spectrum, &mut fft_scratch[..forward_scratch_len]);
+
+            for (k0, output) in (0..)
+                .step_by(self.output_block)
+                .zip(output.chunks_mut(self.output_block))
+            {
+                // The kernel around `t = k0 - j0`, wrapped so bin `k0 + k` collects input
+                // `j0 + i` at `k - i` modulo `l`.
+                let center = k0 + n - 1 - j0;
+                let (head, rest) = product.split_at_mut(output.len());
+                head.copy_from_slice(&self.kernel[center..center + output.len()]);
+                let (middle, tail) = rest.split_at_mut(rest.len() + 1 - input.len());
+                middle.fill(Complex::zero());
+                tail.copy_from_slice(&self.kernel[center + 1 - input.len()..center]);
+
+                self.fft_forward
+                    .process_with_scratch(product, &mut fft_scratch[..forward_scratch_len]);
+                pointwise::multiply_in_place(product, spectrum);
+                self.fft_inverse
+                    .process_with_scratch(product, &mut fft_scratch[..inverse_scratch_len]);
+                for (out, &x) in output.iter_mut().zip(&*product) {
+                    *out = *out + x;
+                }
+            }
+        }
+        pointwise::multiply_in_place(output, &self.x_coefficients);
+    }
+
+    fn block_scratch_len(&self) -> usize {
+        block_scratch_len(&*self.fft_forward, &*self.fft_inverse)
+    }
+}
+
+impl<T: FftNum> Czt<T> for BlockedCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let (n, m) = (self.get_input_len(), self.get_output_len());
+        let (accumulator, scratch) = scratch.split_at_mut(m);
+        self.accumulate(&buffer[..n], accumulator, scratch);
+        buffer[..m].copy_from_slice(accumulator);
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.get_input_len());
+        assert_eq!(output.len(), self.get_output_len());
+        assert_eq!(scratch.len(), self.get_outofplace_scratch_len());
+
+        self.accumulate(input, output, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.get_output_len() + self.block_scratch_len()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.block_scratch_len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.y_coefficients.len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.x_coefficients.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.get_input_len(),
+            m: self.get_output_len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let tables = self.y_coefficients.len() + self.kernel.len() + self.x_coefficients.len();
+        (tables + self.get_inplace_scratch_len()) * core::mem::size_of::<Complex<T>>()
+            + fft_memory_usage(&*self.fft_forward)
+            + fft_memory_usage(&*self.fft_inverse)
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::blocked::<T>(
+            self.get_input_len(),
+            self.get_output_len(),
+            self.block_lens(),
+            self.fft_forward.len(),
+        )
+    }
+}
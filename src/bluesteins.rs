@@ -1,18 +1,209 @@
-use std::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
+use core::mem::MaybeUninit;
 
-use rustfft::{
+use crate::fft::{
     num_complex::Complex,
-    num_traits::{Float, FromPrimitive, Zero},
-    Fft, FftNum, FftPlanner,
+    num_traits::{Float, Zero},
+    Fft, FftNum,
 };
 
-use crate::Czt;
+#[cfg(feature = "rkyv")]
+use crate::saved_plan::ArchivedSavedPlan;
+#[cfg(any(feature = "serde", feature = "rkyv"))]
+use crate::saved_plan::{LoadError, SavedPlan, SavedTables, FORMAT_VERSION};
+use crate::{
+    agrees_within,
+    aligned_vec::AlignedVec,
+    check_finite,
+    cost::{self, CostEstimate},
+    fft_provider::fft_memory_usage,
+    naive_czt::NaiveCzt,
+    pointwise, uninit, verification_signal, Czt, CztError, CztParams, FftProvider, PlanError,
+};
+
+/// `z^(twice_exponent / 2)` evaluated in polar form.
+///
+/// The exponent is split exactly into two doubles, `arg(z) * exponent` is formed as a
+/// double-double and reduced modulo 2 pi before calling `sin`/`cos`, so the chirp phases keep full
+/// precision for large `k^2 / 2` exponents where `Complex::powf` (going through `ln` and `exp`)
+/// does not.
+pub fn polar_pow<T: Float + FftNum>(z: Complex<T>, twice_exponent: i128) -> Complex<T> {
+    // 2 pi as the sum of `TAU` and its rounding error.
+    const TWO_PI_HI: f64 = core::f64::consts::TAU;
+    const TWO_PI_LO: f64 = 2.4492935982947064e-16;
+
+    let (r, theta) = to_f64(z).to_polar();
+    let exponent_hi = twice_exponent as f64;
+    let exponent_lo = (twice_exponent - exponent_hi as i128) as f64;
+    let hi = theta * exponent_hi;
+    let lo = Float::mul_add(theta, exponent_hi, -hi) + theta * exponent_lo;
+    let (hi, lo) = (hi / 2.0, lo / 2.0);
+    let turns = Float::round(hi / TWO_PI_HI);
+    let phase = Float::mul_add(turns, -TWO_PI_HI, hi) - turns * TWO_PI_LO + lo;
+    let magnitude = Float::exp(Float::ln(r) * (exponent_hi + exponent_lo) / 2.0);
+
+    Complex::from_polar(T::from_f64(magnitude).unwrap(), T::from_f64(phase).unwrap())
+}
+
+/// `z` in double precision, which contour parameters are evaluated in before rounding to `T`.
+pub(crate) fn to_f64<T: Float>(z: Complex<T>) -> Complex<f64> {
+    Complex::new(z.re.to_f64().unwrap(), z.im.to_f64().unwrap())
+}
+
+/// `z` rounded from double precision to `T`.
+pub(crate) fn from_f64<T: FftNum>(z: Complex<f64>) -> Complex<T> {
+    Complex::new(T::from_f64(z.re).unwrap(), T::from_f64(z.im).unwrap())
+}
+
+/// How Bluestein's algorithm picks its convolution length `l >= n + m - 1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConvolutionLength {
+    /// The smallest `2^a 3^b 5^c`, which rustfft transforms about as fast as a power of two.
+    #[default]
+    Smooth,
+    /// The next power of two, for FFT backends that only support those.
+    PowerOfTwo,
+}
+
+impl ConvolutionLength {
+    pub fn for_len(self, min_len: usize) -> usize {
+        match self {
+            ConvolutionLength::PowerOfTwo => min_len.next_power_of_two(),
+            ConvolutionLength::Smooth => {
+                let mut best = min_len.next_power_of_two();
+                let mut p5 = 1;
+                while p5 < best {
+                    let mut p35 = p5;
+                    while p35 < best {
+                        let mut candidate = p35;
+                        while candidate < min_len {
+                            candidate *= 2;
+                        }
+                        best = best.min(candidate);
+                        p35 *= 3;
+                    }
+                    p5 *= 5;
+                }
+                best
+            }
+        }
+    }
+}
+
+fn square(k: i64) -> i128 {
+    k as i128 * k as i128
+}
+
+// Input index j and output index k0 + k enter the chirps relative to the centers c and d:
+// j (k0 + k) = (j - c)(k0 + k - d) + j d + c (k0 + k - d), and Bluestein's identity is applied to
+// the first product only.
+fn fill_y_coefficients<T: Float + FftNum>(
+    y: &mut [Complex<T>],
+    a: Complex<T>,
+    w: Complex<T>,
+    j0: i64,
+    (c, d): (i64, i64),
+    window: Option<&[T]>,
+) {
+    for (y, j) in y.iter_mut().zip(j0..) {
+        *y = polar_pow(a, -2 * j as i128) * polar_pow(w, 2 * (j * d) as i128 + square(j - c));
+    }
+    if let Some(window) = window {
+        for (y, &weight) in y.iter_mut().zip(window) {
+            *y = *y * weight;
+        }
+    }
+}
+
+fn fill_v_coefficients<T: Float + FftNum>(
+    v: &mut [Complex<T>],
+    n: usize,
+    m: usize,
+    w: Complex<T>,
+    (j0, k0): (i64, i64),
+    (c, d): (i64, i64),
+    fft_forward: &dyn Fft<T>,
+) {
+    let l = v.len();
+    let shift = k0 - d - j0 + c;
+    let (head, tail) = v.split_at_mut(l - n + 1);
+    for (v, q) in head.iter_mut().zip(0..) {
+        *v = if q < m as i64 {
+            polar_pow(w, -square(shift + q))
+        } else {
+            Complex::zero()
+        };
+    }
+    for (v, q) in tail.iter_mut().zip(l - n + 1..) {
+        *v = polar_pow(w, -square(shift - (l - q) as i64));
+    }
+    fft_forward.process(v);
+}
+
+// Also carries the 1/l of the unnormalized inverse FFT and the plan's output scale.
+fn fill_x_coefficients<T: Float + FftNum>(
+    x: &mut [Complex<T>],
+    l: usize,
+    w: Complex<T>,
+    k0: i64,
+    (c, d): (i64, i64),
+    scale: T,
+) {
+    let factor = scale / T::from_usize(l).unwrap();
+    for (x, k) in x.iter_mut().zip(0..) {
+        let k = k0 + k - d;
+        *x = polar_pow(w, 2 * (c * k) as i128 + square(k)) * factor;
+    }
+}
+
+/// Multiplies bin `k` by `z_(k0 + k)^-delay = e^(-delay (ln a - (k0 + k) ln w))`.
+fn apply_delay<T: Float + FftNum>(
+    x: &mut [Complex<T>],
+    a: Complex<T>,
+    w: Complex<T>,
+    k0: i64,
+    delay: T,
+) {
+    let (ln_a, ln_w) = (to_f64(a).ln(), to_f64(w).ln());
+    let delay = delay.to_f64().unwrap();
+    for (x, k) in x.iter_mut().zip(k0..) {
+        let ramp = ((ln_w * k as f64 - ln_a) * delay).exp();
+        *x = *x * from_f64(ramp);
+    }
+}
+
+// On a spiral the chirp magnitudes |w|^(k^2 / 2) quickly leave the floating point range.
+// Centering both index ranges quarters the largest exponents; unit-circle contours keep the
+// plain tables.
+fn centers<T: Float>(n: usize, m: usize, w: Complex<T>, j0: i64, k0: i64) -> (i64, i64) {
+    if log_radius(w).abs() * ((n + m) as f64).powi(2) > 1.0 {
+        (j0 + (n as i64 - 1) / 2, k0 + (m as i64 - 1) / 2)
+    } else {
+        (0, 0)
+    }
+}
+
+/// `ln |w|`, the rate at which the contour spirals in or out.
+pub fn log_radius<T: Float>(w: Complex<T>) -> f64 {
+    Float::ln(to_f64(w).norm())
+}
 
 pub struct BluesteinsAlgorithm<T: FftNum> {
-    y_coefficients: Vec<Complex<T>>,
-    v_coefficients: Vec<Complex<T>>,
-    x_coefficients: Vec<Complex<T>>,
+    y_coefficients: AlignedVec<Complex<T>>,
+    v_coefficients: AlignedVec<Complex<T>>,
+    x_coefficients: AlignedVec<Complex<T>>,
     fft_forward: Arc<dyn Fft<T>>,
+    fft_inverse: Arc<dyn Fft<T>>,
+    /// Contour starting at bin `k0`.
+    a: Complex<T>,
+    w: Complex<T>,
+    j0: i64,
+    k0: i64,
+    centers: (i64, i64),
+    window: Option<Vec<T>>,
+    output_scale: T,
+    delay: T,
 }
 
 impl<T: FftNum + Float> BluesteinsAlgorithm<T> {
@@ -21,62 +212,384 @@ impl<T: FftNum + Float> BluesteinsAlgorithm<T> {
         m: usize,
         a: Complex<T>,
         w: Complex<T>,
-        fft_planner: &mut FftPlanner<T>,
+        k0: isize,
+        fft_provider: &mut dyn FftProvider<T>,
     ) -> Self {
-        fn square_and_half<T>(n: i32) -> T
-        where
-            T: Float + FromPrimitive,
-        {
-            T::from_i32(n * n).unwrap() / T::from_usize(2).unwrap()
-        }
-        fn compute_y_coefficients<T: Float + FftNum>(
-            n: usize,
-            a: Complex<T>,
-            w: Complex<T>,
-        ) -> Vec<Complex<T>> {
-            (0..n as i32)
-                .map(|n| a.powi(-n) * w.powf(square_and_half(n)))
-                .collect()
-        }
-        fn compute_v_coefficients<T: Float + FftNum>(
-            l: usize,
-            m: usize,
-            n: usize,
-            w: Complex<T>,
-            fft_forward: Arc<dyn Fft<T>>,
-        ) -> Vec<Complex<T>> {
-            let mut out: Vec<_> = (0..m as i32)
-                .map(|n| w.powf(-square_and_half::<T>(n)))
-                .chain((m..l - n + 1).map(|_| Complex::zero()))
-                .chain((l - n + 1..l).map(|n| w.powf(-square_and_half::<T>((l - n) as i32))))
-                .collect();
-            fft_forward.process(&mut out);
-            out
-        }
-        fn compute_x_coefficients<T: Float + FftNum>(m: usize, w: Complex<T>) -> Vec<Complex<T>> {
-            (0..m as i32).map(|k| w.powf(square_and_half(k))).collect()
-        }
-
-        assert!(m <= n);
-
-        let l = (m + n - 1).next_power_of_two();
-
-        let fft_forward = fft_planner.plan_fft_forward(l);
-
-        let y_coefficients = compute_y_coefficients(n, a, w);
-        let v_coefficients = compute_v_coefficients(l, m, n, w, fft_forward.clone());
-        let x_coefficients = compute_x_coefficients(m, w);
+        Self::new_segment(
+            n,
+            m,
+            a,
+            w,
+            0,
+            k0,
+            ConvolutionLength::default(),
+            fft_provider,
+        )
+    }
+
+    /// [`new`](Self::new), or the reason the parameters cannot be planned.
+    pub fn try_new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Result<Self, PlanError> {
+        CztParams { n, m, a, w }.validate()?;
+        Ok(Self::new(n, m, a, w, k0, fft_provider))
+    }
+
+    /// Plans the contribution of inputs `j0..j0 + n` to bins `k0..k0 + m`; the buffer holds the
+    /// `n` inputs starting at `j0`. [`params`](Czt::params) reports the contour of these bins and
+    /// does not reflect `j0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_segment(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        j0: usize,
+        k0: isize,
+        length: ConvolutionLength,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        let l = length.for_len(m + n - 1);
+        Self::with_convolution_len(n, m, a, w, (j0, k0), l, fft_provider)
+    }
+
+    fn with_convolution_len(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        (j0, k0): (usize, isize),
+        l: usize,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        let (j0, k0) = (j0 as i64, k0 as i64);
+        let centers = centers(n, m, w, j0, k0);
+
+        let fft_forward = fft_provider.plan_forward(l);
+        let fft_inverse = fft_provider.plan_inverse(l);
+
+        let zeros = |len| (0..len).map(|_| Complex::zero()).collect::<AlignedVec<_>>();
+        let (mut y_coefficients, mut v_coefficients, mut x_coefficients) =
+            (zeros(n), zeros(l), zeros(m));
+        fill_y_coefficients(&mut y_coefficients, a, w, j0, centers, None);
+        fill_v_coefficients(
+            &mut v_coefficients,
+            n,
+            m,
+            w,
+            (j0, k0),
+            centers,
+            &*fft_forward,
+        );
+        fill_x_coefficients(&mut x_coefficients, l, w, k0, centers, T::one());
 
         Self {
             y_coefficients,
             v_coefficients,
             x_coefficients,
             fft_forward,
+            fft_inverse,
+            a: a * polar_pow(w, -2 * k0 as i128),
+            w,
+            j0,
+            k0,
+            centers,
+            window: None,
+            output_scale: T::one(),
+            delay: T::zero(),
+        }
+    }
+
+    /// Moves the starting point of the contour to `a`, given as to the constructor. Only the
+    /// length-`n` input chirp depends on `a`, so this costs O(n) instead of a replan; windows
+    /// folded into the plan are kept. A folded delay depends on `a` as well and adds O(m).
+    pub fn retune_a(&mut self, a: Complex<T>) {
+        fill_y_coefficients(
+            &mut self.y_coefficients,
+            a,
+            self.w,
+            self.j0,
+            self.centers,
+            self.window.as_deref(),
+        );
+        self.a = a * polar_pow(self.w, -2 * self.k0 as i128);
+        if !self.delay.is_zero() {
+            fill_x_coefficients(
+                &mut self.x_coefficients,
+                self.fft_forward.len(),
+                self.w,
+                self.k0,
+                self.centers,
+                self.output_scale,
+            );
+            let origin = self.origin();
+            apply_delay(
+                &mut self.x_coefficients,
+                origin,
+                self.w,
+                self.k0,
+                self.delay,
+            );
+        }
+    }
+
+    /// Delays the input by `delay` samples, which need not be whole, by folding `z_k^-delay`
+    /// into the output chirp coefficients. On the unit circle that is the linear phase
+    /// `e^(-i omega_k delay)`, with `omega_k = arg a - k arg w` continued along the contour
+    /// from the `a` given to the constructor rather than wrapped, so plans covering consecutive
+    /// stretches of a contour from their `k0` continue one phase ramp. Delays add up and are
+    /// kept by [`retune`](Self::retune).
+    pub fn with_delay(mut self, delay: T) -> Self {
+        let origin = self.origin();
+        apply_delay(&mut self.x_coefficients, origin, self.w, self.k0, delay);
+        self.delay = self.delay + delay;
+        self
+    }
+
+    /// Bin 0 of the contour, the `a` given to the constructor, of which the plan computes the
+    /// bins from `k0`.
+    fn origin(&self) -> Complex<T> {
+        self.a * polar_pow(self.w, 2 * self.k0 as i128)
+    }
+
+    /// The plan's bins of `input` by a [`NaiveCzt`] on its contour, with the window, input offset
+    /// `j0`, output scale and delay folded into the plan applied around it.
+    pub(crate) fn naive_reference(&self, input: &[Complex<T>]) -> Vec<Complex<T>> {
+        let (n, m) = (self.n(), self.m());
+        let mut buffer = vec![Complex::zero(); n.max(m)];
+        for (j, (x, &input)) in buffer.iter_mut().zip(input).enumerate() {
+            *x = self
+                .window
+                .as_ref()
+                .map_or(input, |window| input * window[j]);
+        }
+        NaiveCzt::new(n, m, self.a, self.w).process(&mut buffer);
+        buffer.truncate(m);
+
+        // Input j0 + j weighs z_k^-(j0 + j), z_k^-j0 = a^-j0 w^(j0 k).
+        let offset = polar_pow(self.a, -2 * self.j0 as i128);
+        for (x, k) in buffer.iter_mut().zip(0..) {
+            *x = *x * offset * polar_pow(self.w, 2 * self.j0 as i128 * k) * self.output_scale;
+        }
+        if !self.delay.is_zero() {
+            apply_delay(&mut buffer, self.origin(), self.w, self.k0, self.delay);
+        }
+        buffer
+    }
+
+    /// Moves the plan to the contour `(a, w)`, recomputing every coefficient table into its
+    /// existing allocation. The FFTs depend on `n`, `m` and the convolution length only and are
+    /// kept. Windows, output scaling and delays folded into the plan are kept as well.
+    ///
+    /// The plan stays a single convolution; contours that need splitting, see
+    /// [`SpiralCzt::is_needed`](crate::spiral::SpiralCzt::is_needed), should be replanned.
+    pub fn retune(&mut self, a: Complex<T>, w: Complex<T>) {
+        let (n, m) = (self.n(), self.m());
+        self.w = w;
+        self.centers = centers(n, m, w, self.j0, self.k0);
+        fill_v_coefficients(
+            &mut self.v_coefficients,
+            n,
+            m,
+            w,
+            (self.j0, self.k0),
+            self.centers,
+            &*self.fft_forward,
+        );
+        fill_x_coefficients(
+            &mut self.x_coefficients,
+            self.fft_forward.len(),
+            w,
+            self.k0,
+            self.centers,
+            self.output_scale,
+        );
+        self.retune_a(a);
+    }
+}
+
+#[cfg(any(feature = "serde", feature = "rkyv"))]
+impl<T: FftNum + Float> BluesteinsAlgorithm<T> {
+    /// Describes the plan for persisting it, with the coefficient tables if `with_tables`.
+    pub fn to_saved(&self, with_tables: bool) -> SavedPlan<T> {
+        SavedPlan {
+            version: FORMAT_VERSION,
+            params: self.params(),
+            j0: self.j0 as usize,
+            k0: self.k0 as isize,
+            convolution_len: self.l(),
+            window: self.window.clone(),
+            output_scale: self.output_scale,
+            delay: self.delay,
+            tables: with_tables.then(|| SavedTables {
+                y: self.y_coefficients.to_vec(),
+                v: self.v_coefficients.to_vec(),
+                x: self.x_coefficients.to_vec(),
+            }),
+        }
+    }
+
+    /// Rebuilds a plan from [`to_saved`](Self::to_saved). Saved tables are used as they are and
+    /// only the FFTs are planned; without them every table is recomputed from the description.
+    /// Fails on a version other than [`FORMAT_VERSION`], parameters that cannot be planned or
+    /// tables of the wrong lengths.
+    pub fn from_saved(
+        saved: &SavedPlan<T>,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Result<Self, LoadError> {
+        let tables = saved
+            .tables
+            .as_ref()
+            .map(|t| [&t.y[..], &t.v[..], &t.x[..]]);
+        Self::restore(saved, tables, fft_provider)
+    }
+
+    /// [`from_saved`](Self::from_saved) on an archive from
+    /// [`saved_plan::access`](crate::saved_plan::access). The tables are copied from the archive
+    /// into the plan rather than borrowed, so loading costs a pass over them besides planning the
+    /// FFTs, but nothing is recomputed.
+    #[cfg(feature = "rkyv")]
+    pub fn from_archived(
+        saved: &ArchivedSavedPlan<T>,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Result<Self, LoadError>
+    where
+        T: rkyv::Archive<Archived = T>,
+    {
+        let descriptor = SavedPlan {
+            version: saved.version,
+            params: CztParams {
+                n: saved.params.n as usize,
+                m: saved.params.m as usize,
+                a: saved.params.a,
+                w: saved.params.w,
+            },
+            j0: saved.j0 as usize,
+            k0: saved.k0 as isize,
+            convolution_len: saved.convolution_len as usize,
+            window: saved.window.as_ref().map(|window| window.to_vec()),
+            output_scale: saved.output_scale,
+            delay: saved.delay,
+            tables: None,
+        };
+        let tables = saved
+            .tables
+            .as_ref()
+            .map(|t| [&t.y[..], &t.v[..], &t.x[..]]);
+        Self::restore(&descriptor, tables, fft_provider)
+    }
+
+    /// Rebuilds the plan `saved` describes around `tables`, `[y, v, x]`, or recomputes them.
+    fn restore(
+        saved: &SavedPlan<T>,
+        tables: Option<[&[Complex<T>]; 3]>,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Result<Self, LoadError> {
+        if !saved.is_compatible() {
+            return Err(LoadError::Version {
+                found: saved.version,
+            });
         }
+        saved.params.validate()?;
+        let CztParams { n, m, a, w } = saved.params;
+        let l = saved.convolution_len;
+        if l < n + m - 1 {
+            return Err(LoadError::ConvolutionLength {
+                len: l,
+                min: n + m - 1,
+            });
+        }
+        let window = saved.window.as_ref().map(Vec::len);
+        let [y, v, x] = tables.map_or([None; 3], |tables| tables.map(|table| Some(table.len())));
+        let lengths = [("window", window, n), ("y", y, n), ("v", v, l), ("x", x, m)];
+        for (name, actual, expected) in lengths {
+            if let Some(actual) = actual.filter(|&actual| actual != expected) {
+                return Err(LoadError::TableLength {
+                    name,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        let Some([y, v, x]) = tables else {
+            // `params` reports the contour from bin `k0`.
+            let a = a * polar_pow(w, 2 * saved.k0 as i128);
+            let mut plan =
+                Self::with_convolution_len(n, m, a, w, (saved.j0, saved.k0), l, fft_provider);
+            if let Some(window) = &saved.window {
+                plan = plan.with_window(window);
+            }
+            if saved.output_scale != T::one() {
+                plan = plan.with_output_scale(saved.output_scale);
+            }
+            if !saved.delay.is_zero() {
+                plan = plan.with_delay(saved.delay);
+            }
+            return Ok(plan);
+        };
+
+        let (j0, k0) = (saved.j0 as i64, saved.k0 as i64);
+        Ok(Self {
+            y_coefficients: AlignedVec::from_slice(y),
+            v_coefficients: AlignedVec::from_slice(v),
+            x_coefficients: AlignedVec::from_slice(x),
+            fft_forward: fft_provider.plan_forward(l),
+            fft_inverse: fft_provider.plan_inverse(l),
+            a,
+            w,
+            j0,
+            k0,
+            centers: centers(n, m, w, j0, k0),
+            window: saved.window.clone(),
+            output_scale: saved.output_scale,
+            delay: saved.delay,
+        })
     }
 }
 
 impl<T: FftNum> BluesteinsAlgorithm<T> {
+    /// Folds a per-sample input weighting into the chirp coefficients.
+    pub fn with_window(mut self, window: &[T]) -> Self {
+        assert_eq!(window.len(), self.n());
+        for (y, &weight) in self.y_coefficients.iter_mut().zip(window) {
+            *y = *y * weight;
+        }
+        let window = match self.window.take() {
+            Some(previous) => previous.iter().zip(window).map(|(&p, &w)| p * w).collect(),
+            None => window.to_vec(),
+        };
+        self.window = Some(window);
+        self
+    }
+
+    /// Folds a constant output scale factor into the output chirp coefficients.
+    pub fn with_output_scale(mut self, factor: T) -> Self {
+        for x in self.x_coefficients.iter_mut() {
+            *x = *x * factor;
+        }
+        self.output_scale = self.output_scale * factor;
+        self
+    }
+
+    pub fn y_coefficients(&self) -> &[Complex<T>] {
+        &self.y_coefficients
+    }
+
+    /// Forward transform of the convolution kernel.
+    pub fn v_coefficients(&self) -> &[Complex<T>] {
+        &self.v_coefficients
+    }
+
+    pub fn x_coefficients(&self) -> &[Complex<T>] {
+        &self.x_coefficients
+    }
+
     fn m(&self) -> usize {
         self.x_coefficients.len()
     }
@@ -90,41 +603,255 @@ impl<T: FftNum> BluesteinsAlgorithm<T> {
     }
 }
 
-impl<T: FftNum> Czt<T> for BluesteinsAlgorithm<T> {
-    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
-        assert_eq!(buffer.len(), self.n());
-        assert_eq!(scratch.len(), self.get_scratch_len());
+impl<T: FftNum> BluesteinsAlgorithm<T> {
+    /// Scratch needed by [`Czt::process_batch_with_scratch`] to run `count` signals through
+    /// shared FFT calls; smaller scratch falls back to one signal at a time.
+    pub fn get_batch_scratch_len(&self, count: usize) -> usize {
+        count * self.l() + self.fft_scratch_len()
+    }
 
-        let (expanded_buffer, scratch) = scratch.split_at_mut(self.l());
+    fn fft_scratch_len(&self) -> usize {
+        self.fft_forward
+            .get_inplace_scratch_len()
+            .max(self.fft_inverse.get_inplace_scratch_len())
+    }
+
+    fn process_batched(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        let (expanded_buffers, scratch) = scratch.split_at_mut(count * self.l());
 
         // Perform step one of CZT: y_n = x_n * A^-n * W ^ (n^2 / 2)
-        for i in 0..self.n() {
-            expanded_buffer[i] = buffer[i] * self.y_coefficients[i];
-        }
-        for i in self.n()..self.l() {
-            expanded_buffer[i] = Complex::zero();
+        for (buffer, expanded_buffer) in buffers
+            .chunks_mut(stride)
+            .zip(expanded_buffers.chunks_exact_mut(self.l()))
+        {
+            let (head, tail) = expanded_buffer.split_at_mut(self.n());
+            pointwise::multiply(head, buffer, &self.y_coefficients);
+            tail.fill(Complex::zero());
         }
 
-        // Perform step two of CZT
+        self.convolve(buffers, stride, expanded_buffers, scratch);
+    }
+
+    /// The three steps for one signal, failing at the first intermediate result with an
+    /// infinite or NaN value before `buffer` is written.
+    fn process_staged(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), CztError> {
+        let (expanded_buffer, scratch) = scratch.split_at_mut(self.l());
+        let (head, tail) = expanded_buffer.split_at_mut(self.n());
+        pointwise::multiply(head, buffer, &self.y_coefficients);
+        tail.fill(Complex::zero());
+        check_finite("chirp", expanded_buffer)?;
+
         self.fft_forward
             .process_with_scratch(expanded_buffer, scratch);
+        check_finite("forward FFT", expanded_buffer)?;
+        pointwise::multiply_in_place(expanded_buffer, &self.v_coefficients);
+        check_finite("kernel", expanded_buffer)?;
+        self.fft_inverse
+            .process_with_scratch(expanded_buffer, scratch);
+        check_finite("inverse FFT", expanded_buffer)?;
 
-        for i in 0..self.l() {
-            expanded_buffer[i] = (expanded_buffer[i] * self.v_coefficients[i]).conj();
-        }
+        pointwise::multiply(
+            &mut buffer[..self.m()],
+            &expanded_buffer[..self.m()],
+            &self.x_coefficients,
+        );
+        Ok(())
+    }
 
+    /// Steps two and three, on `expanded_buffers` already holding step one's output.
+    fn convolve(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        expanded_buffers: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        // Perform step two of CZT
         self.fft_forward
-            .process_with_scratch(expanded_buffer, scratch);
+            .process_with_scratch(expanded_buffers, scratch);
 
-        let l = T::from_usize(self.l()).unwrap();
+        for expanded_buffer in expanded_buffers.chunks_exact_mut(self.l()) {
+            pointwise::multiply_in_place(expanded_buffer, &self.v_coefficients);
+        }
+
+        self.fft_inverse
+            .process_with_scratch(expanded_buffers, scratch);
 
         // Perform step three of CZT
-        for i in 0..self.m() {
-            buffer[i] = expanded_buffer[i].conj() * self.x_coefficients[i] / l;
+        for (buffer, expanded_buffer) in buffers
+            .chunks_mut(stride)
+            .zip(expanded_buffers.chunks_exact(self.l()))
+        {
+            pointwise::multiply(
+                &mut buffer[..self.m()],
+                &expanded_buffer[..self.m()],
+                &self.x_coefficients,
+            );
         }
     }
+}
+
+impl<T: FftNum> Czt<T> for BluesteinsAlgorithm<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let len = buffer.len();
+        self.process_batched(buffer, len, 1, scratch);
+    }
+
+    fn process_with_uninit_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [MaybeUninit<Complex<T>>],
+    ) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        // Step one writes the whole expanded buffer; only the FFT scratch needs zeroing.
+        let (expanded_buffer, scratch) = scratch.split_at_mut(self.l());
+        let (head, tail) = expanded_buffer.split_at_mut(self.n());
+        for ((y, &x), &coeff) in head.iter_mut().zip(&*buffer).zip(&*self.y_coefficients) {
+            y.write(x * coeff);
+        }
+        uninit::fill(tail, Complex::zero());
+        // SAFETY: both halves were written above.
+        let expanded_buffer = unsafe { uninit::assume_init(expanded_buffer) };
+
+        let len = buffer.len();
+        self.convolve(
+            buffer,
+            len,
+            expanded_buffer,
+            uninit::fill(scratch, Complex::zero()),
+        );
+    }
+
+    fn process_checked(&self, buffer: &mut [Complex<T>]) -> Result<(), CztError> {
+        let (expected, actual) = (self.get_buffer_len(), buffer.len());
+        if actual != expected {
+            return Err(CztError::BufferLength { expected, actual });
+        }
+        check_finite("input", &buffer[..self.n()])?;
+        let mut scratch = vec![Complex::zero(); self.get_inplace_scratch_len()];
+        if cfg!(debug_assertions) {
+            self.process_staged(buffer, &mut scratch)?;
+        } else {
+            self.process_with_scratch(buffer, &mut scratch);
+        }
+        check_finite("output", &buffer[..self.m()])
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.n());
+        assert_eq!(output.len(), self.m());
+        assert_eq!(scratch.len(), self.get_outofplace_scratch_len());
+
+        // The expanded buffer already separates input from output, so no extra copy is needed.
+        let (expanded_buffer, scratch) = scratch.split_at_mut(self.l());
+        let (head, tail) = expanded_buffer.split_at_mut(self.n());
+        pointwise::multiply(head, input, &self.y_coefficients);
+        tail.fill(Complex::zero());
+
+        let stride = output.len().max(1);
+        self.convolve(output, stride, expanded_buffer, scratch);
+    }
 
-    fn get_scratch_len(&self) -> usize {
-        self.fft_forward.get_inplace_scratch_len() + self.l()
+    fn process_batch(&self, buffers: &mut [Complex<T>], stride: usize, count: usize) {
+        let mut scratch = vec![Complex::zero(); self.get_batch_scratch_len(count)];
+        self.process_batch_with_scratch(buffers, stride, count, &mut scratch);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        let len = self.get_buffer_len();
+        assert!(stride >= len);
+        if count == 0 {
+            return;
+        }
+        assert!(buffers.len() >= (count - 1) * stride + len);
+
+        if scratch.len() >= self.get_batch_scratch_len(count) {
+            let scratch = &mut scratch[..self.get_batch_scratch_len(count)];
+            self.process_batched(buffers, stride, count, scratch);
+        } else {
+            let scratch = &mut scratch[..self.get_inplace_scratch_len()];
+            for buffer in buffers.chunks_mut(stride).take(count) {
+                self.process_with_scratch(&mut buffer[..len], scratch);
+            }
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.fft_scratch_len() + self.l()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.get_inplace_scratch_len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let tables =
+            self.y_coefficients.len() + self.v_coefficients.len() + self.x_coefficients.len();
+        (tables + self.get_inplace_scratch_len()) * core::mem::size_of::<Complex<T>>()
+            + fft_memory_usage(&*self.fft_forward)
+            + fft_memory_usage(&*self.fft_inverse)
+            + self
+                .window
+                .as_ref()
+                .map_or(0, |window| window.len() * core::mem::size_of::<T>())
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::convolution::<T>(self.n(), self.m(), self.l())
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n(),
+            m: self.m(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+    /// Like the default, against a reference with the window, input offset, output scale and
+    /// delay folded into the plan applied around the [`NaiveCzt`].
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        let signal = verification_signal(self.n(), self.get_buffer_len());
+        let expected = self.naive_reference(&signal[..self.n()]);
+        let mut actual = signal;
+        self.process(&mut actual);
+        agrees_within(&expected, &actual[..self.m()], tolerance)
     }
 }
@@ -0,0 +1,44 @@
+use crate::fft::{num_complex::Complex, FftNum};
+use bytemuck::{Pod, PodCastError};
+
+use crate::{double_double::DoubleDouble, Czt};
+
+// SAFETY: two f64 under `repr(C)`, without padding, and every bit pattern is a value.
+unsafe impl bytemuck::Zeroable for DoubleDouble {}
+unsafe impl Pod for DoubleDouble {}
+
+/// Reinterprets raw bytes, e.g. from a file, mmap or packet, as interleaved `re, im` samples in
+/// native byte order. Fails if `bytes` is misaligned for `T` or not a whole number of samples.
+pub fn try_complex_from_bytes<T: Pod>(bytes: &[u8]) -> Result<&[Complex<T>], PodCastError> {
+    bytemuck::try_cast_slice(bytes)
+}
+
+pub fn try_complex_from_bytes_mut<T: Pod>(
+    bytes: &mut [u8],
+) -> Result<&mut [Complex<T>], PodCastError> {
+    bytemuck::try_cast_slice_mut(bytes)
+}
+
+/// Panics where [`try_complex_from_bytes`] fails.
+pub fn complex_from_bytes<T: Pod>(bytes: &[u8]) -> &[Complex<T>] {
+    bytemuck::cast_slice(bytes)
+}
+
+/// Panics where [`try_complex_from_bytes_mut`] fails.
+pub fn complex_from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> &mut [Complex<T>] {
+    bytemuck::cast_slice_mut(bytes)
+}
+
+pub fn complex_as_bytes<T: Pod>(samples: &[Complex<T>]) -> &[u8] {
+    bytemuck::cast_slice(samples)
+}
+
+pub fn complex_as_bytes_mut<T: Pod>(samples: &mut [Complex<T>]) -> &mut [u8] {
+    bytemuck::cast_slice_mut(samples)
+}
+
+/// Runs `plan` in place on a byte buffer of `get_buffer_len()` samples, see
+/// [`complex_from_bytes_mut`].
+pub fn process_bytes<T: FftNum + Pod>(plan: &dyn Czt<T>, bytes: &mut [u8]) {
+    plan.process(complex_from_bytes_mut(bytes));
+}
@@ -0,0 +1,125 @@
+//! C ABI over double-precision plans.
+//!
+//! Samples are `Complex<f64>`, which is `repr(C)` and so matches `double _Complex`, C++'s
+//! `std::complex<double>` and `struct { double re, im; }`. A plan is an opaque pointer from
+//! [`rustczt_plan_create`], released with [`rustczt_plan_destroy`]. Build a shared or static
+//! library with e.g. `cargo rustc --release --features capi --crate-type cdylib`.
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero};
+
+use crate::{Czt, CztError, CztPlanner};
+
+/// Opaque plan handle.
+pub struct RustCztPlan {
+    czt: Arc<dyn Czt<f64>>,
+}
+
+/// [`rustczt_plan_process`] transformed the buffer.
+pub const RUSTCZT_OK: i32 = 0;
+/// A pointer argument was null.
+pub const RUSTCZT_NULL_POINTER: i32 = -1;
+/// The buffer or scratch length does not match the plan.
+pub const RUSTCZT_BAD_LENGTH: i32 = -2;
+/// The plan cannot run.
+pub const RUSTCZT_INVALID_STATE: i32 = -3;
+
+/// Plans `n` inputs and `m` bins at `z_k = a w^-k`, or returns null if a length is zero, `a` or
+/// `w` is zero or not finite, or planning fails.
+#[no_mangle]
+pub extern "C" fn rustczt_plan_create(
+    n: usize,
+    m: usize,
+    a: Complex<f64>,
+    w: Complex<f64>,
+) -> *mut RustCztPlan {
+    catch_unwind(AssertUnwindSafe(|| {
+        CztPlanner::new().try_plan_czt_forward(n, m, a, w)
+    }))
+    .ok()
+    .and_then(Result::ok)
+    .map_or(core::ptr::null_mut(), |czt| {
+        Box::into_raw(Box::new(RustCztPlan { czt }))
+    })
+}
+
+/// Samples the buffer passed to [`rustczt_plan_process`] holds: `max(n, m)`, or 0 for null.
+///
+/// # Safety
+///
+/// `plan` is null or a live handle from [`rustczt_plan_create`].
+#[no_mangle]
+pub unsafe extern "C" fn rustczt_plan_buffer_len(plan: *const RustCztPlan) -> usize {
+    // SAFETY: the caller passes null or a live handle.
+    unsafe { plan.as_ref() }.map_or(0, |plan| plan.czt.get_buffer_len())
+}
+
+/// Scratch samples [`rustczt_plan_process`] takes, or 0 for null.
+///
+/// # Safety
+///
+/// `plan` is null or a live handle from [`rustczt_plan_create`].
+#[no_mangle]
+pub unsafe extern "C" fn rustczt_plan_scratch_len(plan: *const RustCztPlan) -> usize {
+    // SAFETY: the caller passes null or a live handle.
+    unsafe { plan.as_ref() }.map_or(0, |plan| plan.czt.get_inplace_scratch_len())
+}
+
+/// Transforms `buffer` in place: the first `n` samples are the input, the first `m` the bins.
+/// `buffer_len` must equal [`rustczt_plan_buffer_len`]. `scratch` may be null, in which case
+/// it is allocated; otherwise `scratch_len` must equal [`rustczt_plan_scratch_len`]. Returns
+/// [`RUSTCZT_OK`] or an error code, leaving `buffer` untouched on error.
+///
+/// # Safety
+///
+/// `plan` is null or a live handle, and `buffer` and a non-null `scratch` are valid for reads
+/// and writes of their lengths and do not overlap. A plan may be used from several threads at
+/// once, each with its own buffer and scratch.
+#[no_mangle]
+pub unsafe extern "C" fn rustczt_plan_process(
+    plan: *const RustCztPlan,
+    buffer: *mut Complex<f64>,
+    buffer_len: usize,
+    scratch: *mut Complex<f64>,
+    scratch_len: usize,
+) -> i32 {
+    // SAFETY: the caller passes null or a live handle.
+    let Some(plan) = (unsafe { plan.as_ref() }) else {
+        return RUSTCZT_NULL_POINTER;
+    };
+    if buffer.is_null() {
+        return RUSTCZT_NULL_POINTER;
+    }
+    let czt = &plan.czt;
+
+    // SAFETY: the caller guarantees both regions are valid and disjoint.
+    let buffer = unsafe { core::slice::from_raw_parts_mut(buffer, buffer_len) };
+    let result = if scratch.is_null() {
+        let mut scratch: Vec<_> = vec![Complex::zero(); czt.get_inplace_scratch_len()];
+        czt.try_process_with_scratch(buffer, &mut scratch)
+    } else {
+        // SAFETY: as for `buffer`.
+        let scratch = unsafe { core::slice::from_raw_parts_mut(scratch, scratch_len) };
+        czt.try_process_with_scratch(buffer, scratch)
+    };
+    match result {
+        Ok(()) => RUSTCZT_OK,
+        Err(CztError::BufferLength { .. } | CztError::ScratchLength { .. }) => RUSTCZT_BAD_LENGTH,
+        Err(CztError::InvalidState(_) | CztError::NonFinite { .. }) => RUSTCZT_INVALID_STATE,
+    }
+}
+
+/// Releases a plan. Null is ignored.
+///
+/// # Safety
+///
+/// `plan` is null or a live handle from [`rustczt_plan_create`], not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rustczt_plan_destroy(plan: *mut RustCztPlan) {
+    if !plan.is_null() {
+        // SAFETY: the handle came from `Box::into_raw` and is released only once.
+        drop(unsafe { Box::from_raw(plan) });
+    }
+}
@@ -0,0 +1,31 @@
+//! Free functions and plans with the argument order, defaults and conventions of other
+//! environments, for porting code that uses them. They plan with rustfft, so they need `std`.
+
+pub mod matlab;
+pub mod scipy;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, One},
+    FftNum,
+};
+
+/// `m`, `w` and `a` with the defaults MATLAB and SciPy share: the input length `n`,
+/// `exp(-2 pi i / m)` and 1.
+fn defaults<T: FftNum + Float>(
+    n: usize,
+    m: Option<usize>,
+    w: Option<Complex<T>>,
+    a: Option<Complex<T>>,
+) -> (usize, Complex<T>, Complex<T>) {
+    let m = m.unwrap_or(n);
+    assert!(m > 0, "m must be positive");
+    let w = w.unwrap_or_else(|| {
+        let angle = -core::f64::consts::TAU / m as f64;
+        Complex::new(
+            T::from_f64(angle.cos()).unwrap(),
+            T::from_f64(angle.sin()).unwrap(),
+        )
+    });
+    (m, w, a.unwrap_or_else(Complex::one))
+}
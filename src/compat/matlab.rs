@@ -0,0 +1,50 @@
+//! MATLAB's `czt(x, m, w, a)`.
+
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use super::defaults;
+use crate::CztPlanner;
+
+/// `y = czt(x, m, w, a)` with MATLAB's defaults for omitted arguments: `m = length(x)`,
+/// `w = exp(-2 pi i / m)` and `a = 1`.
+///
+/// Plans come from a planner kept per thread and sample type, so repeated calls with the same
+/// arguments reuse one plan.
+pub fn czt<T: FftNum + Float>(
+    x: &[Complex<T>],
+    m: Option<usize>,
+    w: Option<Complex<T>>,
+    a: Option<Complex<T>>,
+) -> Vec<Complex<T>> {
+    assert!(!x.is_empty(), "x must not be empty");
+    let (m, w, a) = defaults(x.len(), m, w, a);
+    let czt = with_planner(|planner| planner.plan_czt_forward(x.len(), m, a, w));
+    let mut buffer = x.to_vec();
+    buffer.resize(czt.get_buffer_len(), Complex::zero());
+    czt.process(&mut buffer);
+    buffer.truncate(m);
+    buffer
+}
+
+fn with_planner<T: FftNum + Float, R>(f: impl FnOnce(&mut CztPlanner<T>) -> R) -> R {
+    use alloc::boxed::Box;
+    use core::any::{Any, TypeId};
+    use std::{cell::RefCell, collections::HashMap};
+
+    std::thread_local! {
+        static PLANNERS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+    }
+    PLANNERS.with(|planners| {
+        let mut planners = planners.borrow_mut();
+        let planner = planners
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(CztPlanner::<T>::new()));
+        f(planner.downcast_mut().unwrap())
+    })
+}
@@ -0,0 +1,128 @@
+//! `scipy.signal`'s `czt`, `zoom_fft`, `czt_points`, `CZT` and `ZoomFFT`.
+//!
+//! Arguments SciPy lets default are `Option`s here: `m` defaults to the input length, `w` to
+//! `exp(-2 pi i / m)` and `a` to 1, so `czt(x, None, None, None)` is the DFT of `x`. The
+//! transform runs along a single slice; SciPy's `axis` is up to the caller.
+
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use super::defaults;
+use crate::{bluesteins::from_f64, freqz::Contour, CztPlanner};
+
+/// `scipy.signal.czt_points`: the `m` points `a w^-k` a [`Czt`] evaluates at.
+pub fn czt_points<T: FftNum + Float>(
+    m: usize,
+    w: Option<Complex<T>>,
+    a: Option<Complex<T>>,
+) -> Vec<Complex<T>> {
+    let (m, w, a) = defaults(m, Some(m), w, a);
+    let contour = Contour::new(m, a, w);
+    (0..m).map(|k| contour.point(k)).collect()
+}
+
+/// `scipy.signal.czt`.
+pub fn czt<T: FftNum + Float>(
+    x: &[Complex<T>],
+    m: Option<usize>,
+    w: Option<Complex<T>>,
+    a: Option<Complex<T>>,
+) -> Vec<Complex<T>> {
+    Czt::new(x.len(), m, w, a).call(x)
+}
+
+/// `scipy.signal.zoom_fft`, see [`ZoomFft::new`].
+pub fn zoom_fft<T: FftNum + Float>(
+    x: &[Complex<T>],
+    band: (T, T),
+    m: Option<usize>,
+    fs: T,
+    endpoint: bool,
+) -> Vec<Complex<T>> {
+    ZoomFft::new(x.len(), band, m, fs, endpoint).call(x)
+}
+
+/// `scipy.signal.CZT`: a plan for inputs of length `n`.
+pub struct Czt<T: FftNum> {
+    czt: Arc<dyn crate::Czt<T>>,
+    m: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> Czt<T> {
+    pub fn new(n: usize, m: Option<usize>, w: Option<Complex<T>>, a: Option<Complex<T>>) -> Self {
+        assert!(n > 0, "n must be positive");
+        let (m, w, a) = defaults(n, m, w, a);
+        Self {
+            czt: CztPlanner::new().plan_czt_forward(n, m, a, w),
+            m,
+            a,
+            w,
+        }
+    }
+
+    /// The transform of `x`, which must hold exactly `n` samples.
+    pub fn call(&self, x: &[Complex<T>]) -> Vec<Complex<T>> {
+        assert_eq!(x.len(), self.czt.get_input_len(), "x must have length n");
+        let mut buffer = x.to_vec();
+        buffer.resize(self.czt.get_buffer_len(), Complex::zero());
+        self.czt.process(&mut buffer);
+        buffer.truncate(self.m);
+        buffer
+    }
+
+    pub fn points(&self) -> Vec<Complex<T>> {
+        czt_points(self.m, Some(self.w), Some(self.a))
+    }
+}
+
+/// `scipy.signal.ZoomFFT`: `m` bins from `band.0` towards `band.1`, in the units of `fs`.
+///
+/// With `endpoint`, the last bin is at `band.1`; otherwise the bins are `(band.1 - band.0) / m`
+/// apart and stop one step short of it, as in SciPy. SciPy's scalar `fn` is the band
+/// `(0, fn)`, and its default `fs` is 2.
+pub struct ZoomFft<T: FftNum> {
+    czt: Czt<T>,
+    f1: T,
+    step: T,
+}
+
+impl<T: FftNum + Float> ZoomFft<T> {
+    pub fn new(n: usize, (f1, f2): (T, T), m: Option<usize>, fs: T, endpoint: bool) -> Self {
+        assert!(f1 <= f2, "band must be increasing");
+        assert!(fs > T::zero(), "fs must be positive");
+        let m = m.unwrap_or(n);
+        assert!(m > 0, "m must be positive");
+        let divisions = if endpoint && m > 1 { m - 1 } else { m };
+        let step = (f2.to_f64().unwrap() - f1.to_f64().unwrap()) / divisions as f64;
+        let tau = core::f64::consts::TAU / fs.to_f64().unwrap();
+        let w = from_f64(Complex::from_polar(1.0, -tau * step));
+        let a = from_f64(Complex::from_polar(1.0, tau * f1.to_f64().unwrap()));
+        Self {
+            czt: Czt::new(n, Some(m), Some(w), Some(a)),
+            f1,
+            step: T::from_f64(step).unwrap(),
+        }
+    }
+
+    pub fn call(&self, x: &[Complex<T>]) -> Vec<Complex<T>> {
+        self.czt.call(x)
+    }
+
+    pub fn points(&self) -> Vec<Complex<T>> {
+        self.czt.points()
+    }
+
+    /// Frequency of each bin, as SciPy users compute with `f1 + arange(m) * step`.
+    pub fn freqs(&self) -> Vec<T> {
+        (0..self.czt.m)
+            .map(|k| self.f1 + self.step * T::from_usize(k).unwrap())
+            .collect()
+    }
+}
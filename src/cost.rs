@@ -0,0 +1,146 @@
+use core::{mem::size_of, ops::Add};
+
+use crate::fft::{num_complex::Complex, FftNum};
+
+use crate::{
+    direct::{simd_width, LANES},
+    ConvolutionLength,
+};
+
+/// Rough work of one transform: real floating-point operations, and bytes read or written with
+/// every pass over a buffer counted once and cache reuse ignored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CostEstimate {
+    pub flops: usize,
+    pub bytes: usize,
+}
+
+impl Add for CostEstimate {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            flops: self.flops + other.flops,
+            bytes: self.bytes + other.bytes,
+        }
+    }
+}
+
+fn complex_bytes<T>(count: usize) -> usize {
+    count * size_of::<Complex<T>>()
+}
+
+/// The Goertzel recursion, one pass over the input per bin.
+pub fn goertzel<T: FftNum>(n: usize, m: usize) -> CostEstimate {
+    CostEstimate {
+        flops: 4 * n * m,
+        bytes: complex_bytes::<T>(n * m + m),
+    }
+}
+
+/// Direct summation with a complex multiply-add per input and bin.
+pub fn naive<T: FftNum>(n: usize, m: usize) -> CostEstimate {
+    CostEstimate {
+        flops: 8 * n * m,
+        bytes: complex_bytes::<T>(n * m + m),
+    }
+}
+
+/// [`DirectCzt`](crate::direct::DirectCzt): the chirps, and a complex multiply-add per input and
+/// bin, with the bins padded to whole blocks. The multiply-adds run on SIMD vectors and are
+/// counted once per vector, and every block streams the chirped input and its kernel window.
+pub fn direct<T: FftNum>(n: usize, m: usize) -> CostEstimate {
+    let padded = m.next_multiple_of(LANES);
+    let blocks = padded / LANES;
+    CostEstimate {
+        flops: 8 * n * padded / simd_width::<T>() + 6 * (n + m),
+        bytes: complex_bytes::<T>(3 * n + 3 * m)
+            + blocks * (complex_bytes::<T>(n) + 2 * (n + LANES - 1) * size_of::<T>()),
+    }
+}
+
+/// Bluestein's algorithm with the convolution length `length` picks.
+pub fn bluesteins<T: FftNum>(n: usize, m: usize, length: ConvolutionLength) -> CostEstimate {
+    convolution::<T>(n, m, length.for_len(n + m - 1))
+}
+
+/// One length-`l` FFT, streaming its buffer once per radix-4 pass.
+pub fn fft<T: FftNum>(l: usize) -> CostEstimate {
+    let log2 = (usize::BITS - l.leading_zeros()) as usize;
+    CostEstimate {
+        flops: 5 * l * log2,
+        bytes: complex_bytes::<T>(2 * l * log2.div_ceil(2)),
+    }
+}
+
+/// Bluestein's algorithm with a convolution of exactly `l` points: two length-`l` FFTs and the
+/// three pointwise complex multiplies.
+pub fn convolution<T: FftNum>(n: usize, m: usize, l: usize) -> CostEstimate {
+    let pointwise = CostEstimate {
+        flops: 6 * (n + l + m),
+        bytes: complex_bytes::<T>(3 * n + (l - n) + 3 * l + 3 * m),
+    };
+    fft::<T>(l) + fft::<T>(l) + pointwise
+}
+
+/// [`MixerCzt`](crate::mixer::MixerCzt) with decimation and filter length `design`: the mixer,
+/// the complex-by-real taps evaluated at the decimated outputs, and the small CZT.
+pub fn mixer<T: FftNum>(
+    n: usize,
+    m: usize,
+    (decimation, taps): (usize, usize),
+    length: ConvolutionLength,
+) -> CostEstimate {
+    let decimated = (n + taps - 2) / decimation + 1;
+    let filter = CostEstimate {
+        flops: 6 * n + 4 * taps * decimated + 6 * m,
+        bytes: complex_bytes::<T>(3 * n + taps * decimated + 3 * m)
+            + taps * decimated * size_of::<T>(),
+    };
+    filter + bluesteins::<T>(decimated, m, length)
+}
+
+/// [`RotationCzt`](crate::rotation::RotationCzt) with a length-`order` FFT: the rotation, the
+/// fold and the bin selection.
+pub fn rotation<T: FftNum>(n: usize, m: usize, order: usize) -> CostEstimate {
+    let pointwise = CostEstimate {
+        flops: 8 * n,
+        bytes: complex_bytes::<T>(4 * n + order + 2 * m),
+    };
+    pointwise + fft::<T>(order)
+}
+
+/// [`BlockedCzt`](crate::blocked::BlockedCzt) with blocks of `input_block` inputs and
+/// `output_block` bins on length-`l` convolutions: the chirps, one FFT per block of inputs, and a
+/// kernel FFT, product and inverse FFT per pair of blocks.
+pub fn blocked<T: FftNum>(
+    n: usize,
+    m: usize,
+    (input_block, output_block): (usize, usize),
+    l: usize,
+) -> CostEstimate {
+    let (inputs, outputs) = (n.div_ceil(input_block), m.div_ceil(output_block));
+    let (fft, pairs) = (fft::<T>(l), inputs * outputs);
+    CostEstimate {
+        flops: 6 * (n + m)
+            + inputs * fft.flops
+            + pairs * (2 * fft.flops + 6 * l + 2 * output_block),
+        bytes: complex_bytes::<T>(3 * (n + m) + inputs * l + pairs * (3 * l + 2 * output_block))
+            + (inputs + 2 * pairs) * fft.bytes,
+    }
+}
+
+/// The cheaper of the direct and Bluestein algorithms for `n` inputs and `m` bins, as a
+/// planner in [`PlannerStrategy::Auto`](crate::PlannerStrategy::Auto) would choose off narrow
+/// arcs of the unit circle.
+pub fn estimate_cost<T: FftNum>(n: usize, m: usize) -> CostEstimate {
+    let (direct, fft_based) = (
+        goertzel::<T>(n, m),
+        bluesteins::<T>(n, m, ConvolutionLength::default()),
+    );
+    if direct.flops <= fft_based.flops {
+        direct
+    } else {
+        fft_based
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{fft_provider::DefaultFftProvider, window::Window, Czt, CztPlanner, FftProvider};
+
+/// Builds [`Cqt`] plans, sharing one [`CztPlanner`] and its plan cache between them.
+pub struct CqtPlanner<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    planner: CztPlanner<T, P>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> CqtPlanner<T> {
+    pub fn new() -> Self {
+        Self::with_czt_planner(CztPlanner::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> Default for CqtPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> CqtPlanner<T, P> {
+    pub fn with_czt_planner(planner: CztPlanner<T, P>) -> Self {
+        Self { planner }
+    }
+
+    /// Plans `bins` bins at `f_min * 2^(k / bins_per_octave)` Hz, evaluated on frames `hop`
+    /// samples apart.
+    ///
+    /// Every bin is its own single-point contour over `ceil(Q * fs / f_k)` Hann-windowed samples,
+    /// `Q = 1 / (2^(1 / bins_per_octave) - 1)`, so the resolution is one bin spacing at every
+    /// frequency. The planner picks the direct algorithm for these.
+    pub fn plan_cqt(
+        &mut self,
+        sample_rate: T,
+        f_min: T,
+        bins_per_octave: usize,
+        bins: usize,
+        hop: usize,
+    ) -> Cqt<T> {
+        assert!(bins_per_octave > 0 && bins > 0);
+        assert!(hop > 0, "hop size must be positive");
+        let two = T::from_f64(2.0).unwrap();
+        let per_octave = T::from_usize(bins_per_octave).unwrap();
+        let q = (two.powf(per_octave.recip()) - T::one()).recip();
+        let freq = |k: usize| f_min * two.powf(T::from_usize(k).unwrap() / per_octave);
+        assert!(f_min > T::zero(), "f_min must be positive");
+        assert!(
+            freq(bins - 1) < sample_rate / two,
+            "highest bin must lie below Nyquist"
+        );
+
+        let one = T::one();
+        let two_pi = T::from_f64(core::f64::consts::TAU).unwrap();
+        let bands: Vec<_> = (0..bins)
+            .map(|k| {
+                let f = freq(k);
+                let len = (q * sample_rate / f).ceil().to_usize().unwrap().max(1);
+                let a = Complex::from_polar(one, two_pi * f / sample_rate);
+                let czt = self
+                    .planner
+                    .plan_czt_forward(len, 1, a, Complex::new(one, T::zero()));
+                let norm = T::from_usize(len).unwrap().recip();
+                let window = Window::<T>::Hann
+                    .coefficients(len)
+                    .into_iter()
+                    .map(|w| w * norm)
+                    .collect();
+                Band { f, window, czt }
+            })
+            .collect();
+
+        Cqt {
+            frame_len: bands[0].window.len(),
+            bands,
+            hop,
+            sample_rate,
+        }
+    }
+}
+
+/// Constant-Q transform. Frame `i` is centered on sample `i * hop + frame_len() / 2`, and every
+/// bin's window is centered there too.
+///
+/// Output is row-major with one row of `bins()` values per frame; each value is the
+/// window-weighted mean `sum_j w_j x_j e^(-2 pi i f_k j / fs) / N_k`.
+pub struct Cqt<T: FftNum> {
+    bands: Vec<Band<T>>,
+    frame_len: usize,
+    hop: usize,
+    sample_rate: T,
+}
+
+struct Band<T: FftNum> {
+    f: T,
+    /// Hann window divided by its length.
+    window: Vec<T>,
+    czt: Arc<dyn Czt<T>>,
+}
+
+impl<T: FftNum> Cqt<T> {
+    pub fn bins(&self) -> usize {
+        self.bands.len()
+    }
+
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Window length of the lowest bin, the span each frame covers.
+    pub fn frame_len(&self) -> usize {
+        self.frame_len
+    }
+
+    pub fn freqs(&self) -> Vec<T> {
+        self.bands.iter().map(|band| band.f).collect()
+    }
+
+    /// Window length of bin `k`.
+    pub fn window_len(&self, k: usize) -> usize {
+        self.bands[k].window.len()
+    }
+
+    pub fn num_frames(&self, len: usize) -> usize {
+        if len < self.frame_len {
+            0
+        } else {
+            (len - self.frame_len) / self.hop + 1
+        }
+    }
+
+    /// Time of the center of frame `i` in seconds.
+    pub fn time_of_frame(&self, i: usize) -> T {
+        T::from_usize(i * self.hop + self.frame_len / 2).unwrap() / self.sample_rate
+    }
+
+    pub fn process(&self, signal: &[Complex<T>]) -> Vec<Complex<T>> {
+        let mut output = vec![Complex::zero(); self.num_frames(signal.len()) * self.bins()];
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(signal, &mut output, &mut scratch);
+        output
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        signal: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        let frames = self.num_frames(signal.len());
+        assert_eq!(output.len(), frames * self.bins());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.frame_len);
+        for i in 0..frames {
+            let center = i * self.hop + self.frame_len / 2;
+            let row = &mut output[i * self.bins()..(i + 1) * self.bins()];
+            for (out, band) in row.iter_mut().zip(&self.bands) {
+                let len = band.window.len();
+                let start = center - len / 2;
+                let buffer = &mut buffer[..len];
+                for ((y, &x), &w) in buffer
+                    .iter_mut()
+                    .zip(&signal[start..start + len])
+                    .zip(&band.window)
+                {
+                    *y = x * w;
+                }
+                band.czt.process_with_scratch(
+                    buffer,
+                    &mut scratch[..band.czt.get_inplace_scratch_len()],
+                );
+                *out = buffer[0];
+            }
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        let czt_scratch = self
+            .bands
+            .iter()
+            .map(|band| band.czt.get_inplace_scratch_len())
+            .max()
+            .unwrap_or(0);
+        self.frame_len + czt_scratch
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    collections::HashMap,
+    os::raw::c_int,
+    sync::{Arc, Mutex},
+};
+
+use crate::fft::{num_complex::Complex, num_traits::Float, FftNum};
+use cudarc::{
+    cufft::{self, result::CufftError, sys::cufftType},
+    driver::{
+        CudaContext, CudaFunction, CudaSlice, CudaStream, DevicePtrMut, DeviceRepr, LaunchConfig,
+        PinnedHostSlice, PushKernelArg, ValidAsZeroBits,
+    },
+    nvrtc,
+};
+use rustfft::FftPlanner;
+
+use crate::{bluesteins::BluesteinsAlgorithm, Czt, CztParams};
+
+const CUFFT_FORWARD: c_int = -1;
+const CUFFT_INVERSE: c_int = 1;
+
+/// Chirp multiplies on interleaved complex data; `real` is substituted per precision.
+const KERNELS: &str = r#"
+extern "C" __global__ void chirp_in(const real* input, const real* y, real* work,
+                                    unsigned long long n, unsigned long long l,
+                                    unsigned long long total) {
+    unsigned long long t = (unsigned long long)blockIdx.x * blockDim.x + threadIdx.x;
+    if (t >= total) return;
+    unsigned long long b = t / l, i = t % l;
+    real re = 0, im = 0;
+    if (i < n) {
+        real xr = input[2 * (b * n + i)], xi = input[2 * (b * n + i) + 1];
+        re = xr * y[2 * i] - xi * y[2 * i + 1];
+        im = xr * y[2 * i + 1] + xi * y[2 * i];
+    }
+    work[2 * t] = re;
+    work[2 * t + 1] = im;
+}
+
+extern "C" __global__ void multiply(real* work, const real* v, unsigned long long l,
+                                    unsigned long long total) {
+    unsigned long long t = (unsigned long long)blockIdx.x * blockDim.x + threadIdx.x;
+    if (t >= total) return;
+    unsigned long long i = t % l;
+    real re = work[2 * t], im = work[2 * t + 1];
+    work[2 * t] = re * v[2 * i] - im * v[2 * i + 1];
+    work[2 * t + 1] = re * v[2 * i + 1] + im * v[2 * i];
+}
+
+extern "C" __global__ void chirp_out(const real* work, const real* x, real* output,
+                                     unsigned long long m, unsigned long long l,
+                                     unsigned long long total) {
+    unsigned long long t = (unsigned long long)blockIdx.x * blockDim.x + threadIdx.x;
+    if (t >= total) return;
+    unsigned long long b = t / m, k = t % m;
+    real re = work[2 * (b * l + k)], im = work[2 * (b * l + k) + 1];
+    output[2 * t] = re * x[2 * k] - im * x[2 * k + 1];
+    output[2 * t + 1] = re * x[2 * k + 1] + im * x[2 * k];
+}
+"#;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+
+    impl Sealed for f64 {}
+}
+
+/// Floating point types with cuFFT complex-to-complex transforms.
+pub trait CudaFloat: FftNum + Float + DeviceRepr + ValidAsZeroBits + sealed::Sealed {
+    #[doc(hidden)]
+    const C_TYPE: &'static str;
+    #[doc(hidden)]
+    const FFT_TYPE: cufftType;
+
+    /// # Safety
+    ///
+    /// `plan` must be a live plan of `Self::FFT_TYPE` and `data` must cover its whole batch.
+    #[doc(hidden)]
+    unsafe fn exec(
+        plan: cufft::sys::cufftHandle,
+        data: u64,
+        direction: c_int,
+    ) -> Result<(), CufftError>;
+}
+
+impl CudaFloat for f32 {
+    const C_TYPE: &'static str = "float";
+    const FFT_TYPE: cufftType = cufftType::CUFFT_C2C;
+
+    unsafe fn exec(
+        plan: cufft::sys::cufftHandle,
+        data: u64,
+        direction: c_int,
+    ) -> Result<(), CufftError> {
+        let data = data as *mut cufft::sys::cufftComplex;
+        cufft::result::exec_c2c(plan, data, data, direction)
+    }
+}
+
+impl CudaFloat for f64 {
+    const C_TYPE: &'static str = "double";
+    const FFT_TYPE: cufftType = cufftType::CUFFT_Z2Z;
+
+    unsafe fn exec(
+        plan: cufft::sys::cufftHandle,
+        data: u64,
+        direction: c_int,
+    ) -> Result<(), CufftError> {
+        let data = data as *mut cufft::sys::cufftDoubleComplex;
+        cufft::result::exec_z2z(plan, data, data, direction)
+    }
+}
+
+/// A batched in-place cuFFT plan.
+struct Plan(cufft::sys::cufftHandle);
+
+impl Plan {
+    fn new<T: CudaFloat>(l: usize, count: usize, stream: &CudaStream) -> Self {
+        let mut len = l as c_int;
+        let plan = unsafe {
+            cufft::result::plan_many(
+                1,
+                &mut len,
+                std::ptr::null_mut(),
+                1,
+                l as c_int,
+                std::ptr::null_mut(),
+                1,
+                l as c_int,
+                T::FFT_TYPE,
+                count as c_int,
+            )
+        }
+        .expect("cuFFT failed to create a plan");
+        unsafe { cufft::result::set_stream(plan, stream.cu_stream() as _) }
+            .expect("cuFFT rejected the stream");
+        Self(plan)
+    }
+}
+
+impl Drop for Plan {
+    fn drop(&mut self) {
+        unsafe { cufft::result::destroy(self.0) }.expect("cuFFT failed to destroy a plan");
+    }
+}
+
+/// Pinned host staging and device buffers for one batch size.
+struct Batch<T: CudaFloat> {
+    plan: Plan,
+    host_input: PinnedHostSlice<T>,
+    host_output: PinnedHostSlice<T>,
+    input: CudaSlice<T>,
+    work: CudaSlice<T>,
+    output: CudaSlice<T>,
+}
+
+/// Bluestein's algorithm on a CUDA device: cuFFT for the convolution, NVRTC-compiled kernels for
+/// the chirp multiplies, and page-locked host buffers for the transfers.
+///
+/// Device resources are allocated the first time a batch size is processed and reused after that.
+pub struct CudaBluesteins<T: CudaFloat> {
+    stream: Arc<CudaStream>,
+    chirp_in: CudaFunction,
+    multiply: CudaFunction,
+    chirp_out: CudaFunction,
+    y_coefficients: CudaSlice<T>,
+    v_coefficients: CudaSlice<T>,
+    x_coefficients: CudaSlice<T>,
+    n: usize,
+    m: usize,
+    l: usize,
+    batches: Mutex<HashMap<usize, Batch<T>>>,
+    params: CztParams<T>,
+}
+
+impl<T: CudaFloat> CudaBluesteins<T> {
+    pub fn new(
+        context: &Arc<CudaContext>,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Self {
+        let reference = BluesteinsAlgorithm::new(n, m, a, w, 0, &mut FftPlanner::new());
+        let l = reference.v_coefficients().len();
+        let stream = context
+            .new_stream()
+            .expect("failed to create a CUDA stream");
+
+        let interleave = |values: &mut dyn Iterator<Item = Complex<T>>| -> CudaSlice<T> {
+            let values: Vec<T> = values.flat_map(|c| [c.re, c.im]).collect();
+            stream
+                .clone_htod(&values)
+                .expect("failed to upload CZT coefficients")
+        };
+        let y_coefficients = interleave(&mut reference.y_coefficients().iter().copied());
+        let v_coefficients = interleave(&mut reference.v_coefficients().iter().copied());
+        let x_coefficients = interleave(&mut reference.x_coefficients().iter().copied());
+
+        let source = format!("typedef {} real;\n{KERNELS}", T::C_TYPE);
+        let ptx = nvrtc::compile_ptx(source).expect("NVRTC failed to compile the CZT kernels");
+        let module = context
+            .load_module(ptx)
+            .expect("failed to load the CZT kernels");
+        let function = |name| {
+            module
+                .load_function(name)
+                .expect("CZT kernel missing from module")
+        };
+
+        Self {
+            chirp_in: function("chirp_in"),
+            multiply: function("multiply"),
+            chirp_out: function("chirp_out"),
+            stream,
+            y_coefficients,
+            v_coefficients,
+            x_coefficients,
+            n,
+            m,
+            l,
+            batches: Mutex::new(HashMap::new()),
+            params: reference.params(),
+        }
+    }
+
+    fn allocate(&self, count: usize) -> Batch<T> {
+        let context = self.stream.context();
+        let stream = &self.stream;
+        unsafe {
+            Batch {
+                plan: Plan::new::<T>(self.l, count, stream),
+                host_input: context
+                    .alloc_pinned(2 * count * self.n)
+                    .expect("failed to allocate pinned memory"),
+                host_output: context
+                    .alloc_pinned(2 * count * self.m)
+                    .expect("failed to allocate pinned memory"),
+                input: stream
+                    .alloc(2 * count * self.n)
+                    .expect("failed to allocate device memory"),
+                work: stream
+                    .alloc(2 * count * self.l)
+                    .expect("failed to allocate device memory"),
+                output: stream
+                    .alloc(2 * count * self.m)
+                    .expect("failed to allocate device memory"),
+            }
+        }
+    }
+
+    fn run(&self, batch: &mut Batch<T>, count: usize) {
+        let stream = &self.stream;
+        let (n, m, l) = (self.n as u64, self.m as u64, self.l as u64);
+        let (padded, bins) = ((count * self.l) as u64, (count * self.m) as u64);
+
+        stream
+            .memcpy_htod(&batch.host_input, &mut batch.input)
+            .expect("failed to copy signals to the device");
+        unsafe {
+            stream
+                .launch_builder(&self.chirp_in)
+                .arg(&batch.input)
+                .arg(&self.y_coefficients)
+                .arg(&mut batch.work)
+                .arg(&n)
+                .arg(&l)
+                .arg(&padded)
+                .launch(LaunchConfig::for_num_elems(padded as u32))
+                .expect("failed to launch chirp_in");
+        }
+        self.transform(batch, CUFFT_FORWARD);
+        unsafe {
+            stream
+                .launch_builder(&self.multiply)
+                .arg(&mut batch.work)
+                .arg(&self.v_coefficients)
+                .arg(&l)
+                .arg(&padded)
+                .launch(LaunchConfig::for_num_elems(padded as u32))
+                .expect("failed to launch multiply");
+        }
+        self.transform(batch, CUFFT_INVERSE);
+        unsafe {
+            stream
+                .launch_builder(&self.chirp_out)
+                .arg(&batch.work)
+                .arg(&self.x_coefficients)
+                .arg(&mut batch.output)
+                .arg(&m)
+                .arg(&l)
+                .arg(&bins)
+                .launch(LaunchConfig::for_num_elems(bins as u32))
+                .expect("failed to launch chirp_out");
+        }
+        stream
+            .memcpy_dtoh(&batch.output, &mut batch.host_output)
+            .expect("failed to copy spectra from the device");
+    }
+
+    fn transform(&self, batch: &mut Batch<T>, direction: c_int) {
+        let (data, _sync) = batch.work.device_ptr_mut(&self.stream);
+        unsafe { T::exec(batch.plan.0, data, direction) }.expect("cuFFT failed to execute");
+    }
+}
+
+impl<T: CudaFloat> Czt<T> for CudaBluesteins<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+
+        let len = buffer.len();
+        self.process_batch_with_scratch(buffer, len, 1, scratch);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        _scratch: &mut [Complex<T>],
+    ) {
+        let len = self.get_buffer_len();
+        assert!(stride >= len);
+        if count == 0 {
+            return;
+        }
+        assert!(buffers.len() >= (count - 1) * stride + len);
+
+        let mut batches = self.batches.lock().unwrap();
+        let batch = batches.entry(count).or_insert_with(|| self.allocate(count));
+
+        let host_input = batch
+            .host_input
+            .as_mut_slice()
+            .expect("failed to access pinned memory");
+        for (packed, buffer) in host_input
+            .chunks_exact_mut(2 * self.n)
+            .zip(buffers.chunks(stride))
+        {
+            for (packed, x) in packed.chunks_exact_mut(2).zip(&buffer[..self.n]) {
+                packed[0] = x.re;
+                packed[1] = x.im;
+            }
+        }
+
+        self.run(batch, count);
+
+        let host_output = batch
+            .host_output
+            .as_slice()
+            .expect("failed to synchronize with the device");
+        for (bins, buffer) in host_output
+            .chunks_exact(2 * self.m)
+            .zip(buffers.chunks_mut(stride))
+        {
+            for (x, bin) in buffer[..self.m].iter_mut().zip(bins.chunks_exact(2)) {
+                *x = Complex::new(bin[0], bin[1]);
+            }
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<T> {
+        self.params
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::sync::Arc;
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::Czt;
+
+/// Two-dimensional CZT of a row-major `rows x cols` buffer.
+///
+/// Every row (length `cols`) is transformed with `row_czt`, then every column (length `rows`) with
+/// `col_czt`. Columns are transposed into scratch so both passes work on contiguous memory.
+pub struct Czt2d<T: FftNum> {
+    row_czt: Arc<dyn Czt<T>>,
+    col_czt: Arc<dyn Czt<T>>,
+}
+
+impl<T: FftNum> Czt2d<T> {
+    pub fn new(row_czt: Arc<dyn Czt<T>>, col_czt: Arc<dyn Czt<T>>) -> Self {
+        assert_eq!(row_czt.get_input_len(), row_czt.get_output_len());
+        assert_eq!(col_czt.get_input_len(), col_czt.get_output_len());
+
+        Self { row_czt, col_czt }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.col_czt.get_input_len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.row_czt.get_input_len()
+    }
+
+    pub fn process(&self, buffer: &mut [Complex<T>]) {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(buffer, &mut scratch);
+    }
+
+    pub fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let (rows, cols) = (self.rows(), self.cols());
+        assert_eq!(buffer.len(), rows * cols);
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (transposed, scratch) = scratch.split_at_mut(rows * cols);
+
+        self.row_czt.process_batch_with_scratch(
+            buffer,
+            cols,
+            rows,
+            &mut scratch[..self.row_czt.get_inplace_scratch_len()],
+        );
+
+        transpose::transpose(buffer, transposed, cols, rows);
+        self.col_czt.process_batch_with_scratch(
+            transposed,
+            rows,
+            cols,
+            &mut scratch[..self.col_czt.get_inplace_scratch_len()],
+        );
+        transpose::transpose(transposed, buffer, rows, cols);
+    }
+
+    /// Multithreaded variant of [`process`](Self::process): rows and columns are transformed in
+    /// parallel on the rayon pool, with a tiled parallel transpose between the passes.
+    #[cfg(feature = "rayon")]
+    pub fn process_parallel(&self, buffer: &mut [Complex<T>]) {
+        use rayon::prelude::*;
+
+        let (rows, cols) = (self.rows(), self.cols());
+        assert_eq!(buffer.len(), rows * cols);
+        if buffer.is_empty() {
+            return;
+        }
+
+        let transform = |czt: &Arc<dyn Czt<T>>, data: &mut [Complex<T>], len: usize| {
+            data.par_chunks_mut(len).for_each_init(
+                || vec![Complex::zero(); czt.get_inplace_scratch_len()],
+                |scratch, line| czt.process_with_scratch(line, scratch),
+            );
+        };
+
+        let mut transposed = vec![Complex::zero(); rows * cols];
+        transform(&self.row_czt, buffer, cols);
+        transpose_parallel(buffer, &mut transposed, cols, rows);
+        transform(&self.col_czt, &mut transposed, rows);
+        transpose_parallel(&transposed, buffer, rows, cols);
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.rows() * self.cols()
+            + self
+                .row_czt
+                .get_inplace_scratch_len()
+                .max(self.col_czt.get_inplace_scratch_len())
+    }
+
+    /// Heap footprint in bytes, see [`Czt::get_memory_usage`]. A plan shared between rows and
+    /// columns is counted once.
+    pub fn get_memory_usage(&self) -> usize {
+        let size = core::mem::size_of::<Complex<T>>();
+        let tables =
+            |czt: &Arc<dyn Czt<T>>| czt.get_memory_usage() - czt.get_inplace_scratch_len() * size;
+        let mut usage = tables(&self.row_czt) + self.get_scratch_len() * size;
+        if !Arc::ptr_eq(&self.row_czt, &self.col_czt) {
+            usage += tables(&self.col_czt);
+        }
+        usage
+    }
+}
+
+/// Side of the square tiles the parallel transpose copies at a time.
+#[cfg(feature = "rayon")]
+const TRANSPOSE_TILE: usize = 32;
+
+/// Transposes a row-major `height x width` matrix; each task owns a band of output rows and walks
+/// it tile by tile so reads and writes both stay within a few cache lines.
+#[cfg(feature = "rayon")]
+fn transpose_parallel<T: Copy + Send + Sync>(
+    input: &[T],
+    output: &mut [T],
+    width: usize,
+    height: usize,
+) {
+    use rayon::prelude::*;
+
+    output
+        .par_chunks_mut(TRANSPOSE_TILE * height)
+        .enumerate()
+        .for_each(|(band, output)| {
+            let first_col = band * TRANSPOSE_TILE;
+            let band_width = output.len() / height;
+            for first_row in (0..height).step_by(TRANSPOSE_TILE) {
+                let last_row = (first_row + TRANSPOSE_TILE).min(height);
+                for c in 0..band_width {
+                    for r in first_row..last_row {
+                        output[c * height + r] = input[r * width + first_col + c];
+                    }
+                }
+            }
+        });
+}
@@ -0,0 +1,89 @@
+use alloc::sync::Arc;
+
+use crate::fft::{num_complex::Complex, Direction, Fft, FftDirection, FftNum, Length};
+
+use crate::Czt;
+
+/// Exposes a DFT-equivalent CZT plan (`a = 1`, `w = exp(-+2 pi i / n)`, `m = n`) through
+/// `rustfft::Fft`.
+///
+/// The adapter trusts the caller that the wrapped plan evaluates the DFT in `direction`; it only
+/// checks that input and output lengths agree.
+pub struct CztFft<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+    direction: FftDirection,
+}
+
+impl<T: FftNum> CztFft<T> {
+    pub fn new(czt: Arc<dyn Czt<T>>, direction: FftDirection) -> Self {
+        assert_eq!(czt.get_input_len(), czt.get_output_len());
+
+        Self { czt, direction }
+    }
+}
+
+impl<T: FftNum> Length for CztFft<T> {
+    fn len(&self) -> usize {
+        self.czt.get_input_len()
+    }
+}
+
+impl<T: FftNum> Direction for CztFft<T> {
+    fn fft_direction(&self) -> FftDirection {
+        self.direction
+    }
+}
+
+impl<T: FftNum> Fft<T> for CztFft<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len() % self.len(), 0);
+        assert!(scratch.len() >= self.get_inplace_scratch_len());
+
+        let scratch = &mut scratch[..self.czt.get_inplace_scratch_len()];
+        for chunk in buffer.chunks_exact_mut(self.len()) {
+            self.czt.process_with_scratch(chunk, scratch);
+        }
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.process_immutable_with_scratch(input, output, scratch);
+    }
+
+    /// The wrapped plan never writes its input, so this is the out-of-place transform.
+    fn process_immutable_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), output.len());
+        assert_eq!(input.len() % self.len(), 0);
+        assert!(scratch.len() >= self.get_immutable_scratch_len());
+
+        let scratch = &mut scratch[..self.czt.get_outofplace_scratch_len()];
+        for (input, output) in input
+            .chunks_exact(self.len())
+            .zip(output.chunks_exact_mut(self.len()))
+        {
+            self.czt
+                .process_outofplace_with_scratch(input, output, scratch);
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.czt.get_inplace_scratch_len()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.czt.get_outofplace_scratch_len()
+    }
+
+    fn get_immutable_scratch_len(&self) -> usize {
+        self.czt.get_outofplace_scratch_len()
+    }
+}
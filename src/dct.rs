@@ -0,0 +1,153 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::f64::consts::PI;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, One, Zero},
+    FftNum,
+};
+
+use crate::{bluesteins::from_f64, fft_provider::DefaultFftProvider, Czt, CztPlanner, FftProvider};
+
+/// Real-to-real trigonometric transforms, unnormalized as in rustdct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DctType {
+    /// `X_k = sum x_j cos(pi k (2j + 1) / 2N)`
+    Dct2,
+    /// `X_k = x_0 / 2 + sum_{j >= 1} x_j cos(pi j (2k + 1) / 2N)`, the inverse of `Dct2` up to
+    /// a factor `N / 2`.
+    Dct3,
+    /// `X_k = sum x_j sin(pi (k + 1) (2j + 1) / 2N)`
+    Dst2,
+    /// `X_k = (-1)^k x_(N-1) / 2 + sum_{j < N-1} x_j sin(pi (j + 1) (2k + 1) / 2N)`, the inverse
+    /// of `Dst2` up to a factor `N / 2`.
+    Dst3,
+}
+
+/// Builds [`Dct`] plans, sharing one [`CztPlanner`] and its plan cache between them.
+pub struct DctPlanner<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    planner: CztPlanner<T, P>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> DctPlanner<T> {
+    pub fn new() -> Self {
+        Self::with_czt_planner(CztPlanner::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> Default for DctPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> DctPlanner<T, P> {
+    pub fn with_czt_planner(planner: CztPlanner<T, P>) -> Self {
+        Self { planner }
+    }
+
+    pub fn plan_dct2(&mut self, n: usize) -> Dct<T> {
+        self.plan(n, DctType::Dct2)
+    }
+
+    pub fn plan_dct3(&mut self, n: usize) -> Dct<T> {
+        self.plan(n, DctType::Dct3)
+    }
+
+    pub fn plan_dst2(&mut self, n: usize) -> Dct<T> {
+        self.plan(n, DctType::Dst2)
+    }
+
+    pub fn plan_dst3(&mut self, n: usize) -> Dct<T> {
+        self.plan(n, DctType::Dst3)
+    }
+
+    /// Every type is `Re(post_k sum_j pre_j x_j e^(-i pi j k / N))`: a CZT along the upper half of
+    /// the unit circle, with the half-sample shifts folded into the twiddles on either side.
+    pub fn plan(&mut self, n: usize, kind: DctType) -> Dct<T> {
+        assert!(n > 0);
+        let nf = n as f64;
+        let twiddle = |phase: f64| from_f64(Complex::from_polar(1.0, -PI * phase / nf));
+        let half = T::from_f64(0.5).unwrap();
+        // Multiplying by i turns the real part into minus the imaginary part, for the sines.
+        let i = Complex::new(T::zero(), T::one());
+
+        let (pre, post): (Vec<_>, Vec<_>) = match kind {
+            DctType::Dct2 => (
+                vec![Complex::one(); n],
+                (0..n).map(|k| twiddle(k as f64 / 2.0)).collect(),
+            ),
+            DctType::Dct3 => {
+                let mut pre: Vec<_> = (0..n).map(|j| twiddle(j as f64 / 2.0)).collect();
+                pre[0] = pre[0] * half;
+                (pre, vec![Complex::one(); n])
+            }
+            DctType::Dst2 => (
+                (0..n).map(|j| twiddle(j as f64)).collect(),
+                (0..n).map(|k| i * twiddle((k + 1) as f64 / 2.0)).collect(),
+            ),
+            DctType::Dst3 => {
+                let mut pre: Vec<_> = (0..n).map(|j| twiddle((j + 1) as f64 / 2.0)).collect();
+                pre[n - 1] = pre[n - 1] * half;
+                (pre, (0..n).map(|k| i * twiddle(k as f64)).collect())
+            }
+        };
+
+        let czt = self
+            .planner
+            .plan_czt_forward(n, n, Complex::one(), twiddle(1.0));
+        Dct {
+            kind,
+            czt,
+            pre,
+            post,
+        }
+    }
+}
+
+/// A DCT or DST of any length, see [`DctType`].
+pub struct Dct<T: FftNum> {
+    kind: DctType,
+    czt: Arc<dyn Czt<T>>,
+    pre: Vec<Complex<T>>,
+    post: Vec<Complex<T>>,
+}
+
+impl<T: FftNum> Dct<T> {
+    pub fn kind(&self) -> DctType {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.pre.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre.is_empty()
+    }
+
+    pub fn process(&self, buffer: &mut [T]) {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(buffer, &mut scratch);
+    }
+
+    pub fn process_with_scratch(&self, buffer: &mut [T], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.len());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (spectrum, scratch) = scratch.split_at_mut(self.len());
+        for ((y, &x), &p) in spectrum.iter_mut().zip(&*buffer).zip(&self.pre) {
+            *y = p * x;
+        }
+        self.czt.process_with_scratch(spectrum, scratch);
+        for ((x, y), &p) in buffer.iter_mut().zip(&*spectrum).zip(&self.post) {
+            *x = (*y * p).re;
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.len() + self.czt.get_inplace_scratch_len()
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::sync::Arc;
+
+use crate::fft::{num_complex::Complex, num_traits::Float, Fft, FftDirection, FftNum};
+
+use crate::{
+    bluesteins::from_f64,
+    cost::{self, CostEstimate},
+    fft_provider::fft_memory_usage,
+    Czt, CztParams, FftProvider,
+};
+
+/// A CZT that is a plain DFT, `a = 1`, `m = n` and `w = e^(-+2 pi i / n)`, run as one FFT.
+///
+/// The planners return it for such contours instead of a length-`l >= 2n - 1` convolution.
+pub struct DftCzt<T: FftNum> {
+    fft: Arc<dyn Fft<T>>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> DftCzt<T> {
+    pub fn new(n: usize, direction: FftDirection, fft_provider: &mut dyn FftProvider<T>) -> Self {
+        let fft = match direction {
+            FftDirection::Forward => fft_provider.plan_forward(n),
+            FftDirection::Inverse => fft_provider.plan_inverse(n),
+        };
+        Self {
+            fft,
+            w: dft_point(n, direction),
+        }
+    }
+
+    /// The direction of the DFT the contour evaluates, if it is one: `a = 1`, `m = n` and `w`
+    /// equal to `e^(-+2 pi i / n)` up to the rounding of `T`.
+    pub fn direction(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Option<FftDirection> {
+        if n == 0 || m != n || a != Complex::new(T::one(), T::zero()) {
+            return None;
+        }
+        let close = |z: Complex<T>| {
+            (w.re - z.re).abs() <= T::epsilon() && (w.im - z.im).abs() <= T::epsilon()
+        };
+        [FftDirection::Forward, FftDirection::Inverse]
+            .into_iter()
+            .find(|&direction| close(dft_point(n, direction)))
+    }
+}
+
+/// `e^(-+2 pi i / n)`, rounded once from `f64`.
+fn dft_point<T: FftNum + Float>(n: usize, direction: FftDirection) -> Complex<T> {
+    let sign = match direction {
+        FftDirection::Forward => -1.0,
+        FftDirection::Inverse => 1.0,
+    };
+    from_f64(Complex::from_polar(
+        1.0,
+        sign * core::f64::consts::TAU / n as f64,
+    ))
+}
+
+impl<T: FftNum> Czt<T> for DftCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        self.fft.process_with_scratch(buffer, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.fft.get_inplace_scratch_len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.fft.len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.fft.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.fft.len(),
+            m: self.fft.len(),
+            a: Complex::new(T::one(), T::zero()),
+            w: self.w,
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        fft_memory_usage(&*self.fft)
+            + self.get_inplace_scratch_len() * core::mem::size_of::<Complex<T>>()
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::fft::<T>(self.fft.len())
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{
+    bluesteins::polar_pow,
+    cost::{self, CostEstimate},
+    pointwise::{self, cast},
+    Czt, CztParams,
+};
+use wide::{f32x8, f64x4};
+
+/// Bins evaluated together, one per SIMD lane.
+pub(crate) const LANES: usize = 8;
+
+/// Lanes of one vector operation in the kernel for `T`: a whole `f32x8`, half of the two
+/// `f64x4`, and a single lane in the scalar loop for other types.
+pub(crate) fn simd_width<T: 'static>() -> usize {
+    if TypeId::of::<T>() == TypeId::of::<f32>() {
+        8
+    } else if TypeId::of::<T>() == TypeId::of::<f64>() {
+        4
+    } else {
+        1
+    }
+}
+
+/// Direct `O(n m)` evaluation as Bluestein's convolution, without FFTs.
+///
+/// With `(j - c)(k - d) = ((j - c)^2 + (k - d)^2 - (k - j - d + c)^2) / 2` for the centers `c` and
+/// `d` of the two index ranges, and the remaining `j k - (j - c)(k - d) = j d + c (k - d)` folded
+/// into the chirps, every bin is a dot product of the chirped input with a window of one
+/// precomputed chirp table. Eight consecutive bins read overlapping windows, so they are
+/// accumulated together from planar copies of the table, in explicit `wide` vectors for `f64`
+/// (two `f64x4` per part) and `f32` (one `f32x8`). On x86_64 with `std` the kernels are also
+/// compiled for AVX and FMA and picked at runtime.
+///
+/// Competes with the FFT-based plans for medium sizes and needs no FFT at all.
+pub struct DirectCzt<T: FftNum> {
+    /// `a^-j w^(j d + (j - c)^2 / 2)`
+    y_coefficients: Vec<Complex<T>>,
+    /// Real and imaginary parts of `w^-(t - d + c)^2 / 2` for `t = k - j` from `1 - n`, padded
+    /// with zeros to whole blocks of bins.
+    kernel_re: Vec<T>,
+    kernel_im: Vec<T>,
+    /// `w^(c (k - d) + (k - d)^2 / 2)`
+    x_coefficients: Vec<Complex<T>>,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> DirectCzt<T> {
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        assert!(n > 0 && m > 0);
+        let (c, d) = ((n as i128 - 1) / 2, (m as i128 - 1) / 2);
+        let y_coefficients = (0..n as i128)
+            .map(|j| polar_pow(a, -2 * j) * polar_pow(w, 2 * j * d + (j - c).pow(2)))
+            .collect();
+        let (kernel_re, kernel_im) = (1 - n as i128..m.next_multiple_of(LANES) as i128)
+            .map(|t| {
+                if t < m as i128 {
+                    polar_pow(w, -(t - d + c).pow(2))
+                } else {
+                    Complex::zero()
+                }
+            })
+            .map(|v| (v.re, v.im))
+            .unzip();
+        let x_coefficients = (0..m as i128)
+            .map(|k| polar_pow(w, 2 * c * (k - d) + (k - d).pow(2)))
+            .collect();
+
+        Self {
+            y_coefficients,
+            kernel_re,
+            kernel_im,
+            x_coefficients,
+            a,
+            w,
+        }
+    }
+
+    /// Rough growth of the rounding error with the input length, in units of `T::epsilon()`:
+    /// every bin sums `n` rounded products, whose errors add up like `sqrt(n)`.
+    pub fn error_growth(n: usize) -> f64 {
+        (n as f64).sqrt()
+    }
+}
+
+impl<T: FftNum> Czt<T> for DirectCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let n = self.y_coefficients.len();
+        pointwise::multiply(scratch, &buffer[..n], &self.y_coefficients);
+        for (k, bins) in (0..)
+            .step_by(LANES)
+            .zip(buffer[..self.get_output_len()].chunks_mut(LANES))
+        {
+            let block = block(
+                scratch,
+                &self.kernel_re[k..k + n - 1 + LANES],
+                &self.kernel_im[k..k + n - 1 + LANES],
+            );
+            for ((out, sum), &x) in bins.iter_mut().zip(block).zip(&self.x_coefficients[k..]) {
+                *out = sum * x;
+            }
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.y_coefficients.len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.y_coefficients.len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.x_coefficients.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.get_input_len(),
+            m: self.get_output_len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let complex = core::mem::size_of::<Complex<T>>();
+        (self.y_coefficients.len() * 2 + self.x_coefficients.len()) * complex
+            + self.kernel_re.len() * 2 * core::mem::size_of::<T>()
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::direct::<T>(self.get_input_len(), self.get_output_len())
+    }
+}
+
+/// `sum_j y_j v_(l + n - 1 - j)` for every lane `l`, with `v` the kernel window of the block.
+fn block<T: FftNum>(y: &[Complex<T>], re: &[T], im: &[T]) -> [Complex<T>; LANES] {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx") && is_x86_feature_detected!("fma") {
+            return unsafe { block_avx(y, re, im) };
+        }
+    }
+    block_vector(y, re, im)
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx,fma")]
+unsafe fn block_avx<T: FftNum>(y: &[Complex<T>], re: &[T], im: &[T]) -> [Complex<T>; LANES] {
+    block_vector(y, re, im)
+}
+
+/// Runs the vector kernel for `f64` and `f32`, and the scalar loop for other types.
+#[inline(always)]
+fn block_vector<T: FftNum>(y: &[Complex<T>], re: &[T], im: &[T]) -> [Complex<T>; LANES] {
+    // SAFETY: each branch only reinterprets `T` as the type it was just compared equal to.
+    unsafe {
+        if TypeId::of::<T>() == TypeId::of::<f64>() {
+            return core::mem::transmute_copy(&block_f64(cast(y), cast(re), cast(im)));
+        }
+        if TypeId::of::<T>() == TypeId::of::<f32>() {
+            return core::mem::transmute_copy(&block_f32(cast(y), cast(re), cast(im)));
+        }
+    }
+    block_generic(y, re, im)
+}
+
+#[inline(always)]
+fn block_f64(y: &[Complex<f64>], re: &[f64], im: &[f64]) -> [Complex<f64>; LANES] {
+    let (mut sum_re, mut sum_im) = ([f64x4::ZERO; 2], [f64x4::ZERO; 2]);
+    for (t, &y) in y.iter().rev().enumerate() {
+        let (y_re, y_im) = (f64x4::splat(y.re), f64x4::splat(y.im));
+        for half in 0..2 {
+            let at = t + 4 * half;
+            let re = f64x4::new(re[at..at + 4].try_into().unwrap());
+            let im = f64x4::new(im[at..at + 4].try_into().unwrap());
+            sum_re[half] = y_im.mul_neg_add(im, y_re.mul_add(re, sum_re[half]));
+            sum_im[half] = y_im.mul_add(re, y_re.mul_add(im, sum_im[half]));
+        }
+    }
+    let (re, im) = (sum_re.map(f64x4::to_array), sum_im.map(f64x4::to_array));
+    core::array::from_fn(|lane| Complex::new(re[lane / 4][lane % 4], im[lane / 4][lane % 4]))
+}
+
+#[inline(always)]
+fn block_f32(y: &[Complex<f32>], re: &[f32], im: &[f32]) -> [Complex<f32>; LANES] {
+    let (mut sum_re, mut sum_im) = (f32x8::ZERO, f32x8::ZERO);
+    for (t, &y) in y.iter().rev().enumerate() {
+        let (y_re, y_im) = (f32x8::splat(y.re), f32x8::splat(y.im));
+        let re = f32x8::new(re[t..t + LANES].try_into().unwrap());
+        let im = f32x8::new(im[t..t + LANES].try_into().unwrap());
+        sum_re = y_im.mul_neg_add(im, y_re.mul_add(re, sum_re));
+        sum_im = y_im.mul_add(re, y_re.mul_add(im, sum_im));
+    }
+    let (re, im) = (sum_re.to_array(), sum_im.to_array());
+    core::array::from_fn(|lane| Complex::new(re[lane], im[lane]))
+}
+
+#[inline(always)]
+fn block_generic<T: FftNum>(y: &[Complex<T>], re: &[T], im: &[T]) -> [Complex<T>; LANES] {
+    let (mut sum_re, mut sum_im) = ([T::zero(); LANES], [T::zero(); LANES]);
+    for (t, &y) in y.iter().rev().enumerate() {
+        let re: &[T; LANES] = re[t..t + LANES].try_into().unwrap();
+        let im: &[T; LANES] = im[t..t + LANES].try_into().unwrap();
+        for lane in 0..LANES {
+            sum_re[lane] = sum_re[lane] + y.re * re[lane] - y.im * im[lane];
+            sum_im[lane] = sum_im[lane] + y.re * im[lane] + y.im * re[lane];
+        }
+    }
+    core::array::from_fn(|lane| Complex::new(sum_re[lane], sum_im[lane]))
+}
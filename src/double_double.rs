@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Num, One, Zero},
+};
+
+use crate::{Czt, CztParams};
+
+/// An unevaluated sum `hi + lo` of two f64 with `|lo| <= ulp(hi) / 2`, carrying about 106
+/// significant bits. Arithmetic uses the error-free transformations of Dekker and Knuth, so
+/// [`Complex<DoubleDouble>`] works with the usual operators.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+impl DoubleDouble {
+    /// `2^-104`, the relative precision.
+    pub const EPSILON: f64 = f64::EPSILON * f64::EPSILON;
+
+    pub fn new(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = quick_two_sum(hi, lo);
+        Self { hi, lo }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    pub fn abs(self) -> Self {
+        if self.hi < 0.0 {
+            -self
+        } else {
+            self
+        }
+    }
+
+    pub fn sqrt(self) -> Self {
+        if self.hi <= 0.0 {
+            return Self::from(Float::sqrt(self.hi));
+        }
+        // One Newton step from the f64 root doubles its precision.
+        let root = Float::sqrt(self.hi);
+        let correction = (self - Self::from(root) * Self::from(root)).hi * 0.5 / root;
+        Self::from(root) + Self::from(correction)
+    }
+
+    pub fn trunc(self) -> Self {
+        let hi = Float::trunc(self.hi);
+        if hi == self.hi {
+            Self::new(hi, Float::trunc(self.lo))
+        } else {
+            Self::from(hi)
+        }
+    }
+}
+
+impl From<f64> for DoubleDouble {
+    fn from(hi: f64) -> Self {
+        Self { hi, lo: 0.0 }
+    }
+}
+
+impl PartialOrd for DoubleDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.hi.partial_cmp(&other.hi) {
+            Some(Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ordering => ordering,
+        }
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (s, e) = two_sum(self.hi, rhs.hi);
+        let (t, f) = two_sum(self.lo, rhs.lo);
+        let (s, e) = quick_two_sum(s, e + t);
+        let (hi, lo) = quick_two_sum(s, e + f);
+        Self { hi, lo }
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (p, e) = two_prod(self.hi, rhs.hi);
+        let (hi, lo) = quick_two_sum(p, e + (self.hi * rhs.lo + self.lo * rhs.hi));
+        Self { hi, lo }
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        // Long division, one f64 digit of the quotient at a time.
+        let q1 = self.hi / rhs.hi;
+        let r = self - rhs * Self::from(q1);
+        let q2 = r.hi / rhs.hi;
+        let r = r - rhs * Self::from(q2);
+        let q3 = r.hi / rhs.hi;
+        Self::new(q1, q2) + Self::from(q3)
+    }
+}
+
+impl Rem for DoubleDouble {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        self - (self / rhs).trunc() * rhs
+    }
+}
+
+impl AddAssign for DoubleDouble {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for DoubleDouble {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for DoubleDouble {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Zero for DoubleDouble {
+    fn zero() -> Self {
+        Self::from(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.hi == 0.0
+    }
+}
+
+impl One for DoubleDouble {
+    fn one() -> Self {
+        Self::from(1.0)
+    }
+}
+
+impl Num for DoubleDouble {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    /// Parses to the nearest f64 only.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        f64::from_str_radix(s, radix).map(Self::from)
+    }
+}
+
+/// `a + b` and its rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let v = s - a;
+    (s, (a - (s - v)) + (b - v))
+}
+
+/// [`two_sum`] for `|a| >= |b|`.
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+/// Dekker's splitting into two 26-bit halves, so their products are exact without an FMA.
+fn split(a: f64) -> (f64, f64) {
+    let t = 134217729.0 * a; // 2^27 + 1
+    let hi = t - (t - a);
+    (hi, a - hi)
+}
+
+/// `a * b` and its rounding error.
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    (
+        p,
+        ((a_hi * b_hi - p) + a_hi * b_lo + a_lo * b_hi) + a_lo * b_lo,
+    )
+}
+
+/// Bluestein CZT with double-double coefficient tables, FFTs and accumulation.
+///
+/// The f64 plans round every bin to about `1e-16` of the largest bin; here the error floor is
+/// near `1e-32` of it, so bins 160 dB and more below the peak keep nearly full f64 precision. It costs
+/// roughly ten times an f64 plan.
+///
+/// As in [`NttCzt`](crate::ntt::NttCzt), `jk = C(j + k, 2) - C(j, 2) - C(k, 2)` replaces the
+/// square root of `w`, and the chirps are built by recurrence in double-double.
+pub struct DoubleDoubleCzt {
+    n: usize,
+    m: usize,
+    a: Complex<DoubleDouble>,
+    w: Complex<DoubleDouble>,
+    /// `a^-j w^-C(j, 2)`
+    y: Vec<Complex<DoubleDouble>>,
+    /// Forward FFT of `w^C(t, 2)`, `t < n + m - 1`, scaled by `1 / l`.
+    v: Vec<Complex<DoubleDouble>>,
+    /// `w^-C(k, 2)`
+    x: Vec<Complex<DoubleDouble>>,
+    /// `e^(-2 pi i t / l)`, `t < l / 2`.
+    twiddles: Vec<Complex<DoubleDouble>>,
+}
+
+impl DoubleDoubleCzt {
+    pub fn new(n: usize, m: usize, a: Complex<f64>, w: Complex<f64>) -> Self {
+        let to_dd = |z: Complex<f64>| Complex::new(z.re.into(), z.im.into());
+        Self::with_double_double_contour(n, m, to_dd(a), to_dd(w))
+    }
+
+    /// A contour given to double-double precision, for frequencies that f64 cannot place.
+    pub fn with_double_double_contour(
+        n: usize,
+        m: usize,
+        a: Complex<DoubleDouble>,
+        w: Complex<DoubleDouble>,
+    ) -> Self {
+        assert!(n > 0 && m > 0);
+        let l = (n + m - 1).next_power_of_two();
+        let twiddles = twiddles(l);
+
+        // w^C(j, 2) advances by w^j from one j to the next.
+        let binomial_powers = |base: Complex<DoubleDouble>, count: usize| {
+            let (mut power, mut step) = (Complex::one(), Complex::one());
+            (0..count).map(move |_| {
+                let current = power;
+                power = power * step;
+                step = step * base;
+                current
+            })
+        };
+
+        let (a_inv, w_inv) = (a.inv(), w.inv());
+        let mut a_power = Complex::one();
+        let y = binomial_powers(w_inv, n)
+            .map(|chirp| {
+                let value = chirp * a_power;
+                a_power = a_power * a_inv;
+                value
+            })
+            .collect();
+        let mut v: Vec<_> = binomial_powers(w, n + m - 1).collect();
+        v.resize(l, Complex::zero());
+        fft(&mut v, &twiddles, false);
+        let scale = DoubleDouble::one() / DoubleDouble::from(l as f64);
+        for v in v.iter_mut() {
+            *v = v.scale(scale);
+        }
+        let x = binomial_powers(w_inv, m).collect();
+
+        Self {
+            n,
+            m,
+            a,
+            w,
+            y,
+            v,
+            x,
+            twiddles,
+        }
+    }
+
+    pub fn process_double_double(&self, buffer: &mut [Complex<DoubleDouble>]) {
+        let mut scratch = vec![Complex::zero(); self.v.len()];
+        self.process_double_double_with_scratch(buffer, &mut scratch);
+    }
+
+    /// Transforms a double-double buffer of `max(n, m)` elements in place; `scratch` holds
+    /// [`get_double_double_scratch_len`](Self::get_double_double_scratch_len) elements.
+    pub fn process_double_double_with_scratch(
+        &self,
+        buffer: &mut [Complex<DoubleDouble>],
+        scratch: &mut [Complex<DoubleDouble>],
+    ) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        self.correlate(|j| buffer[j], scratch);
+        for ((out, &s), &x) in buffer.iter_mut().zip(&scratch[self.n - 1..]).zip(&self.x) {
+            *out = s * x;
+        }
+    }
+
+    pub fn get_double_double_scratch_len(&self) -> usize {
+        self.v.len()
+    }
+
+    /// Leaves `sum_j x_j y_j v_(j + k)` in `scratch[n - 1 + k]`.
+    fn correlate(
+        &self,
+        input: impl Fn(usize) -> Complex<DoubleDouble>,
+        scratch: &mut [Complex<DoubleDouble>],
+    ) {
+        assert_eq!(scratch.len(), self.v.len());
+        scratch.fill(Complex::zero());
+        for (j, &y) in self.y.iter().enumerate() {
+            scratch[self.n - 1 - j] = input(j) * y;
+        }
+        fft(scratch, &self.twiddles, false);
+        for (s, &v) in scratch.iter_mut().zip(&self.v) {
+            *s = *s * v;
+        }
+        fft(scratch, &self.twiddles, true);
+    }
+}
+
+impl Czt<f64> for DoubleDoubleCzt {
+    fn process_with_scratch(&self, buffer: &mut [Complex<f64>], scratch: &mut [Complex<f64>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+        let scratch = as_double_double(scratch);
+
+        self.correlate(
+            |j| Complex::new(buffer[j].re.into(), buffer[j].im.into()),
+            scratch,
+        );
+        for ((out, &s), &x) in buffer.iter_mut().zip(&scratch[self.n - 1..]).zip(&self.x) {
+            let bin = s * x;
+            *out = Complex::new(bin.re.to_f64(), bin.im.to_f64());
+        }
+    }
+
+    /// Two f64 per double-double.
+    fn get_inplace_scratch_len(&self) -> usize {
+        2 * self.v.len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<f64> {
+        CztParams {
+            n: self.n,
+            m: self.m,
+            a: Complex::new(self.a.re.to_f64(), self.a.im.to_f64()),
+            w: Complex::new(self.w.re.to_f64(), self.w.im.to_f64()),
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let tables = self.y.len() + self.v.len() + self.x.len() + self.twiddles.len();
+        (tables + self.v.len()) * core::mem::size_of::<Complex<DoubleDouble>>()
+    }
+}
+
+fn as_double_double(scratch: &mut [Complex<f64>]) -> &mut [Complex<DoubleDouble>] {
+    assert!(scratch.len().is_multiple_of(2));
+    // SAFETY: `Complex` and `DoubleDouble` are `repr(C)`, so a `Complex<DoubleDouble>` is four
+    // f64, laid out and aligned like two `Complex<f64>`.
+    unsafe { core::slice::from_raw_parts_mut(scratch.as_mut_ptr().cast(), scratch.len() / 2) }
+}
+
+/// `e^(-2 pi i t / l)` for `t < l / 2`. The primitive roots of order `4, 8, ..., l` come from
+/// the half-angle formulas, and each run of powers is rebuilt from them to limit the drift of
+/// the recurrence.
+fn twiddles(l: usize) -> Vec<Complex<DoubleDouble>> {
+    let half = DoubleDouble::from(0.5);
+    let mut roots = vec![Complex::new(DoubleDouble::zero(), -DoubleDouble::one())];
+    while roots.len() + 2 <= l.trailing_zeros() as usize {
+        let root = roots[roots.len() - 1];
+        let re = ((DoubleDouble::one() + root.re) * half).sqrt();
+        roots.push(Complex::new(re, root.im / (re + re)));
+    }
+
+    // t = sum of bits b_i 2^i; e^(-2 pi i t / l) is the product of the matching roots.
+    (0..l / 2)
+        .map(|t| {
+            let mut twiddle = Complex::one();
+            for (bit, root) in roots.iter().rev().enumerate() {
+                if t >> bit & 1 == 1 {
+                    twiddle = twiddle * *root;
+                }
+            }
+            twiddle
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; `inverse` conjugates the twiddles and does not scale.
+fn fft(data: &mut [Complex<DoubleDouble>], twiddles: &[Complex<DoubleDouble>], inverse: bool) {
+    let l = data.len();
+    let bits = l.trailing_zeros();
+    if l < 2 {
+        return;
+    }
+    for i in 0..l {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= l {
+        let stride = l / len;
+        for chunk in data.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (t, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let twiddle = twiddles[t * stride];
+                let twiddle = if inverse { twiddle.conj() } else { twiddle };
+                let product = *v * twiddle;
+                *v = *u - product;
+                *u = *u + product;
+            }
+        }
+        len <<= 1;
+    }
+}
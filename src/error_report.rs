@@ -0,0 +1,55 @@
+use core::fmt;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, FromPrimitive},
+};
+
+/// How far a spectrum is from the expected one, bin by bin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ErrorReport<T> {
+    /// Largest `|actual_k - expected_k|`.
+    pub max_abs_error: T,
+    /// `sqrt(mean |actual_k - expected_k|^2)`.
+    pub rms_error: T,
+    /// The bin with the largest error.
+    pub worst_bin: usize,
+}
+
+impl<T: Float + FromPrimitive> ErrorReport<T> {
+    /// Compares two spectra of equal length. A NaN error is the largest, so non-finite bins
+    /// are reported rather than skipped. Empty spectra report no error.
+    pub fn compare(expected: &[Complex<T>], actual: &[Complex<T>]) -> Self {
+        assert_eq!(expected.len(), actual.len());
+
+        let mut report = Self {
+            max_abs_error: T::zero(),
+            rms_error: T::zero(),
+            worst_bin: 0,
+        };
+        let mut sum_sq = T::zero();
+        for (k, (&e, &a)) in expected.iter().zip(actual).enumerate() {
+            let error = (a - e).norm();
+            sum_sq = sum_sq + error * error;
+            let worst = report.max_abs_error;
+            if error > worst || (error.is_nan() && !worst.is_nan()) {
+                report.max_abs_error = error;
+                report.worst_bin = k;
+            }
+        }
+        if !expected.is_empty() {
+            report.rms_error = (sum_sq / T::from_usize(expected.len()).unwrap()).sqrt();
+        }
+        report
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ErrorReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "max error {} at bin {}, rms error {}",
+            self.max_abs_error, self.worst_bin, self.rms_error
+        )
+    }
+}
@@ -0,0 +1,90 @@
+//! The FFT interface the CZTs run on.
+//!
+//! With `std` these are rustfft's own traits, so its planners and algorithms plug straight in.
+//! rustfft needs `std`, so without it the same traits are defined here and FFTs come from an
+//! [`FftProvider`](crate::FftProvider) such as the `microfft` feature's `MicroFftProvider`.
+
+pub use num_complex;
+pub use num_traits;
+
+#[cfg(feature = "std")]
+pub use rustfft::{Direction, Fft, FftDirection, FftNum, Length};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::*;
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use core::fmt::{Debug, Display, Formatter};
+
+    use num_complex::Complex;
+    use num_traits::{FromPrimitive, Signed, Zero};
+
+    /// Generic floating point number, implemented for f32 and f64.
+    pub trait FftNum: Copy + FromPrimitive + Signed + Sync + Send + Debug + 'static {}
+
+    impl<T> FftNum for T where T: Copy + FromPrimitive + Signed + Sync + Send + Debug + 'static {}
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum FftDirection {
+        Forward,
+        Inverse,
+    }
+
+    impl FftDirection {
+        pub fn opposite_direction(&self) -> FftDirection {
+            match self {
+                Self::Forward => Self::Inverse,
+                Self::Inverse => Self::Forward,
+            }
+        }
+    }
+
+    impl Display for FftDirection {
+        fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+            match self {
+                Self::Forward => f.write_str("Forward"),
+                Self::Inverse => f.write_str("Inverse"),
+            }
+        }
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    pub trait Length {
+        fn len(&self) -> usize;
+    }
+
+    pub trait Direction {
+        fn fft_direction(&self) -> FftDirection;
+    }
+
+    /// An FFT of one length and direction, unnormalized, with the same contract as rustfft's.
+    pub trait Fft<T: FftNum>: Length + Direction + Sync + Send {
+        fn process(&self, buffer: &mut [Complex<T>]) {
+            let mut scratch = vec![Complex::zero(); self.get_inplace_scratch_len()];
+            self.process_with_scratch(buffer, &mut scratch);
+        }
+
+        fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]);
+
+        fn process_outofplace_with_scratch(
+            &self,
+            input: &mut [Complex<T>],
+            output: &mut [Complex<T>],
+            scratch: &mut [Complex<T>],
+        );
+
+        fn process_immutable_with_scratch(
+            &self,
+            input: &[Complex<T>],
+            output: &mut [Complex<T>],
+            scratch: &mut [Complex<T>],
+        );
+
+        fn get_inplace_scratch_len(&self) -> usize;
+
+        fn get_outofplace_scratch_len(&self) -> usize;
+
+        fn get_immutable_scratch_len(&self) -> usize;
+    }
+}
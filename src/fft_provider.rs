@@ -0,0 +1,81 @@
+use alloc::sync::Arc;
+
+#[cfg(feature = "std")]
+use rustfft::{FftPlanner, FftPlannerAvx, FftPlannerNeon};
+
+use crate::fft::{num_complex::Complex, Fft, FftNum};
+
+/// Source of the FFTs used for the internal convolutions.
+///
+/// Implement this to run the CZT on top of an FFT library other than rustfft, or to inject mock
+/// FFTs in tests.
+pub trait FftProvider<T: FftNum> {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>>;
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>>;
+}
+
+#[cfg(feature = "std")]
+impl<T: FftNum> FftProvider<T> for FftPlanner<T> {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_forward(len)
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_inverse(len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FftNum> FftProvider<T> for FftPlannerAvx<T> {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_forward(len)
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_inverse(len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FftNum> FftProvider<T> for FftPlannerNeon<T> {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_forward(len)
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        self.plan_fft_inverse(len)
+    }
+}
+
+/// The provider of planners built without one: rustfft's planner.
+#[cfg(feature = "std")]
+pub type DefaultFftProvider<T> = FftPlanner<T>;
+
+/// Without `std` there is no default provider, so planners take one through
+/// [`with_fft_provider`](crate::CztPlanner::with_fft_provider).
+#[cfg(not(feature = "std"))]
+pub type DefaultFftProvider<T> = NoFftProvider<T>;
+
+/// The uninhabited default provider without `std`; it cannot be constructed.
+#[cfg(not(feature = "std"))]
+pub struct NoFftProvider<T>(core::convert::Infallible, core::marker::PhantomData<T>);
+
+#[cfg(not(feature = "std"))]
+impl<T: FftNum> FftProvider<T> for NoFftProvider<T> {
+    fn plan_forward(&mut self, _len: usize) -> Arc<dyn Fft<T>> {
+        match self.0 {}
+    }
+
+    fn plan_inverse(&mut self, _len: usize) -> Arc<dyn Fft<T>> {
+        match self.0 {}
+    }
+}
+
+/// Estimated heap footprint in bytes of an FFT plan, not counting its scratch.
+///
+/// [`Fft`] does not report its memory; rustfft's algorithms keep roughly one twiddle factor per
+/// point, which is what this assumes.
+pub fn fft_memory_usage<T: FftNum>(fft: &dyn Fft<T>) -> usize {
+    fft.len() * core::mem::size_of::<Complex<T>>()
+}
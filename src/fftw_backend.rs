@@ -0,0 +1,187 @@
+use std::sync::{Arc, Mutex};
+
+use crate::fft::{num_complex::Complex, Direction, Fft, FftDirection, FftNum, Length};
+use fftw::{
+    array::AlignedVec,
+    plan::{C2CPlan, C2CPlan32, C2CPlan64},
+    types::{Flag, Sign},
+};
+
+use crate::FftProvider;
+
+/// Floating point types FFTW has complex-to-complex plans for.
+pub trait FftwFloat: FftNum {
+    type Plan: C2CPlan<Complex = Complex<Self>> + Send;
+
+    fn aligned_buffer(len: usize) -> AlignedVec<Complex<Self>>;
+}
+
+impl FftwFloat for f64 {
+    type Plan = C2CPlan64;
+
+    fn aligned_buffer(len: usize) -> AlignedVec<Complex<Self>> {
+        AlignedVec::new(len)
+    }
+}
+
+impl FftwFloat for f32 {
+    type Plan = C2CPlan32;
+
+    fn aligned_buffer(len: usize) -> AlignedVec<Complex<Self>> {
+        AlignedVec::new(len)
+    }
+}
+
+struct FftwState<T: FftwFloat> {
+    plan: T::Plan,
+    input: AlignedVec<Complex<T>>,
+    output: AlignedVec<Complex<T>>,
+}
+
+/// A single FFTW plan behind the `rustfft::Fft` interface.
+///
+/// FFTW executes from its own aligned buffers, so every transform is staged through them; the
+/// plan is guarded by a mutex to satisfy `Sync`.
+pub struct FftwFft<T: FftwFloat> {
+    len: usize,
+    direction: FftDirection,
+    state: Mutex<FftwState<T>>,
+}
+
+impl<T: FftwFloat> FftwFft<T> {
+    pub fn new(len: usize, direction: FftDirection, flags: Flag) -> Self {
+        let sign = match direction {
+            FftDirection::Forward => Sign::Forward,
+            FftDirection::Inverse => Sign::Backward,
+        };
+        let plan = T::Plan::aligned(&[len], sign, flags).expect("FFTW failed to create a plan");
+
+        Self {
+            len,
+            direction,
+            state: Mutex::new(FftwState {
+                plan,
+                input: T::aligned_buffer(len),
+                output: T::aligned_buffer(len),
+            }),
+        }
+    }
+}
+
+impl<T: FftwFloat> Length for FftwFft<T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: FftwFloat> Direction for FftwFft<T> {
+    fn fft_direction(&self) -> FftDirection {
+        self.direction
+    }
+}
+
+impl<T: FftwFloat> Fft<T> for FftwFft<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], _scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len() % self.len, 0);
+
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        for chunk in buffer.chunks_exact_mut(self.len) {
+            state.input.as_slice_mut().copy_from_slice(chunk);
+            state
+                .plan
+                .c2c(&mut state.input, &mut state.output)
+                .expect("FFTW rejected its own buffers");
+            chunk.copy_from_slice(&state.output);
+        }
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.process_immutable_with_scratch(input, output, scratch);
+    }
+
+    fn process_immutable_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), output.len());
+        output.copy_from_slice(input);
+        self.process_with_scratch(output, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_immutable_scratch_len(&self) -> usize {
+        0
+    }
+}
+
+#[link(name = "fftw3_threads")]
+extern "C" {}
+
+#[link(name = "fftw3f_threads")]
+extern "C" {}
+
+/// Plans the internal convolution FFTs with FFTW.
+pub struct FftwProvider {
+    flags: Flag,
+}
+
+impl FftwProvider {
+    pub fn new() -> Self {
+        Self::with_flags(Flag::ESTIMATE)
+    }
+
+    /// Uses the given planner flags, e.g. `Flag::MEASURE` for tuned kernels.
+    pub fn with_flags(flags: Flag) -> Self {
+        Self { flags }
+    }
+
+    /// Lets FFTW use `threads` threads for every plan created afterwards, process wide.
+    pub fn with_threads(self, threads: usize) -> Self {
+        unsafe {
+            fftw_sys::fftw_init_threads();
+            fftw_sys::fftwf_init_threads();
+            fftw_sys::fftw_plan_with_nthreads(threads as i32);
+            fftw_sys::fftwf_plan_with_nthreads(threads as i32);
+        }
+        self
+    }
+}
+
+impl Default for FftwProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FftwFloat> FftProvider<T> for FftwProvider {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        Arc::new(FftwFft::<T>::new(
+            len,
+            FftDirection::Forward,
+            Flag::from_bits_retain(self.flags.bits()),
+        ))
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        Arc::new(FftwFft::<T>::new(
+            len,
+            FftDirection::Inverse,
+            Flag::from_bits_retain(self.flags.bits()),
+        ))
+    }
+}
@@ -0,0 +1,347 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+#[cfg(feature = "std")]
+use rustfft::FftPlanner;
+
+use crate::fft::{num_complex::Complex, num_traits::Float};
+
+use crate::{bluesteins::BluesteinsAlgorithm, ConvolutionLength, FftProvider};
+
+/// What happens when a result does not fit the sample type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Clamp to the most positive or most negative value.
+    #[default]
+    Saturate,
+    /// Two's complement wrap-around, like plain integer arithmetic on most DSPs.
+    Wrap,
+}
+
+mod sealed {
+    use super::Overflow;
+
+    pub trait Wide: Sized {
+        fn to_wide(self) -> i64;
+
+        fn from_wide(x: i64, overflow: Overflow) -> Self;
+    }
+}
+
+/// Signed fractional sample formats: `i16` is Q15 and `i32` is Q31, both covering `[-1, 1)`.
+pub trait FixedPoint:
+    sealed::Wide + Copy + Default + Debug + PartialEq + Send + Sync + 'static
+{
+    const FRAC_BITS: u32;
+
+    /// Quantizes `x` with round-to-nearest.
+    fn from_f64(x: f64, overflow: Overflow) -> Self {
+        Self::from_wide(
+            (x * (1i64 << Self::FRAC_BITS) as f64).round() as i64,
+            overflow,
+        )
+    }
+
+    fn to_f64(self) -> f64 {
+        self.to_wide() as f64 / (1i64 << Self::FRAC_BITS) as f64
+    }
+}
+
+macro_rules! impl_fixed_point {
+    ($t:ty, $frac_bits:expr) => {
+        impl sealed::Wide for $t {
+            fn to_wide(self) -> i64 {
+                self as i64
+            }
+
+            fn from_wide(x: i64, overflow: Overflow) -> Self {
+                match overflow {
+                    Overflow::Saturate => x.clamp(<$t>::MIN as i64, <$t>::MAX as i64) as $t,
+                    Overflow::Wrap => x as $t,
+                }
+            }
+        }
+
+        impl FixedPoint for $t {
+            const FRAC_BITS: u32 = $frac_bits;
+        }
+    };
+}
+
+impl_fixed_point!(i16, 15);
+impl_fixed_point!(i32, 31);
+
+/// Product of two fractional values, rounded back to the fractional format.
+fn mul<S: FixedPoint>(a: S, b: S) -> i64 {
+    (a.to_wide() * b.to_wide() + (1 << (S::FRAC_BITS - 1))) >> S::FRAC_BITS
+}
+
+fn complex_mul<S: FixedPoint>(a: Complex<S>, b: Complex<S>, overflow: Overflow) -> Complex<S> {
+    Complex::new(
+        S::from_wide(mul(a.re, b.re) - mul(a.im, b.im), overflow),
+        S::from_wide(mul(a.re, b.im) + mul(a.im, b.re), overflow),
+    )
+}
+
+fn conj<S: FixedPoint>(x: Complex<S>, overflow: Overflow) -> Complex<S> {
+    Complex::new(x.re, S::from_wide(-x.im.to_wide(), overflow))
+}
+
+fn max_component<S: FixedPoint>(block: &[Complex<S>]) -> i64 {
+    block
+        .iter()
+        .map(|x| x.re.to_wide().abs().max(x.im.to_wide().abs()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Multiplies every value by `2^bits`, rounding when shifting right.
+fn scale<S: FixedPoint>(block: &mut [Complex<S>], bits: i32, overflow: Overflow) {
+    let shift = |x: S| {
+        let x = x.to_wide();
+        let x = if bits >= 0 {
+            x << bits
+        } else {
+            (x + (1 << (-bits - 1))) >> -bits
+        };
+        S::from_wide(x, overflow)
+    };
+    for x in block {
+        *x = Complex::new(shift(x.re), shift(x.im));
+    }
+}
+
+/// Shifts the block so its largest component lies in `[2^-headroom / 2, 2^-headroom)`. Returns the
+/// exponent the block's scale grew by.
+fn normalize<S: FixedPoint>(block: &mut [Complex<S>], headroom: i32, overflow: Overflow) -> i32 {
+    let max = max_component(block);
+    if max == 0 {
+        return 0;
+    }
+    let bits = (S::FRAC_BITS as i32 - headroom) - (64 - max.leading_zeros() as i32);
+    scale(block, bits, overflow);
+    -bits
+}
+
+/// Quantizes a coefficient table with a shared exponent chosen so that every component fits.
+fn quantize<S: FixedPoint>(table: &[Complex<f64>], overflow: Overflow) -> (Vec<Complex<S>>, i32) {
+    let max = table
+        .iter()
+        .map(|x| x.re.abs().max(x.im.abs()))
+        .fold(0.0, f64::max);
+    let exponent = if max > 0.0 {
+        Float::log2(max).floor() as i32 + 1
+    } else {
+        0
+    };
+    let factor = Float::powi(2.0, -exponent);
+    let table = table
+        .iter()
+        .map(|x| {
+            Complex::new(
+                S::from_f64(x.re * factor, overflow),
+                S::from_f64(x.im * factor, overflow),
+            )
+        })
+        .collect();
+    (table, exponent)
+}
+
+/// Radix-2 decimation-in-time FFT with block floating point scaling: before every stage the block
+/// is halved until its components are below 1/4, so the butterflies cannot overflow. Returns the
+/// number of halvings.
+fn fft<S: FixedPoint>(
+    buffer: &mut [Complex<S>],
+    twiddles: &[Complex<S>],
+    overflow: Overflow,
+) -> i32 {
+    let len = buffer.len();
+    let bits = len.trailing_zeros();
+    if bits == 0 {
+        return 0;
+    }
+    for i in 0..len {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+
+    let mut exponent = 0;
+    let mut half = 1;
+    while half < len {
+        while max_component(buffer) >= 1 << (S::FRAC_BITS - 2) {
+            scale(buffer, -1, overflow);
+            exponent += 1;
+        }
+        let stride = len / (2 * half);
+        for chunk in buffer.chunks_exact_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for (k, (lo, hi)) in lo.iter_mut().zip(hi).enumerate() {
+                let t = complex_mul(*hi, twiddles[k * stride], overflow);
+                let (a, b) = (lo.re.to_wide(), lo.im.to_wide());
+                let (c, d) = (t.re.to_wide(), t.im.to_wide());
+                *lo = Complex::new(S::from_wide(a + c, overflow), S::from_wide(b + d, overflow));
+                *hi = Complex::new(S::from_wide(a - c, overflow), S::from_wide(b - d, overflow));
+            }
+        }
+        half *= 2;
+    }
+    exponent
+}
+
+/// Bluestein CZT in integer fixed point, for DSP targets without an FPU.
+///
+/// The chirp tables are computed in `f64` when the plan is built and quantized to `S`, each with
+/// its own power of two scale. Processing is integer only and tracks a single block exponent
+/// through the convolution: the block is renormalized before each FFT, halved whenever a
+/// butterfly stage could overflow, and the exponent is returned so that the true output is
+/// `buffer[k] * 2^exponent`.
+pub struct FixedPointCzt<S: FixedPoint> {
+    y_coefficients: Vec<Complex<S>>,
+    v_coefficients: Vec<Complex<S>>,
+    x_coefficients: Vec<Complex<S>>,
+    twiddles: Vec<Complex<S>>,
+    /// Sum of the table exponents.
+    exponent: i32,
+    overflow: Overflow,
+}
+
+impl<S: FixedPoint> FixedPointCzt<S> {
+    #[cfg(feature = "std")]
+    pub fn new(n: usize, m: usize, a: Complex<f64>, w: Complex<f64>) -> Self {
+        Self::with_overflow(n, m, a, w, Overflow::default())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_overflow(
+        n: usize,
+        m: usize,
+        a: Complex<f64>,
+        w: Complex<f64>,
+        overflow: Overflow,
+    ) -> Self {
+        Self::with_fft_provider(n, m, a, w, overflow, &mut FftPlanner::new())
+    }
+
+    /// Computes the quantized tables with FFTs from `fft_provider`, which needs `f64` power of
+    /// two lengths; without `std` this is the only constructor.
+    pub fn with_fft_provider(
+        n: usize,
+        m: usize,
+        a: Complex<f64>,
+        w: Complex<f64>,
+        overflow: Overflow,
+        fft_provider: &mut dyn FftProvider<f64>,
+    ) -> Self {
+        let reference = BluesteinsAlgorithm::new_segment(
+            n,
+            m,
+            a,
+            w,
+            0,
+            0,
+            ConvolutionLength::PowerOfTwo,
+            fft_provider,
+        );
+        let l = reference.v_coefficients().len();
+
+        let (y_coefficients, y_exponent) = quantize(reference.y_coefficients(), overflow);
+        let (v_coefficients, v_exponent) = quantize(reference.v_coefficients(), overflow);
+        let (x_coefficients, x_exponent) = quantize(reference.x_coefficients(), overflow);
+        let twiddles = (0..l / 2)
+            .map(|k| {
+                let t =
+                    Complex::from_polar(1.0, -2.0 * core::f64::consts::PI * k as f64 / l as f64);
+                Complex::new(S::from_f64(t.re, overflow), S::from_f64(t.im, overflow))
+            })
+            .collect();
+
+        Self {
+            y_coefficients,
+            v_coefficients,
+            x_coefficients,
+            twiddles,
+            exponent: y_exponent + v_exponent + x_exponent,
+            overflow,
+        }
+    }
+
+    /// Transforms `buffer` in place and returns the block exponent of the output.
+    pub fn process(&self, buffer: &mut [Complex<S>]) -> i32 {
+        let mut scratch = vec![Complex::new(S::default(), S::default()); self.get_scratch_len()];
+        self.process_with_scratch(buffer, &mut scratch)
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        buffer: &mut [Complex<S>],
+        scratch: &mut [Complex<S>],
+    ) -> i32 {
+        let (n, m) = (self.get_input_len(), self.get_output_len());
+        assert!(buffer.len() >= n.max(m));
+        assert_eq!(scratch.len(), self.get_scratch_len());
+        let overflow = self.overflow;
+
+        for ((s, &x), &y) in scratch
+            .iter_mut()
+            .zip(&buffer[..n])
+            .zip(&self.y_coefficients)
+        {
+            *s = complex_mul(x, y, overflow);
+        }
+        scratch[n..].fill(Complex::new(S::default(), S::default()));
+
+        let mut exponent = self.exponent;
+        exponent += normalize(scratch, 2, overflow);
+        exponent += fft(scratch, &self.twiddles, overflow);
+
+        // The inverse FFT is the forward FFT between two conjugations.
+        for (s, &v) in scratch.iter_mut().zip(&self.v_coefficients) {
+            *s = conj(complex_mul(*s, v, overflow), overflow);
+        }
+        exponent += normalize(scratch, 2, overflow);
+        exponent += fft(scratch, &self.twiddles, overflow);
+
+        for ((b, &s), &x) in buffer
+            .iter_mut()
+            .zip(&scratch[..m])
+            .zip(&self.x_coefficients)
+        {
+            *b = complex_mul(conj(s, overflow), x, overflow);
+        }
+        exponent + normalize(&mut buffer[..m], 0, overflow)
+    }
+
+    /// Length of the convolution, `l`.
+    pub fn get_scratch_len(&self) -> usize {
+        self.v_coefficients.len()
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.y_coefficients.len()
+    }
+
+    pub fn get_output_len(&self) -> usize {
+        self.x_coefficients.len()
+    }
+
+    /// Heap footprint in bytes of the tables plus one scratch buffer.
+    pub fn get_memory_usage(&self) -> usize {
+        (self.y_coefficients.len()
+            + self.v_coefficients.len()
+            + self.x_coefficients.len()
+            + self.twiddles.len()
+            + self.get_scratch_len())
+            * core::mem::size_of::<Complex<S>>()
+    }
+}
+
+/// Converts a block floating point output back to floats.
+pub fn to_complex_f64<S: FixedPoint>(block: &[Complex<S>], exponent: i32) -> Vec<Complex<f64>> {
+    let factor = Float::powi(2.0, exponent);
+    block
+        .iter()
+        .map(|x| Complex::new(x.re.to_f64(), x.im.to_f64()) * factor)
+        .collect()
+}
@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{bluesteins::from_f64, CztPlanner, FftProvider};
+
+/// The `m` points `z_k = a w^-k` a CZT evaluates at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contour<T> {
+    pub m: usize,
+    pub a: Complex<T>,
+    pub w: Complex<T>,
+}
+
+impl<T: FftNum + Float> Contour<T> {
+    pub fn new(m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        Self { m, a, w }
+    }
+
+    /// `m` points on the unit circle from `f_start` to `f_end` Hz.
+    pub fn arc(m: usize, sample_rate: T, f_start: T, f_end: T) -> Self {
+        assert!(m > 0);
+        let (f_start, f_end) = (f_start.to_f64().unwrap(), f_end.to_f64().unwrap());
+        let step = if m > 1 {
+            (f_end - f_start) / (m - 1) as f64
+        } else {
+            0.0
+        };
+        let tau = core::f64::consts::TAU / sample_rate.to_f64().unwrap();
+        Self {
+            m,
+            a: from_f64(Complex::from_polar(1.0, tau * f_start)),
+            w: from_f64(Complex::from_polar(1.0, -tau * step)),
+        }
+    }
+
+    /// The point `z_k`.
+    pub fn point(&self, k: usize) -> Complex<T> {
+        self.a * self.w.powi(-(k as i32))
+    }
+}
+
+/// Evaluates `H(z) = (b_0 + b_1 z^-1 + ...) / (a_0 + a_1 z^-1 + ...)` on `contour`, with one
+/// real-input CZT for each polynomial and a pointwise divide.
+///
+/// Where the denominator vanishes the result is infinite or NaN, as with direct evaluation.
+pub fn freqz_czt<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    b: &[T],
+    a: &[T],
+    contour: Contour<T>,
+) -> Vec<Complex<T>> {
+    assert!(!b.is_empty() && !a.is_empty());
+    let mut numerator = vec![Complex::zero(); contour.m];
+    planner
+        .plan_real_czt_forward(b.len(), contour.m, contour.a, contour.w)
+        .process(b, &mut numerator);
+    let mut denominator = vec![Complex::zero(); contour.m];
+    planner
+        .plan_real_czt_forward(a.len(), contour.m, contour.a, contour.w)
+        .process(a, &mut denominator);
+
+    for (h, d) in numerator.iter_mut().zip(&denominator) {
+        *h = *h / *d;
+    }
+    numerator
+}
@@ -0,0 +1,148 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Euclid, Float, One, Zero},
+    FftNum,
+};
+
+use crate::{bluesteins::from_f64, fft_provider::DefaultFftProvider, Czt, CztPlanner, FftProvider};
+
+/// Builds [`Frft`] plans, sharing one [`CztPlanner`] and its plan cache between them.
+pub struct FrftPlanner<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    planner: CztPlanner<T, P>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> FrftPlanner<T> {
+    pub fn new() -> Self {
+        Self::with_czt_planner(CztPlanner::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> Default for FrftPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> FrftPlanner<T, P> {
+    pub fn with_czt_planner(planner: CztPlanner<T, P>) -> Self {
+        Self { planner }
+    }
+
+    /// Plans the fractional Fourier transform of `n` samples by the angle `alpha` in radians;
+    /// `alpha = pi / 2` is the centered unitary DFT.
+    ///
+    /// The kernel `sqrt(1 - i cot(alpha)) e^(i pi (cot(alpha) (u^2 + x^2) - 2 csc(alpha) u x))` is
+    /// sampled at `x_j = (j - floor(n / 2)) / sqrt(n)`, which turns the cross term into a CZT
+    /// between two chirp multiplications. The chirps alias for angles within `pi / 4` of a
+    /// multiple of `pi`, so those are computed as a DFT followed by the transform by
+    /// `alpha -/+ pi / 2`.
+    pub fn plan_frft(&mut self, n: usize, alpha: T) -> Frft<T> {
+        assert!(n > 0);
+        let angle = alpha.to_f64().unwrap();
+        assert!(angle.is_finite(), "angle must be finite");
+
+        // Reduce to (-pi, pi].
+        let mut reduced = Euclid::rem_euclid(&angle, &(2.0 * PI));
+        if reduced > PI {
+            reduced -= 2.0 * PI;
+        }
+        let stages = if reduced.abs() < FRAC_PI_4 || reduced.abs() > 3.0 * FRAC_PI_4 {
+            let step = FRAC_PI_2.copysign(reduced);
+            vec![self.plan_stage(n, step), self.plan_stage(n, reduced - step)]
+        } else {
+            vec![self.plan_stage(n, reduced)]
+        };
+
+        Frft { stages, n, alpha }
+    }
+
+    fn plan_stage(&mut self, n: usize, alpha: f64) -> Stage<T> {
+        let (cot, csc) = (alpha.cos() / alpha.sin(), alpha.sin().recip());
+        let nf = n as f64;
+        let c = (n / 2) as f64;
+        let beta = csc / nf;
+        let chirp = |j: usize| {
+            let x = j as f64 - c;
+            PI * (cot * x * x / nf + 2.0 * beta * c * j as f64)
+        };
+
+        let pre = (0..n)
+            .map(|j| from_f64(Complex::from_polar(1.0, chirp(j))))
+            .collect();
+        let amplitude = Complex::new(1.0, -cot).sqrt() / nf.sqrt();
+        let post = (0..n)
+            .map(|k| {
+                let phase = chirp(k) - 2.0 * PI * beta * c * c;
+                from_f64(amplitude * Complex::from_polar(1.0, phase))
+            })
+            .collect();
+
+        let w = from_f64(Complex::from_polar(1.0, -2.0 * PI * beta));
+        let czt = self.planner.plan_czt_forward(n, n, Complex::one(), w);
+        Stage { pre, czt, post }
+    }
+}
+
+/// Fractional Fourier transform, see [`FrftPlanner::plan_frft`].
+pub struct Frft<T: FftNum> {
+    stages: Vec<Stage<T>>,
+    n: usize,
+    alpha: T,
+}
+
+/// One chirp-multiply, CZT, chirp-multiply pass.
+struct Stage<T: FftNum> {
+    pre: Vec<Complex<T>>,
+    czt: Arc<dyn Czt<T>>,
+    post: Vec<Complex<T>>,
+}
+
+impl<T: FftNum> Frft<T> {
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Rotation angle in radians.
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    pub fn process(&self, buffer: &mut [Complex<T>]) {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(buffer, &mut scratch);
+    }
+
+    pub fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.n);
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        for stage in &self.stages {
+            for (x, &p) in buffer.iter_mut().zip(&stage.pre) {
+                *x = *x * p;
+            }
+            stage
+                .czt
+                .process_with_scratch(buffer, &mut scratch[..stage.czt.get_inplace_scratch_len()]);
+            for (x, &p) in buffer.iter_mut().zip(&stage.post) {
+                *x = *x * p;
+            }
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.stages
+            .iter()
+            .map(|stage| stage.czt.get_inplace_scratch_len())
+            .max()
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{
+    bluesteins::{polar_pow, to_f64},
+    cost::{self, CostEstimate},
+    Czt, CztParams,
+};
+
+/// Direct evaluation of each output bin with a second-order Goertzel recursion.
+///
+/// Every bin costs O(n) real multiply-adds and no FFTs are involved, which beats Bluestein's
+/// algorithm when only a handful of bins are needed. The recursion's rounding error grows with
+/// `n` though, see [`error_growth`](Self::error_growth), so it only pays off on short inputs.
+pub struct GoertzelAlgorithm<T: FftNum> {
+    // r_k = z_k^-1 together with the real recursion coefficients 2 Re(r_k) and |r_k|^2
+    bins: Vec<(Complex<T>, T, T)>,
+    n: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> GoertzelAlgorithm<T> {
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        let a_inv = a.inv();
+        let bins = (0..m as i128)
+            .map(|k| {
+                let r = a_inv * polar_pow(w, 2 * k);
+                (r, r.re + r.re, r.norm_sqr())
+            })
+            .collect();
+
+        Self { bins, n, a, w }
+    }
+
+    /// Rough growth of the rounding error with the input length, in units of `T::epsilon()`:
+    /// `n / |sin theta|` for the bin angle `theta` closest to 0 or pi. There the two roots of
+    /// `t^2 - 2 Re(r) t + |r|^2` meet, and every step of the recursion loses more accuracy.
+    pub fn error_growth(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> f64 {
+        let (theta_0, step) = (-to_f64(a).arg(), to_f64(w).arg());
+        let sin = (0..m)
+            .map(|k| (theta_0 + k as f64 * step).sin().abs())
+            .fold(f64::INFINITY, f64::min);
+        n as f64 / sin
+    }
+}
+
+impl<T: FftNum> Czt<T> for GoertzelAlgorithm<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        // X_k = P(r_k) with P(t) = sum_j x_j t^j, reduced modulo (t - r_k)(t - conj(r_k))
+        for (out, &(r, p, q)) in scratch.iter_mut().zip(&self.bins) {
+            let mut s1 = Complex::zero();
+            let mut s2 = Complex::zero();
+            for &x in buffer[..self.n].iter().rev() {
+                let s0 = x + s1 * p - s2 * q;
+                s2 = s1;
+                s1 = s0;
+            }
+            *out = s1 - r.conj() * s2;
+        }
+
+        buffer[..self.bins.len()].copy_from_slice(scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.bins.len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        self.bins.len()
+            * (core::mem::size_of::<(Complex<T>, T, T)>() + core::mem::size_of::<Complex<T>>())
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::goertzel::<T>(self.n, self.bins.len())
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.bins.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.bins.len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use std::sync::Arc;
+
+use crate::fft::{num_complex::Complex, num_traits::Zero};
+use rustfft::FftPlanner;
+use wgpu::util::DeviceExt;
+
+use crate::{bluesteins::BluesteinsAlgorithm, ConvolutionLength, Czt, CztParams};
+
+const WORKGROUP_SIZE: u32 = 64;
+const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65535;
+
+/// A wgpu device with the compiled Bluestein kernels.
+pub struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    chirp_in: wgpu::ComputePipeline,
+    fft_stage: wgpu::ComputePipeline,
+    multiply: wgpu::ComputePipeline,
+    chirp_out: wgpu::ComputePipeline,
+}
+
+impl GpuContext {
+    /// Opens the default adapter, or returns `None` if the system has none.
+    pub fn new() -> Option<Self> {
+        pollster::block_on(Self::request())
+    }
+
+    async fn request() -> Option<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .ok()?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("rustczt"),
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+            .await
+            .ok()?;
+
+        let module = device.create_shader_module(wgpu::include_wgsl!("gpu.wgsl"));
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rustczt"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage(1, true),
+                storage(2, false),
+                storage(3, true),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("rustczt"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        Some(Self {
+            chirp_in: pipeline("chirp_in"),
+            fft_stage: pipeline("fft_stage"),
+            multiply: pipeline("multiply"),
+            chirp_out: pipeline("chirp_out"),
+            device,
+            queue,
+            layout,
+        })
+    }
+
+    fn params(&self, l: usize, n: usize, m: usize, ns: usize, sign: f32) -> wgpu::Buffer {
+        let words = [
+            l as u32,
+            n as u32,
+            m as u32,
+            ns as u32,
+            sign.to_bits(),
+            0,
+            0,
+            0,
+        ];
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&words),
+                usage: wgpu::BufferUsages::UNIFORM,
+            })
+    }
+
+    fn upload(&self, values: &[Complex<f32>], usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        let floats: Vec<f32> = values.iter().flat_map(|c| [c.re, c.im]).collect();
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&floats),
+                usage,
+            })
+    }
+
+    fn storage(&self, len: usize, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (len * std::mem::size_of::<Complex<f32>>()) as u64,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        params: &wgpu::Buffer,
+        src: &wgpu::Buffer,
+        dst: &wgpu::Buffer,
+        coefficients: &wgpu::Buffer,
+        threads: usize,
+    ) {
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: src.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: dst.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: coefficients.as_entire_binding(),
+                },
+            ],
+        });
+
+        let groups = (threads as u32).div_ceil(WORKGROUP_SIZE);
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(
+            groups.min(MAX_WORKGROUPS_PER_DIMENSION),
+            groups.div_ceil(MAX_WORKGROUPS_PER_DIMENSION),
+            1,
+        );
+    }
+}
+
+/// Bluestein's algorithm on the GPU in single precision.
+///
+/// The chirp tables are computed on the host in double precision and uploaded once. Every call
+/// stages the signals into device memory, runs chirp multiply, FFT, kernel multiply, inverse FFT
+/// and chirp multiply as compute passes over the whole batch, and reads the bins back.
+pub struct GpuBluesteins {
+    context: Arc<GpuContext>,
+    n: usize,
+    m: usize,
+    l: usize,
+    y_coefficients: wgpu::Buffer,
+    v_coefficients: wgpu::Buffer,
+    x_coefficients: wgpu::Buffer,
+    pointwise_params: wgpu::Buffer,
+    forward_stages: Vec<wgpu::Buffer>,
+    inverse_stages: Vec<wgpu::Buffer>,
+    params: CztParams<f32>,
+}
+
+impl GpuBluesteins {
+    pub fn new(
+        context: Arc<GpuContext>,
+        n: usize,
+        m: usize,
+        a: Complex<f64>,
+        w: Complex<f64>,
+    ) -> Self {
+        let reference = BluesteinsAlgorithm::new_segment(
+            n,
+            m,
+            a,
+            w,
+            0,
+            0,
+            ConvolutionLength::PowerOfTwo,
+            &mut FftPlanner::new(),
+        );
+        let l = reference.v_coefficients().len();
+        let narrow = |c: &Complex<f64>| Complex::new(c.re as f32, c.im as f32);
+        let upload =
+            |values: Vec<Complex<f32>>| context.upload(&values, wgpu::BufferUsages::STORAGE);
+
+        let y_coefficients = upload(reference.y_coefficients().iter().map(narrow).collect());
+        let v_coefficients = upload(reference.v_coefficients().iter().map(narrow).collect());
+        let x_coefficients = upload(reference.x_coefficients().iter().map(narrow).collect());
+
+        let spans = || (0..l.trailing_zeros()).map(|stage| 1 << stage);
+        let forward_stages = spans()
+            .map(|ns| context.params(l, n, m, ns, -1.0))
+            .collect();
+        let inverse_stages = spans().map(|ns| context.params(l, n, m, ns, 1.0)).collect();
+
+        Self {
+            pointwise_params: context.params(l, n, m, 0, 0.0),
+            context,
+            n,
+            m,
+            l,
+            y_coefficients,
+            v_coefficients,
+            x_coefficients,
+            forward_stages,
+            inverse_stages,
+            params: CztParams {
+                n,
+                m,
+                a: narrow(&a),
+                w: narrow(&w),
+            },
+        }
+    }
+
+    /// Most signals one submission can hold within the device's storage binding limit.
+    fn max_batch(&self) -> usize {
+        let limit = self.context.device.limits().max_storage_buffer_binding_size as usize;
+        (limit / (self.l * std::mem::size_of::<Complex<f32>>())).max(1)
+    }
+
+    /// Transforms `count` packed length-n signals into `count` packed length-m spectra.
+    fn run(&self, input: &[Complex<f32>], count: usize) -> Vec<Complex<f32>> {
+        let context = &self.context;
+        let input = context.upload(input, wgpu::BufferUsages::STORAGE);
+        let mut src = context.storage(count * self.l, wgpu::BufferUsages::STORAGE);
+        let mut dst = context.storage(count * self.l, wgpu::BufferUsages::STORAGE);
+        let output = context.storage(
+            count * self.m,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = context.storage(
+            count * self.m,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let (pointwise, butterflies) = (count * self.l, count * self.l / 2);
+
+        context.dispatch(
+            &mut encoder,
+            &context.chirp_in,
+            &self.pointwise_params,
+            &input,
+            &src,
+            &self.y_coefficients,
+            pointwise,
+        );
+        for params in &self.forward_stages {
+            context.dispatch(
+                &mut encoder,
+                &context.fft_stage,
+                params,
+                &src,
+                &dst,
+                &self.v_coefficients,
+                butterflies,
+            );
+            std::mem::swap(&mut src, &mut dst);
+        }
+        context.dispatch(
+            &mut encoder,
+            &context.multiply,
+            &self.pointwise_params,
+            &src,
+            &dst,
+            &self.v_coefficients,
+            pointwise,
+        );
+        std::mem::swap(&mut src, &mut dst);
+        for params in &self.inverse_stages {
+            context.dispatch(
+                &mut encoder,
+                &context.fft_stage,
+                params,
+                &src,
+                &dst,
+                &self.v_coefficients,
+                butterflies,
+            );
+            std::mem::swap(&mut src, &mut dst);
+        }
+        context.dispatch(
+            &mut encoder,
+            &context.chirp_out,
+            &self.pointwise_params,
+            &src,
+            &output,
+            &self.x_coefficients,
+            count * self.m,
+        );
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, readback.size());
+        context.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| {
+            result.expect("failed to map the GPU readback buffer")
+        });
+        context
+            .device
+            .poll(wgpu::PollType::Wait)
+            .expect("GPU did not finish the transform");
+
+        let floats: Vec<f32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+        floats
+            .chunks_exact(2)
+            .map(|c| Complex::new(c[0], c[1]))
+            .collect()
+    }
+}
+
+impl Czt<f32> for GpuBluesteins {
+    fn process_with_scratch(&self, buffer: &mut [Complex<f32>], scratch: &mut [Complex<f32>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+
+        let len = buffer.len();
+        self.process_batch_with_scratch(buffer, len, 1, scratch);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<f32>],
+        stride: usize,
+        count: usize,
+        _scratch: &mut [Complex<f32>],
+    ) {
+        let len = self.get_buffer_len();
+        assert!(stride >= len);
+        if count == 0 {
+            return;
+        }
+        assert!(buffers.len() >= (count - 1) * stride + len);
+
+        let buffers = &mut buffers[..(count - 1) * stride + len];
+        for group in buffers.chunks_mut(stride * self.max_batch()) {
+            let signals = group.len().div_ceil(stride);
+            let mut input = vec![Complex::zero(); signals * self.n];
+            for (packed, buffer) in input.chunks_exact_mut(self.n).zip(group.chunks(stride)) {
+                packed.copy_from_slice(&buffer[..self.n]);
+            }
+
+            let output = self.run(&input, signals);
+            for (buffer, bins) in group.chunks_mut(stride).zip(output.chunks_exact(self.m)) {
+                buffer[..self.m].copy_from_slice(bins);
+            }
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<f32> {
+        self.params
+    }
+}
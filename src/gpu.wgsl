@@ -0,0 +1,84 @@
+struct Params {
+    l: u32,
+    n: u32,
+    m: u32,
+    ns: u32,
+    sign: f32,
+    pad0: u32,
+    pad1: u32,
+    pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> src: array<vec2<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec2<f32>>;
+@group(0) @binding(3) var<storage, read> coefficients: array<vec2<f32>>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+const PI: f32 = 3.14159265358979;
+
+fn cmul(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    return vec2<f32>(a.x * b.x - a.y * b.y, a.x * b.y + a.y * b.x);
+}
+
+// Large dispatches spill into the y dimension of the grid.
+fn thread_index(id: vec3<u32>, groups: vec3<u32>) -> u32 {
+    return id.x + id.y * groups.x * WORKGROUP_SIZE;
+}
+
+// Step one: dst[b * l + i] = src[b * n + i] * y[i], zero padded to l.
+@compute @workgroup_size(64)
+fn chirp_in(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let t = thread_index(id, groups);
+    if (t >= arrayLength(&dst)) {
+        return;
+    }
+    let b = t / params.l;
+    let i = t % params.l;
+    if (i < params.n) {
+        dst[t] = cmul(src[b * params.n + i], coefficients[i]);
+    } else {
+        dst[t] = vec2<f32>(0.0, 0.0);
+    }
+}
+
+// One radix-2 Stockham stage of span ns over every signal in the batch.
+@compute @workgroup_size(64)
+fn fft_stage(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let t = thread_index(id, groups);
+    if (t >= arrayLength(&dst) / 2u) {
+        return;
+    }
+    let half = params.l / 2u;
+    let base = (t / half) * params.l;
+    let j = t % half;
+    let k = j % params.ns;
+    let angle = params.sign * PI * f32(k) / f32(params.ns);
+    let a = src[base + j];
+    let b = cmul(src[base + j + half], vec2<f32>(cos(angle), sin(angle)));
+    let out = base + (j / params.ns) * params.ns * 2u + k;
+    dst[out] = a + b;
+    dst[out + params.ns] = a - b;
+}
+
+// Step two: pointwise product with the transformed kernel.
+@compute @workgroup_size(64)
+fn multiply(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let t = thread_index(id, groups);
+    if (t >= arrayLength(&dst)) {
+        return;
+    }
+    dst[t] = cmul(src[t], coefficients[t % params.l]);
+}
+
+// Step three: dst[b * m + k] = src[b * l + k] * x[k].
+@compute @workgroup_size(64)
+fn chirp_out(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let t = thread_index(id, groups);
+    if (t >= arrayLength(&dst)) {
+        return;
+    }
+    let b = t / params.m;
+    let k = t % params.m;
+    dst[t] = cmul(src[b * params.l + k], coefficients[k]);
+}
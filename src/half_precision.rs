@@ -0,0 +1,83 @@
+use alloc::sync::Arc;
+
+use crate::fft::num_complex::Complex;
+use half::{f16, slice::HalfFloatSliceExt};
+
+use crate::{Czt, CztParams};
+
+/// Widens interleaved `re, im` pairs of f16 into complex f32, with the hardware conversion
+/// instructions where the target has them.
+pub fn widen(src: &[f16], dst: &mut [Complex<f32>]) {
+    assert_eq!(src.len(), 2 * dst.len());
+    src.convert_to_f32_slice(as_interleaved_mut(dst));
+}
+
+/// Rounds complex f32 to interleaved `re, im` pairs of f16. Values beyond the f16 range become
+/// infinite.
+pub fn narrow(src: &[Complex<f32>], dst: &mut [f16]) {
+    assert_eq!(2 * src.len(), dst.len());
+    dst.convert_from_f32_slice(as_interleaved(src));
+}
+
+/// Runs an f32 plan on interleaved f16 input and output, widening into and narrowing out of the
+/// scratch buffer.
+pub struct HalfCzt {
+    czt: Arc<dyn Czt<f32>>,
+}
+
+impl HalfCzt {
+    pub fn new(czt: Arc<dyn Czt<f32>>) -> Self {
+        Self { czt }
+    }
+
+    /// `input` holds `2n` and `output` `2m` f16.
+    pub fn process(&self, input: &[f16], output: &mut [f16]) {
+        let mut scratch = vec![Complex::default(); self.get_scratch_len()];
+        self.process_with_scratch(input, output, &mut scratch);
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        input: &[f16],
+        output: &mut [f16],
+        scratch: &mut [Complex<f32>],
+    ) {
+        let (n, m) = (self.get_input_len(), self.get_output_len());
+        assert_eq!(input.len(), 2 * n);
+        assert_eq!(output.len(), 2 * m);
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.czt.get_buffer_len());
+        widen(input, &mut buffer[..n]);
+        buffer[n..].fill(Complex::default());
+        self.czt.process_with_scratch(buffer, scratch);
+        narrow(&buffer[..m], output);
+    }
+
+    /// In complex f32 elements.
+    pub fn get_scratch_len(&self) -> usize {
+        self.czt.get_buffer_len() + self.czt.get_inplace_scratch_len()
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.czt.get_input_len()
+    }
+
+    pub fn get_output_len(&self) -> usize {
+        self.czt.get_output_len()
+    }
+
+    pub fn params(&self) -> CztParams<f32> {
+        self.czt.params()
+    }
+}
+
+fn as_interleaved(slice: &[Complex<f32>]) -> &[f32] {
+    // SAFETY: `Complex<f32>` is `repr(C)`, two f32 without padding.
+    unsafe { core::slice::from_raw_parts(slice.as_ptr().cast(), 2 * slice.len()) }
+}
+
+fn as_interleaved_mut(slice: &mut [Complex<f32>]) -> &mut [f32] {
+    // SAFETY: as in `as_interleaved`.
+    unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), 2 * slice.len()) }
+}
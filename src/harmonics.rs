@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{peak::PeakInterpolator, window::Window, Czt, CztPlanner, FftProvider};
+
+/// Bins in each zoom around a harmonic. They span four DFT bins, and a parabola through the
+/// largest bin and its neighbours locates the peak between them.
+const ZOOM_BINS: usize = 129;
+
+/// Result of [`analyze_harmonics`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HarmonicAnalysis<T> {
+    /// Measured frequency of each harmonic in Hz, the fundamental first.
+    pub frequencies: Vec<T>,
+    /// Peak amplitude of each harmonic, corrected for the window's coherent gain.
+    pub amplitudes: Vec<T>,
+    /// Total harmonic distortion, `sqrt(A_2^2 + A_3^2 + ...) / A_1`.
+    pub thd: T,
+    /// Total harmonic distortion plus noise: everything but the fundamental and DC, relative to
+    /// the fundamental, in RMS.
+    pub thd_n: T,
+}
+
+/// Measures the fundamental near `fundamental` Hz and its first `harmonics - 1` overtones in the
+/// real `signal` with a narrow zoom transform around each.
+///
+/// The fundamental is searched within two DFT bins of the estimate and harmonic `h` within two
+/// DFT bins of `h` times the measured fundamental; harmonics at or above Nyquist are skipped.
+pub fn analyze_harmonics<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    signal: &[T],
+    sample_rate: T,
+    fundamental: T,
+    harmonics: usize,
+    window: Window<T>,
+) -> HarmonicAnalysis<T> {
+    let n = signal.len();
+    assert!(n > 1 && harmonics > 0);
+    let two = T::from_f64(2.0).unwrap();
+    let nyquist = sample_rate / two;
+    let span = two * sample_rate / T::from_usize(n).unwrap();
+    let coefficients = window.coefficients(n);
+    let coherent_gain = coefficients.iter().fold(T::zero(), |acc, &w| acc + w);
+
+    let input: Vec<_> = signal.iter().map(|&x| Complex::new(x, T::zero())).collect();
+    let mut spectrum = vec![Complex::zero(); ZOOM_BINS];
+    let mut scratch = Vec::new();
+    let mut peak = |center: T| {
+        let lo = (center - span).max(T::zero());
+        let hi = (center + span).min(nyquist);
+        let zoom = planner.plan_zoom_fft_hz_windowed(n, ZOOM_BINS, sample_rate, lo, hi, window);
+        scratch.resize(zoom.get_outofplace_scratch_len(), Complex::zero());
+        zoom.process_outofplace_with_scratch(&input, &mut spectrum, &mut scratch);
+        let mut k = 0;
+        for (i, x) in spectrum.iter().enumerate() {
+            if x.norm_sqr() > spectrum[k].norm_sqr() {
+                k = i;
+            }
+        }
+
+        let estimate = zoom.interpolate_peak(&spectrum, k, PeakInterpolator::Quadratic);
+        (estimate.frequency, two * estimate.magnitude / coherent_gain)
+    };
+
+    let (f1, a1) = peak(fundamental);
+    let (mut frequencies, mut amplitudes) = (vec![f1], vec![a1]);
+    for h in 2..=harmonics {
+        let center = f1 * T::from_usize(h).unwrap();
+        if center >= nyquist {
+            break;
+        }
+        let (f, a) = peak(center);
+        frequencies.push(f);
+        amplitudes.push(a);
+    }
+
+    let distortion = amplitudes[1..]
+        .iter()
+        .fold(T::zero(), |acc, &a| acc + a * a);
+    let thd = distortion.sqrt() / a1;
+
+    // Total power is measured through the window too; a tapered window keeps a non-integer
+    // number of periods from biasing it.
+    let weighted = signal.iter().zip(&coefficients);
+    let mean = weighted
+        .clone()
+        .fold(T::zero(), |acc, (&x, &w)| acc + w * x)
+        / coherent_gain;
+    let (power, energy) = weighted.fold((T::zero(), T::zero()), |(p, e), (&x, &w)| {
+        (p + w * w * (x - mean) * (x - mean), e + w * w)
+    });
+    let power = power / energy;
+    let fundamental_power = a1 * a1 / two;
+    let thd_n = ((power - fundamental_power).max(T::zero()) / fundamental_power).sqrt();
+
+    HarmonicAnalysis {
+        frequencies,
+        amplitudes,
+        thd,
+        thd_n,
+    }
+}
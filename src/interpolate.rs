@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{bluesteins::from_f64, CztPlanner, FftProvider};
+
+/// Band-limited upsampling: `ceil(n factor)` samples at times `p / factor`, in input samples.
+///
+/// The record is taken as one period of a band-limited signal, see
+/// [`Resampler`](crate::resample::Resampler); zero-pad pulses so they do not wrap around.
+pub fn interpolate<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    signal: &[Complex<T>],
+    factor: T,
+) -> Vec<Complex<T>> {
+    let resampler = planner.plan_resampler(signal.len(), T::one(), factor);
+    let mut output = vec![Complex::zero(); resampler.get_output_len()];
+    resampler.process(signal, &mut output);
+    output
+}
+
+/// Evaluates the band-limited interpolant of `signal` at arbitrary `times`, in samples, e.g. to
+/// refine the time of a pulse's peak. The Fourier coefficients come from one CZT; each time then
+/// costs `O(n)`.
+pub fn at_times<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    signal: &[Complex<T>],
+    times: &[T],
+) -> Vec<Complex<T>> {
+    let n = signal.len();
+    assert!(n > 0);
+    let one = Complex::new(T::one(), T::zero());
+    let dft = Complex::from_polar(1.0, -core::f64::consts::TAU / n as f64);
+    let mut spectrum = signal.to_vec();
+    planner
+        .plan_czt_forward(n, n, one, from_f64(dft))
+        .process(&mut spectrum);
+
+    // Terms -h..=h, with the Nyquist bin of an even n split between +-h.
+    let h = n / 2;
+    let nyquist_weight = if n.is_multiple_of(2) { 0.5 } else { 1.0 };
+    let scale = T::from_f64(1.0 / n as f64).unwrap();
+    let series: Vec<_> = (0..=2 * h)
+        .map(|i| {
+            let k = i as isize - h as isize;
+            let weight = if h > 0 && k.unsigned_abs() == h {
+                nyquist_weight
+            } else {
+                1.0
+            };
+            spectrum[k.rem_euclid(n as isize) as usize] * scale * T::from_f64(weight).unwrap()
+        })
+        .collect();
+
+    times
+        .iter()
+        .map(|&t| {
+            // Horner in e^(2 pi i t / n) from k = -h.
+            let turn = core::f64::consts::TAU * t.to_f64().unwrap() / n as f64;
+            let step = from_f64(Complex::from_polar(1.0, turn));
+            let sum = series
+                .iter()
+                .rev()
+                .fold(Complex::<T>::zero(), |acc, &c| acc * step + c);
+            sum * from_f64(Complex::from_polar(1.0, -turn * h as f64))
+        })
+        .collect()
+}
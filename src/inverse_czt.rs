@@ -0,0 +1,276 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, One, Zero},
+    Fft, FftNum,
+};
+
+use crate::{
+    agrees_within,
+    bluesteins::{from_f64, polar_pow, to_f64, BluesteinsAlgorithm},
+    fft_provider::fft_memory_usage,
+    naive_czt::NaiveCzt,
+    verification_signal, Czt, CztParams, FftProvider,
+};
+
+/// Inverse chirp Z transform after Sukhoy & Stoytchev.
+///
+/// Recovers `x` from `X = czt(x, n, n, a, w)` by solving the underlying Vandermonde system with
+/// nodes `w^k` in O(n log n): a Lagrange weighting, a forward CZT and a Toeplitz product.
+pub struct InverseCzt<T: FftNum> {
+    c_coefficients: Vec<Complex<T>>,
+    g_coefficients: Vec<Complex<T>>,
+    x_coefficients: Vec<Complex<T>>,
+    czt: BluesteinsAlgorithm<T>,
+    fft_forward: Arc<dyn Fft<T>>,
+    fft_inverse: Arc<dyn Fft<T>>,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> InverseCzt<T> {
+    pub fn new(
+        n: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        // 1 / Q'(w^k) = (-1)^(n-1-k) / (w^(k(k-1)/2 + k(n-1-k)) p_k p_(n-1-k)), where
+        // Q(t) = (t - 1)(t - w)...(t - w^(n-1)) and p_k = (w - 1)(w^2 - 1)...(w^k - 1)
+        fn compute_c_coefficients<T: Float + FftNum>(n: usize, w: Complex<T>) -> Vec<Complex<T>> {
+            let mut p = Vec::with_capacity(n);
+            p.push(LogPolar::ONE);
+            for s in 1..n {
+                let next = p[s - 1].mul(LogPolar::power_minus_one(w, s as i128));
+                p.push(next);
+            }
+            (0..n)
+                .map(|k| {
+                    let (k, n) = (k as i128, n as i128);
+                    let exponent = k * (k - 1) / 2 + k * (n - 1 - k);
+                    let sign = if (n - 1 - k) % 2 == 0 { 1.0 } else { -1.0 };
+                    let denominator = LogPolar::power(w, exponent)
+                        .mul(p[k as usize])
+                        .mul(p[(n - 1 - k) as usize]);
+                    denominator.inv().to_complex(sign)
+                })
+                .collect()
+        }
+        // Coefficients of Q(t) in descending order: g_j = q_(n - j) = (-1)^j w^(j(j-1)/2) [n, j]_w
+        fn compute_g_coefficients<T: Float + FftNum>(
+            n: usize,
+            l: usize,
+            w: Complex<T>,
+            fft_forward: &dyn Fft<T>,
+        ) -> Vec<Complex<T>> {
+            let mut out = vec![Complex::zero(); l];
+            let mut binomial = LogPolar::ONE;
+            for (j, g) in out.iter_mut().enumerate().take(n) {
+                let j = j as i128;
+                if j > 0 {
+                    binomial = binomial
+                        .mul(LogPolar::power_minus_one(w, n as i128 - j + 1))
+                        .mul(LogPolar::power_minus_one(w, j).inv());
+                }
+                let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+                *g = binomial
+                    .mul(LogPolar::power(w, j * (j - 1) / 2))
+                    .to_complex(sign);
+            }
+            fft_forward.process(&mut out);
+            let l = T::from_usize(l).unwrap();
+            out.iter_mut().for_each(|g| *g = *g / l);
+            out
+        }
+
+        let l = (2 * n - 1).next_power_of_two();
+        let fft_forward = fft_provider.plan_forward(l);
+        let fft_inverse = fft_provider.plan_inverse(l);
+
+        let c_coefficients = compute_c_coefficients(n, w);
+        let g_coefficients = compute_g_coefficients(n, l, w, fft_forward.as_ref());
+        let x_coefficients = (0..n as i128).map(|j| polar_pow(a, 2 * j)).collect();
+        let czt = BluesteinsAlgorithm::new(n, n, Complex::one(), w, 0, fft_provider);
+
+        Self {
+            c_coefficients,
+            g_coefficients,
+            x_coefficients,
+            czt,
+            fft_forward,
+            fft_inverse,
+            a,
+            w,
+        }
+    }
+}
+
+/// A complex number as `ln |z|` and `z / |z|`, so the long products behind the Lagrange weights,
+/// whose magnitudes reach `e^(0.16 n)` on the unit circle, neither overflow nor underflow before
+/// they cancel.
+#[derive(Clone, Copy)]
+struct LogPolar {
+    ln_norm: f64,
+    phase: Complex<f64>,
+}
+
+impl LogPolar {
+    const ONE: Self = Self {
+        ln_norm: 0.0,
+        phase: Complex::new(1.0, 0.0),
+    };
+
+    fn new(z: Complex<f64>) -> Self {
+        let norm = z.norm();
+        Self {
+            ln_norm: norm.ln(),
+            phase: z / norm,
+        }
+    }
+
+    /// `w^exponent`, with the phase reduced exactly as in [`polar_pow`].
+    fn power<T: Float + FftNum>(w: Complex<T>, exponent: i128) -> Self {
+        let (r, _) = to_f64(w).to_polar();
+        Self {
+            ln_norm: r.ln() * exponent as f64,
+            phase: Self::new(polar_pow(to_f64(w) / r, 2 * exponent)).phase,
+        }
+    }
+
+    /// `w^exponent - 1`, factoring out `w^exponent` when it is large so the difference keeps
+    /// its precision and range.
+    fn power_minus_one<T: Float + FftNum>(w: Complex<T>, exponent: i128) -> Self {
+        let power = Self::power(w, exponent);
+        if power.ln_norm > 0.0 {
+            let one = Complex::new(1.0, 0.0);
+            power.mul(Self::new(one - polar_pow(to_f64(w), -2 * exponent)))
+        } else {
+            Self::new(polar_pow(to_f64(w), 2 * exponent) - 1.0)
+        }
+    }
+
+    fn mul(self, other: Self) -> Self {
+        let phase = self.phase * other.phase;
+        Self {
+            ln_norm: self.ln_norm + other.ln_norm,
+            phase: phase / phase.norm(),
+        }
+    }
+
+    fn inv(self) -> Self {
+        Self {
+            ln_norm: -self.ln_norm,
+            phase: self.phase.conj(),
+        }
+    }
+
+    /// `sign * z`, rounded to `T`.
+    fn to_complex<T: FftNum>(self, sign: f64) -> Complex<T> {
+        from_f64(self.phase * (sign * self.ln_norm.exp()))
+    }
+}
+
+impl<T: FftNum> InverseCzt<T> {
+    fn n(&self) -> usize {
+        self.c_coefficients.len()
+    }
+
+    fn l(&self) -> usize {
+        self.fft_forward.len()
+    }
+}
+
+impl<T: FftNum> Czt<T> for InverseCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.n());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let (expanded_buffer, scratch) = scratch.split_at_mut(self.l());
+
+        // Step one: weight by the Lagrange denominators, then S_r = sum_k c_k X_k w^(rk)
+        for (x, &c) in buffer.iter_mut().zip(&self.c_coefficients) {
+            *x = *x * c;
+        }
+        self.czt
+            .process_with_scratch(buffer, &mut scratch[..self.czt.get_inplace_scratch_len()]);
+
+        // Step two: y_(n-1-j) = sum_r S_r g_(j-r), a linear convolution
+        let (head, tail) = expanded_buffer.split_at_mut(self.n());
+        head.copy_from_slice(buffer);
+        tail.fill(Complex::zero());
+
+        let fft_scratch = &mut scratch[..self.fft_forward.get_inplace_scratch_len()];
+        self.fft_forward
+            .process_with_scratch(expanded_buffer, fft_scratch);
+        for (y, &g) in expanded_buffer.iter_mut().zip(&self.g_coefficients) {
+            *y = *y * g;
+        }
+        let fft_scratch = &mut scratch[..self.fft_inverse.get_inplace_scratch_len()];
+        self.fft_inverse
+            .process_with_scratch(expanded_buffer, fft_scratch);
+
+        // Step three: undo the starting point, x_j = A^j y_j
+        for ((x, &y), &coeff) in buffer
+            .iter_mut()
+            .zip(expanded_buffer[..self.n()].iter().rev())
+            .zip(&self.x_coefficients)
+        {
+            *x = y * coeff;
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.l()
+            + self
+                .czt
+                .get_inplace_scratch_len()
+                .max(self.fft_forward.get_inplace_scratch_len())
+                .max(self.fft_inverse.get_inplace_scratch_len())
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let size = core::mem::size_of::<Complex<T>>();
+        let tables = self.c_coefficients.len()
+            + self.g_coefficients.len()
+            + self.x_coefficients.len()
+            + self.get_inplace_scratch_len();
+        // The inner CZT runs in this plan's scratch.
+        tables * size + self.czt.get_memory_usage() - self.czt.get_inplace_scratch_len() * size
+            + fft_memory_usage(&*self.fft_forward)
+            + fft_memory_usage(&*self.fft_inverse)
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.n()
+    }
+
+    /// Contour of the forward transform being inverted, not of a transform this plan computes.
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n(),
+            m: self.n(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    /// Runs the forward transform of a fixed pseudo-random signal, by a
+    /// [`NaiveCzt`](crate::naive_czt::NaiveCzt) on [`params`](Czt::params), through the plan and
+    /// reports whether every sample comes back within `tolerance` relative to the largest one.
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        let n = self.n();
+        let signal = verification_signal(n, n);
+        let mut buffer = signal.clone();
+        NaiveCzt::new(n, n, self.a, self.w).process(&mut buffer);
+        self.process(&mut buffer);
+        agrees_within(&signal, &buffer, tolerance)
+    }
+}
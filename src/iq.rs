@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::{io, path::Path};
+
+use crate::fft::num_complex::Complex;
+
+/// Byte order of multi-byte components.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Interleaved `I, Q` integer pairs as SDR receivers capture them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IqFormat {
+    /// Signed 16-bit components, scaled by `1 / 32768`.
+    I16(Endianness),
+    /// Signed 8-bit components (e.g. HackRF), scaled by `1 / 128`.
+    I8,
+    /// Offset-binary 8-bit components (e.g. RTL-SDR), centered on 127.5 and scaled by
+    /// `1 / 127.5`.
+    U8,
+}
+
+impl IqFormat {
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            IqFormat::I16(_) => 4,
+            IqFormat::I8 | IqFormat::U8 => 2,
+        }
+    }
+
+    fn component(&self, bytes: &[u8]) -> f32 {
+        match *self {
+            IqFormat::I16(Endianness::Little) => {
+                i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0
+            }
+            IqFormat::I16(Endianness::Big) => {
+                i16::from_be_bytes([bytes[0], bytes[1]]) as f32 / 32768.0
+            }
+            IqFormat::I8 => bytes[0] as i8 as f32 / 128.0,
+            IqFormat::U8 => (bytes[0] as f32 - 127.5) / 127.5,
+        }
+    }
+}
+
+/// Decodes every whole sample of `bytes`; a trailing partial sample is dropped.
+pub fn decode_iq(bytes: &[u8], format: IqFormat) -> Vec<Complex<f32>> {
+    let half = format.bytes_per_sample() / 2;
+    bytes
+        .chunks_exact(2 * half)
+        .map(|pair| {
+            Complex::new(
+                format.component(&pair[..half]),
+                format.component(&pair[half..]),
+            )
+        })
+        .collect()
+}
+
+/// Reads `reader` to its end and decodes it, see [`decode_iq`].
+#[cfg(feature = "std")]
+pub fn read_iq<R: io::Read>(mut reader: R, format: IqFormat) -> io::Result<Vec<Complex<f32>>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(decode_iq(&bytes, format))
+}
+
+/// Loads a capture file, see [`decode_iq`].
+#[cfg(feature = "std")]
+pub fn load_iq(path: impl AsRef<Path>, format: IqFormat) -> io::Result<Vec<Complex<f32>>> {
+    Ok(decode_iq(&std::fs::read(path)?, format))
+}
@@ -0,0 +1,126 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::mem::MaybeUninit;
+
+use crate::fft::{num_complex::Complex, num_traits::Float, FftNum};
+
+use crate::{Czt, CztParams};
+
+/// A CZT plan evaluating `H(e^(sT)) = sum h_j e^(-s j T)` of an impulse response sampled every
+/// `T = 1 / sample_rate` seconds along the vertical line `s = sigma + i omega`, on `m` equally
+/// spaced `omega` in rad/s.
+///
+/// The line maps to an arc of radius `e^(sigma T)` in the z-plane; `omega` is periodic in
+/// `2 pi sample_rate`. Multiply by `T` to approximate the Laplace transform of the underlying
+/// continuous response.
+pub struct SPlaneLine<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+    sigma: T,
+    omega_start: T,
+    omega_step: T,
+}
+
+impl<T: FftNum> SPlaneLine<T> {
+    pub fn new(czt: Arc<dyn Czt<T>>, sigma: T, omega_start: T, omega_step: T) -> Self {
+        Self {
+            czt,
+            sigma,
+            omega_start,
+            omega_step,
+        }
+    }
+
+    /// Real part of the line in 1/s.
+    pub fn sigma(&self) -> T {
+        self.sigma
+    }
+
+    /// Spacing of the `omega` grid in rad/s.
+    pub fn omega_step(&self) -> T {
+        self.omega_step
+    }
+
+    pub fn omega_of_bin(&self, k: usize) -> T {
+        self.omega_start + self.omega_step * T::from_usize(k).unwrap()
+    }
+
+    pub fn omegas(&self) -> Vec<T> {
+        (0..self.get_output_len())
+            .map(|k| self.omega_of_bin(k))
+            .collect()
+    }
+
+    /// The point `s = sigma + i omega` of bin `k`.
+    pub fn s_of_bin(&self, k: usize) -> Complex<T> {
+        Complex::new(self.sigma, self.omega_of_bin(k))
+    }
+}
+
+impl<T: FftNum> Czt<T> for SPlaneLine<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        self.czt.process_with_scratch(buffer, scratch);
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.czt
+            .process_outofplace_with_scratch(input, output, scratch);
+    }
+
+    fn process_with_uninit_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [MaybeUninit<Complex<T>>],
+    ) {
+        self.czt.process_with_uninit_scratch(buffer, scratch);
+    }
+
+    fn process_batch(&self, buffers: &mut [Complex<T>], stride: usize, count: usize) {
+        self.czt.process_batch(buffers, stride, count);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        self.czt
+            .process_batch_with_scratch(buffers, stride, count, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.czt.get_inplace_scratch_len()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.czt.get_outofplace_scratch_len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        self.czt.get_memory_usage()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.czt.get_input_len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.czt.get_output_len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        self.czt.params()
+    }
+
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        self.czt.verify(tolerance)
+    }
+}
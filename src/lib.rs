@@ -1,19 +1,606 @@
 //! # RustCZT
 //!
-use rustfft::{num_complex::Complex, num_traits::Zero, FftNum};
+//! Builds without `std` (but with `alloc`) when the default `std` feature is disabled; the
+//! accelerator backends and `rayon` require it. rustfft is only a dependency with `std`, so
+//! without it planners are built with [`CztPlanner::with_fft_provider`] and the FFT traits come
+//! from [`fft`].
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[macro_use]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+#[cfg(all(feature = "accelerate", target_vendor = "apple"))]
+pub mod accelerate;
+mod aligned_vec;
+#[cfg(feature = "astro-float")]
+pub mod arbitrary_precision;
+#[cfg(feature = "tokio")]
+pub mod async_czt;
+pub mod blocked;
 pub mod bluesteins;
+#[cfg(feature = "bytemuck")]
+pub mod bytes;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "std")]
+pub mod compat;
+pub mod cost;
+pub mod cqt;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+pub mod czt2d;
+pub mod czt_fft;
+pub mod dct;
+pub mod dft;
+pub mod direct;
+pub mod double_double;
+pub mod error_report;
+pub mod fft;
+pub mod fft_provider;
+#[cfg(feature = "fftw")]
+pub mod fftw_backend;
+pub mod fixed_point;
+pub mod freqz;
+pub mod frft;
+pub mod goertzel;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "half")]
+pub mod half_precision;
+pub mod harmonics;
+pub mod interpolate;
+pub mod inverse_czt;
+pub mod iq;
+pub mod laplace;
+#[cfg(feature = "microfft")]
+pub mod microfft_backend;
+pub mod mixer;
+#[cfg(feature = "mkl")]
+pub mod mkl_backend;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
 pub mod naive_czt;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra_ext;
+#[cfg(feature = "ndarray")]
+pub mod ndarray_axis;
+pub mod normalization;
+#[cfg(feature = "npy")]
+pub mod npy;
+pub mod ntt;
+pub mod peak;
+#[cfg(feature = "std")]
+pub mod pipeline;
 pub mod plan;
-pub use plan::CztPlanner;
+mod pointwise;
+pub mod poly;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod range_doppler;
+pub mod real_czt;
+pub mod realtime;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod refine;
+pub mod resample;
+pub mod rotation;
+#[cfg(any(feature = "serde", feature = "rkyv"))]
+pub mod saved_plan;
+pub mod sliding;
+pub mod spectrogram;
+pub mod spiral;
+#[cfg(feature = "std")]
+pub mod stream;
+mod uninit;
+pub mod warped;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod window;
+pub mod wisdom;
+pub mod zoom;
+pub mod zoom_psd;
+pub use bluesteins::ConvolutionLength;
+pub use fft_provider::FftProvider;
+pub use normalization::Normalization;
+pub use plan::{CztPlanner, PlannerStrategy};
+pub use window::Window;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+/// Sign convention of the exponent, analogous to `rustfft::FftDirection`.
+///
+/// `Forward` evaluates `X_k = sum_j x_j z_k^-j` on `z_k = a * w^-k`; `Inverse` evaluates
+/// `X_k = sum_j x_j z_k^j`, i.e. the forward transform on the contour `(a^-1, w^-1)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CztDirection {
+    Forward,
+    Inverse,
+}
+
+impl CztDirection {
+    pub fn opposite_direction(&self) -> CztDirection {
+        match self {
+            CztDirection::Forward => CztDirection::Inverse,
+            CztDirection::Inverse => CztDirection::Forward,
+        }
+    }
+}
+
+/// The contour a plan evaluates: bin `k` is `sum_j x_j z_k^-j` with `z_k = a * w^-k`, for
+/// `n` inputs and `m` bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes)
+)]
+pub struct CztParams<T> {
+    pub n: usize,
+    pub m: usize,
+    pub a: Complex<T>,
+    pub w: Complex<T>,
+}
+
+impl<T: FftNum + Float> CztParams<T> {
+    /// Checks that the transform is well defined: `n` and `m` positive and `a` and `w` finite
+    /// and nonzero.
+    pub fn validate(&self) -> Result<(), PlanError> {
+        for (name, len) in [("n", self.n), ("m", self.m)] {
+            if len == 0 {
+                return Err(PlanError::ZeroLength { name });
+            }
+        }
+        for (name, z) in [("a", self.a), ("w", self.w)] {
+            if !(z.re.is_finite() && z.im.is_finite()) || z.is_zero() {
+                return Err(PlanError::InvalidPoint { name });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why parameters cannot be planned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlanError {
+    /// `n` or `m` is zero.
+    ZeroLength { name: &'static str },
+    /// `a` or `w` is zero, infinite or NaN.
+    InvalidPoint { name: &'static str },
+}
+
+impl core::fmt::Display for PlanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PlanError::ZeroLength { name } => write!(f, "{name} must be positive"),
+            PlanError::InvalidPoint { name } => write!(f, "{name} must be finite and nonzero"),
+        }
+    }
+}
+
+impl core::error::Error for PlanError {}
+
+/// Why a plan refused to process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CztError {
+    BufferLength {
+        expected: usize,
+        actual: usize,
+    },
+    ScratchLength {
+        expected: usize,
+        actual: usize,
+    },
+    /// The plan cannot run, e.g. because a resource it depends on is gone.
+    InvalidState(&'static str),
+    /// [`process_checked`](Czt::process_checked) found an infinite or NaN value at `index` of
+    /// the named stage's result.
+    NonFinite {
+        stage: &'static str,
+        index: usize,
+    },
+}
+
+impl core::fmt::Display for CztError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CztError::BufferLength { expected, actual } => {
+                write!(
+                    f,
+                    "buffer holds {actual} samples, the plan needs {expected}"
+                )
+            }
+            CztError::ScratchLength { expected, actual } => {
+                write!(
+                    f,
+                    "scratch holds {actual} samples, the plan needs {expected}"
+                )
+            }
+            CztError::InvalidState(reason) => write!(f, "plan cannot run: {reason}"),
+            CztError::NonFinite { stage, index } => {
+                write!(f, "non-finite value at index {index} of the {stage} stage")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CztError {}
+
+/// Fails on the first infinite or NaN value; `x * 0` is zero exactly for finite `x`.
+fn check_finite<T: FftNum>(stage: &'static str, values: &[Complex<T>]) -> Result<(), CztError> {
+    match values
+        .iter()
+        .position(|&x| x * T::zero() != Complex::zero())
+    {
+        Some(index) => Err(CztError::NonFinite { stage, index }),
+        None => Ok(()),
+    }
+}
+
+/// The fixed pseudo-random signal [`Czt::verify`] runs: `n` samples in `[-1, 1)` from xorshift64,
+/// zero-padded to `len`.
+pub(crate) fn verification_signal<T: FftNum>(n: usize, len: usize) -> Vec<Complex<T>> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        T::from_f64((state >> 11) as f64 / (1u64 << 52) as f64 - 1.0).unwrap()
+    };
+    let mut signal = vec![Complex::zero(); len];
+    for x in &mut signal[..n] {
+        *x = Complex::new(next(), next());
+    }
+    signal
+}
+
+/// Whether `actual` is within `tolerance` of `expected`, relative to its largest value.
+pub(crate) fn agrees_within<T: FftNum + Float>(
+    expected: &[Complex<T>],
+    actual: &[Complex<T>],
+    tolerance: T,
+) -> bool {
+    let scale = expected.iter().fold(T::zero(), |max, x| max.max(x.norm()));
+    error_report::ErrorReport::compare(expected, actual).max_abs_error <= tolerance * scale
+}
 
 pub trait Czt<T: FftNum>: Sync + Send {
     fn process(&self, buffer: &mut [Complex<T>]) {
-        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
-        self.process_with_scratch(buffer, &mut scratch);
+        let len = self.get_inplace_scratch_len();
+        let mut scratch = Vec::with_capacity(len);
+        self.process_with_uninit_scratch(buffer, &mut scratch.spare_capacity_mut()[..len]);
     }
 
     fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]);
 
+    /// Like [`process_with_scratch`](Self::process_with_scratch), but `scratch` may be
+    /// uninitialized. Plans that write their scratch before reading it skip zeroing it.
+    fn process_with_uninit_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [MaybeUninit<Complex<T>>],
+    ) {
+        self.process_with_scratch(buffer, uninit::fill(scratch, Complex::zero()));
+    }
+
+    /// [`process`](Self::process), returning an error instead of panicking when `buffer` is
+    /// not [`get_buffer_len`](Self::get_buffer_len) long.
+    fn try_process(&self, buffer: &mut [Complex<T>]) -> Result<(), CztError> {
+        let (expected, actual) = (self.get_buffer_len(), buffer.len());
+        if actual != expected {
+            return Err(CztError::BufferLength { expected, actual });
+        }
+        self.process(buffer);
+        Ok(())
+    }
+
+    /// [`try_process`](Self::try_process) that also fails if the input or the output holds an
+    /// infinite or NaN value, naming the stage where it appeared. Plans built from several
+    /// stages check each of them in debug builds, so a blowup is reported where it starts;
+    /// `buffer` is left untouched unless only the output is affected.
+    fn process_checked(&self, buffer: &mut [Complex<T>]) -> Result<(), CztError> {
+        let (expected, actual) = (self.get_buffer_len(), buffer.len());
+        if actual != expected {
+            return Err(CztError::BufferLength { expected, actual });
+        }
+        check_finite("input", &buffer[..self.get_input_len()])?;
+        self.process(buffer);
+        check_finite("output", &buffer[..self.get_output_len()])
+    }
+
+    /// [`process_with_scratch`](Self::process_with_scratch), returning an error instead of
+    /// panicking when a length does not match the plan.
+    fn try_process_with_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), CztError> {
+        let (expected, actual) = (self.get_buffer_len(), buffer.len());
+        if actual != expected {
+            return Err(CztError::BufferLength { expected, actual });
+        }
+        let (expected, actual) = (self.get_inplace_scratch_len(), scratch.len());
+        if actual != expected {
+            return Err(CztError::ScratchLength { expected, actual });
+        }
+        self.process_with_scratch(buffer, scratch);
+        Ok(())
+    }
+
+    /// Processes `count` signals laid out `stride` elements apart, sharing one scratch buffer.
+    fn process_batch(&self, buffers: &mut [Complex<T>], stride: usize, count: usize) {
+        let mut scratch = vec![Complex::zero(); self.get_inplace_scratch_len()];
+        self.process_batch_with_scratch(buffers, stride, count, &mut scratch);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        let len = self.get_buffer_len();
+        assert!(stride >= len);
+        if count == 0 {
+            return;
+        }
+        assert!(buffers.len() >= (count - 1) * stride + len);
+
+        for buffer in buffers.chunks_mut(stride).take(count) {
+            self.process_with_scratch(&mut buffer[..len], scratch);
+        }
+    }
+
+    /// Processes independent signals, each exactly `get_buffer_len()` long, on the rayon thread
+    /// pool. One scratch arena is allocated up front and split between the worker shards.
+    #[cfg(feature = "rayon")]
+    fn process_batch_parallel(&self, signals: &mut [&mut [Complex<T>]]) {
+        use rayon::prelude::*;
+
+        if signals.is_empty() {
+            return;
+        }
+        let shards = rayon::current_num_threads().min(signals.len());
+        let scratch_len = self.get_inplace_scratch_len();
+        let mut arena = vec![Complex::zero(); shards * scratch_len.max(1)];
+
+        signals
+            .par_chunks_mut(signals.len().div_ceil(shards))
+            .zip(arena.par_chunks_mut(scratch_len.max(1)))
+            .for_each(|(signals, scratch)| {
+                for signal in signals {
+                    self.process_with_scratch(signal, &mut scratch[..scratch_len]);
+                }
+            });
+    }
+
+    /// Transforms `input` (`n` samples) into `output` (`m` bins), leaving `input` untouched.
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.get_input_len());
+        assert_eq!(output.len(), self.get_output_len());
+        assert_eq!(scratch.len(), self.get_outofplace_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.get_buffer_len());
+        buffer[..input.len()].copy_from_slice(input);
+        buffer[input.len()..].fill(Complex::zero());
+        self.process_with_scratch(buffer, scratch);
+        output.copy_from_slice(&buffer[..output.len()]);
+    }
+
+    /// Transforms every channel of channel-interleaved data, `get_buffer_len()` frames of
+    /// `channels` samples: sample `j` of channel `c` is read from `j * channels + c` and bin `k`
+    /// written to `k * channels + c`.
+    fn process_multichannel(&self, buffer: &mut [Complex<T>], channels: usize) {
+        let mut scratch = vec![Complex::zero(); self.get_strided_scratch_len()];
+        self.process_multichannel_with_scratch(buffer, channels, &mut scratch);
+    }
+
+    /// [`process_multichannel`](Self::process_multichannel) with a scratch of
+    /// `get_strided_scratch_len()`.
+    fn process_multichannel_with_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        channels: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        assert!(channels > 0);
+        assert_eq!(buffer.len(), channels * self.get_buffer_len());
+        for c in 0..channels {
+            self.process_strided_with_scratch(&mut buffer[c..], channels, scratch);
+        }
+    }
+
+    /// Transforms the signal at every `stride`th element of `buffer`, e.g. a column of a
+    /// row-major matrix: sample `j` is read from `j * stride` and bin `k` written to
+    /// `k * stride`, leaving the elements in between untouched.
+    fn process_strided(&self, buffer: &mut [Complex<T>], stride: usize) {
+        let mut scratch = vec![Complex::zero(); self.get_strided_scratch_len()];
+        self.process_strided_with_scratch(buffer, stride, &mut scratch);
+    }
+
+    /// [`process_strided`](Self::process_strided) with a scratch of
+    /// `get_strided_scratch_len()`, into which the signal is gathered.
+    fn process_strided_with_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        stride: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        assert!(stride > 0);
+        assert!(buffer.len() > (self.get_buffer_len() - 1) * stride);
+        assert_eq!(scratch.len(), self.get_strided_scratch_len());
+
+        let (n, m) = (self.get_input_len(), self.get_output_len());
+        let (signal, scratch) = scratch.split_at_mut(self.get_buffer_len());
+        for (x, &sample) in signal[..n].iter_mut().zip(buffer.iter().step_by(stride)) {
+            *x = sample;
+        }
+        signal[n..].fill(Complex::zero());
+        self.process_with_scratch(signal, scratch);
+        for (bin, &x) in buffer.iter_mut().step_by(stride).zip(&signal[..m]) {
+            *bin = x;
+        }
+    }
+
+    /// Transforms planar data, the real and imaginary parts in separate buffers of
+    /// `get_buffer_len()`.
+    fn process_split(&self, re: &mut [T], im: &mut [T]) {
+        let mut scratch = vec![Complex::zero(); self.get_strided_scratch_len()];
+        self.process_split_with_scratch(re, im, &mut scratch);
+    }
+
+    /// [`process_split`](Self::process_split) with a scratch of `get_strided_scratch_len()`,
+    /// into which the parts are packed.
+    fn process_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>]) {
+        assert_eq!(re.len(), self.get_buffer_len());
+        assert_eq!(im.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_strided_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.get_buffer_len());
+        for ((x, &re), &im) in buffer.iter_mut().zip(re.iter()).zip(im.iter()) {
+            *x = Complex::new(re, im);
+        }
+        self.process_with_scratch(buffer, scratch);
+        for ((x, re), im) in buffer.iter().zip(re.iter_mut()).zip(im.iter_mut()) {
+            (*re, *im) = (x.re, x.im);
+        }
+    }
+
+    /// The `m x n` matrix of the plan, row-major: entry `(k, j)` is bin `k` of a unit impulse at
+    /// sample `j`. The plan runs on every impulse, so windows and scaling folded into it are
+    /// included, and an [`InverseCzt`](inverse_czt::InverseCzt) gives the inverse of the matrix
+    /// of its [`params`](Self::params).
+    fn to_matrix(&self) -> Vec<Complex<T>> {
+        let (n, m, len) = (
+            self.get_input_len(),
+            self.get_output_len(),
+            self.get_buffer_len(),
+        );
+        let mut impulses = vec![Complex::zero(); n * len];
+        for (j, impulse) in impulses.chunks_exact_mut(len).enumerate() {
+            impulse[j] = Complex::new(T::one(), T::zero());
+        }
+        self.process_batch(&mut impulses, len, n);
+
+        let mut matrix = Vec::with_capacity(m * n);
+        for k in 0..m {
+            matrix.extend(impulses.chunks_exact(len).map(|column| column[k]));
+        }
+        matrix
+    }
+
+    /// Runs a fixed pseudo-random signal through the plan and a [`NaiveCzt`](naive_czt::NaiveCzt)
+    /// on the same [`params`](Self::params), and reports whether every bin agrees within
+    /// `tolerance` relative to the largest reference bin. Costs one `O(n m)` evaluation. Plans
+    /// that fold a window, output scaling or a delay into their bins apply it to the reference
+    /// as well, and an [`InverseCzt`](inverse_czt::InverseCzt) checks its round trip instead.
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        let params = self.params();
+        let reference = naive_czt::NaiveCzt::new(params.n, params.m, params.a, params.w);
+        let mut expected = verification_signal(params.n, self.get_buffer_len());
+        let mut actual = expected.clone();
+        reference.process(&mut expected);
+        self.process(&mut actual);
+        agrees_within(&expected[..params.m], &actual[..params.m], tolerance)
+    }
+
+    #[deprecated(note = "use `get_inplace_scratch_len` or `get_outofplace_scratch_len`")]
+    fn get_scratch_len(&self) -> usize {
+        self.get_inplace_scratch_len()
+    }
+
+    /// Scratch needed by [`process_with_scratch`](Self::process_with_scratch).
+    fn get_inplace_scratch_len(&self) -> usize;
+
+    /// Scratch needed by
+    /// [`process_outofplace_with_scratch`](Self::process_outofplace_with_scratch).
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.get_buffer_len() + self.get_inplace_scratch_len()
+    }
+
+    /// Scratch needed by [`process_strided_with_scratch`](Self::process_strided_with_scratch)
+    /// and [`process_split_with_scratch`](Self::process_split_with_scratch): a buffer to gather
+    /// the signal into and the in-place scratch to transform it with. Plans that override the
+    /// out-of-place path may need less than this there.
+    fn get_strided_scratch_len(&self) -> usize {
+        self.get_buffer_len() + self.get_inplace_scratch_len()
+    }
+
+    /// Number of input samples `n` read from the front of the buffer.
+    fn get_input_len(&self) -> usize;
+
+    /// Number of output bins `m` written to the front of the buffer.
+    fn get_output_len(&self) -> usize;
+
+    /// Contour evaluated by the plan. Windows and output scaling folded into a plan are not
+    /// reported. An [`InverseCzt`](inverse_czt::InverseCzt) reports the forward contour it
+    /// inverts, which its own [`verify`](Self::verify) accounts for.
+    fn params(&self) -> CztParams<T>;
+
+    /// Required buffer length, `max(n, m)`.
+    fn get_buffer_len(&self) -> usize {
+        self.get_input_len().max(self.get_output_len())
+    }
+
+    /// Heap footprint in bytes of the plan's coefficient tables and FFTs plus one scratch buffer.
+    ///
+    /// FFT memory is estimated, see [`fft_memory_usage`](fft_provider::fft_memory_usage). FFTs
+    /// shared with other plans through a planner's cache are counted in full by each of them.
+    fn get_memory_usage(&self) -> usize {
+        self.get_inplace_scratch_len() * core::mem::size_of::<Complex<T>>()
+    }
+
+    /// Rough work of one transform. Plans that do not know better report
+    /// [`estimate_cost`](cost::estimate_cost) for their lengths.
+    fn cost(&self) -> cost::CostEstimate {
+        cost::estimate_cost::<T>(self.get_input_len(), self.get_output_len())
+    }
+}
+
+pub trait RealCzt<T: FftNum>: Sync + Send {
+    fn process(&self, input: &[T], output: &mut [Complex<T>]) {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(input, output, &mut scratch);
+    }
+
+    fn process_with_scratch(
+        &self,
+        input: &[T],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    );
+
     fn get_scratch_len(&self) -> usize;
+
+    fn get_input_len(&self) -> usize;
+
+    fn get_output_len(&self) -> usize;
+
+    /// Contour of the complex transform the real input is fed through, in the same sense as
+    /// [`Czt::params`].
+    fn params(&self) -> CztParams<T>;
+
+    /// Heap footprint in bytes, see [`Czt::get_memory_usage`].
+    fn get_memory_usage(&self) -> usize {
+        self.get_scratch_len() * core::mem::size_of::<Complex<T>>()
+    }
 }
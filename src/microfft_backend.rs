@@ -0,0 +1,128 @@
+use alloc::sync::Arc;
+
+use crate::fft::{num_complex::Complex, Direction, Fft, FftDirection, Length};
+use microfft::complex::*;
+
+use crate::FftProvider;
+
+/// Expands to a `match` handing `$chunk` to the microfft kernel of the matching length.
+macro_rules! cfft {
+    ($chunk:expr, $($len:literal => $kernel:ident),*) => {
+        match $chunk.len() {
+            1 => {}
+            $($len => {
+                let _ = $kernel($chunk.try_into().unwrap());
+            })*
+            len => panic!("microfft has no kernel for length {len}"),
+        }
+    };
+}
+
+/// A `microfft` kernel behind the [`Fft`] interface, with or without `std`.
+///
+/// The kernels are static, table driven and never allocate, but only exist for `f32` and power of
+/// two lengths up to 4096. Inverse transforms conjugate around the forward kernel so that they stay
+/// unnormalized like rustfft's.
+pub struct MicroFft {
+    len: usize,
+    direction: FftDirection,
+}
+
+impl MicroFft {
+    pub fn new(len: usize, direction: FftDirection) -> Self {
+        assert!(
+            len.is_power_of_two() && len <= 4096,
+            "microfft only supports power of two lengths up to 4096, got {len}"
+        );
+
+        Self { len, direction }
+    }
+
+    fn transform(chunk: &mut [Complex<f32>]) {
+        cfft!(chunk,
+            2 => cfft_2, 4 => cfft_4, 8 => cfft_8, 16 => cfft_16, 32 => cfft_32, 64 => cfft_64,
+            128 => cfft_128, 256 => cfft_256, 512 => cfft_512, 1024 => cfft_1024,
+            2048 => cfft_2048, 4096 => cfft_4096
+        )
+    }
+}
+
+impl Length for MicroFft {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Direction for MicroFft {
+    fn fft_direction(&self) -> FftDirection {
+        self.direction
+    }
+}
+
+impl Fft<f32> for MicroFft {
+    fn process_with_scratch(&self, buffer: &mut [Complex<f32>], _scratch: &mut [Complex<f32>]) {
+        assert_eq!(buffer.len() % self.len, 0);
+
+        for chunk in buffer.chunks_exact_mut(self.len) {
+            match self.direction {
+                FftDirection::Forward => Self::transform(chunk),
+                FftDirection::Inverse => {
+                    chunk.iter_mut().for_each(|x| *x = x.conj());
+                    Self::transform(chunk);
+                    chunk.iter_mut().for_each(|x| *x = x.conj());
+                }
+            }
+        }
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &mut [Complex<f32>],
+        output: &mut [Complex<f32>],
+        scratch: &mut [Complex<f32>],
+    ) {
+        self.process_immutable_with_scratch(input, output, scratch);
+    }
+
+    fn process_immutable_with_scratch(
+        &self,
+        input: &[Complex<f32>],
+        output: &mut [Complex<f32>],
+        scratch: &mut [Complex<f32>],
+    ) {
+        assert_eq!(input.len(), output.len());
+        output.copy_from_slice(input);
+        self.process_with_scratch(output, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_immutable_scratch_len(&self) -> usize {
+        0
+    }
+}
+
+/// Plans the internal convolution FFTs with microfft, for `no_std` targets, which have no rustfft
+/// and so no default planner.
+///
+/// Construction is cheap and infallible, so `CztPlanner::with_fft_provider(MicroFftProvider)` can
+/// be set up directly in an embedded `main`. The planner must use [`ConvolutionLength::PowerOfTwo`](crate::ConvolutionLength),
+/// and plans whose convolution length exceeds 4096 panic.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MicroFftProvider;
+
+impl FftProvider<f32> for MicroFftProvider {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<f32>> {
+        Arc::new(MicroFft::new(len, FftDirection::Forward))
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<f32>> {
+        Arc::new(MicroFft::new(len, FftDirection::Inverse))
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{
+    bluesteins::{from_f64, to_f64, BluesteinsAlgorithm},
+    cost::{self, CostEstimate},
+    ConvolutionLength, Czt, CztParams, FftProvider, Window,
+};
+
+/// Zoom CZT by heterodyning, low-pass filtering and decimating before a small CZT, for long
+/// inputs and narrow bands on the unit circle.
+///
+/// The band's center is mixed down to DC, and only every `D`th output of a Kaiser-windowed sinc
+/// low-pass is computed. A CZT of those `~(n + L) / D` samples gives the bins, each divided by the
+/// exact filter response at its frequency, so the passband ripple cancels. What remains is
+/// aliasing, which the stopband keeps below `1000 * T::epsilon() * sum_j |x_j|` in every bin, on
+/// top of the rounding error a Bluestein plan has as well.
+///
+/// The filter itself is evaluated in `T`, and in single precision its rounding leaves the bins
+/// about three digits behind Bluestein's whatever the stopband, so the mixer is only designed for
+/// `T` at least as wide as `f64`.
+///
+/// The contour is taken to lie exactly on the unit circle, at the angles of `a` and `w`.
+pub struct MixerCzt<T: FftNum> {
+    n: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+    decimation: usize,
+    /// `e^(-i omega_c j)`, moving the band center `omega_c` to DC.
+    mixer: Vec<Complex<T>>,
+    taps: Vec<T>,
+    /// `D / H(nu_k)` at each bin's offset `nu_k` from the band center.
+    equalizer: Vec<Complex<T>>,
+    inner: BluesteinsAlgorithm<T>,
+}
+
+impl<T: FftNum + Float> MixerCzt<T> {
+    /// Decimation factor `D` and filter length `L` for the contour, or `None` when `T` is
+    /// narrower than `f64`, the contour leaves the unit circle or the band is too wide to
+    /// decimate by at least 2.
+    pub fn design(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Option<(usize, usize)> {
+        if T::epsilon().to_f64().unwrap() > f64::EPSILON {
+            return None;
+        }
+        let tolerance = 16.0 * T::epsilon().to_f64().unwrap();
+        if (to_f64(a).norm() - 1.0).abs() > tolerance || (to_f64(w).norm() - 1.0).abs() > tolerance
+        {
+            return None;
+        }
+
+        // Aliases of the band, |nu| <= b, land at least 2 pi / D - b from DC; D = pi / (4 b)
+        // leaves a transition band of 3b for the filter.
+        let half_band = (m - 1) as f64 * to_f64(w).arg().abs() / 2.0;
+        let decimation = if half_band > 0.0 {
+            ((core::f64::consts::PI / (4.0 * half_band)) as usize).min(n)
+        } else {
+            n
+        };
+        if decimation < 2 {
+            return None;
+        }
+        let transition = core::f64::consts::TAU / decimation as f64 - 2.0 * half_band;
+        let taps =
+            ((attenuation::<T>(decimation) - 8.0) / (2.285 * transition)).ceil() as usize + 1;
+        Some((decimation, taps))
+    }
+
+    /// Panics unless [`design`](Self::design) accepts the contour.
+    pub fn new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        let (decimation, len) =
+            Self::design(n, m, a, w).expect("contour must be a narrow arc of the unit circle");
+
+        // Bin k sits at omega_0 + k step.
+        let (omega_0, step) = (to_f64(a).arg(), -to_f64(w).arg());
+        let center = omega_0 + (m - 1) as f64 / 2.0 * step;
+        let mixer = (0..n)
+            .map(|j| from_f64(Complex::from_polar(1.0, -center * j as f64)))
+            .collect();
+
+        // Kaiser-windowed sinc with its cutoff halfway between passband and stopband.
+        let cutoff = core::f64::consts::PI / decimation as f64;
+        let attenuation = attenuation::<T>(decimation);
+        let window = Window::Kaiser(0.1102 * (attenuation - 8.7)).coefficients(len);
+        let taps: Vec<f64> = window
+            .iter()
+            .enumerate()
+            .map(|(l, &weight)| {
+                let x = cutoff * (l as f64 - (len - 1) as f64 / 2.0);
+                let sinc = if x == 0.0 { 1.0 } else { x.sin() / x };
+                cutoff / core::f64::consts::PI * sinc * weight
+            })
+            .collect();
+
+        let equalizer = (0..m)
+            .map(|k| {
+                let nu = (k as f64 - (m - 1) as f64 / 2.0) * step;
+                let response: Complex<f64> = taps
+                    .iter()
+                    .enumerate()
+                    .map(|(l, &h)| Complex::from_polar(h, -nu * l as f64))
+                    .sum();
+                from_f64(response.inv() * decimation as f64)
+            })
+            .collect();
+
+        let decimated = (n + len - 2) / decimation + 1;
+        let d = decimation as f64;
+        let inner = BluesteinsAlgorithm::new(
+            decimated,
+            m,
+            from_f64(Complex::from_polar(1.0, -((m - 1) as f64) / 2.0 * step * d)),
+            from_f64(Complex::from_polar(1.0, -step * d)),
+            0,
+            fft_provider,
+        );
+
+        Self {
+            n,
+            a,
+            w,
+            decimation,
+            mixer,
+            taps: taps.iter().map(|&h| T::from_f64(h).unwrap()).collect(),
+            equalizer,
+            inner,
+        }
+    }
+}
+
+impl<T: FftNum> MixerCzt<T> {
+    pub fn decimation(&self) -> usize {
+        self.decimation
+    }
+
+    pub fn filter_len(&self) -> usize {
+        self.taps.len()
+    }
+}
+
+/// Stopband attenuation in dB keeping the `D - 1` aliases within `1000 epsilon` together.
+fn attenuation<T: Float>(decimation: usize) -> f64 {
+    -20.0 * (1000.0 * T::epsilon().to_f64().unwrap() / decimation as f64).log10()
+}
+
+impl<T: FftNum> Czt<T> for MixerCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+        let (n, len) = (self.n, self.taps.len());
+
+        for (x, &mix) in buffer.iter_mut().zip(&self.mixer) {
+            *x = *x * mix;
+        }
+
+        // Output j = q D of the full convolution sums taps l with 0 <= j - l < n.
+        let (decimated, scratch) = scratch.split_at_mut(self.inner.get_buffer_len());
+        let count = (n + len - 2) / self.decimation + 1;
+        for (q, v) in decimated[..count].iter_mut().enumerate() {
+            let j = q * self.decimation;
+            let first = j.saturating_sub(n - 1);
+            let last = j.min(len - 1);
+            *v = (first..=last).fold(Complex::zero(), |acc, l| acc + buffer[j - l] * self.taps[l]);
+        }
+        decimated[count..].fill(Complex::zero());
+        self.inner.process_with_scratch(decimated, scratch);
+
+        for ((out, &v), &e) in buffer.iter_mut().zip(decimated.iter()).zip(&self.equalizer) {
+            *out = v * e;
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.inner.get_buffer_len() + self.inner.get_inplace_scratch_len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.equalizer.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.get_output_len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        let complex = core::mem::size_of::<Complex<T>>();
+        (self.mixer.len() + self.equalizer.len() + self.inner.get_buffer_len()) * complex
+            + self.taps.len() * core::mem::size_of::<T>()
+            + self.inner.get_memory_usage()
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::mixer::<T>(
+            self.n,
+            self.get_output_len(),
+            (self.decimation, self.taps.len()),
+            ConvolutionLength::default(),
+        )
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::HashMap,
+    os::raw::{c_int, c_long, c_void},
+    sync::{Arc, Mutex},
+};
+
+use crate::fft::{num_complex::Complex, Direction, Fft, FftDirection, FftNum, Length};
+
+use crate::FftProvider;
+
+type MklLong = c_long;
+type DftiHandle = *mut c_void;
+
+const DFTI_NUMBER_OF_TRANSFORMS: c_int = 7;
+const DFTI_INPUT_DISTANCE: c_int = 14;
+const DFTI_OUTPUT_DISTANCE: c_int = 15;
+const DFTI_COMPLEX: c_int = 32;
+const DFTI_SINGLE: c_int = 35;
+const DFTI_DOUBLE: c_int = 36;
+
+#[link(name = "mkl_rt")]
+extern "C" {
+    fn DftiCreateDescriptor(
+        handle: *mut DftiHandle,
+        precision: c_int,
+        domain: c_int,
+        dimension: MklLong,
+        ...
+    ) -> MklLong;
+    fn DftiSetValue(handle: DftiHandle, param: c_int, ...) -> MklLong;
+    fn DftiCommitDescriptor(handle: DftiHandle) -> MklLong;
+    fn DftiComputeForward(handle: DftiHandle, buffer: *mut c_void, ...) -> MklLong;
+    fn DftiComputeBackward(handle: DftiHandle, buffer: *mut c_void, ...) -> MklLong;
+    fn DftiFreeDescriptor(handle: *mut DftiHandle) -> MklLong;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+
+    impl Sealed for f64 {}
+}
+
+/// Floating point types MKL has complex DFTI descriptors for.
+pub trait MklFloat: FftNum + sealed::Sealed {
+    #[doc(hidden)]
+    const PRECISION: c_int;
+}
+
+impl MklFloat for f32 {
+    const PRECISION: c_int = DFTI_SINGLE;
+}
+
+impl MklFloat for f64 {
+    const PRECISION: c_int = DFTI_DOUBLE;
+}
+
+fn check(status: MklLong, call: &str) {
+    assert_eq!(status, 0, "{call} failed with MKL status {status}");
+}
+
+/// A committed in-place descriptor for `count` contiguous transforms of one length.
+struct Descriptor(DftiHandle);
+
+// Committed descriptors are read-only during computation and may be shared between threads.
+unsafe impl Send for Descriptor {}
+unsafe impl Sync for Descriptor {}
+
+impl Descriptor {
+    fn new<T: MklFloat>(len: usize, count: usize) -> Self {
+        let mut handle = std::ptr::null_mut();
+        unsafe {
+            check(
+                DftiCreateDescriptor(&mut handle, T::PRECISION, DFTI_COMPLEX, 1, len as MklLong),
+                "DftiCreateDescriptor",
+            );
+            let descriptor = Self(handle);
+            if count > 1 {
+                check(
+                    DftiSetValue(handle, DFTI_NUMBER_OF_TRANSFORMS, count as MklLong),
+                    "DftiSetValue",
+                );
+                check(
+                    DftiSetValue(handle, DFTI_INPUT_DISTANCE, len as MklLong),
+                    "DftiSetValue",
+                );
+                check(
+                    DftiSetValue(handle, DFTI_OUTPUT_DISTANCE, len as MklLong),
+                    "DftiSetValue",
+                );
+            }
+            check(DftiCommitDescriptor(handle), "DftiCommitDescriptor");
+            descriptor
+        }
+    }
+}
+
+impl Drop for Descriptor {
+    fn drop(&mut self) {
+        unsafe { DftiFreeDescriptor(&mut self.0) };
+    }
+}
+
+/// An MKL DFTI transform behind the `rustfft::Fft` interface.
+///
+/// Buffers holding several transforms are computed with one batched descriptor, committed the
+/// first time a given batch size is seen.
+pub struct MklFft<T: MklFloat> {
+    len: usize,
+    direction: FftDirection,
+    descriptors: Mutex<HashMap<usize, Arc<Descriptor>>>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: MklFloat> MklFft<T> {
+    pub fn new(len: usize, direction: FftDirection) -> Self {
+        let descriptors = HashMap::from([(1, Arc::new(Descriptor::new::<T>(len, 1)))]);
+        Self {
+            len,
+            direction,
+            descriptors: Mutex::new(descriptors),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn descriptor(&self, count: usize) -> Arc<Descriptor> {
+        self.descriptors
+            .lock()
+            .unwrap()
+            .entry(count)
+            .or_insert_with(|| Arc::new(Descriptor::new::<T>(self.len, count)))
+            .clone()
+    }
+}
+
+impl<T: MklFloat> Length for MklFft<T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: MklFloat> Direction for MklFft<T> {
+    fn fft_direction(&self) -> FftDirection {
+        self.direction
+    }
+}
+
+impl<T: MklFloat> Fft<T> for MklFft<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], _scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len() % self.len, 0);
+        let count = buffer.len() / self.len;
+        if count == 0 {
+            return;
+        }
+
+        let descriptor = self.descriptor(count);
+        let data = buffer.as_mut_ptr() as *mut c_void;
+        let status = unsafe {
+            match self.direction {
+                FftDirection::Forward => DftiComputeForward(descriptor.0, data),
+                FftDirection::Inverse => DftiComputeBackward(descriptor.0, data),
+            }
+        };
+        check(status, "DftiCompute");
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.process_immutable_with_scratch(input, output, scratch);
+    }
+
+    fn process_immutable_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), output.len());
+        output.copy_from_slice(input);
+        self.process_with_scratch(output, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_immutable_scratch_len(&self) -> usize {
+        0
+    }
+}
+
+/// Plans the internal convolution FFTs as MKL DFTI descriptors.
+#[derive(Default)]
+pub struct MklProvider;
+
+impl MklProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<T: MklFloat> FftProvider<T> for MklProvider {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        Arc::new(MklFft::<T>::new(len, FftDirection::Forward))
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<T>> {
+        Arc::new(MklFft::<T>::new(len, FftDirection::Inverse))
+    }
+}
@@ -0,0 +1,132 @@
+//! uniffi interface for Swift and Kotlin apps: zoom CZT plans over single-precision audio.
+//!
+//! The interface is declared with uniffi's proc macros, so there is no UDL file. Build the
+//! library with e.g. `cargo rustc --release --features uniffi --crate-type cdylib` (`staticlib`
+//! for iOS), then generate the bindings from it with
+//! `cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate --library <library>
+//! --language swift --out-dir <dir>`.
+//!
+//! A [`ZoomPlan`] is a handle the app keeps across calls. Samples go in and bins come out as
+//! byte arrays of little-endian `f32`s, `Data` in Swift and `ByteArray` in Kotlin, which cross
+//! the FFI as one copy rather than element by element.
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use std::sync::Mutex;
+
+use crate::fft::{num_complex::Complex, num_traits::Zero};
+
+use crate::{zoom::ZoomFft, Czt, CztPlanner};
+
+/// Why a plan could not be built or a buffer processed, thrown as an exception.
+#[derive(Debug, PartialEq, uniffi::Error)]
+pub enum ZoomError {
+    /// The lengths, sample rate or band cannot be planned.
+    InvalidPlan { reason: String },
+    /// The sample bytes do not hold exactly `expected` little-endian `f32`s.
+    SampleBytes { expected: u32, actual: u32 },
+}
+
+impl core::fmt::Display for ZoomError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ZoomError::InvalidPlan { reason } => write!(f, "cannot plan: {reason}"),
+            ZoomError::SampleBytes { expected, actual } => write!(
+                f,
+                "got {actual} sample bytes, the plan needs {expected} samples of 4 bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ZoomError {}
+
+/// `n` real samples at `sample_rate` to `m` bins from `f_start` to `f_end` Hz, both included.
+///
+/// The buffer and scratch are allocated once with the plan; calls on one plan from several
+/// threads take turns.
+#[derive(uniffi::Object)]
+pub struct ZoomPlan {
+    zoom: Arc<ZoomFft<f32>>,
+    buffers: Mutex<Buffers>,
+}
+
+struct Buffers {
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+}
+
+#[uniffi::export]
+impl ZoomPlan {
+    #[uniffi::constructor]
+    pub fn new(
+        n: u32,
+        m: u32,
+        sample_rate: f32,
+        f_start: f32,
+        f_end: f32,
+    ) -> Result<Arc<Self>, ZoomError> {
+        let invalid = |reason: &str| {
+            Err(ZoomError::InvalidPlan {
+                reason: reason.into(),
+            })
+        };
+        if n == 0 || m == 0 {
+            return invalid("n and m must be positive");
+        }
+        if !(sample_rate.is_finite() && sample_rate > 0.0) {
+            return invalid("sample_rate must be finite and positive");
+        }
+        if !(f_start.is_finite() && f_end.is_finite()) {
+            return invalid("f_start and f_end must be finite");
+        }
+
+        let zoom =
+            CztPlanner::new().plan_zoom_fft_hz(n as usize, m as usize, sample_rate, f_start, f_end);
+        let buffers = Buffers {
+            buffer: vec![Complex::zero(); zoom.get_buffer_len()],
+            scratch: vec![Complex::zero(); zoom.get_inplace_scratch_len()],
+        };
+        Ok(Arc::new(Self {
+            zoom,
+            buffers: Mutex::new(buffers),
+        }))
+    }
+
+    pub fn input_len(&self) -> u32 {
+        self.zoom.get_input_len() as u32
+    }
+
+    pub fn output_len(&self) -> u32 {
+        self.zoom.get_output_len() as u32
+    }
+
+    /// Bin frequencies in Hz.
+    pub fn frequencies(&self) -> Vec<f32> {
+        self.zoom.freqs()
+    }
+
+    /// Transforms `input_len` samples into `output_len` bins as interleaved `re, im` pairs, all
+    /// little-endian `f32`s.
+    pub fn process(&self, samples: Vec<u8>) -> Result<Vec<u8>, ZoomError> {
+        let (n, m) = (self.zoom.get_input_len(), self.zoom.get_output_len());
+        if samples.len() != 4 * n {
+            return Err(ZoomError::SampleBytes {
+                expected: n as u32,
+                actual: samples.len() as u32,
+            });
+        }
+
+        let mut buffers = self.buffers.lock().unwrap();
+        let Buffers { buffer, scratch } = &mut *buffers;
+        for (x, bytes) in buffer.iter_mut().zip(samples.chunks_exact(4)) {
+            *x = Complex::new(f32::from_le_bytes(bytes.try_into().unwrap()), 0.0);
+        }
+        buffer[n..].fill(Complex::zero());
+        self.zoom.process_with_scratch(buffer, scratch);
+        Ok(buffer[..m]
+            .iter()
+            .flat_map(|bin| [bin.re, bin.im])
+            .flat_map(f32::to_le_bytes)
+            .collect())
+    }
+}
@@ -1,34 +1,143 @@
-use rustfft::{num_complex::Complex, FftNum};
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
 
-use crate::Czt;
+use crate::{
+    bluesteins::polar_pow,
+    cost::{self, CostEstimate},
+    Czt, CztParams,
+};
 
+/// Direct `O(n m)` evaluation of every bin.
+///
+/// Powers come from a recurrence, one complex multiply per term: within a block of about
+/// `sqrt(n)` inputs `z_k^-j` advances by `z_k^-1`. Each block starts from
+/// `z_k^-j0 = a^-j0 w^(k j0)` and each bin from `z_k^-1 = a^-1 w^k`, both evaluated directly with
+/// [`polar_pow`], so the rounding error of every power stays within about `sqrt(n)` ulps where a
+/// single running product over all `n` inputs reaches `n`.
+///
+/// With the `rayon` feature, blocks of bins of large transforms are evaluated in parallel.
 pub struct NaiveCzt<T: FftNum> {
     a: Complex<T>,
     w: Complex<T>,
-    czt_size: usize,
+    n: usize,
+    m: usize,
+    compensated: bool,
 }
 
 impl<T: FftNum> NaiveCzt<T> {
-    pub fn new(czt_size: usize, a: Complex<T>, w: Complex<T>) -> Self {
-        Self { czt_size, a, w }
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        Self {
+            a,
+            w,
+            n,
+            m,
+            compensated: false,
+        }
+    }
+
+    /// Accumulates each bin with Kahan summation, so its rounding error no longer grows with
+    /// `n`. Worth it when the plan serves as a reference for long signals; costs three more
+    /// additions per term.
+    pub fn with_compensated_summation(mut self) -> Self {
+        self.compensated = true;
+        self
+    }
+}
+
+impl<T: FftNum + Float> NaiveCzt<T> {
+    /// Evaluates consecutive bins, the first being bin `k0`.
+    fn process_bins(&self, input: &[Complex<T>], bins: &mut [Complex<T>], k0: usize) {
+        let input_block = self.n.isqrt().max(1);
+        for (out, k) in bins.iter_mut().zip(k0 as i128..) {
+            let z_inv = polar_pow(self.a, -2) * polar_pow(self.w, 2 * k);
+            let blocks = input
+                .chunks(input_block)
+                .zip((0..).step_by(input_block))
+                .map(|(block, j0): (_, i128)| {
+                    let mut z_power = polar_pow(self.a, -2 * j0) * polar_pow(self.w, 2 * k * j0);
+                    let terms = block.iter().map(|&x| {
+                        let term = x * z_power;
+                        z_power = z_power * z_inv;
+                        term
+                    });
+                    sum(terms, self.compensated)
+                });
+            *out = sum(blocks, self.compensated);
+        }
+    }
+}
+
+/// Work `n m` from which bins are evaluated on the rayon pool.
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_WORK: usize = 1 << 16;
+
+/// Adds up `terms`, with Kahan summation if `compensated`.
+fn sum<T: FftNum>(terms: impl Iterator<Item = Complex<T>>, compensated: bool) -> Complex<T> {
+    if compensated {
+        let mut compensation = Complex::zero();
+        terms.fold(Complex::zero(), |sum, term| {
+            let y = term - compensation;
+            let t = sum + y;
+            compensation = (t - sum) - y;
+            t
+        })
+    } else {
+        terms.fold(Complex::zero(), |sum, term| sum + term)
     }
 }
 
-impl<T: FftNum> Czt<T> for NaiveCzt<T> {
+impl<T: FftNum + Float> Czt<T> for NaiveCzt<T> {
     fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
-        for k in 0..self.czt_size {
-            let z = self.a * self.w.powi(-1 * (k as i32));
-            for n in 0..self.czt_size {
-                scratch[k] = scratch[k] + buffer[n] * z.powi(-1 * (n as i32));
-            }
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        // Blocks of about `sqrt(m)` bins, each with the index of its first bin.
+        let bin_block = self.m.isqrt().max(1);
+        let input = &buffer[..self.n];
+        let blocks = scratch.chunks_mut(bin_block).zip((0..).step_by(bin_block));
+
+        #[cfg(feature = "rayon")]
+        if self.n * self.m >= PARALLEL_MIN_WORK {
+            use rayon::prelude::*;
+
+            let blocks: Vec<_> = blocks.collect();
+            blocks
+                .into_par_iter()
+                .for_each(|(bins, k0)| self.process_bins(input, bins, k0));
+        } else {
+            blocks.for_each(|(bins, k0)| self.process_bins(input, bins, k0));
         }
+        #[cfg(not(feature = "rayon"))]
+        blocks.for_each(|(bins, k0)| self.process_bins(input, bins, k0));
+
+        buffer[..self.m].copy_from_slice(scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.m
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
 
-        for k in 0..self.czt_size {
-            buffer[k] = scratch[k];
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.m,
+            a: self.a,
+            w: self.w,
         }
     }
 
-    fn get_scratch_len(&self) -> usize {
-        self.czt_size
+    fn cost(&self) -> CostEstimate {
+        cost::naive::<T>(self.n, self.m)
     }
 }
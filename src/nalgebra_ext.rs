@@ -0,0 +1,44 @@
+use alloc::vec::Vec;
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+use nalgebra::{DMatrix, DVector};
+
+use crate::Czt;
+
+/// Runs a plan on an `n`-element `DVector`, returning the `m` bins as a new vector.
+pub trait CztVectorExt<T: FftNum> {
+    fn czt(&self, plan: &dyn Czt<T>) -> DVector<Complex<T>>;
+}
+
+/// Runs a plan on every column of an `n`-row `DMatrix`, returning an `m`-row matrix with one
+/// column of bins per input column.
+pub trait CztMatrixExt<T: FftNum> {
+    fn czt_columns(&self, plan: &dyn Czt<T>) -> DMatrix<Complex<T>>;
+}
+
+impl<T: FftNum> CztVectorExt<T> for DVector<Complex<T>> {
+    fn czt(&self, plan: &dyn Czt<T>) -> DVector<Complex<T>> {
+        assert_eq!(self.len(), plan.get_input_len());
+        DVector::from_vec(transform_columns(plan, self.as_slice()))
+    }
+}
+
+impl<T: FftNum> CztMatrixExt<T> for DMatrix<Complex<T>> {
+    fn czt_columns(&self, plan: &dyn Czt<T>) -> DMatrix<Complex<T>> {
+        assert_eq!(self.nrows(), plan.get_input_len());
+        let bins = transform_columns(plan, self.as_slice());
+        DMatrix::from_vec(plan.get_output_len(), self.ncols(), bins)
+    }
+}
+
+/// Transforms the back-to-back `n`-sample columns of column-major `data` out of place, sharing
+/// one scratch buffer.
+fn transform_columns<T: FftNum>(plan: &dyn Czt<T>, data: &[Complex<T>]) -> Vec<Complex<T>> {
+    let (n, m) = (plan.get_input_len(), plan.get_output_len());
+    let mut bins = vec![Complex::zero(); data.len() / n * m];
+    let mut scratch = vec![Complex::zero(); plan.get_outofplace_scratch_len()];
+    for (input, output) in data.chunks_exact(n).zip(bins.chunks_exact_mut(m)) {
+        plan.process_outofplace_with_scratch(input, output, &mut scratch);
+    }
+    bins
+}
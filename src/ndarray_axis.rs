@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+use ndarray::{Array2, ArrayView2, ArrayViewMut2, Axis, Zip};
+
+use crate::{zoom::ZoomFft, Czt};
+
+/// Transforms every lane of `array` along `axis` in place, which must be `get_buffer_len()`
+/// long; the bins are written to the first `m` elements of each lane. Contiguous lanes are
+/// processed where they are, others are gathered into the one scratch buffer all lanes share.
+pub fn czt_axis_inplace<T: FftNum>(
+    plan: &dyn Czt<T>,
+    mut array: ArrayViewMut2<Complex<T>>,
+    axis: Axis,
+) {
+    let len = plan.get_buffer_len();
+    assert_eq!(array.len_of(axis), len);
+    let mut scratch = vec![Complex::zero(); plan.get_outofplace_scratch_len()];
+    let (buffer, scratch) = scratch.split_at_mut(len);
+    for mut lane in array.lanes_mut(axis) {
+        match lane.as_slice_mut() {
+            Some(lane) => plan.process_with_scratch(lane, scratch),
+            None => {
+                buffer.iter_mut().zip(&lane).for_each(|(x, &y)| *x = y);
+                plan.process_with_scratch(buffer, scratch);
+                lane.iter_mut()
+                    .zip(buffer.iter())
+                    .for_each(|(y, &x)| *y = x);
+            }
+        }
+    }
+}
+
+/// Transforms every lane of `array` along `axis`, `n` samples each, into a new array with `m`
+/// bins along that axis.
+pub fn czt_axis<T: FftNum>(
+    plan: &dyn Czt<T>,
+    array: ArrayView2<Complex<T>>,
+    axis: Axis,
+) -> Array2<Complex<T>> {
+    let (n, m, len) = (
+        plan.get_input_len(),
+        plan.get_output_len(),
+        plan.get_buffer_len(),
+    );
+    assert_eq!(array.len_of(axis), n);
+    let mut dim = array.raw_dim();
+    dim[axis.index()] = m;
+    let mut output = Array2::zeros(dim);
+
+    let mut scratch = vec![Complex::zero(); plan.get_outofplace_scratch_len()];
+    let (buffer, scratch) = scratch.split_at_mut(len);
+    Zip::from(array.lanes(axis))
+        .and(output.lanes_mut(axis))
+        .for_each(|input, mut output| {
+            buffer[..n].iter_mut().zip(input).for_each(|(x, &y)| *x = y);
+            buffer[n..].fill(Complex::zero());
+            plan.process_with_scratch(buffer, scratch);
+            output
+                .iter_mut()
+                .zip(buffer.iter())
+                .for_each(|(y, &x)| *y = x);
+        });
+    output
+}
+
+/// [`czt_axis`] with a zoom plan, also returning the frequencies of the bins along `axis`.
+pub fn zoom_axis<T: FftNum>(
+    zoom: &ZoomFft<T>,
+    array: ArrayView2<Complex<T>>,
+    axis: Axis,
+) -> (Array2<Complex<T>>, Vec<T>) {
+    (czt_axis(zoom, array, axis), zoom.freqs())
+}
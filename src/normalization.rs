@@ -0,0 +1,26 @@
+use crate::fft::num_traits::{Float, FromPrimitive};
+
+/// Output scaling convention applied by a plan.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization<T> {
+    /// Unscaled sums, matching numpy/scipy forward transforms.
+    None,
+    /// Divide by the input length `n`.
+    OneOverN,
+    /// Divide by `sqrt(n)`, the unitary convention for DFT contours.
+    OneOverSqrtN,
+    Custom(T),
+}
+
+impl<T: Float + FromPrimitive> Normalization<T> {
+    /// Scale factor for a transform with `n` input samples.
+    pub fn factor(&self, n: usize) -> T {
+        let n = T::from_usize(n).unwrap();
+        match *self {
+            Normalization::None => T::one(),
+            Normalization::OneOverN => n.recip(),
+            Normalization::OneOverSqrtN => n.sqrt().recip(),
+            Normalization::Custom(factor) => factor,
+        }
+    }
+}
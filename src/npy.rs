@@ -0,0 +1,303 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::{
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use crate::fft::{num_complex::Complex, num_traits::Float, FftNum};
+
+/// A complex array as NumPy stores it, in C order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NpyArray<T> {
+    pub shape: Vec<usize>,
+    pub data: Vec<Complex<T>>,
+}
+
+impl<T> NpyArray<T> {
+    /// A one-dimensional array.
+    pub fn from_vec(data: Vec<Complex<T>>) -> Self {
+        Self {
+            shape: vec![data.len()],
+            data,
+        }
+    }
+}
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+fn invalid(message: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Reads a `.npy` array of complex (`c8`, `c16`) or real (`f4`, `f8`) floats in either byte
+/// order, converting to `T`. Fortran-ordered arrays of more than one dimension are rejected.
+pub fn read_npy<T: FftNum, R: Read>(mut reader: R) -> io::Result<NpyArray<T>> {
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid("not a .npy file"));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => return Err(invalid(format!("unsupported .npy version {version}"))),
+    };
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = core::str::from_utf8(&header).map_err(|_| invalid("header is not text"))?;
+
+    let descr = header_value(header, "descr")
+        .and_then(|value| value.strip_prefix('\'')?.split('\'').next())
+        .ok_or_else(|| invalid("header lacks descr"))?;
+    let fortran_order = header_value(header, "fortran_order")
+        .ok_or_else(|| invalid("header lacks fortran_order"))?
+        .starts_with("True");
+    let shape: Vec<usize> = header_value(header, "shape")
+        .and_then(|value| value.strip_prefix('(')?.split(')').next())
+        .ok_or_else(|| invalid("header lacks shape"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse().map_err(|_| invalid("malformed shape")))
+        .collect::<io::Result<_>>()?;
+    if fortran_order && shape.len() > 1 {
+        return Err(invalid("Fortran-ordered arrays are not supported"));
+    }
+
+    let (big_endian, kind) = match descr.as_bytes().first() {
+        Some(b'<' | b'=') => (false, &descr[1..]),
+        Some(b'>') => (true, &descr[1..]),
+        _ => return Err(invalid(format!("unsupported dtype {descr}"))),
+    };
+    let (width, complex) = match kind {
+        "c8" => (4, true),
+        "c16" => (8, true),
+        "f4" => (4, false),
+        "f8" => (8, false),
+        _ => return Err(invalid(format!("unsupported dtype {descr}"))),
+    };
+    let len: usize = shape.iter().product();
+    let components = if complex { 2 } else { 1 };
+    let mut bytes = vec![0; len * components * width];
+    reader.read_exact(&mut bytes)?;
+
+    let decode = |b: &[u8]| -> T {
+        let value = match (width, big_endian) {
+            (4, false) => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+            (4, true) => f32::from_be_bytes(b.try_into().unwrap()) as f64,
+            (_, false) => f64::from_le_bytes(b.try_into().unwrap()),
+            (_, true) => f64::from_be_bytes(b.try_into().unwrap()),
+        };
+        T::from_f64(value).unwrap()
+    };
+    let data = bytes
+        .chunks_exact(components * width)
+        .map(|sample| {
+            let im = if complex {
+                decode(&sample[width..])
+            } else {
+                T::zero()
+            };
+            Complex::new(decode(&sample[..width]), im)
+        })
+        .collect();
+    Ok(NpyArray { shape, data })
+}
+
+/// The text following `'key':` in a header dictionary.
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+    Some(header[start..].trim_start().strip_prefix(':')?.trim_start())
+}
+
+/// Writes `array` as a version 1.0 `.npy` file of little-endian `c8` if `T` is 4 bytes wide and
+/// `c16` otherwise.
+pub fn write_npy<T: FftNum + Float, W: Write>(
+    mut writer: W,
+    array: &NpyArray<T>,
+) -> io::Result<()> {
+    assert_eq!(array.shape.iter().product::<usize>(), array.data.len());
+    let single = core::mem::size_of::<T>() == 4;
+    let shape = match array.shape.as_slice() {
+        [len] => format!("({len},)"),
+        dims => format!(
+            "({})",
+            dims.iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let descr = if single { "<c8" } else { "<c16" };
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+    // The data starts 64-byte aligned and the header ends with a newline.
+    let padded = (MAGIC.len() + 4 + header.len() + 1).next_multiple_of(64);
+    header.extend(core::iter::repeat_n(
+        ' ',
+        padded - (MAGIC.len() + 4 + header.len() + 1),
+    ));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(padded + array.data.len() * if single { 8 } else { 16 });
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    let header_len = u16::try_from(header.len()).map_err(|_| invalid("shape too long"))?;
+    bytes.extend_from_slice(&header_len.to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for x in &array.data {
+        for component in [x.re, x.im] {
+            let value = component.to_f64().unwrap();
+            if single {
+                bytes.extend_from_slice(&(value as f32).to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+    writer.write_all(&bytes)
+}
+
+pub fn load_npy<T: FftNum>(path: impl AsRef<Path>) -> io::Result<NpyArray<T>> {
+    read_npy(io::BufReader::new(std::fs::File::open(path)?))
+}
+
+pub fn save_npy<T: FftNum + Float>(path: impl AsRef<Path>, array: &NpyArray<T>) -> io::Result<()> {
+    write_npy(std::fs::File::create(path)?, array)
+}
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+
+/// Reads the arrays of an `.npz` archive as written by `numpy.savez`, named without the `.npy`
+/// suffix. Compressed archives (`numpy.savez_compressed`) are rejected.
+pub fn read_npz<T: FftNum, R: Read>(mut reader: R) -> io::Result<Vec<(String, NpyArray<T>)>> {
+    let mut archive = Vec::new();
+    reader.read_to_end(&mut archive)?;
+    let truncated = || invalid("truncated .npz archive");
+    let u16_at = |i: usize| -> io::Result<usize> {
+        let bytes = archive.get(i..i + 2).ok_or_else(truncated)?;
+        Ok(u16::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+    let u32_at = |i: usize| -> io::Result<u32> {
+        let bytes = archive.get(i..i + 4).ok_or_else(truncated)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    };
+    let u64_at = |i: usize| -> io::Result<usize> {
+        let bytes = archive.get(i..i + 8).ok_or_else(truncated)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+
+    let mut arrays = Vec::new();
+    let mut offset = 0;
+    while u32_at(offset)? == LOCAL_HEADER {
+        let (flags, method) = (u16_at(offset + 6)?, u16_at(offset + 8)?);
+        // Entries are stored, so the uncompressed size is the size of the data.
+        let mut size = u32_at(offset + 22)? as usize;
+        let (name_len, extra_len) = (u16_at(offset + 26)?, u16_at(offset + 28)?);
+        let name = archive
+            .get(offset + 30..offset + 30 + name_len)
+            .ok_or_else(truncated)?;
+        let name = core::str::from_utf8(name).map_err(|_| invalid("entry name is not UTF-8"))?;
+        let extra = offset + 30 + name_len;
+        if method != 0 {
+            return Err(invalid("compressed .npz archives are not supported"));
+        }
+        if flags & 0x8 != 0 {
+            return Err(invalid("streamed .npz entries are not supported"));
+        }
+        // numpy forces zip64, moving the sizes into an extra field.
+        if size == u32::MAX as usize {
+            let mut field = extra;
+            size = loop {
+                if field + 4 > extra + extra_len {
+                    return Err(invalid("zip64 entry lacks its sizes"));
+                }
+                if u16_at(field)? == 1 {
+                    break u64_at(field + 4)?;
+                }
+                field += 4 + u16_at(field + 2)?;
+            };
+        }
+        let data = extra + extra_len;
+        let entry = archive.get(data..data + size).ok_or_else(truncated)?;
+        let name = name.strip_suffix(".npy").unwrap_or(name);
+        arrays.push((name.into(), read_npy(entry)?));
+        offset = data + size;
+    }
+    Ok(arrays)
+}
+
+/// Writes `arrays` as an uncompressed `.npz` archive, each entry named `<name>.npy`.
+pub fn write_npz<T: FftNum + Float, W: Write>(
+    mut writer: W,
+    arrays: &[(&str, &NpyArray<T>)],
+) -> io::Result<()> {
+    let mut archive = Vec::new();
+    let mut directory = Vec::new();
+    for &(name, array) in arrays {
+        let name = format!("{name}.npy");
+        let mut entry = Vec::new();
+        write_npy(&mut entry, array)?;
+        let (offset, size) = (archive.len(), entry.len());
+        if offset > u32::MAX as usize || size > u32::MAX as usize {
+            return Err(invalid("archive exceeds 4 GiB"));
+        }
+        let crc = crc32(&entry);
+
+        // Version 2.0, no flags, stored, a zero DOS date, CRC and sizes.
+        let mut fields = Vec::new();
+        for value in [20u16, 0, 0, 0, 0x21] {
+            fields.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [crc, size as u32, size as u32] {
+            fields.extend_from_slice(&value.to_le_bytes());
+        }
+        fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        fields.extend_from_slice(&0u16.to_le_bytes());
+
+        archive.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+        archive.extend_from_slice(&fields);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&entry);
+
+        directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes());
+        directory.extend_from_slice(&fields);
+        // No comment, disk 0 and no attributes, then the local header's offset.
+        directory.extend_from_slice(&[0; 10]);
+        directory.extend_from_slice(&(offset as u32).to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+    let directory_offset = archive.len();
+    archive.extend_from_slice(&directory);
+    archive.extend_from_slice(&END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]);
+    for _ in 0..2 {
+        archive.extend_from_slice(&(arrays.len() as u16).to_le_bytes());
+    }
+    archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&(directory_offset as u32).to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes());
+    writer.write_all(&archive)
+}
+
+/// CRC-32 (IEEE) of a zip entry.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
@@ -0,0 +1,238 @@
+use alloc::vec::Vec;
+
+/// Exact CZT over the integers modulo a prime `p < 2^63`:
+/// `X_k = sum_j x_j a^-j w^(jk) mod p`, i.e. `sum_j x_j z_k^-j` on `z_k = a w^-k`.
+///
+/// Bluestein's identity is used in the form `jk = C(j + k, 2) - C(j, 2) - C(k, 2)`, which needs
+/// no square root of `w`, and the correlation is a number-theoretic transform. That needs a
+/// power-of-two length `l >= n + m - 1` dividing `p - 1`, as for `998244353 = 119 * 2^23 + 1`
+/// or `29 * 2^57 + 1`; for other primes the plan falls back to direct `O(nm)` evaluation.
+pub struct NttCzt {
+    p: u64,
+    n: usize,
+    m: usize,
+    a: u64,
+    w: u64,
+    bluestein: Option<NttBluestein>,
+}
+
+/// Tables of the NTT path.
+struct NttBluestein {
+    /// `a^-j w^-C(j, 2)`
+    y: Vec<u64>,
+    /// Forward NTT of `w^C(t, 2)`, `t < n + m - 1`.
+    v: Vec<u64>,
+    /// `w^-C(k, 2)`
+    x: Vec<u64>,
+    /// Primitive `l`-th root of unity.
+    root: u64,
+}
+
+impl NttCzt {
+    /// `p` must be prime, and `a` and `w` nonzero modulo `p`.
+    pub fn new(p: u64, n: usize, m: usize, a: u64, w: u64) -> Self {
+        assert!(p > 1 && p < 1 << 63, "modulus must be a prime below 2^63");
+        assert!(n > 0 && m > 0);
+        let (a, w) = (a % p, w % p);
+        assert!(a != 0 && w != 0, "a and w must be invertible modulo p");
+
+        let l = (n + m - 1).next_power_of_two() as u64;
+        let bluestein = (p - 1).is_multiple_of(l).then(|| {
+            let root = root_of_unity(p, l);
+            let (a_inv, w_inv) = (inverse(a, p), inverse(w, p));
+
+            // w^C(j, 2) advances by w^j from one j to the next.
+            let binomial_powers = |base: u64, count: usize| {
+                let (mut power, mut step) = (1, 1);
+                (0..count).map(move |_| {
+                    let current = power;
+                    power = mul(power, step, p);
+                    step = mul(step, base, p);
+                    current
+                })
+            };
+
+            let mut a_power = 1;
+            let y = binomial_powers(w_inv, n)
+                .map(|chirp| {
+                    let value = mul(chirp, a_power, p);
+                    a_power = mul(a_power, a_inv, p);
+                    value
+                })
+                .collect();
+            let mut v: Vec<_> = binomial_powers(w, n + m - 1).collect();
+            v.resize(l as usize, 0);
+            ntt(&mut v, root, p);
+            let x = binomial_powers(w_inv, m).collect();
+            NttBluestein { y, v, x, root }
+        });
+
+        Self {
+            p,
+            n,
+            m,
+            a,
+            w,
+            bluestein,
+        }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    pub fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    /// Whether the plan runs in `O((n + m) log(n + m))` rather than `O(nm)`.
+    pub fn is_fast(&self) -> bool {
+        self.bluestein.is_some()
+    }
+
+    pub fn process(&self, input: &[u64]) -> Vec<u64> {
+        let mut output = vec![0; self.m];
+        let mut scratch = vec![0; self.get_scratch_len()];
+        self.process_with_scratch(input, &mut output, &mut scratch);
+        output
+    }
+
+    /// Inputs may be any `u64`; they are reduced modulo `p`.
+    pub fn process_with_scratch(&self, input: &[u64], output: &mut [u64], scratch: &mut [u64]) {
+        assert_eq!(input.len(), self.n);
+        assert_eq!(output.len(), self.m);
+        assert_eq!(scratch.len(), self.get_scratch_len());
+        let p = self.p;
+
+        let Some(bluestein) = &self.bluestein else {
+            let a_inv = inverse(self.a, p);
+            let mut w_k = 1;
+            for out in output.iter_mut() {
+                // z_k^-1 = a^-1 w^k
+                let z_inv = mul(a_inv, w_k, p);
+                let mut power = 1;
+                *out = 0;
+                for &x in input {
+                    *out = add(*out, mul(x % p, power, p), p);
+                    power = mul(power, z_inv, p);
+                }
+                w_k = mul(w_k, self.w, p);
+            }
+            return;
+        };
+
+        // Correlate y with v by convolving its reversal.
+        scratch.fill(0);
+        for (j, (&x, &y)) in input.iter().zip(&bluestein.y).enumerate() {
+            scratch[self.n - 1 - j] = mul(x % p, y, p);
+        }
+        ntt(scratch, bluestein.root, p);
+        for (s, &v) in scratch.iter_mut().zip(&bluestein.v) {
+            *s = mul(*s, v, p);
+        }
+        ntt(scratch, inverse(bluestein.root, p), p);
+
+        let l_inv = inverse(scratch.len() as u64 % p, p);
+        for ((out, &s), &x) in output
+            .iter_mut()
+            .zip(&scratch[self.n - 1..])
+            .zip(&bluestein.x)
+        {
+            *out = mul(mul(s, x, p), l_inv, p);
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.bluestein
+            .as_ref()
+            .map_or(0, |bluestein| bluestein.v.len())
+    }
+}
+
+/// Evaluates `coeffs[0] + coeffs[1] x + ...` modulo the prime `p` at the `m` points
+/// `x_k = a w^-k`, the exact counterpart of [`evaluate_poly`](crate::poly::evaluate_poly).
+pub fn evaluate_poly_mod(p: u64, coeffs: &[u64], a: u64, w: u64, m: usize) -> Vec<u64> {
+    if coeffs.is_empty() || m == 0 {
+        return vec![0; m];
+    }
+    let (a, w) = (a % p, w % p);
+    assert!(a != 0 && w != 0, "a and w must be invertible modulo p");
+    NttCzt::new(p, coeffs.len(), m, inverse(a, p), inverse(w, p)).process(coeffs)
+}
+
+fn add(x: u64, y: u64, p: u64) -> u64 {
+    let sum = x + y;
+    if sum >= p {
+        sum - p
+    } else {
+        sum
+    }
+}
+
+fn mul(x: u64, y: u64, p: u64) -> u64 {
+    (x as u128 * y as u128 % p as u128) as u64
+}
+
+fn pow(mut base: u64, mut exponent: u64, p: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(result, base, p);
+        }
+        base = mul(base, base, p);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Inverse by Fermat's little theorem.
+fn inverse(x: u64, p: u64) -> u64 {
+    pow(x, p - 2, p)
+}
+
+/// An element of order exactly `l`, a power of two dividing `p - 1`: `g^((p - 1) / l)` for the
+/// first `g` whose power does not already have a smaller order.
+fn root_of_unity(p: u64, l: u64) -> u64 {
+    if l == 1 {
+        return 1;
+    }
+    (2..p)
+        .map(|g| pow(g, (p - 1) / l, p))
+        .find(|&root| pow(root, l / 2, p) != 1)
+        .unwrap()
+}
+
+/// In-place iterative radix-2 NTT with `root` of order `data.len()`.
+fn ntt(data: &mut [u64], root: u64, p: u64) {
+    let l = data.len();
+    let bits = l.trailing_zeros();
+    if l < 2 {
+        return;
+    }
+    for i in 0..l {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= l {
+        let step = pow(root, (l / len) as u64, p);
+        for chunk in data.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut twiddle = 1;
+            for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = mul(*v, twiddle, p);
+                *v = add(*u, p - t, p);
+                *u = add(*u, t, p);
+                twiddle = mul(twiddle, step, p);
+            }
+        }
+        len <<= 1;
+    }
+}
@@ -0,0 +1,89 @@
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, FromPrimitive},
+};
+
+/// Sub-bin peak interpolation from three adjacent bins `[X(k - 1), X(k), X(k + 1)]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeakInterpolator {
+    /// Parabola through the three magnitudes. Works for any window and bin spacing, and improves
+    /// as the bins get denser than the main lobe.
+    Quadratic,
+    /// Jacobsen's estimator, `-Re((X(k+1) - X(k-1)) / (2 X(k) - X(k-1) - X(k+1)))`. Meant for
+    /// unwindowed data sampled at the DFT spacing `fs / n`.
+    Jacobsen,
+    /// Quinn's second estimator. Meant for unwindowed data sampled at the DFT spacing `fs / n`.
+    Quinn,
+}
+
+/// Interpolated location and height of a spectral peak.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeakEstimate<T> {
+    /// Peak position relative to the center bin, in bins.
+    pub offset: T,
+    /// Frequency of the peak, in the units of the bin spacing.
+    pub frequency: T,
+    /// Magnitude at the peak, read off the parabola through the three magnitudes.
+    pub magnitude: T,
+}
+
+impl PeakInterpolator {
+    /// Offset of the peak from the center bin, in bins. Returns zero when the three bins do not
+    /// describe a peak.
+    pub fn offset<T: Float + FromPrimitive>(self, bins: [Complex<T>; 3]) -> T {
+        let [below, center, above] = bins;
+        let offset = match self {
+            PeakInterpolator::Quadratic => {
+                let (alpha, beta, gamma) = (below.norm(), center.norm(), above.norm());
+                let curvature = alpha - (beta + beta) + gamma;
+                if curvature < T::zero() {
+                    (alpha - gamma) / (curvature + curvature)
+                } else {
+                    T::zero()
+                }
+            }
+            PeakInterpolator::Jacobsen => -((above - below) / (center + center - below - above)).re,
+            PeakInterpolator::Quinn => {
+                let ap = (above / center).re;
+                let am = (below / center).re;
+                let dp = -ap / (T::one() - ap);
+                let dm = am / (T::one() - am);
+                (dp + dm) / T::from_f64(2.0).unwrap() + tau(dp * dp) - tau(dm * dm)
+            }
+        };
+        if offset.is_finite() && offset.abs() <= T::one() {
+            offset
+        } else {
+            T::zero()
+        }
+    }
+
+    /// Interpolates the peak around `bins[1]`, which lies at `center_freq` on a grid of
+    /// `bin_width` spacing.
+    pub fn interpolate<T: Float + FromPrimitive>(
+        self,
+        bins: [Complex<T>; 3],
+        center_freq: T,
+        bin_width: T,
+    ) -> PeakEstimate<T> {
+        let offset = self.offset(bins);
+        let (alpha, beta, gamma) = (bins[0].norm(), bins[1].norm(), bins[2].norm());
+        let half = T::from_f64(0.5).unwrap();
+        let magnitude = beta
+            + offset * (gamma - alpha) * half
+            + offset * offset * (alpha - (beta + beta) + gamma) * half;
+        PeakEstimate {
+            offset,
+            frequency: center_freq + offset * bin_width,
+            magnitude,
+        }
+    }
+}
+
+fn tau<T: Float + FromPrimitive>(x: T) -> T {
+    let c = T::from_f64(2.0 / 3.0).unwrap().sqrt();
+    let three = T::from_f64(3.0).unwrap();
+    let six = T::from_f64(6.0).unwrap();
+    T::from_f64(0.25).unwrap() * (three * x * x + six * x + T::one()).ln()
+        - six.sqrt() / T::from_f64(24.0).unwrap() * ((x + T::one() - c) / (x + T::one() + c)).ln()
+}
@@ -0,0 +1,280 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use std::{
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::Czt;
+
+/// Runs continuous capture through acquisition, optional decimation, the CZT and a sink, each
+/// stage on its own threads and linked by bounded channels.
+///
+/// A full channel blocks the stage feeding it, so a slow stage throttles the source instead of
+/// dropping blocks. The decimation threads filter whole blocks and the CZT threads transform
+/// back-to-back frames of `get_input_len()` decimated samples, both in any order; blocks are
+/// put back in order before framing and the sink receives the spectra in frame order. At most
+/// `capacity + threads` blocks or frames are past each parallel stage's input without having
+/// been put back in order, so a stalled thread also throttles the source rather than letting
+/// the others' results pile up.
+pub struct PipelineBuilder<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+    decimation: Option<(usize, Vec<T>)>,
+    capacity: usize,
+    decimation_threads: usize,
+    czt_threads: usize,
+}
+
+/// Blocks and frames that went through a [`PipelineBuilder::run`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStats {
+    pub blocks: usize,
+    pub samples: usize,
+    pub frames: usize,
+}
+
+impl<T: FftNum> PipelineBuilder<T> {
+    pub fn new(czt: Arc<dyn Czt<T>>) -> Self {
+        Self {
+            czt,
+            decimation: None,
+            capacity: 16,
+            decimation_threads: 1,
+            czt_threads: 1,
+        }
+    }
+
+    /// Filters the acquired samples with the FIR `taps` and keeps every `factor`th output,
+    /// starting with the first; the filter starts from zeros.
+    pub fn decimate(mut self, factor: usize, taps: Vec<T>) -> Self {
+        assert!(factor > 0 && !taps.is_empty());
+        self.decimation = Some((factor, taps));
+        self
+    }
+
+    /// Blocks or frames each channel holds before its sender blocks.
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0);
+        self.capacity = capacity;
+        self
+    }
+
+    /// Threads filtering blocks for [`decimate`](Self::decimate). Each output costs one multiply
+    /// per tap, so long filters at high sample rates need several.
+    pub fn decimation_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0);
+        self.decimation_threads = threads;
+        self
+    }
+
+    pub fn czt_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0);
+        self.czt_threads = threads;
+        self
+    }
+
+    /// Pulls blocks from `source` until it returns `None` and passes every spectrum to `sink`
+    /// with its frame index, returning once all stages have drained. Samples after the last
+    /// whole frame are dropped.
+    pub fn run<S, K>(self, mut source: S, mut sink: K) -> PipelineStats
+    where
+        S: FnMut() -> Option<Vec<Complex<T>>> + Send,
+        K: FnMut(usize, &[Complex<T>]),
+    {
+        let (block_tx, block_rx) = sync_channel::<Vec<Complex<T>>>(self.capacity);
+        let (job_tx, job_rx) = sync_channel::<DecimationJob<T>>(self.capacity);
+        let (decimated_tx, decimated_rx) = sync_channel::<(usize, Vec<Complex<T>>)>(self.capacity);
+        let (frame_tx, frame_rx) = sync_channel::<(usize, Vec<Complex<T>>)>(self.capacity);
+        let (spectrum_tx, spectrum_rx) = sync_channel::<(usize, Vec<Complex<T>>)>(self.capacity);
+        let (job_rx, frame_rx) = (Mutex::new(job_rx), Mutex::new(frame_rx));
+        let (block_release, block_credits) = credits(self.capacity + self.decimation_threads);
+        let (frame_release, frame_credits) = credits(self.capacity + self.czt_threads);
+        let decimation = self.decimation.as_ref();
+        let n = self.czt.get_input_len();
+
+        thread::scope(|scope| {
+            let acquisition = scope.spawn(move || {
+                let mut stats = PipelineStats::default();
+                while let Some(block) = source() {
+                    stats.blocks += 1;
+                    stats.samples += block.len();
+                    if block_tx.send(block).is_err() {
+                        break;
+                    }
+                }
+                stats
+            });
+
+            scope.spawn(move || {
+                let mut splitter = decimation.map(|(factor, taps)| Splitter::new(*factor, taps));
+                for (index, block) in block_rx.into_iter().enumerate() {
+                    let job = match &mut splitter {
+                        Some(splitter) => splitter.split(index, block),
+                        None => DecimationJob {
+                            index,
+                            buffer: block,
+                            first: 0,
+                        },
+                    };
+                    if block_credits.recv().is_err() || job_tx.send(job).is_err() {
+                        return;
+                    }
+                }
+            });
+
+            for _ in 0..self.decimation_threads {
+                let (job_rx, decimated_tx) = (&job_rx, decimated_tx.clone());
+                scope.spawn(move || {
+                    while let Ok(job) = receive(job_rx) {
+                        let samples = match decimation {
+                            Some((factor, taps)) => job.filter(*factor, taps),
+                            None => job.buffer,
+                        };
+                        if decimated_tx.send((job.index, samples)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            drop(decimated_tx);
+
+            // Blocks finishing out of order wait here for their predecessors.
+            scope.spawn(move || {
+                let (mut next, mut waiting) = (0, BTreeMap::new());
+                let mut frame = Vec::with_capacity(n);
+                let mut index = 0;
+                for (block, samples) in decimated_rx {
+                    waiting.insert(block, samples);
+                    while let Some(samples) = waiting.remove(&next) {
+                        next += 1;
+                        let _ = block_release.send(());
+                        for sample in samples {
+                            frame.push(sample);
+                            if frame.len() == n {
+                                let full = core::mem::replace(&mut frame, Vec::with_capacity(n));
+                                if frame_credits.recv().is_err()
+                                    || frame_tx.send((index, full)).is_err()
+                                {
+                                    return;
+                                }
+                                index += 1;
+                            }
+                        }
+                    }
+                }
+            });
+
+            for _ in 0..self.czt_threads {
+                let (czt, frame_rx, spectrum_tx) = (&self.czt, &frame_rx, spectrum_tx.clone());
+                scope.spawn(move || {
+                    let mut scratch = vec![Complex::zero(); czt.get_outofplace_scratch_len()];
+                    loop {
+                        let Ok((index, frame)) = receive(frame_rx) else {
+                            return;
+                        };
+                        let mut spectrum = vec![Complex::zero(); czt.get_output_len()];
+                        czt.process_outofplace_with_scratch(&frame, &mut spectrum, &mut scratch);
+                        if spectrum_tx.send((index, spectrum)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            drop(spectrum_tx);
+
+            // Spectra finishing out of order wait here for their predecessors.
+            let (mut next, mut waiting) = (0, BTreeMap::new());
+            for (index, spectrum) in spectrum_rx {
+                waiting.insert(index, spectrum);
+                while let Some(spectrum) = waiting.remove(&next) {
+                    sink(next, &spectrum);
+                    next += 1;
+                    let _ = frame_release.send(());
+                }
+            }
+            PipelineStats {
+                frames: next,
+                ..acquisition.join().unwrap()
+            }
+        })
+    }
+}
+
+/// Takes the next item, holding the lock only while waiting for it.
+fn receive<F>(items: &Mutex<Receiver<F>>) -> Result<F, std::sync::mpsc::RecvError> {
+    items.lock().unwrap().recv()
+}
+
+/// `window` credits: a producer takes one from the receiver before sending an item, and the
+/// consumer returns it through the sender once the item is back in order.
+fn credits(window: usize) -> (SyncSender<()>, Receiver<()>) {
+    let (release, credits) = sync_channel(window);
+    for _ in 0..window {
+        release.send(()).unwrap();
+    }
+    (release, credits)
+}
+
+/// A block for the decimation threads, preceded by the `taps.len() - 1` samples the filter
+/// needs from before it.
+struct DecimationJob<T> {
+    index: usize,
+    buffer: Vec<Complex<T>>,
+    /// Position in `buffer` of the newest sample of the block's first output.
+    first: usize,
+}
+
+impl<T: FftNum> DecimationJob<T> {
+    fn filter(&self, factor: usize, taps: &[T]) -> Vec<Complex<T>> {
+        (self.first..self.buffer.len())
+            .step_by(factor)
+            .map(|newest| {
+                taps.iter()
+                    .enumerate()
+                    .fold(Complex::zero(), |acc, (l, &h)| {
+                        acc + self.buffer[newest - l] * h
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Cuts the acquired stream into [`DecimationJob`]s, carrying the filter history and the
+/// output phase across blocks; the filter starts from zeros.
+struct Splitter<T> {
+    factor: usize,
+    history: Vec<Complex<T>>,
+    /// Position, counted from the start of `history`, of the newest sample of the next output.
+    next: usize,
+}
+
+impl<T: FftNum> Splitter<T> {
+    fn new(factor: usize, taps: &[T]) -> Self {
+        Self {
+            factor,
+            history: vec![Complex::zero(); taps.len() - 1],
+            next: taps.len() - 1,
+        }
+    }
+
+    fn split(&mut self, index: usize, block: Vec<Complex<T>>) -> DecimationJob<T> {
+        let carried = self.history.len();
+        let mut buffer = core::mem::take(&mut self.history);
+        buffer.extend_from_slice(&block);
+
+        let first = self.next;
+        let outputs = buffer.len().saturating_sub(first).div_ceil(self.factor);
+        let consumed = buffer.len() - carried;
+        self.history = buffer[consumed..].to_vec();
+        self.next = first + outputs * self.factor - consumed;
+        DecimationJob {
+            index,
+            buffer,
+            first,
+        }
+    }
+}
@@ -1,24 +1,245 @@
-use std::sync::Arc;
+use alloc::{collections::BTreeMap, sync::Arc};
 
-use rustfft::{num_complex::Complex, num_traits::Float, FftNum, FftPlanner};
+#[cfg(feature = "std")]
+use rustfft::{FftPlanner, FftPlannerAvx, FftPlannerNeon};
 
-use crate::{bluesteins::BluesteinsAlgorithm, Czt};
+use crate::fft::{num_complex::Complex, num_traits::Float, Fft, FftDirection, FftNum};
 
-pub enum ChosenCztPlanner<T: Float + FftNum> {
-    Scalar(CztPlannerScalar<T>),
+#[cfg(feature = "gpu")]
+use crate::gpu::{GpuBluesteins, GpuContext};
+use crate::{
+    blocked::BlockedCzt,
+    bluesteins::{from_f64, to_f64, BluesteinsAlgorithm},
+    cost,
+    czt2d::Czt2d,
+    czt_fft::CztFft,
+    dft::DftCzt,
+    direct::DirectCzt,
+    fft_provider::DefaultFftProvider,
+    goertzel::GoertzelAlgorithm,
+    inverse_czt::InverseCzt,
+    laplace::SPlaneLine,
+    mixer::MixerCzt,
+    naive_czt::NaiveCzt,
+    normalization::Normalization,
+    range_doppler::{FmcwConfig, RangeDoppler},
+    real_czt::{RealCztPacked, RealCztPromoted},
+    resample::Resampler,
+    rotation::RotationCzt,
+    spectrogram::Spectrogram,
+    spiral::SpiralCzt,
+    window::Window,
+    wisdom::{Algorithm, Wisdom, WisdomEntry},
+    zoom::ZoomFft,
+    zoom_psd::ZoomPsd,
+    ConvolutionLength, Czt, CztDirection, CztParams, FftProvider, PlanError, RealCzt,
+};
+
+/// Which algorithm `plan_czt_forward` builds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlannerStrategy {
+    /// Pick the cheapest of the direct and FFT-based algorithms for each request, among those
+    /// whose rounding error stays within a small multiple of Bluestein's. Contours that are a
+    /// plain DFT get a [`DftCzt`], and those whose `w` is a root of unity are considered for a
+    /// [`RotationCzt`].
+    #[default]
+    Auto,
+    ForceNaive,
+    ForceBluestein,
+    /// [`MixerCzt`] where [`MixerCzt::design`] accepts the contour, Bluestein elsewhere.
+    ForceMixer,
+}
+
+/// Whether an algorithm whose rounding error grows like `growth` (in units of `T::epsilon()`)
+/// stays within a small multiple of Bluestein's on a length-`l` convolution, which grows like
+/// `log2(l)`.
+fn as_accurate_as_bluestein(growth: f64, l: usize) -> bool {
+    growth <= 4.0 * l.max(2).ilog2() as f64
+}
+
+/// The cheapest of the direct, rotation, mixer and Bluestein algorithms by the rough operation
+/// counts, leaving out the direct algorithms where they would be less accurate than Bluestein.
+fn auto_algorithm<T: Float + FftNum>(
+    n: usize,
+    m: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+    length: ConvolutionLength,
+) -> Algorithm {
+    let l = length.for_len(n + m - 1);
+    let goertzel = if as_accurate_as_bluestein(GoertzelAlgorithm::error_growth(n, m, a, w), l) {
+        cost::goertzel::<T>(n, m).flops
+    } else {
+        usize::MAX
+    };
+    let direct = if as_accurate_as_bluestein(DirectCzt::<T>::error_growth(n), l) {
+        cost::direct::<T>(n, m).flops
+    } else {
+        usize::MAX
+    };
+    let fft_based = cost::bluesteins::<T>(n, m, length).flops;
+    let rotation = RotationCzt::root(w, l).map_or(usize::MAX, |(order, _)| {
+        cost::rotation::<T>(n, m, order).flops
+    });
+    let mixer = MixerCzt::design(n, m, a, w).map_or(usize::MAX, |design| {
+        cost::mixer::<T>(n, m, design, length).flops
+    });
+    [
+        (Algorithm::Goertzel, goertzel),
+        (Algorithm::Direct, direct),
+        (Algorithm::Rotation, rotation),
+        (Algorithm::Bluestein, fft_based),
+        (Algorithm::Mixer, mixer),
+    ]
+    .into_iter()
+    .min_by_key(|&(_, flops)| flops)
+    .unwrap()
+    .0
+}
+
+/// Transforms timed by [`CztPlannerScalar::plan_czt_forward_measured`] for each candidate.
+pub const MEASURE_TRIALS: usize = 3;
+
+/// The fastest of [`MEASURE_TRIALS`] transforms of `czt` in nanoseconds, after a warm-up.
+#[cfg(feature = "std")]
+fn measure<T: FftNum>(czt: &dyn Czt<T>) -> u64 {
+    use crate::fft::num_traits::Zero;
+
+    let mut buffer = alloc::vec![Complex::zero(); czt.get_buffer_len()];
+    let mut scratch = alloc::vec![Complex::zero(); czt.get_inplace_scratch_len()];
+    czt.process_with_scratch(&mut buffer, &mut scratch);
+    (0..MEASURE_TRIALS)
+        .map(|_| {
+            let start = std::time::Instant::now();
+            czt.process_with_scratch(&mut buffer, &mut scratch);
+            start.elapsed().as_nanos() as u64
+        })
+        .min()
+        .unwrap()
+}
+
+/// Expands to a `match` running `$body` against whichever planner variant is active.
+macro_rules! dispatch {
+    ($chosen:expr, $planner:ident => $body:expr) => {
+        match $chosen {
+            ChosenCztPlanner::Scalar($planner) => $body,
+            #[cfg(feature = "std")]
+            ChosenCztPlanner::Avx($planner) => $body,
+            #[cfg(feature = "std")]
+            ChosenCztPlanner::Neon($planner) => $body,
+            #[cfg(feature = "gpu")]
+            ChosenCztPlanner::Gpu($planner) => $body,
+        }
+    };
 }
 
-pub struct CztPlanner<T: Float + FftNum> {
-    chosen_planner: ChosenCztPlanner<T>,
+pub enum ChosenCztPlanner<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    Scalar(CztPlannerScalar<T, P>),
+    #[cfg(feature = "std")]
+    Avx(CztPlannerScalar<T, FftPlannerAvx<T>>),
+    #[cfg(feature = "std")]
+    Neon(CztPlannerScalar<T, FftPlannerNeon<T>>),
+    #[cfg(feature = "gpu")]
+    Gpu(CztPlannerGpu<T, P>),
 }
 
+pub struct CztPlanner<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    chosen_planner: ChosenCztPlanner<T, P>,
+}
+
+#[cfg(feature = "std")]
 impl<T: Float + FftNum> CztPlanner<T> {
+    /// Picks the AVX or Neon FFT planner when the CPU supports it, and the portable one otherwise.
     pub fn new() -> Self {
+        let chosen_planner = if let Ok(fft_planner) = FftPlannerAvx::new() {
+            ChosenCztPlanner::Avx(CztPlannerScalar::with_fft_provider(fft_planner))
+        } else if let Ok(fft_planner) = FftPlannerNeon::new() {
+            ChosenCztPlanner::Neon(CztPlannerScalar::with_fft_provider(fft_planner))
+        } else {
+            ChosenCztPlanner::Scalar(CztPlannerScalar::new())
+        };
+        Self { chosen_planner }
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> CztPlanner<T, P> {
+    pub fn with_fft_provider(fft_provider: P) -> Self {
         Self {
-            chosen_planner: ChosenCztPlanner::<T>::Scalar(CztPlannerScalar::<T>::new()),
+            chosen_planner: ChosenCztPlanner::Scalar(CztPlannerScalar::with_fft_provider(
+                fft_provider,
+            )),
         }
     }
 
+    /// Plans on the default GPU adapter, or returns `None` unless `T` is `f32` and an adapter is
+    /// available.
+    #[cfg(feature = "gpu")]
+    pub fn with_gpu(fft_provider: P) -> Option<Self> {
+        Some(Self {
+            chosen_planner: ChosenCztPlanner::Gpu(CztPlannerGpu::with_fft_provider(fft_provider)?),
+        })
+    }
+
+    pub fn strategy(&self) -> PlannerStrategy {
+        dispatch!(&self.chosen_planner, planner => planner.strategy())
+    }
+
+    pub fn set_strategy(&mut self, strategy: PlannerStrategy) {
+        dispatch!(&mut self.chosen_planner, planner => planner.set_strategy(strategy))
+    }
+
+    pub fn convolution_length(&self) -> ConvolutionLength {
+        dispatch!(&self.chosen_planner, planner => planner.convolution_length())
+    }
+
+    /// Selects how Bluestein plans size their convolution, e.g. `PowerOfTwo` for FFT providers
+    /// that only support those lengths.
+    pub fn set_convolution_length(&mut self, length: ConvolutionLength) {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.set_convolution_length(length)
+        })
+    }
+
+    /// Decisions made in [`PlannerStrategy::Auto`], see [`Wisdom`].
+    pub fn wisdom(&self) -> &Wisdom {
+        dispatch!(&self.chosen_planner, planner => planner.wisdom())
+    }
+
+    pub fn import_wisdom(&mut self, wisdom: &Wisdom) {
+        dispatch!(&mut self.chosen_planner, planner => planner.import_wisdom(wisdom))
+    }
+
+    /// Writes the planner's wisdom to `path` as text.
+    #[cfg(feature = "std")]
+    pub fn save_wisdom(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.wisdom().to_text())
+    }
+
+    /// Imports the wisdom saved at `path`, failing with [`std::io::ErrorKind::InvalidData`] if
+    /// it does not parse.
+    #[cfg(feature = "std")]
+    pub fn load_wisdom(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        let wisdom = Wisdom::from_text(&text).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed wisdom")
+        })?;
+        self.import_wisdom(&wisdom);
+        Ok(())
+    }
+
+    /// [`plan_czt_forward`](Self::plan_czt_forward), or the reason the parameters cannot be
+    /// planned.
+    pub fn try_plan_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Result<Arc<dyn Czt<T>>, PlanError> {
+        CztParams { n, m, a, w }.validate()?;
+        Ok(self.plan_czt_forward(n, m, a, w))
+    }
+
     pub fn plan_czt_forward(
         &mut self,
         n: usize,
@@ -26,17 +247,242 @@ impl<T: Float + FftNum> CztPlanner<T> {
         a: Complex<T>,
         w: Complex<T>,
     ) -> Arc<dyn Czt<T>> {
-        match &mut self.chosen_planner {
-            ChosenCztPlanner::Scalar(planner) => planner.plan_czt_forward(n, m, a, w),
-        }
+        dispatch!(&mut self.chosen_planner, planner => planner.plan_czt_forward(n, m, a, w))
+    }
+
+    /// Like [`plan_czt_forward`](Self::plan_czt_forward), but picks the fastest candidate by
+    /// timing a few transforms instead of by the cost model, see
+    /// [`CztPlannerScalar::plan_czt_forward_measured`].
+    #[cfg(feature = "std")]
+    pub fn plan_czt_forward_measured(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_measured(n, m, a, w)
+        })
+    }
+
+    pub fn plan_czt_forward_with_offset(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_with_offset(n, m, a, w, k0)
+        })
+    }
+
+    pub fn plan_czt_forward_blocked(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        max_scratch: usize,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_blocked(n, m, a, w, max_scratch)
+        })
+    }
+
+    pub fn plan_czt(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        direction: CztDirection,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => planner.plan_czt(n, m, a, w, direction))
+    }
+
+    pub fn plan_czt_backward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.plan_czt(n, m, a, w, CztDirection::Inverse)
+    }
+
+    /// Plans a length-`n` DFT as the CZT with `a = 1`, `w = e^(-+2 pi i / n)` and exposes it
+    /// through [`Fft`]. Under [`PlannerStrategy::Auto`] that is a [`DftCzt`] over the planner's
+    /// FFT; forced strategies build their algorithm. Like rustfft, the inverse is unnormalized.
+    pub fn plan_fft_via_czt(&mut self, n: usize, direction: FftDirection) -> Arc<dyn Fft<T>> {
+        let sign = match direction {
+            FftDirection::Forward => -1.0,
+            FftDirection::Inverse => 1.0,
+        };
+        let w = from_f64(Complex::from_polar(
+            1.0,
+            sign * core::f64::consts::TAU / n as f64,
+        ));
+        let czt = self.plan_czt_forward(n, n, Complex::new(T::one(), T::zero()), w);
+        Arc::new(CztFft::new(czt, direction))
+    }
+
+    /// Plans an [`SPlaneLine`] of `m` points at `s = sigma + i omega`, `omega` running from
+    /// `omega_start` to `omega_end` rad/s, for impulse responses of `n` samples.
+    pub fn plan_s_plane_line(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        sigma: T,
+        omega_start: T,
+        omega_end: T,
+    ) -> Arc<SPlaneLine<T>> {
+        assert!(m > 0);
+        let omega_step = if m > 1 {
+            (omega_end - omega_start) / T::from_usize(m - 1).unwrap()
+        } else {
+            T::zero()
+        };
+        let period = sample_rate.to_f64().unwrap().recip();
+        let a = to_f64(Complex::new(sigma, omega_start)).scale(period).exp();
+        let w = Complex::from_polar(1.0, -omega_step.to_f64().unwrap() * period);
+        let czt = self.plan_czt_forward(n, m, from_f64(a), from_f64(w));
+        Arc::new(SPlaneLine::new(czt, sigma, omega_start, omega_step))
+    }
+
+    /// Plans a [`Resampler`] from blocks of `n` samples at `input_rate` to `output_rate`; the
+    /// ratio need not be rational.
+    pub fn plan_resampler(&mut self, n: usize, input_rate: T, output_rate: T) -> Arc<Resampler<T>> {
+        assert!(n > 0);
+        let ratio = input_rate.to_f64().unwrap() / output_rate.to_f64().unwrap();
+        assert!(ratio > 0.0 && ratio.is_finite());
+        // Output times p / output_rate before the end of the block, n / input_rate.
+        let m = (n as f64 / ratio * (1.0 - 1e-12)).ceil() as usize;
+
+        // Series terms up to the lower of the two Nyquist frequencies, in DFT bins.
+        let cutoff = n as f64 / 2.0 * ratio.recip().min(1.0);
+        let h = cutoff as usize;
+        let scale = 1.0 / n as f64;
+        let terms = (0..=2 * h)
+            .map(|i| {
+                let k = i as isize - h as isize;
+                let weight = if h > 0 && k.unsigned_abs() == h && h as f64 == cutoff {
+                    scale / 2.0
+                } else {
+                    scale
+                };
+                (
+                    k.rem_euclid(n as isize) as usize,
+                    T::from_f64(weight).unwrap(),
+                )
+            })
+            .collect();
+
+        let theta = core::f64::consts::TAU * ratio / n as f64;
+        let phases = (0..m)
+            .map(|p| from_f64(Complex::from_polar(1.0, -theta * (h * p) as f64)))
+            .collect();
+
+        let one = Complex::new(T::one(), T::zero());
+        let dft = Complex::from_polar(1.0, -core::f64::consts::TAU / n as f64);
+        let forward = self.plan_czt_forward(n, n, one, from_f64(dft));
+        let backward = self.plan_czt_backward(
+            2 * h + 1,
+            m,
+            one,
+            from_f64(Complex::from_polar(1.0, -theta)),
+        );
+        Arc::new(Resampler::new(forward, backward, terms, phases))
+    }
+
+    pub fn plan_czt_forward_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        window: Window<T>,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_windowed(n, m, a, w, window)
+        })
+    }
+
+    pub fn plan_czt_forward_normalized(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        normalization: Normalization<T>,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_normalized(n, m, a, w, normalization)
+        })
+    }
+
+    /// Plans a CZT of the input delayed by `delay` samples, see
+    /// [`BluesteinsAlgorithm::with_delay`].
+    pub fn plan_czt_forward_delayed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        delay: T,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt_forward_delayed(n, m, a, w, delay)
+        })
+    }
+
+    pub fn plan_czt_inverse_exact(
+        &mut self,
+        n: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => planner.plan_czt_inverse_exact(n, a, w))
+    }
+
+    pub fn plan_real_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn RealCzt<T>> {
+        dispatch!(&mut self.chosen_planner, planner => planner.plan_real_czt_forward(n, m, a, w))
+    }
+
+    pub fn plan_czt2_forward(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        a_row: Complex<T>,
+        w_row: Complex<T>,
+        a_col: Complex<T>,
+        w_col: Complex<T>,
+    ) -> Arc<Czt2d<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_czt2_forward(rows, cols, a_row, w_row, a_col, w_col)
+        })
     }
 }
 
-impl<T: Float + FftNum> CztPlanner<T> {
-    pub fn plan_zoom_fft(&mut self, czt_len: usize, start: T, end: T) -> Arc<dyn Czt<T>> {
-        match &mut self.chosen_planner {
-            ChosenCztPlanner::Scalar(planner) => planner.plan_zoom_fft(czt_len, start, end),
-        }
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> Default for CztPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> CztPlanner<T, P> {
+    pub fn plan_zoom_fft(&mut self, czt_len: usize, start: T, end: T) -> Arc<ZoomFft<T>> {
+        dispatch!(&mut self.chosen_planner, planner => planner.plan_zoom_fft(czt_len, start, end))
     }
 
     pub fn plan_zoom_fft_with_m(
@@ -45,24 +491,242 @@ impl<T: Float + FftNum> CztPlanner<T> {
         m: usize,
         start: T,
         end: T,
-    ) -> Arc<dyn Czt<T>> {
-        match &mut self.chosen_planner {
-            ChosenCztPlanner::Scalar(planner) => planner.plan_zoom_fft_with_m(n, m, start, end),
+    ) -> Arc<ZoomFft<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_zoom_fft_with_m(n, m, start, end)
+        })
+    }
+
+    pub fn plan_zoom_fft_hz(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+    ) -> Arc<ZoomFft<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_zoom_fft_hz(n, m, sample_rate, f_start, f_end)
+        })
+    }
+
+    pub fn plan_zoom_fft_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_zoom_fft_windowed(n, m, start, end, window)
+        })
+    }
+
+    pub fn plan_zoom_fft_hz_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_zoom_fft_hz_windowed(n, m, sample_rate, f_start, f_end, window)
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_spectrogram(
+        &mut self,
+        frame_len: usize,
+        hop: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Spectrogram<T> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_spectrogram(frame_len, hop, m, sample_rate, f_start, f_end, window)
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_zoom_psd(
+        &mut self,
+        segment_len: usize,
+        overlap: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> ZoomPsd<T> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_zoom_psd(segment_len, overlap, m, sample_rate, f_start, f_end, window)
+        })
+    }
+
+    pub fn plan_range_doppler(
+        &mut self,
+        config: FmcwConfig<T>,
+        v_min: T,
+        v_max: T,
+        velocity_bins: usize,
+        window: Window<T>,
+    ) -> RangeDoppler<T> {
+        dispatch!(&mut self.chosen_planner, planner => {
+            planner.plan_range_doppler(config, v_min, v_max, velocity_bins, window)
+        })
+    }
+}
+
+/// Identifies a plan by its lengths and the exact bit patterns of its contour parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct CztKey {
+    n: usize,
+    m: usize,
+    contour: [(u64, i16, i8); 4],
+    strategy: PlannerStrategy,
+    length: ConvolutionLength,
+    fold: Option<(u8, (u64, i16, i8))>,
+}
+
+impl CztKey {
+    fn new<T: Float>(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        strategy: PlannerStrategy,
+        length: ConvolutionLength,
+    ) -> Self {
+        Self {
+            n,
+            m,
+            strategy,
+            length,
+            fold: None,
+            contour: [
+                a.re.integer_decode(),
+                a.im.integer_decode(),
+                w.re.integer_decode(),
+                w.im.integer_decode(),
+            ],
+        }
+    }
+
+    /// The key of the plan with `fold` folded in.
+    fn folding<T: Float + FftNum>(self, fold: Fold<T>) -> Self {
+        let (kind, parameter) = fold.kind();
+        Self {
+            fold: Some((kind, parameter.integer_decode())),
+            ..self
+        }
+    }
+}
+
+/// A factor folded into the chirp tables of a Bluestein-based plan.
+#[derive(Clone, Copy)]
+enum Fold<T> {
+    Window(Window<T>),
+    OutputScale(T),
+    Delay(T),
+}
+
+impl<T: Float + FftNum> Fold<T> {
+    /// Which fold this is, and its parameter where it has one.
+    fn kind(self) -> (u8, T) {
+        let zero = T::zero();
+        match self {
+            Fold::Window(Window::Rectangular) => (0, zero),
+            Fold::Window(Window::Hann) => (1, zero),
+            Fold::Window(Window::Hamming) => (2, zero),
+            Fold::Window(Window::BlackmanHarris) => (3, zero),
+            Fold::Window(Window::Kaiser(beta)) => (4, beta),
+            Fold::Window(Window::FlatTop) => (5, zero),
+            Fold::OutputScale(factor) => (6, factor),
+            Fold::Delay(delay) => (7, delay),
+        }
+    }
+
+    fn bluesteins(self, czt: BluesteinsAlgorithm<T>) -> BluesteinsAlgorithm<T> {
+        match self {
+            Fold::Window(window) => {
+                let coefficients = window.coefficients(czt.get_input_len());
+                czt.with_window(&coefficients)
+            }
+            Fold::OutputScale(factor) => czt.with_output_scale(factor),
+            Fold::Delay(delay) => czt.with_delay(delay),
+        }
+    }
+
+    fn spiral(self, czt: SpiralCzt<T>) -> SpiralCzt<T> {
+        match self {
+            Fold::Window(window) => {
+                let coefficients = window.coefficients(czt.get_input_len());
+                czt.with_window(&coefficients)
+            }
+            Fold::OutputScale(factor) => czt.with_output_scale(factor),
+            Fold::Delay(delay) => czt.with_delay(delay),
         }
     }
 }
 
-pub struct CztPlannerScalar<T: Float + FftNum> {
-    fft_planner: FftPlanner<T>,
+pub struct CztPlannerScalar<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    fft_planner: P,
+    algorithm_cache: BTreeMap<CztKey, Arc<dyn Czt<T>>>,
+    strategy: PlannerStrategy,
+    convolution_length: ConvolutionLength,
+    wisdom: Wisdom,
 }
 
+#[cfg(feature = "std")]
 impl<T: Float + FftNum> CztPlannerScalar<T> {
     pub fn new() -> Self {
+        Self::with_fft_provider(FftPlanner::new())
+    }
+}
+
+impl<T: Float + FftNum, P: FftProvider<T>> CztPlannerScalar<T, P> {
+    pub fn with_fft_provider(fft_provider: P) -> Self {
         Self {
-            fft_planner: FftPlanner::new(),
+            fft_planner: fft_provider,
+            algorithm_cache: BTreeMap::new(),
+            strategy: PlannerStrategy::Auto,
+            convolution_length: ConvolutionLength::default(),
+            wisdom: Wisdom::new(),
         }
     }
 
+    pub fn strategy(&self) -> PlannerStrategy {
+        self.strategy
+    }
+
+    pub fn set_strategy(&mut self, strategy: PlannerStrategy) {
+        self.strategy = strategy;
+    }
+
+    pub fn convolution_length(&self) -> ConvolutionLength {
+        self.convolution_length
+    }
+
+    pub fn set_convolution_length(&mut self, length: ConvolutionLength) {
+        self.convolution_length = length;
+    }
+
+    pub fn wisdom(&self) -> &Wisdom {
+        &self.wisdom
+    }
+
+    /// Adds `wisdom` to the planner's, taking precedence over decisions already made. Plans
+    /// already cached are not rebuilt.
+    pub fn import_wisdom(&mut self, wisdom: &Wisdom) {
+        self.wisdom.merge(wisdom);
+    }
+
     pub fn plan_czt_forward(
         &mut self,
         n: usize,
@@ -70,28 +734,816 @@ impl<T: Float + FftNum> CztPlannerScalar<T> {
         a: Complex<T>,
         w: Complex<T>,
     ) -> Arc<dyn Czt<T>> {
-        Arc::new(BluesteinsAlgorithm::new(n, m, a, w, &mut self.fft_planner))
+        let key = CztKey::new(n, m, a, w, self.strategy, self.convolution_length);
+        if let Some(instance) = self.algorithm_cache.get(&key) {
+            return Arc::clone(instance);
+        }
+
+        let length = self.convolution_length;
+        let instance = match self.strategy {
+            PlannerStrategy::ForceNaive => self.build(n, m, a, w, Algorithm::Naive, length),
+            PlannerStrategy::ForceBluestein => self.build(n, m, a, w, Algorithm::Bluestein, length),
+            PlannerStrategy::ForceMixer => self.build(n, m, a, w, Algorithm::Mixer, length),
+            PlannerStrategy::Auto => {
+                if let Some(direction) = DftCzt::direction(n, m, a, w) {
+                    Arc::new(DftCzt::new(n, direction, &mut self.fft_planner))
+                } else {
+                    self.auto(n, m, a, w)
+                }
+            }
+        };
+        self.algorithm_cache.insert(key, Arc::clone(&instance));
+        instance
     }
-}
 
-impl<T: FftNum + Float> CztPlannerScalar<T> {
-    pub fn plan_zoom_fft(&mut self, czt_len: usize, start: T, end: T) -> Arc<dyn Czt<T>> {
-        self.plan_zoom_fft_with_m(czt_len, czt_len, start, end)
+    /// Follows the wisdom for the contour, or records what the cost model picks.
+    fn auto(&mut self, n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Arc<dyn Czt<T>> {
+        let length = self.convolution_length;
+        let entry = self.wisdom.get(n, m, a, w).unwrap_or_else(|| {
+            let entry = WisdomEntry {
+                algorithm: auto_algorithm(n, m, a, w, length),
+                length,
+                nanos: None,
+            };
+            self.wisdom.insert(n, m, a, w, entry);
+            entry
+        });
+        self.build(n, m, a, w, entry.algorithm, entry.length)
     }
 
-    pub fn plan_zoom_fft_with_m(
+    /// Times the direct algorithms, the rotation, the mixer and Bluestein with each convolution length on
+    /// [`MEASURE_TRIALS`] transforms after a warm-up, and keeps the fastest. The result is
+    /// recorded in the wisdom with its time, and later calls and `Auto` plans for the contour
+    /// reuse it without measuring again. The direct algorithms are skipped where the cost model
+    /// puts them far behind, so planning does not take longer than necessary, and also where
+    /// their rounding error would exceed Bluestein's, so the fastest plan is never a less
+    /// accurate one. Plain DFTs get a
+    /// [`DftCzt`] without measuring.
+    #[cfg(feature = "std")]
+    pub fn plan_czt_forward_measured(
         &mut self,
         n: usize,
         m: usize,
-        start: T,
-        end: T,
+        a: Complex<T>,
+        w: Complex<T>,
     ) -> Arc<dyn Czt<T>> {
-        let one = T::from_f64(1.0).unwrap();
-        let two_pi = T::from_f64(std::f64::consts::PI * 2.0).unwrap();
-        let n_minus_one = T::from_usize(n - 1).unwrap();
-        let a = Complex::from_polar(one, two_pi * start);
-        let w = Complex::from_polar(one, -two_pi * (end - start) / n_minus_one);
+        let key = CztKey::new(n, m, a, w, PlannerStrategy::Auto, self.convolution_length);
+        if let Some(direction) = DftCzt::direction(n, m, a, w) {
+            // `Auto` plans the same DftCzt under this key, so any cached plan is reusable.
+            if let Some(instance) = self.algorithm_cache.get(&key) {
+                return Arc::clone(instance);
+            }
+            let instance: Arc<dyn Czt<T>> =
+                Arc::new(DftCzt::new(n, direction, &mut self.fft_planner));
+            self.algorithm_cache.insert(key, Arc::clone(&instance));
+            return instance;
+        }
+        let measured = self
+            .wisdom
+            .get(n, m, a, w)
+            .filter(|entry| entry.nanos.is_some());
+        if let (Some(instance), Some(_)) = (self.algorithm_cache.get(&key), measured) {
+            return Arc::clone(instance);
+        }
+
+        let mut candidates = alloc::vec::Vec::new();
+        let length = self.convolution_length;
+        if cost::goertzel::<T>(n, m).flops / 16 <= cost::bluesteins::<T>(n, m, length).flops {
+            let l = length.for_len(n + m - 1);
+            if as_accurate_as_bluestein(GoertzelAlgorithm::error_growth(n, m, a, w), l) {
+                candidates.push((Algorithm::Goertzel, length));
+            }
+            if as_accurate_as_bluestein(DirectCzt::<T>::error_growth(n), l) {
+                candidates.push((Algorithm::Direct, length));
+            }
+        }
+        if RotationCzt::root(w, length.for_len(n + m - 1)).is_some() {
+            candidates.push((Algorithm::Rotation, length));
+        }
+        if MixerCzt::design(n, m, a, w).is_some() {
+            candidates.push((Algorithm::Mixer, length));
+        }
+        for candidate in [ConvolutionLength::Smooth, ConvolutionLength::PowerOfTwo] {
+            let duplicate = candidates.iter().any(|&(algorithm, other)| {
+                algorithm == Algorithm::Bluestein
+                    && other.for_len(n + m - 1) == candidate.for_len(n + m - 1)
+            });
+            if !duplicate {
+                candidates.push((Algorithm::Bluestein, candidate));
+            }
+        }
+
+        let (instance, entry) = candidates
+            .into_iter()
+            .map(|(algorithm, length)| {
+                let instance = self.build(n, m, a, w, algorithm, length);
+                let entry = WisdomEntry {
+                    algorithm,
+                    length,
+                    nanos: Some(measure(&*instance)),
+                };
+                (instance, entry)
+            })
+            .min_by_key(|(_, entry)| entry.nanos)
+            .unwrap();
+        self.wisdom.insert(n, m, a, w, entry);
+        self.algorithm_cache.insert(key, Arc::clone(&instance));
+        instance
+    }
+
+    /// Builds `algorithm`, falling back from the rotation and the mixer to Bluestein where
+    /// [`RotationCzt::root`] or [`MixerCzt::design`] rejects the contour.
+    fn build(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        algorithm: Algorithm,
+        length: ConvolutionLength,
+    ) -> Arc<dyn Czt<T>> {
+        match algorithm {
+            Algorithm::Naive => Arc::new(NaiveCzt::new(n, m, a, w)),
+            Algorithm::Goertzel => Arc::new(GoertzelAlgorithm::new(n, m, a, w)),
+            Algorithm::Direct => Arc::new(DirectCzt::new(n, m, a, w)),
+            Algorithm::Mixer if MixerCzt::design(n, m, a, w).is_some() => {
+                Arc::new(MixerCzt::new(n, m, a, w, &mut self.fft_planner))
+            }
+            Algorithm::Rotation => match RotationCzt::root(w, length.for_len(n + m - 1)) {
+                Some(root) => Arc::new(RotationCzt::new(n, m, a, root, &mut self.fft_planner)),
+                None => self.fft_based(n, m, a, w, 0, length),
+            },
+            Algorithm::Mixer | Algorithm::Bluestein => self.fft_based(n, m, a, w, 0, length),
+        }
+    }
+
+    /// Plans bins `k0..k0 + m` of the transform on the contour `a * w^-k`.
+    pub fn plan_czt_forward_with_offset(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+    ) -> Arc<dyn Czt<T>> {
+        self.fft_based(n, m, a, w, k0, self.convolution_length)
+    }
+
+    /// A [`BlockedCzt`] whose out-of-place scratch fits in `max_scratch` elements, for inputs
+    /// too long for one convolution's working set.
+    pub fn plan_czt_forward_blocked(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        max_scratch: usize,
+    ) -> Arc<dyn Czt<T>> {
+        Arc::new(BlockedCzt::new(
+            n,
+            m,
+            a,
+            w,
+            max_scratch,
+            self.convolution_length,
+            &mut self.fft_planner,
+        ))
+    }
 
-        self.plan_czt_forward(n, m, a, w)
+    /// [`fft_based`](Self::fft_based) with `fold` folded into its tables. These plans are
+    /// cached like the others, but are Bluestein-based whatever the strategy.
+    fn fft_based_folded(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        fold: Fold<T>,
+    ) -> Arc<dyn Czt<T>> {
+        let length = self.convolution_length;
+        let key = CztKey::new(n, m, a, w, PlannerStrategy::ForceBluestein, length).folding(fold);
+        if let Some(instance) = self.algorithm_cache.get(&key) {
+            return Arc::clone(instance);
+        }
+
+        let instance: Arc<dyn Czt<T>> = if SpiralCzt::is_needed(n, m, w) {
+            let czt = SpiralCzt::new(n, m, a, w, 0, length, &mut self.fft_planner);
+            Arc::new(fold.spiral(czt))
+        } else {
+            let czt =
+                BluesteinsAlgorithm::new_segment(n, m, a, w, 0, 0, length, &mut self.fft_planner);
+            Arc::new(fold.bluesteins(czt))
+        };
+        self.algorithm_cache.insert(key, Arc::clone(&instance));
+        instance
+    }
+
+    /// Bluestein, or the spiral split where a single convolution would lose accuracy.
+    fn fft_based(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+        length: ConvolutionLength,
+    ) -> Arc<dyn Czt<T>> {
+        if SpiralCzt::is_needed(n, m, w) {
+            Arc::new(SpiralCzt::new(
+                n,
+                m,
+                a,
+                w,
+                k0,
+                length,
+                &mut self.fft_planner,
+            ))
+        } else {
+            Arc::new(BluesteinsAlgorithm::new_segment(
+                n,
+                m,
+                a,
+                w,
+                0,
+                k0,
+                length,
+                &mut self.fft_planner,
+            ))
+        }
+    }
+
+    pub fn plan_czt(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        direction: CztDirection,
+    ) -> Arc<dyn Czt<T>> {
+        match direction {
+            CztDirection::Forward => self.plan_czt_forward(n, m, a, w),
+            CztDirection::Inverse => self.plan_czt_forward(n, m, a.inv(), w.inv()),
+        }
+    }
+
+    pub fn plan_czt_backward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.plan_czt(n, m, a, w, CztDirection::Inverse)
+    }
+
+    pub fn plan_czt_forward_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        window: Window<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.fft_based_folded(n, m, a, w, Fold::Window(window))
+    }
+
+    pub fn plan_czt_forward_normalized(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        normalization: Normalization<T>,
+    ) -> Arc<dyn Czt<T>> {
+        let factor = normalization.factor(n);
+        self.fft_based_folded(n, m, a, w, Fold::OutputScale(factor))
+    }
+
+    pub fn plan_czt_forward_delayed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        delay: T,
+    ) -> Arc<dyn Czt<T>> {
+        self.fft_based_folded(n, m, a, w, Fold::Delay(delay))
+    }
+
+    pub fn plan_czt_inverse_exact(
+        &mut self,
+        n: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        Arc::new(InverseCzt::new(n, a, w, &mut self.fft_planner))
+    }
+
+    pub fn plan_real_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn RealCzt<T>> {
+        match RealCztPacked::find_mirror(m, a, w) {
+            Some(mirror) => {
+                let czt = self.plan_czt_forward(n.div_ceil(2), mirror + 1, a * a, w * w);
+                Arc::new(RealCztPacked::new(n, m, a, w, mirror, czt))
+            }
+            None => Arc::new(RealCztPromoted::new(self.plan_czt_forward(n, m, a, w))),
+        }
+    }
+
+    pub fn plan_czt2_forward(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        a_row: Complex<T>,
+        w_row: Complex<T>,
+        a_col: Complex<T>,
+        w_col: Complex<T>,
+    ) -> Arc<Czt2d<T>> {
+        let row_czt = self.plan_czt_forward(cols, cols, a_row, w_row);
+        let col_czt = self.plan_czt_forward(rows, rows, a_col, w_col);
+        Arc::new(Czt2d::new(row_czt, col_czt))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + FftNum> Default for CztPlannerScalar<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FftNum + Float, P: FftProvider<T>> CztPlannerScalar<T, P> {
+    pub fn plan_zoom_fft(&mut self, czt_len: usize, start: T, end: T) -> Arc<ZoomFft<T>> {
+        self.plan_zoom_fft_with_m(czt_len, czt_len, start, end)
+    }
+
+    /// Plans `m` bins from `start` cycles per sample, spaced so that the first `n` of them span
+    /// `[start, end]` with both ends inclusive. Fewer bins stop short of `end` and more run past
+    /// it. A single input sample gets the one bin spacing `end - start`.
+    pub fn plan_zoom_fft_with_m(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        end: T,
+    ) -> Arc<ZoomFft<T>> {
+        self.plan_zoom_fft_windowed(n, m, start, end, Window::Rectangular)
+    }
+
+    /// [`plan_zoom_fft_with_m`](Self::plan_zoom_fft_with_m) with `window` folded into the plan.
+    pub fn plan_zoom_fft_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        let step = (end - start) / T::from_usize(n.max(2) - 1).unwrap();
+
+        self.plan_zoom(n, m, start, step, T::one(), window)
+    }
+
+    /// Plans `m` bins evenly spanning `[f_start, f_end]` Hz, both ends inclusive.
+    pub fn plan_zoom_fft_hz(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+    ) -> Arc<ZoomFft<T>> {
+        self.plan_zoom_fft_hz_windowed(n, m, sample_rate, f_start, f_end, Window::Rectangular)
+    }
+
+    pub fn plan_zoom_fft_hz_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        let zero = T::zero();
+        let nyquist = sample_rate / T::from_f64(2.0).unwrap();
+
+        assert!(sample_rate > zero, "sample rate must be positive");
+        assert!(
+            -nyquist <= f_start && f_end <= nyquist,
+            "zoom band must lie within [-fs/2, fs/2]"
+        );
+        assert!(f_start <= f_end, "f_start must not exceed f_end");
+
+        let start = f_start / sample_rate;
+        let step = if m > 1 {
+            (f_end - f_start) / sample_rate / T::from_usize(m - 1).unwrap()
+        } else {
+            zero
+        };
+
+        self.plan_zoom(n, m, start, step, sample_rate, window)
+    }
+
+    /// Plans a [`Spectrogram`] of `m` bins spanning `[f_start, f_end]` Hz over frames of
+    /// `frame_len` samples, `hop` samples apart.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_spectrogram(
+        &mut self,
+        frame_len: usize,
+        hop: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Spectrogram<T> {
+        let zoom =
+            self.plan_zoom_fft_hz_windowed(frame_len, m, sample_rate, f_start, f_end, window);
+        Spectrogram::new(zoom, hop)
+    }
+
+    /// Plans a Welch [`ZoomPsd`] of `m` bins spanning `[f_start, f_end]` Hz over segments of
+    /// `segment_len` samples that overlap by `overlap` samples.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_zoom_psd(
+        &mut self,
+        segment_len: usize,
+        overlap: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> ZoomPsd<T> {
+        assert!(
+            overlap < segment_len,
+            "overlap must be shorter than a segment"
+        );
+        let zoom =
+            self.plan_zoom_fft_hz_windowed(segment_len, m, sample_rate, f_start, f_end, window);
+        ZoomPsd::new(
+            zoom,
+            segment_len - overlap,
+            &window.coefficients(segment_len),
+        )
+    }
+
+    /// Plans a [`RangeDoppler`] map of `velocity_bins` velocities spanning `[v_min, v_max]` m/s.
+    /// `window` is applied along both fast and slow time.
+    pub fn plan_range_doppler(
+        &mut self,
+        config: FmcwConfig<T>,
+        v_min: T,
+        v_max: T,
+        velocity_bins: usize,
+        window: Window<T>,
+    ) -> RangeDoppler<T> {
+        let samples = config.samples_per_chirp;
+        let doppler_per_velocity = T::from_f64(2.0).unwrap() / config.wavelength();
+        let doppler = self.plan_zoom_fft_hz_windowed(
+            config.chirps,
+            velocity_bins,
+            config.chirp_interval.recip(),
+            v_min * doppler_per_velocity,
+            v_max * doppler_per_velocity,
+            window,
+        );
+        let range_fft = self.fft_planner.plan_forward(samples);
+        RangeDoppler::new(config, range_fft, window.coefficients(samples), doppler)
+    }
+
+    fn plan_zoom(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        step: T,
+        sample_rate: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        let one = T::from_f64(1.0).unwrap();
+        let two_pi = T::from_f64(core::f64::consts::PI * 2.0).unwrap();
+        let a = Complex::from_polar(one, two_pi * start);
+        let w = Complex::from_polar(one, -two_pi * step);
+
+        let czt = match window {
+            Window::Rectangular => self.plan_czt_forward(n, m, a, w),
+            window => self.plan_czt_forward_windowed(n, m, a, w, window),
+        };
+        Arc::new(ZoomFft::new(czt, start, step, sample_rate))
+    }
+}
+
+/// Runs `plan_czt_forward` and `plan_czt` as single-precision Bluestein pipelines on a wgpu
+/// device. Naive plans and every other plan kind are built by the wrapped scalar planner.
+#[cfg(feature = "gpu")]
+pub struct CztPlannerGpu<T: Float + FftNum, P: FftProvider<T> = DefaultFftProvider<T>> {
+    context: Arc<GpuContext>,
+    scalar_planner: CztPlannerScalar<T, P>,
+    algorithm_cache: BTreeMap<CztKey, Arc<dyn Czt<T>>>,
+}
+
+#[cfg(feature = "gpu")]
+impl<T: Float + FftNum> CztPlannerGpu<T> {
+    pub fn new() -> Option<Self> {
+        Self::with_fft_provider(FftPlanner::new())
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl<T: Float + FftNum, P: FftProvider<T>> CztPlannerGpu<T, P> {
+    /// Returns `None` unless `T` is `f32` and a GPU adapter is available.
+    pub fn with_fft_provider(fft_provider: P) -> Option<Self> {
+        if core::any::TypeId::of::<T>() != core::any::TypeId::of::<f32>() {
+            return None;
+        }
+
+        Some(Self {
+            context: Arc::new(GpuContext::new()?),
+            scalar_planner: CztPlannerScalar::with_fft_provider(fft_provider),
+            algorithm_cache: BTreeMap::new(),
+        })
+    }
+
+    pub fn strategy(&self) -> PlannerStrategy {
+        self.scalar_planner.strategy()
+    }
+
+    pub fn set_strategy(&mut self, strategy: PlannerStrategy) {
+        self.scalar_planner.set_strategy(strategy);
+    }
+
+    pub fn convolution_length(&self) -> ConvolutionLength {
+        self.scalar_planner.convolution_length()
+    }
+
+    /// Only affects plans delegated to the CPU; the GPU kernels always use powers of two.
+    pub fn set_convolution_length(&mut self, length: ConvolutionLength) {
+        self.scalar_planner.set_convolution_length(length);
+    }
+
+    pub fn wisdom(&self) -> &Wisdom {
+        self.scalar_planner.wisdom()
+    }
+
+    /// Only affects plans delegated to the CPU.
+    pub fn import_wisdom(&mut self, wisdom: &Wisdom) {
+        self.scalar_planner.import_wisdom(wisdom);
+    }
+
+    /// Measures the CPU algorithms only; the result runs on the CPU.
+    #[cfg(feature = "std")]
+    pub fn plan_czt_forward_measured(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner.plan_czt_forward_measured(n, m, a, w)
+    }
+
+    pub fn plan_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        if self.strategy() == PlannerStrategy::ForceNaive {
+            return self.scalar_planner.plan_czt_forward(n, m, a, w);
+        }
+
+        let key = CztKey::new(n, m, a, w, self.strategy(), self.convolution_length());
+        if let Some(instance) = self.algorithm_cache.get(&key) {
+            return Arc::clone(instance);
+        }
+
+        let instance: Arc<dyn Czt<f32>> = Arc::new(GpuBluesteins::new(
+            Arc::clone(&self.context),
+            n,
+            m,
+            to_f64(a),
+            to_f64(w),
+        ));
+        // The constructor only admits f32, so this downcast cannot fail.
+        let instance: Box<dyn core::any::Any> = Box::new(instance);
+        let instance = *instance.downcast::<Arc<dyn Czt<T>>>().unwrap();
+
+        self.algorithm_cache.insert(key, Arc::clone(&instance));
+        instance
+    }
+
+    pub fn plan_czt_forward_with_offset(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner
+            .plan_czt_forward_with_offset(n, m, a, w, k0)
+    }
+
+    /// Runs on the CPU; the GPU kernels need the whole convolution in memory.
+    pub fn plan_czt_forward_blocked(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        max_scratch: usize,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner
+            .plan_czt_forward_blocked(n, m, a, w, max_scratch)
+    }
+
+    pub fn plan_czt(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        direction: CztDirection,
+    ) -> Arc<dyn Czt<T>> {
+        match direction {
+            CztDirection::Forward => self.plan_czt_forward(n, m, a, w),
+            CztDirection::Inverse => self.plan_czt_forward(n, m, a.inv(), w.inv()),
+        }
+    }
+
+    pub fn plan_czt_forward_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        window: Window<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner
+            .plan_czt_forward_windowed(n, m, a, w, window)
+    }
+
+    pub fn plan_czt_forward_normalized(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        normalization: Normalization<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner
+            .plan_czt_forward_normalized(n, m, a, w, normalization)
+    }
+
+    pub fn plan_czt_forward_delayed(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        delay: T,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner
+            .plan_czt_forward_delayed(n, m, a, w, delay)
+    }
+
+    pub fn plan_czt_inverse_exact(
+        &mut self,
+        n: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn Czt<T>> {
+        self.scalar_planner.plan_czt_inverse_exact(n, a, w)
+    }
+
+    pub fn plan_real_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Arc<dyn RealCzt<T>> {
+        self.scalar_planner.plan_real_czt_forward(n, m, a, w)
+    }
+
+    pub fn plan_czt2_forward(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        a_row: Complex<T>,
+        w_row: Complex<T>,
+        a_col: Complex<T>,
+        w_col: Complex<T>,
+    ) -> Arc<Czt2d<T>> {
+        self.scalar_planner
+            .plan_czt2_forward(rows, cols, a_row, w_row, a_col, w_col)
+    }
+
+    pub fn plan_zoom_fft(&mut self, czt_len: usize, start: T, end: T) -> Arc<ZoomFft<T>> {
+        self.scalar_planner.plan_zoom_fft(czt_len, start, end)
+    }
+
+    pub fn plan_zoom_fft_with_m(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        end: T,
+    ) -> Arc<ZoomFft<T>> {
+        self.scalar_planner.plan_zoom_fft_with_m(n, m, start, end)
+    }
+
+    pub fn plan_zoom_fft_hz(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+    ) -> Arc<ZoomFft<T>> {
+        self.scalar_planner
+            .plan_zoom_fft_hz(n, m, sample_rate, f_start, f_end)
+    }
+
+    pub fn plan_zoom_fft_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        start: T,
+        end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        self.scalar_planner
+            .plan_zoom_fft_windowed(n, m, start, end, window)
+    }
+
+    pub fn plan_zoom_fft_hz_windowed(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Arc<ZoomFft<T>> {
+        self.scalar_planner
+            .plan_zoom_fft_hz_windowed(n, m, sample_rate, f_start, f_end, window)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_spectrogram(
+        &mut self,
+        frame_len: usize,
+        hop: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> Spectrogram<T> {
+        self.scalar_planner
+            .plan_spectrogram(frame_len, hop, m, sample_rate, f_start, f_end, window)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_zoom_psd(
+        &mut self,
+        segment_len: usize,
+        overlap: usize,
+        m: usize,
+        sample_rate: T,
+        f_start: T,
+        f_end: T,
+        window: Window<T>,
+    ) -> ZoomPsd<T> {
+        self.scalar_planner.plan_zoom_psd(
+            segment_len,
+            overlap,
+            m,
+            sample_rate,
+            f_start,
+            f_end,
+            window,
+        )
+    }
+
+    pub fn plan_range_doppler(
+        &mut self,
+        config: FmcwConfig<T>,
+        v_min: T,
+        v_max: T,
+        velocity_bins: usize,
+        window: Window<T>,
+    ) -> RangeDoppler<T> {
+        self.scalar_planner
+            .plan_range_doppler(config, v_min, v_max, velocity_bins, window)
     }
 }
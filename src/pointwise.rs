@@ -0,0 +1,184 @@
+//! The pointwise complex multiplies around the convolution FFTs.
+//!
+//! `f64` and `f32` run explicit `wide` kernels on planar copies of each chunk, four complex
+//! numbers per `f64x4` and eight per `f32x8`, which map onto SSE or Neon registers; other types
+//! run the scalar loop. On x86_64 the kernels are additionally compiled with AVX and FMA enabled
+//! and that copy is picked at runtime when the CPU supports it. Detection needs `std`; without it
+//! the baseline copy is always used. The products are rounded exactly like `Complex`'s `*`.
+
+use core::any::TypeId;
+
+use crate::fft::{num_complex::Complex, FftNum};
+use wide::{f32x8, f64x4};
+
+macro_rules! dispatch {
+    ($vector:ident, $avx:ident ($($arg:expr),*)) => {{
+        #[cfg(all(feature = "std", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx") && is_x86_feature_detected!("fma") {
+                return unsafe { $avx($($arg),*) };
+            }
+        }
+        $vector($($arg),*)
+    }};
+}
+
+/// `output[i] = input[i] * coefficients[i]`
+pub fn multiply<T: FftNum>(
+    output: &mut [Complex<T>],
+    input: &[Complex<T>],
+    coefficients: &[Complex<T>],
+) {
+    dispatch!(multiply_vector, multiply_avx(output, input, coefficients))
+}
+
+/// `buffer[i] = buffer[i] * coefficients[i]`
+pub fn multiply_in_place<T: FftNum>(buffer: &mut [Complex<T>], coefficients: &[Complex<T>]) {
+    dispatch!(
+        multiply_in_place_vector,
+        multiply_in_place_avx(buffer, coefficients)
+    )
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx,fma")]
+unsafe fn multiply_avx<T: FftNum>(
+    output: &mut [Complex<T>],
+    input: &[Complex<T>],
+    coefficients: &[Complex<T>],
+) {
+    multiply_vector(output, input, coefficients)
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx,fma")]
+unsafe fn multiply_in_place_avx<T: FftNum>(buffer: &mut [Complex<T>], coefficients: &[Complex<T>]) {
+    multiply_in_place_vector(buffer, coefficients)
+}
+
+/// Runs the vector kernel for `f64` and `f32`, and the scalar loop for other types.
+#[inline(always)]
+fn multiply_vector<T: FftNum>(
+    output: &mut [Complex<T>],
+    input: &[Complex<T>],
+    coefficients: &[Complex<T>],
+) {
+    // SAFETY: each branch only reinterprets `T` as the type it was just compared equal to.
+    unsafe {
+        if TypeId::of::<T>() == TypeId::of::<f64>() {
+            return multiply_f64(cast_mut(output), cast(input), cast(coefficients));
+        }
+        if TypeId::of::<T>() == TypeId::of::<f32>() {
+            return multiply_f32(cast_mut(output), cast(input), cast(coefficients));
+        }
+    }
+    multiply_generic(output, input, coefficients)
+}
+
+/// Runs the vector kernel for `f64` and `f32`, and the scalar loop for other types.
+#[inline(always)]
+fn multiply_in_place_vector<T: FftNum>(buffer: &mut [Complex<T>], coefficients: &[Complex<T>]) {
+    // SAFETY: as in `multiply_vector`.
+    unsafe {
+        if TypeId::of::<T>() == TypeId::of::<f64>() {
+            return multiply_in_place_f64(cast_mut(buffer), cast(coefficients));
+        }
+        if TypeId::of::<T>() == TypeId::of::<f32>() {
+            return multiply_in_place_f32(cast_mut(buffer), cast(coefficients));
+        }
+    }
+    multiply_in_place_generic(buffer, coefficients)
+}
+
+/// Views a slice as one of `U`, which the caller guarantees to be the same type as `T`.
+pub(crate) unsafe fn cast<T, U>(slice: &[T]) -> &[U] {
+    core::slice::from_raw_parts(slice.as_ptr().cast(), slice.len())
+}
+
+/// Like [`cast`], for a mutable slice.
+unsafe fn cast_mut<T, U>(slice: &mut [T]) -> &mut [U] {
+    core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len())
+}
+
+#[inline(always)]
+fn multiply_generic<T: FftNum>(
+    output: &mut [Complex<T>],
+    input: &[Complex<T>],
+    coefficients: &[Complex<T>],
+) {
+    for ((y, &x), &coeff) in output.iter_mut().zip(input).zip(coefficients) {
+        *y = x * coeff;
+    }
+}
+
+#[inline(always)]
+fn multiply_in_place_generic<T: FftNum>(buffer: &mut [Complex<T>], coefficients: &[Complex<T>]) {
+    for (y, &v) in buffer.iter_mut().zip(coefficients) {
+        *y = *y * v;
+    }
+}
+
+/// Defines the two kernels for one float type: whole chunks of `$lanes` products are computed in
+/// planar vectors, the remainder by the scalar loop.
+macro_rules! vector_kernels {
+    ($multiply:ident, $multiply_in_place:ident, $float:ty, $vector:ty, $lanes:literal) => {
+        #[inline(always)]
+        fn $multiply(
+            output: &mut [Complex<$float>],
+            input: &[Complex<$float>],
+            coefficients: &[Complex<$float>],
+        ) {
+            let len = output.len().min(input.len()).min(coefficients.len());
+            let whole = len - len % $lanes;
+            for ((y, x), v) in output[..whole]
+                .chunks_exact_mut($lanes)
+                .zip(input.chunks_exact($lanes))
+                .zip(coefficients.chunks_exact($lanes))
+            {
+                y.copy_from_slice(&product!($vector, $lanes, x, v));
+            }
+            multiply_generic(
+                &mut output[whole..len],
+                &input[whole..len],
+                &coefficients[whole..len],
+            );
+        }
+
+        #[inline(always)]
+        fn $multiply_in_place(buffer: &mut [Complex<$float>], coefficients: &[Complex<$float>]) {
+            let len = buffer.len().min(coefficients.len());
+            let whole = len - len % $lanes;
+            for (y, v) in buffer[..whole]
+                .chunks_exact_mut($lanes)
+                .zip(coefficients.chunks_exact($lanes))
+            {
+                let product = product!($vector, $lanes, y, v);
+                y.copy_from_slice(&product);
+            }
+            multiply_in_place_generic(&mut buffer[whole..len], &coefficients[whole..len]);
+        }
+    };
+}
+
+/// `x[i] * v[i]` for one chunk, as `re = x.re v.re - x.im v.im` and
+/// `im = x.re v.im + x.im v.re` without fused multiply-adds.
+macro_rules! product {
+    ($vector:ty, $lanes:literal, $x:expr, $v:expr) => {{
+        let (x, v) = (&$x, &$v);
+        let planar = |z: &[Complex<_>]| {
+            (
+                <$vector>::new(core::array::from_fn(|lane| z[lane].re)),
+                <$vector>::new(core::array::from_fn(|lane| z[lane].im)),
+            )
+        };
+        let ((x_re, x_im), (v_re, v_im)) = (planar(x), planar(v));
+        let re = (x_re * v_re - x_im * v_im).to_array();
+        let im = (x_re * v_im + x_im * v_re).to_array();
+        let product: [Complex<_>; $lanes] =
+            core::array::from_fn(|lane| Complex::new(re[lane], im[lane]));
+        product
+    }};
+}
+
+vector_kernels!(multiply_f64, multiply_in_place_f64, f64, f64x4, 4);
+vector_kernels!(multiply_f32, multiply_in_place_f32, f32, f32x8, 8);
@@ -0,0 +1,33 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{CztPlanner, FftProvider};
+
+/// Evaluates `p(x) = coeffs[0] + coeffs[1] x + ... + coeffs[n-1] x^(n-1)` at the `m` points
+/// `x_k = a w^-k`, in `O((n + m) log(n + m))`.
+///
+/// Coefficients are in ascending powers, as in `numpy.polynomial` and the reverse of
+/// `numpy.polyval`. The points follow the CZT's own `a w^-k`, but the CZT sums powers of
+/// `z_k^-1`: this is the CZT of `coeffs` with parameters `1 / a` and `1 / w`.
+pub fn evaluate_poly<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    coeffs: &[Complex<T>],
+    a: Complex<T>,
+    w: Complex<T>,
+    m: usize,
+) -> Vec<Complex<T>> {
+    let mut values = vec![Complex::zero(); m];
+    if coeffs.is_empty() || m == 0 {
+        return values;
+    }
+
+    let czt = planner.plan_czt_forward(coeffs.len(), m, a.inv(), w.inv());
+    let mut scratch = vec![Complex::zero(); czt.get_outofplace_scratch_len()];
+    czt.process_outofplace_with_scratch(coeffs, &mut values, &mut scratch);
+    values
+}
@@ -0,0 +1,183 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::num_complex::Complex;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{compat::scipy, Czt, CztPlanner};
+
+type Bins<'py> = PyResult<Bound<'py, PyArray1<Complex<f64>>>>;
+
+/// Copies a 1-D complex128 array of `len` samples out of NumPy, so the transform can run with
+/// the GIL released.
+fn samples(x: PyReadonlyArray1<Complex<f64>>, len: Option<usize>) -> PyResult<Vec<Complex<f64>>> {
+    let x = x.as_array().to_vec();
+    match len {
+        Some(len) if x.len() != len => Err(PyValueError::new_err(format!(
+            "x must have length {len}, got {}",
+            x.len()
+        ))),
+        None if x.is_empty() => Err(PyValueError::new_err("x must not be empty")),
+        _ => Ok(x),
+    }
+}
+
+fn positive(name: &str, value: Option<usize>) -> PyResult<()> {
+    match value {
+        Some(0) => Err(PyValueError::new_err(format!("{name} must be positive"))),
+        _ => Ok(()),
+    }
+}
+
+/// The `m` bins of `x` on `czt`, zero-padding the buffer to the plan's length.
+fn transform(czt: &dyn Czt<f64>, mut buffer: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+    buffer.resize(czt.get_buffer_len(), Complex::default());
+    czt.process(&mut buffer);
+    buffer.truncate(czt.get_output_len());
+    buffer
+}
+
+/// `czt(x, m=None, w=None, a=None)`: `scipy.signal.czt` of a 1-D complex128 array.
+#[pyfunction]
+#[pyo3(signature = (x, m=None, w=None, a=None))]
+fn czt<'py>(
+    py: Python<'py>,
+    x: PyReadonlyArray1<Complex<f64>>,
+    m: Option<usize>,
+    w: Option<Complex<f64>>,
+    a: Option<Complex<f64>>,
+) -> Bins<'py> {
+    let x = samples(x, None)?;
+    positive("m", m)?;
+    let bins = py.detach(|| scipy::czt(&x, m, w, a));
+    Ok(bins.into_pyarray(py))
+}
+
+/// SciPy's `fn`: a `(f1, f2)` band, or `f2` for `(0, f2)`.
+#[derive(FromPyObject)]
+enum Band {
+    Range(f64, f64),
+    Upper(f64),
+}
+
+/// `ZoomFFT(n, fn, m=None, fs=2.0, endpoint=False)`: `scipy.signal.ZoomFFT`, called on 1-D
+/// complex128 arrays of `n` samples.
+#[pyclass(name = "ZoomFFT", frozen)]
+struct PyZoomFft {
+    zoom: scipy::ZoomFft<f64>,
+    n: usize,
+}
+
+#[pymethods]
+impl PyZoomFft {
+    #[new]
+    #[pyo3(signature = (n, r#fn, m=None, fs=2.0, endpoint=false))]
+    fn new(n: usize, r#fn: Band, m: Option<usize>, fs: f64, endpoint: bool) -> PyResult<Self> {
+        positive("n", Some(n))?;
+        positive("m", m)?;
+        let band = match r#fn {
+            Band::Range(f1, f2) => (f1, f2),
+            Band::Upper(f2) => (0.0, f2),
+        };
+        if !(band.0 <= band.1 && fs > 0.0) {
+            return Err(PyValueError::new_err(
+                "fn must be an increasing band and fs positive",
+            ));
+        }
+        Ok(Self {
+            zoom: scipy::ZoomFft::new(n, band, m, fs, endpoint),
+            n,
+        })
+    }
+
+    fn __call__<'py>(&self, py: Python<'py>, x: PyReadonlyArray1<Complex<f64>>) -> Bins<'py> {
+        let x = samples(x, Some(self.n))?;
+        let bins = py.detach(|| self.zoom.call(&x));
+        Ok(bins.into_pyarray(py))
+    }
+
+    fn points<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<Complex<f64>>> {
+        self.zoom.points().into_pyarray(py)
+    }
+
+    /// Frequency of each bin, in the units of `fs`.
+    fn freqs<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        self.zoom.freqs().into_pyarray(py)
+    }
+}
+
+/// A plan from [`PyCztPlanner`], exported as `Czt`.
+#[pyclass(name = "Czt", frozen)]
+struct PyCzt(Arc<dyn Czt<f64>>);
+
+#[pymethods]
+impl PyCzt {
+    #[getter]
+    fn input_len(&self) -> usize {
+        self.0.get_input_len()
+    }
+
+    #[getter]
+    fn output_len(&self) -> usize {
+        self.0.get_output_len()
+    }
+
+    /// The `output_len` bins of `input_len` samples, computed with the GIL released.
+    fn process<'py>(&self, py: Python<'py>, x: PyReadonlyArray1<Complex<f64>>) -> Bins<'py> {
+        let x = samples(x, Some(self.0.get_input_len()))?;
+        let bins = py.detach(|| transform(self.0.as_ref(), x));
+        Ok(bins.into_pyarray(py))
+    }
+}
+
+/// [`CztPlanner`], exported as `CztPlanner`: plans are cached, so planning the same transform
+/// twice returns the same plan.
+#[pyclass(name = "CztPlanner", unsendable)]
+struct PyCztPlanner(CztPlanner<f64>);
+
+#[pymethods]
+impl PyCztPlanner {
+    #[new]
+    fn new() -> Self {
+        Self(CztPlanner::new())
+    }
+
+    /// `n` samples to `m` bins on the contour `a * w^-k`.
+    fn plan_czt_forward(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<f64>,
+        w: Complex<f64>,
+    ) -> PyResult<PyCzt> {
+        positive("n", Some(n))?;
+        positive("m", Some(m))?;
+        Ok(PyCzt(self.0.plan_czt_forward(n, m, a, w)))
+    }
+
+    /// `n` samples at `sample_rate` to `m` bins from `f_start` to `f_end` Hz, both included.
+    fn plan_zoom_fft_hz(
+        &mut self,
+        n: usize,
+        m: usize,
+        sample_rate: f64,
+        f_start: f64,
+        f_end: f64,
+    ) -> PyResult<PyCzt> {
+        positive("n", Some(n))?;
+        positive("m", Some(m))?;
+        let zoom = self.0.plan_zoom_fft_hz(n, m, sample_rate, f_start, f_end);
+        Ok(PyCzt(zoom))
+    }
+}
+
+/// The `rustczt._rustczt` extension module, which the `rustczt` Python package re-exports.
+#[pymodule]
+#[pyo3(name = "_rustczt")]
+pub fn extension_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(czt, module)?)?;
+    module.add_class::<PyZoomFft>()?;
+    module.add_class::<PyCzt>()?;
+    module.add_class::<PyCztPlanner>()?;
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    Fft, FftNum,
+};
+
+use crate::{zoom::ZoomFft, Czt};
+
+/// Speed of light in m/s.
+const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// Parameters of an FMCW chirp sequence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FmcwConfig<T> {
+    /// ADC samples in each chirp (fast time).
+    pub samples_per_chirp: usize,
+    /// Chirps in each frame (slow time).
+    pub chirps: usize,
+    /// ADC sample rate in Hz.
+    pub sample_rate: T,
+    /// Frequency slope of the chirp in Hz/s.
+    pub slope: T,
+    /// Time from the start of one chirp to the start of the next in s.
+    pub chirp_interval: T,
+    /// Carrier frequency in Hz.
+    pub carrier: T,
+}
+
+impl<T: Float + FftNum> FmcwConfig<T> {
+    /// Carrier wavelength in m.
+    pub fn wavelength(&self) -> T {
+        T::from_f64(SPEED_OF_LIGHT).unwrap() / self.carrier
+    }
+}
+
+/// Range–Doppler map of an FMCW frame: an FFT over the samples of every chirp gives range, and a
+/// zoom CZT across chirps evaluates only the velocities of interest.
+///
+/// Input frames are row-major with one row of `samples_per_chirp` complex samples per chirp. The
+/// output is row-major with one row of `velocity_bins()` values per range bin.
+///
+/// Range bin `k` is the beat frequency `k * sample_rate / samples_per_chirp`, in
+/// `[0, sample_rate)` as for complex baseband ADCs; keep the first half for real ADC data.
+/// Velocity `v` appears at the Doppler frequency `2 v carrier / c` of the slow-time phase
+/// `e^(2 pi i f t)`.
+pub struct RangeDoppler<T: FftNum> {
+    config: FmcwConfig<T>,
+    range_fft: Arc<dyn Fft<T>>,
+    /// Fast-time window.
+    window: Vec<T>,
+    /// Slow-time zoom over Doppler frequency, with the window folded in.
+    doppler: Arc<ZoomFft<T>>,
+}
+
+impl<T: FftNum + Float> RangeDoppler<T> {
+    /// `range_fft` must be a forward FFT of `samples_per_chirp` points and `doppler` a zoom plan
+    /// in Hz over `chirps` samples at the chirp rate. `window` is applied along fast time.
+    pub fn new(
+        config: FmcwConfig<T>,
+        range_fft: Arc<dyn Fft<T>>,
+        window: Vec<T>,
+        doppler: Arc<ZoomFft<T>>,
+    ) -> Self {
+        assert_eq!(range_fft.len(), config.samples_per_chirp);
+        assert_eq!(window.len(), config.samples_per_chirp);
+        assert_eq!(doppler.get_input_len(), config.chirps);
+        Self {
+            config,
+            range_fft,
+            window,
+            doppler,
+        }
+    }
+
+    pub fn config(&self) -> FmcwConfig<T> {
+        self.config
+    }
+
+    pub fn range_bins(&self) -> usize {
+        self.config.samples_per_chirp
+    }
+
+    pub fn velocity_bins(&self) -> usize {
+        self.doppler.get_output_len()
+    }
+
+    /// Spacing of the range bins in m, `c / (2 * bandwidth)` of the sampled sweep.
+    pub fn range_resolution(&self) -> T {
+        let c = T::from_f64(SPEED_OF_LIGHT).unwrap();
+        let n = T::from_usize(self.config.samples_per_chirp).unwrap();
+        let two = T::from_f64(2.0).unwrap();
+        c * self.config.sample_rate / (two * self.config.slope * n)
+    }
+
+    /// Spacing of the velocity bins in m/s.
+    pub fn velocity_resolution(&self) -> T {
+        self.doppler.bin_width() * self.half_wavelength()
+    }
+
+    /// Range of every range bin in m.
+    pub fn ranges(&self) -> Vec<T> {
+        let resolution = self.range_resolution();
+        (0..self.range_bins())
+            .map(|k| T::from_usize(k).unwrap() * resolution)
+            .collect()
+    }
+
+    /// Radial velocity of every velocity bin in m/s.
+    pub fn velocities(&self) -> Vec<T> {
+        let half_wavelength = self.half_wavelength();
+        self.doppler
+            .freqs()
+            .into_iter()
+            .map(|f| f * half_wavelength)
+            .collect()
+    }
+
+    fn half_wavelength(&self) -> T {
+        self.config.wavelength() / T::from_f64(2.0).unwrap()
+    }
+
+    pub fn process(&self, frame: &[Complex<T>]) -> Vec<Complex<T>> {
+        let mut map = vec![Complex::zero(); self.range_bins() * self.velocity_bins()];
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(frame, &mut map, &mut scratch);
+        map
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        frame: &[Complex<T>],
+        map: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        let (samples, chirps) = (self.config.samples_per_chirp, self.config.chirps);
+        let bins = self.velocity_bins();
+        assert_eq!(frame.len(), chirps * samples);
+        assert_eq!(map.len(), samples * bins);
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (cube, scratch) = scratch.split_at_mut(chirps * samples);
+        let (column, scratch) = scratch.split_at_mut(chirps);
+        for (row, chirp) in cube
+            .chunks_exact_mut(samples)
+            .zip(frame.chunks_exact(samples))
+        {
+            for ((y, &x), &w) in row.iter_mut().zip(chirp).zip(&self.window) {
+                *y = x * w;
+            }
+        }
+        self.range_fft.process_with_scratch(
+            cube,
+            &mut scratch[..self.range_fft.get_inplace_scratch_len()],
+        );
+
+        let scratch = &mut scratch[..self.doppler.get_outofplace_scratch_len()];
+        for (k, row) in map.chunks_exact_mut(bins).enumerate() {
+            for (y, chirp) in column.iter_mut().zip(cube.chunks_exact(samples)) {
+                *y = chirp[k];
+            }
+            self.doppler
+                .process_outofplace_with_scratch(column, row, scratch);
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        let (samples, chirps) = (self.config.samples_per_chirp, self.config.chirps);
+        let transform_scratch = self
+            .range_fft
+            .get_inplace_scratch_len()
+            .max(self.doppler.get_outofplace_scratch_len());
+        chirps * samples + chirps + transform_scratch
+    }
+}
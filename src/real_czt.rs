@@ -0,0 +1,174 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{Czt, CztParams, RealCzt};
+
+/// Real-input CZT that promotes the samples to complex and runs a full-size plan.
+pub struct RealCztPromoted<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+}
+
+impl<T: FftNum> RealCztPromoted<T> {
+    pub fn new(czt: Arc<dyn Czt<T>>) -> Self {
+        Self { czt }
+    }
+}
+
+impl<T: FftNum> RealCzt<T> for RealCztPromoted<T> {
+    fn process_with_scratch(
+        &self,
+        input: &[T],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.get_input_len());
+        assert_eq!(output.len(), self.get_output_len());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.czt.get_buffer_len());
+        for (y, &x) in buffer.iter_mut().zip(input) {
+            *y = Complex::new(x, T::zero());
+        }
+        buffer[input.len()..].fill(Complex::zero());
+
+        self.czt.process_with_scratch(buffer, scratch);
+        output.copy_from_slice(&buffer[..output.len()]);
+    }
+
+    fn get_scratch_len(&self) -> usize {
+        self.czt.get_buffer_len() + self.czt.get_inplace_scratch_len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        self.czt.get_memory_usage() + self.czt.get_buffer_len() * core::mem::size_of::<Complex<T>>()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.czt.get_input_len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.czt.get_output_len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        self.czt.params()
+    }
+}
+
+/// Real-input CZT for contours whose points come in conjugate pairs, `conj(z_k) = z_(K-k)`.
+///
+/// Even and odd samples are packed into one complex signal of half the length, transformed on the
+/// squared contour `(a^2, w^2)`, and separated again using the conjugate symmetry of the contour.
+pub struct RealCztPacked<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+    twiddles: Vec<Complex<T>>,
+    n: usize,
+    mirror: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> RealCztPacked<T> {
+    /// Returns the mirror index `K` if the contour is conjugate symmetric, otherwise `None`.
+    pub fn find_mirror(m: usize, a: Complex<T>, w: Complex<T>) -> Option<usize> {
+        let tolerance = T::epsilon() * T::from_usize(16 * (m + 1)).unwrap();
+        if Float::abs(w.norm() - T::one()) > tolerance {
+            return None;
+        }
+        [m.saturating_sub(1), m]
+            .into_iter()
+            .find(|&k| (a * w.powi(-(k as i32)) - a.conj()).norm() <= tolerance * a.norm())
+    }
+
+    /// `czt` must be a plan for `(n + 1) / 2` inputs and `mirror + 1` outputs on `(a^2, w^2)`.
+    pub fn new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        mirror: usize,
+        czt: Arc<dyn Czt<T>>,
+    ) -> Self {
+        assert_eq!(czt.get_input_len(), n.div_ceil(2));
+        assert_eq!(czt.get_output_len(), mirror + 1);
+        assert!(mirror + 1 >= m);
+
+        let a_inv = a.inv();
+        let twiddles = (0..m as i32).map(|k| a_inv * w.powi(k)).collect();
+
+        Self {
+            czt,
+            twiddles,
+            n,
+            mirror,
+            a,
+            w,
+        }
+    }
+}
+
+impl<T: FftNum> RealCzt<T> for RealCztPacked<T> {
+    fn process_with_scratch(
+        &self,
+        input: &[T],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.get_input_len());
+        assert_eq!(output.len(), self.get_output_len());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (buffer, scratch) = scratch.split_at_mut(self.czt.get_buffer_len());
+
+        // Pack z_j = x_2j + i x_2j+1
+        let (packed, tail) = buffer.split_at_mut(self.czt.get_input_len());
+        for (z, pair) in packed.iter_mut().zip(input.chunks(2)) {
+            *z = Complex::new(pair[0], pair.get(1).copied().unwrap_or_else(T::zero));
+        }
+        tail.fill(Complex::zero());
+
+        self.czt.process_with_scratch(buffer, scratch);
+
+        // Separate even and odd parts: E_k = (Z_k + conj(Z_K-k)) / 2, O_k = (Z_k - conj(Z_K-k)) / 2i
+        let half = T::from_f64(0.5).unwrap();
+        for (k, (x, &t)) in output.iter_mut().zip(&self.twiddles).enumerate() {
+            let z = buffer[k];
+            let z_mirror = buffer[self.mirror - k].conj();
+            let even = (z + z_mirror) * half;
+            let odd = (z - z_mirror) * Complex::new(T::zero(), -half);
+            *x = even + t * odd;
+        }
+    }
+
+    fn get_scratch_len(&self) -> usize {
+        self.czt.get_buffer_len() + self.czt.get_inplace_scratch_len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        (self.twiddles.len() + self.czt.get_buffer_len()) * core::mem::size_of::<Complex<T>>()
+            + self.czt.get_memory_usage()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.twiddles.len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.twiddles.len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+}
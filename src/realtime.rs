@@ -0,0 +1,187 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::Czt;
+
+/// Splits a plan across a real-time thread, a worker and a reader of the latest spectrum.
+///
+/// The [`SampleProducer`] pushes into a preallocated single-producer single-consumer ring of
+/// `capacity` samples, without locks or allocation, so it can run in an audio or SDR callback.
+/// The [`FrameWorker`] pops frames of `get_input_len()` samples every `hop` samples, runs the
+/// plan into preallocated scratch and publishes each spectrum through a triple buffer, from which
+/// the [`SpectrumReader`] takes the newest one, also without blocking.
+pub fn channel<T: FftNum>(
+    czt: Arc<dyn Czt<T>>,
+    hop: usize,
+    capacity: usize,
+) -> (SampleProducer<T>, FrameWorker<T>, SpectrumReader<T>) {
+    let n = czt.get_input_len();
+    assert!(hop > 0 && hop <= n, "hop must be in 1..=frame length");
+    assert!(capacity > 0);
+
+    let ring = Arc::new(Ring {
+        samples: (0..capacity)
+            .map(|_| UnsafeCell::new(Complex::zero()))
+            .collect(),
+        written: AtomicUsize::new(0),
+        read: AtomicUsize::new(0),
+    });
+    let spectra = Arc::new(TripleBuffer {
+        slots: core::array::from_fn(|_| {
+            UnsafeCell::new(vec![Complex::zero(); czt.get_output_len()])
+        }),
+        middle: AtomicU8::new(1),
+    });
+    (
+        SampleProducer { ring: ring.clone() },
+        FrameWorker {
+            ring,
+            frame: vec![Complex::zero(); n],
+            filled: 0,
+            hop,
+            scratch: vec![Complex::zero(); czt.get_outofplace_scratch_len()],
+            czt,
+            spectra: spectra.clone(),
+            back: 0,
+        },
+        SpectrumReader { spectra, front: 2 },
+    )
+}
+
+struct Ring<T> {
+    samples: Box<[UnsafeCell<Complex<T>>]>,
+    /// Samples pushed and popped so far, wrapping.
+    written: AtomicUsize,
+    read: AtomicUsize,
+}
+
+// SAFETY: the producer only writes slots the consumer has released, and the consumer only reads
+// slots the producer has published, ordered by the release stores of `written` and `read`.
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+/// Three spectra: the writer's back buffer, the reader's front buffer and the one in the middle,
+/// whose index sits in `middle` together with `DIRTY` when it is newer than the front buffer.
+struct TripleBuffer<T> {
+    slots: [UnsafeCell<Vec<Complex<T>>>; 3],
+    middle: AtomicU8,
+}
+
+const DIRTY: u8 = 4;
+
+// SAFETY: the writer and reader each own one slot at a time and exchange them through `middle`.
+unsafe impl<T: Send> Sync for TripleBuffer<T> {}
+
+/// The real-time end: pushes samples without locking or allocating.
+pub struct SampleProducer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T: FftNum> SampleProducer<T> {
+    /// Pushes as many of `samples` as fit, returning how many did; the rest are dropped.
+    pub fn push(&mut self, samples: &[Complex<T>]) -> usize {
+        let ring = &self.ring;
+        let written = ring.written.load(Ordering::Relaxed);
+        let free = ring.samples.len() - written.wrapping_sub(ring.read.load(Ordering::Acquire));
+        let count = samples.len().min(free);
+        for (i, &sample) in samples[..count].iter().enumerate() {
+            let slot = &ring.samples[written.wrapping_add(i) % ring.samples.len()];
+            // SAFETY: the slot is free, see `Ring`.
+            unsafe { *slot.get() = sample };
+        }
+        ring.written
+            .store(written.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Samples that can be pushed without dropping any.
+    pub fn free(&self) -> usize {
+        let ring = &self.ring;
+        ring.samples.len()
+            - ring
+                .written
+                .load(Ordering::Relaxed)
+                .wrapping_sub(ring.read.load(Ordering::Acquire))
+    }
+}
+
+/// Pops frames from the ring, runs the plan and publishes the spectra.
+pub struct FrameWorker<T: FftNum> {
+    ring: Arc<Ring<T>>,
+    czt: Arc<dyn Czt<T>>,
+    frame: Vec<Complex<T>>,
+    /// Valid samples at the start of `frame`.
+    filled: usize,
+    hop: usize,
+    scratch: Vec<Complex<T>>,
+    spectra: Arc<TripleBuffer<T>>,
+    back: u8,
+}
+
+impl<T: FftNum> FrameWorker<T> {
+    /// Transforms every frame the ring holds, returning how many were published.
+    pub fn process(&mut self) -> usize {
+        let mut published = 0;
+        while self.fill() {
+            // SAFETY: the writer owns the back slot.
+            let back = unsafe { &mut *self.spectra.slots[self.back as usize].get() };
+            self.czt
+                .process_outofplace_with_scratch(&self.frame, back, &mut self.scratch);
+            self.back = self
+                .spectra
+                .middle
+                .swap(self.back | DIRTY, Ordering::AcqRel)
+                & !DIRTY;
+            published += 1;
+
+            self.frame.copy_within(self.hop.., 0);
+            self.filled = self.frame.len() - self.hop;
+        }
+        published
+    }
+
+    /// Pops samples into the frame, returning whether it is full.
+    fn fill(&mut self) -> bool {
+        let ring = &self.ring;
+        let read = ring.read.load(Ordering::Relaxed);
+        let available = ring.written.load(Ordering::Acquire).wrapping_sub(read);
+        let count = available.min(self.frame.len() - self.filled);
+        for (i, sample) in self.frame[self.filled..self.filled + count]
+            .iter_mut()
+            .enumerate()
+        {
+            let slot = &ring.samples[read.wrapping_add(i) % ring.samples.len()];
+            // SAFETY: the slot was published, see `Ring`.
+            *sample = unsafe { *slot.get() };
+        }
+        ring.read.store(read.wrapping_add(count), Ordering::Release);
+        self.filled += count;
+        self.filled == self.frame.len()
+    }
+}
+
+/// Reads the newest published spectrum without blocking the worker.
+pub struct SpectrumReader<T> {
+    spectra: Arc<TripleBuffer<T>>,
+    front: u8,
+}
+
+impl<T: FftNum> SpectrumReader<T> {
+    /// Whether a spectrum newer than the last one returned by [`latest`](Self::latest) exists.
+    pub fn has_update(&self) -> bool {
+        self.spectra.middle.load(Ordering::Relaxed) & DIRTY != 0
+    }
+
+    /// The newest spectrum, all zeros before the first frame.
+    pub fn latest(&mut self) -> &[Complex<T>] {
+        if self.has_update() {
+            self.front = self.spectra.middle.swap(self.front, Ordering::AcqRel) & !DIRTY;
+        }
+        // SAFETY: the reader owns the front slot.
+        unsafe { &*self.spectra.slots[self.front as usize].get() }
+    }
+}
@@ -0,0 +1,93 @@
+//! Ground truth for testing CZT implementations.
+//!
+//! Every bin is evaluated directly in [`DoubleDouble`] arithmetic, so sums and contour points
+//! carry about 106 bits and are rounded to `T` only once. This is `O(n m)` and far slower than
+//! [`NaiveCzt`](crate::naive_czt::NaiveCzt), whose `T` arithmetic loses accuracy as `n` and `m`
+//! grow; use it to produce expected values, not spectra.
+
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, One, Zero},
+    FftNum,
+};
+
+use crate::{double_double::DoubleDouble, Czt, CztParams};
+
+/// `sum_j x_j (a w^-k)^-j` for `k < m`, with `n = input.len()`.
+pub fn czt<T: FftNum + Float>(
+    input: &[Complex<T>],
+    m: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+) -> Vec<Complex<T>> {
+    let input: Vec<_> = input.iter().map(|&x| to_double_double(x)).collect();
+    let (a_inv, w) = (to_double_double(a).inv(), to_double_double(w));
+
+    // `z_k^-1 = a^-1 w^k`, and Horner's rule in `z_k^-1` sums the bin without forming powers.
+    let mut w_power = Complex::<DoubleDouble>::one();
+    (0..m)
+        .map(|_| {
+            let z_inv = a_inv * w_power;
+            w_power = w_power * w;
+            let sum = input
+                .iter()
+                .rev()
+                .fold(Complex::<DoubleDouble>::zero(), |sum, &x| sum * z_inv + x);
+            Complex::new(
+                T::from_f64(sum.re.to_f64()).unwrap(),
+                T::from_f64(sum.im.to_f64()).unwrap(),
+            )
+        })
+        .collect()
+}
+
+fn to_double_double<T: Float>(z: Complex<T>) -> Complex<DoubleDouble> {
+    Complex::new(z.re.to_f64().unwrap().into(), z.im.to_f64().unwrap().into())
+}
+
+/// [`czt`] as a plan, to compare against other plans through the [`Czt`] interface.
+pub struct ReferenceCzt<T: FftNum> {
+    n: usize,
+    m: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> ReferenceCzt<T> {
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        Self { n, m, a, w }
+    }
+}
+
+impl<T: FftNum + Float> Czt<T> for ReferenceCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let output = czt(&buffer[..self.n], self.m, self.a, self.w);
+        buffer[..self.m].copy_from_slice(&output);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        0
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.m,
+            a: self.a,
+            w: self.w,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{Czt, CztPlanner, FftProvider};
+
+/// Bins in every zoom stage. Each stage spans four bins of the previous one, so the resolution
+/// improves by a factor of `(STAGE_BINS - 1) / 4` per stage.
+const STAGE_BINS: usize = 16;
+
+/// Stops refinement once the span no longer shrinks in floating point.
+const MAX_STAGES: usize = 64;
+
+/// Result of [`refine_frequency`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrequencyEstimate<T> {
+    /// Frequency of the spectral peak in Hz.
+    pub frequency: T,
+    /// Bin spacing of the last zoom stage in Hz.
+    pub resolution: T,
+    /// `|X(f)|^2 / (n * sum |x_j|^2)`: the share of the signal's energy explained by a single
+    /// complex exponential at `frequency`, between 0 and 1.
+    pub confidence: T,
+    /// Number of zoom stages that were evaluated.
+    pub stages: usize,
+}
+
+/// Locates the spectral peak near `coarse_f` Hz to within `target_resolution` Hz.
+///
+/// The first stage zooms two DFT bins either side of `coarse_f`; every following stage zooms two
+/// bins of the previous stage either side of its largest bin, until the bin spacing drops to
+/// `target_resolution`. The signal is not windowed.
+pub fn refine_frequency<T: FftNum + Float, P: FftProvider<T>>(
+    planner: &mut CztPlanner<T, P>,
+    signal: &[Complex<T>],
+    sample_rate: T,
+    coarse_f: T,
+    target_resolution: T,
+) -> FrequencyEstimate<T> {
+    let n = signal.len();
+    assert!(n > 0);
+    assert!(
+        target_resolution > T::zero(),
+        "target resolution must be positive"
+    );
+    let two = T::from_f64(2.0).unwrap();
+
+    let mut spectrum = vec![Complex::zero(); STAGE_BINS];
+    let mut scratch = Vec::new();
+    let (mut frequency, mut peak) = (coarse_f, T::zero());
+    let mut span = two * sample_rate / T::from_usize(n).unwrap();
+    let mut resolution = span;
+    let mut stages = 0;
+    while stages < MAX_STAGES {
+        let zoom = planner.plan_zoom_fft_hz(
+            n,
+            STAGE_BINS,
+            sample_rate,
+            frequency - span,
+            frequency + span,
+        );
+        scratch.resize(zoom.get_outofplace_scratch_len(), Complex::zero());
+        zoom.process_outofplace_with_scratch(signal, &mut spectrum, &mut scratch);
+        stages += 1;
+
+        let mut k = 0;
+        for (i, x) in spectrum.iter().enumerate() {
+            if x.norm_sqr() > spectrum[k].norm_sqr() {
+                k = i;
+            }
+        }
+        frequency = zoom.freq_of_bin(k);
+        peak = spectrum[k].norm_sqr();
+
+        let width = zoom.bin_width();
+        if width <= target_resolution || width >= resolution {
+            resolution = width;
+            break;
+        }
+        resolution = width;
+        span = two * width;
+    }
+
+    let energy = signal.iter().fold(T::zero(), |acc, x| acc + x.norm_sqr());
+    let confidence = if energy > T::zero() {
+        peak / (T::from_usize(n).unwrap() * energy)
+    } else {
+        T::zero()
+    };
+
+    FrequencyEstimate {
+        frequency,
+        resolution,
+        confidence,
+        stages,
+    }
+}
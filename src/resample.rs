@@ -0,0 +1,97 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::Czt;
+
+/// Band-limited resampling of a block to an arbitrary, even irrational, rate.
+///
+/// The block of `n` samples is taken as one period of a band-limited signal: a CZT on the unit
+/// circle gives its Fourier coefficients, those above `min(input_rate, output_rate) / 2` are
+/// dropped, and an inverse-direction CZT with `w = e^(-2 pi i input_rate / (n output_rate))`
+/// evaluates the series at `t_p = p / output_rate` for every `t_p` inside the block. A
+/// coefficient exactly at the cutoff is split evenly between `+f` and `-f`, so real input stays
+/// real.
+///
+/// As with FFT resampling, a block whose ends do not join smoothly rings near its edges.
+pub struct Resampler<T: FftNum> {
+    forward: Arc<dyn Czt<T>>,
+    backward: Arc<dyn Czt<T>>,
+    /// `(DFT bin, weight)` for the series terms `-h..=h`, with the `1 / n` folded in.
+    terms: Vec<(usize, T)>,
+    /// `e^(-i theta h p)`, undoing the shift of the series index by `h`.
+    phases: Vec<Complex<T>>,
+}
+
+impl<T: FftNum> Resampler<T> {
+    pub fn new(
+        forward: Arc<dyn Czt<T>>,
+        backward: Arc<dyn Czt<T>>,
+        terms: Vec<(usize, T)>,
+        phases: Vec<Complex<T>>,
+    ) -> Self {
+        assert_eq!(backward.get_input_len(), terms.len());
+        assert_eq!(backward.get_output_len(), phases.len());
+        Self {
+            forward,
+            backward,
+            terms,
+            phases,
+        }
+    }
+
+    pub fn process(&self, input: &[Complex<T>], output: &mut [Complex<T>]) {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(input, output, &mut scratch);
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(input.len(), self.get_input_len());
+        assert_eq!(output.len(), self.get_output_len());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (spectrum, scratch) = scratch.split_at_mut(self.forward.get_buffer_len());
+        let (series, scratch) = scratch.split_at_mut(self.backward.get_buffer_len());
+        spectrum.copy_from_slice(input);
+        self.forward.process_with_scratch(
+            spectrum,
+            &mut scratch[..self.forward.get_inplace_scratch_len()],
+        );
+
+        for (s, &(bin, weight)) in series.iter_mut().zip(&self.terms) {
+            *s = spectrum[bin] * weight;
+        }
+        series[self.terms.len()..].fill(Complex::zero());
+        self.backward.process_with_scratch(
+            series,
+            &mut scratch[..self.backward.get_inplace_scratch_len()],
+        );
+
+        for ((out, &s), &phase) in output.iter_mut().zip(series.iter()).zip(&self.phases) {
+            *out = s * phase;
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.forward.get_buffer_len()
+            + self.backward.get_buffer_len()
+            + self
+                .forward
+                .get_inplace_scratch_len()
+                .max(self.backward.get_inplace_scratch_len())
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.forward.get_input_len()
+    }
+
+    /// Samples at `p / output_rate` inside the block: `ceil(n output_rate / input_rate)`.
+    pub fn get_output_len(&self) -> usize {
+        self.phases.len()
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    Fft, FftNum,
+};
+
+use crate::{
+    bluesteins::{from_f64, polar_pow, to_f64},
+    cost::{self, CostEstimate},
+    fft_provider::fft_memory_usage,
+    pointwise, Czt, CztParams, FftProvider,
+};
+
+/// A CZT whose `w = e^(-2 pi i r / M)` is an `M`th root of unity, run as one length-`M` FFT.
+///
+/// `w^(j k)` only depends on `j mod M`, so the input is rotated by `a^-j`, folded to `M` points
+/// and transformed, and bin `k` is FFT bin `r k mod M`. There is no convolution, which makes zoom
+/// grids aligned to FFT bins cost little more than the FFT itself.
+pub struct RotationCzt<T: FftNum> {
+    /// `a^-j`
+    rotation: Vec<Complex<T>>,
+    fft: Arc<dyn Fft<T>>,
+    step: usize,
+    m: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+impl<T: FftNum + Float> RotationCzt<T> {
+    /// Plans the contour `a * w^-k` with `w = e^(-2 pi i step / order)`, as [`Self::root`]
+    /// finds them.
+    pub fn new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        (order, step): (usize, usize),
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        assert!(n > 0 && m > 0 && order > 0 && step < order);
+        Self {
+            rotation: (0..n as i128).map(|j| polar_pow(a, -2 * j)).collect(),
+            fft: fft_provider.plan_forward(order),
+            step,
+            m,
+            a,
+            w: root_point(order, step),
+        }
+    }
+
+    /// The smallest order `M <= max_order` and the step `r < M` with `w` exactly
+    /// `e^(-2 pi i r / M)` as rounded to `T`, if there is one.
+    ///
+    /// Nothing less than bit equality will do: the transform evaluates the root itself, and a `w`
+    /// off it by `d` radians moves bin `k` of input `j` by `j k d`, far more than a rounding error
+    /// for long transforms. Roots of high order are dense enough for any `w` to lie within a few
+    /// roundings of one.
+    pub fn root(w: Complex<T>, max_order: usize) -> Option<(usize, usize)> {
+        let turns = -to_f64(w).arg() / core::f64::consts::TAU;
+        (1..=max_order).find_map(|order| {
+            let step = (turns * order as f64).round() as i64;
+            let step = step.rem_euclid(order as i64) as usize;
+            (root_point(order, step) == w).then_some((order, step))
+        })
+    }
+}
+
+/// `e^(-2 pi i step / order)`, rounded once from `f64`. Steps past half a turn are taken the other
+/// way round, so conjugate roots are exact conjugates.
+fn root_point<T: FftNum + Float>(order: usize, step: usize) -> Complex<T> {
+    let step = if 2 * step > order {
+        step as f64 - order as f64
+    } else {
+        step as f64
+    };
+    from_f64(Complex::from_polar(
+        1.0,
+        -core::f64::consts::TAU * step / order as f64,
+    ))
+}
+
+impl<T: FftNum> Czt<T> for RotationCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let order = self.fft.len();
+        let (folded, fft_scratch) = scratch.split_at_mut(order);
+        let input = &mut buffer[..self.rotation.len()];
+        pointwise::multiply_in_place(input, &self.rotation);
+        folded.fill(Complex::zero());
+        for chunk in input.chunks(order) {
+            for (sum, &x) in folded.iter_mut().zip(chunk) {
+                *sum = *sum + x;
+            }
+        }
+        self.fft.process_with_scratch(folded, fft_scratch);
+
+        let mut bin = 0;
+        for out in &mut buffer[..self.m] {
+            *out = folded[bin];
+            bin += self.step;
+            if bin >= order {
+                bin -= order;
+            }
+        }
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.fft.len() + self.fft.get_inplace_scratch_len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.rotation.len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.get_input_len(),
+            m: self.m,
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        (self.rotation.len() + self.get_inplace_scratch_len()) * core::mem::size_of::<Complex<T>>()
+            + fft_memory_usage(&*self.fft)
+    }
+
+    fn cost(&self) -> CostEstimate {
+        cost::rotation::<T>(self.get_input_len(), self.m, self.fft.len())
+    }
+}
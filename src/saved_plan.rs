@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+use crate::fft::num_complex::Complex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{CztParams, PlanError};
+
+/// Version written by [`BluesteinsAlgorithm::to_saved`](crate::bluesteins::BluesteinsAlgorithm::to_saved);
+/// bumped whenever the meaning of a field or table changes.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// A persisted Bluestein plan: everything needed to rebuild it and, unless saved as a descriptor
+/// only, its coefficient tables. FFTs are always planned anew on load, so a plan can be restored
+/// on a machine with a different FFT backend.
+///
+/// With `rkyv` the archived form keeps the tables as plain `Complex<T>` arrays, so a plan written
+/// by [`to_bytes`] can be memory mapped, checked by [`access`] and loaded by
+/// [`BluesteinsAlgorithm::from_archived`](crate::bluesteins::BluesteinsAlgorithm::from_archived)
+/// without deserializing anything. Loading is still linear in the archive's size: `access`
+/// validates every byte and the plan copies the tables into memory of its own. What it saves is
+/// recomputing the tables, with their chirps and the kernel's FFT.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes)
+)]
+pub struct SavedPlan<T> {
+    pub version: u32,
+    /// The contour from bin `k0`, as the plan reports it.
+    pub params: CztParams<T>,
+    pub j0: usize,
+    pub k0: isize,
+    pub convolution_len: usize,
+    pub window: Option<Vec<T>>,
+    pub output_scale: T,
+    pub delay: T,
+    pub tables: Option<SavedTables<T>>,
+}
+
+/// Input chirp `y`, transformed kernel `v` and output chirp `x`, with windows, scaling and
+/// delays folded in.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes)
+)]
+pub struct SavedTables<T> {
+    pub y: Vec<Complex<T>>,
+    pub v: Vec<Complex<T>>,
+    pub x: Vec<Complex<T>>,
+}
+
+/// Why a saved plan cannot be loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LoadError {
+    /// Written in a format version other than [`FORMAT_VERSION`].
+    Version { found: u32 },
+    /// The saved contour cannot be planned.
+    Params(PlanError),
+    /// The convolution is shorter than the `n + m - 1` it has to hold.
+    ConvolutionLength { len: usize, min: usize },
+    /// The window or a coefficient table does not match the plan's lengths.
+    TableLength {
+        name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl From<PlanError> for LoadError {
+    fn from(error: PlanError) -> Self {
+        LoadError::Params(error)
+    }
+}
+
+impl core::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LoadError::Version { found } => write!(
+                f,
+                "saved plan has format version {found}, expected {FORMAT_VERSION}"
+            ),
+            LoadError::Params(error) => write!(f, "saved plan: {error}"),
+            LoadError::ConvolutionLength { len, min } => {
+                write!(f, "saved convolution length {len} is shorter than {min}")
+            }
+            LoadError::TableLength {
+                name,
+                expected,
+                actual,
+            } => write!(f, "saved {name} has length {actual}, expected {expected}"),
+        }
+    }
+}
+
+impl core::error::Error for LoadError {}
+
+impl<T> SavedPlan<T> {
+    /// Whether this version of the crate can load the plan.
+    pub fn is_compatible(&self) -> bool {
+        self.version == FORMAT_VERSION
+    }
+
+    /// The same plan without its tables, to be recomputed on load.
+    pub fn descriptor_only(mut self) -> Self {
+        self.tables = None;
+        self
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T: rkyv::Archive> ArchivedSavedPlan<T> {
+    /// Whether this version of the crate can load the plan.
+    pub fn is_compatible(&self) -> bool {
+        self.version == FORMAT_VERSION
+    }
+}
+
+/// Archives `plan` for [`access`].
+#[cfg(feature = "rkyv")]
+pub fn to_bytes<T>(plan: &SavedPlan<T>) -> rkyv::AlignedVec
+where
+    SavedPlan<T>: rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<1024>>,
+{
+    rkyv::to_bytes::<_, 1024>(plan).expect("archiving into memory cannot fail")
+}
+
+/// The archived plan in `bytes`, borrowed in place after checking that it is well formed, or
+/// `None` if it is not. The check reads the whole archive. `bytes` must be aligned as [`to_bytes`] returned them.
+#[cfg(feature = "rkyv")]
+pub fn access<'a, T: rkyv::Archive>(bytes: &'a [u8]) -> Option<&'a ArchivedSavedPlan<T>>
+where
+    ArchivedSavedPlan<T>: rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'a>>,
+{
+    rkyv::check_archived_root::<SavedPlan<T>>(bytes).ok()
+}
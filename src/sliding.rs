@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{
+    bluesteins::{from_f64, to_f64},
+    CztParams,
+};
+
+/// Tracks the `m` bins of the CZT of the latest `n` samples, updating every bin in O(1) per
+/// sample as in the sliding DFT.
+///
+/// With the oldest sample at `j = 0`, bin `k` is `X_k = sum_j x_j z_k^-j` on `z_k = a w^-k`, and a
+/// new sample updates it to `(X_k - x_oldest) z_k + x_new z_k^-(n-1)`. Before `n` samples have
+/// arrived the window is zero-filled.
+///
+/// Rounding errors are carried from one update to the next and grow with the number of samples,
+/// on a contour outside the unit circle exponentially; [`refresh`](Self::refresh) recomputes the
+/// bins from the window.
+pub struct SlidingCzt<T: FftNum> {
+    a: Complex<T>,
+    w: Complex<T>,
+    /// `z_k`
+    z: Vec<Complex<T>>,
+    /// `z_k^-(n-1)`
+    tail: Vec<Complex<T>>,
+    /// The window as a ring buffer, the oldest sample at `oldest`.
+    history: Vec<Complex<T>>,
+    oldest: usize,
+    bins: Vec<Complex<T>>,
+}
+
+impl<T: FftNum + Float> SlidingCzt<T> {
+    pub fn new(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> Self {
+        assert!(n > 0);
+        let (ln_a, ln_w) = (to_f64(a).ln(), to_f64(w).ln());
+        let ln_z = |k: usize| ln_a - ln_w * k as f64;
+
+        Self {
+            a,
+            w,
+            z: (0..m).map(|k| from_f64(ln_z(k).exp())).collect(),
+            tail: (0..m)
+                .map(|k| from_f64((-ln_z(k) * (n - 1) as f64).exp()))
+                .collect(),
+            history: vec![Complex::zero(); n],
+            oldest: 0,
+            bins: vec![Complex::zero(); m],
+        }
+    }
+
+    /// Slides the window by one sample and returns the updated bins.
+    pub fn push(&mut self, sample: Complex<T>) -> &[Complex<T>] {
+        let oldest = core::mem::replace(&mut self.history[self.oldest], sample);
+        self.oldest = (self.oldest + 1) % self.history.len();
+        for ((bin, &z), &tail) in self.bins.iter_mut().zip(&self.z).zip(&self.tail) {
+            *bin = (*bin - oldest) * z + sample * tail;
+        }
+        &self.bins
+    }
+
+    /// Pushes every sample of `samples`, returning the bins after the last one.
+    pub fn extend(&mut self, samples: &[Complex<T>]) -> &[Complex<T>] {
+        for &sample in samples {
+            self.push(sample);
+        }
+        &self.bins
+    }
+
+    pub fn bins(&self) -> &[Complex<T>] {
+        &self.bins
+    }
+
+    /// The window, oldest sample first.
+    pub fn window(&self) -> impl Iterator<Item = &Complex<T>> {
+        let (newer, older) = self.history.split_at(self.oldest);
+        older.iter().chain(newer)
+    }
+
+    /// Recomputes the bins from the window in O(nm), discarding accumulated rounding errors.
+    pub fn refresh(&mut self) {
+        let (newer, older) = self.history.split_at(self.oldest);
+        for (bin, &z) in self.bins.iter_mut().zip(&self.z) {
+            // Horner in z^-1 from the newest sample.
+            let z_inv = z.inv();
+            *bin = newer
+                .iter()
+                .rev()
+                .chain(older.iter().rev())
+                .fold(Complex::zero(), |acc, &x| acc * z_inv + x);
+        }
+    }
+
+    /// Zero-fills the window.
+    pub fn reset(&mut self) {
+        self.history.fill(Complex::zero());
+        self.bins.fill(Complex::zero());
+        self.oldest = 0;
+    }
+
+    pub fn get_input_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn get_output_len(&self) -> usize {
+        self.bins.len()
+    }
+
+    pub fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.history.len(),
+            m: self.bins.len(),
+            a: self.a,
+            w: self.w,
+        }
+    }
+}
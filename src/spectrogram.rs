@@ -0,0 +1,90 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::{zoom::ZoomFft, Czt};
+
+/// Short-time zoom transform: the zoom plan runs on frames of `frame_len` samples taken every
+/// `hop` samples, producing a time-frequency matrix.
+///
+/// Output is row-major with one row of `bins()` values per frame.
+pub struct Spectrogram<T: FftNum> {
+    zoom: Arc<ZoomFft<T>>,
+    hop: usize,
+}
+
+impl<T: FftNum> Spectrogram<T> {
+    /// The frame length is the zoom plan's input length; windows are folded into the plan.
+    pub fn new(zoom: Arc<ZoomFft<T>>, hop: usize) -> Self {
+        assert!(hop > 0, "hop size must be positive");
+        Self { zoom, hop }
+    }
+
+    pub fn frame_len(&self) -> usize {
+        self.zoom.get_input_len()
+    }
+
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Number of frequency bins per frame.
+    pub fn bins(&self) -> usize {
+        self.zoom.get_output_len()
+    }
+
+    /// Number of whole frames in a signal of `len` samples.
+    pub fn num_frames(&self, len: usize) -> usize {
+        if len < self.frame_len() {
+            0
+        } else {
+            (len - self.frame_len()) / self.hop + 1
+        }
+    }
+
+    /// Frequencies of the bins, see [`ZoomFft::freqs`].
+    pub fn freqs(&self) -> Vec<T> {
+        self.zoom.freqs()
+    }
+
+    /// Time of the center of frame `i`, in seconds for plans in Hz and samples otherwise.
+    pub fn time_of_frame(&self, i: usize) -> T {
+        let center = T::from_usize(i * self.hop).unwrap()
+            + T::from_usize(self.frame_len() - 1).unwrap() / T::from_f64(2.0).unwrap();
+        center / self.zoom.sample_rate()
+    }
+
+    pub fn process(&self, signal: &[Complex<T>]) -> Vec<Complex<T>> {
+        let mut output = vec![Complex::zero(); self.num_frames(signal.len()) * self.bins()];
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(signal, &mut output, &mut scratch);
+        output
+    }
+
+    /// Writes `num_frames(signal.len())` rows to `output`; trailing samples that do not fill a
+    /// frame are ignored.
+    pub fn process_with_scratch(
+        &self,
+        signal: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        let frames = self.num_frames(signal.len());
+        assert_eq!(output.len(), frames * self.bins());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let (frame_len, bins) = (self.frame_len(), self.bins());
+        for i in 0..frames {
+            let start = i * self.hop;
+            self.zoom.process_outofplace_with_scratch(
+                &signal[start..start + frame_len],
+                &mut output[i * bins..(i + 1) * bins],
+                scratch,
+            );
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        self.zoom.get_outofplace_scratch_len()
+    }
+}
@@ -0,0 +1,311 @@
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{
+    agrees_within,
+    bluesteins::{log_radius, BluesteinsAlgorithm, ConvolutionLength},
+    check_finite,
+    cost::CostEstimate,
+    uninit, verification_signal, Czt, CztError, CztParams, FftProvider,
+};
+
+/// Largest `|ln |w|| * p^2 / 2` a single convolution kernel may span, i.e. its magnitudes stay
+/// within a factor of `e^8` of each other.
+const MAX_LOG_RANGE: f64 = 8.0;
+
+/// Most blocks a contour is split into. Every block owns its own coefficient tables, so their
+/// memory grows with `n * m / block_len`; past this many blocks the planner falls back to a
+/// single convolution instead.
+const MAX_BLOCKS: usize = 4096;
+
+/// Bluestein's algorithm for contours that spiral noticeably in or out (`|w| != 1`).
+///
+/// The convolution kernel `w^(-p^2 / 2)` for `p = -(n - 1)..m` grows or shrinks geometrically and
+/// the FFT's rounding error scales with its largest entry, so one length-`l` convolution loses all
+/// precision long before its tables overflow. The transform is instead split into blocks of inputs
+/// and outputs small enough that each block's centered kernel stays within a bounded magnitude
+/// range, and the block results are accumulated.
+///
+/// Each of the `(n / block_len) * (m / block_len)` blocks is a separate Bluestein plan with its own
+/// tables. [`is_needed`](Self::is_needed) caps their count, so the planner only splits contours
+/// where that stays affordable.
+pub struct SpiralCzt<T: FftNum> {
+    blocks: Vec<Block<T>>,
+    n: usize,
+    m: usize,
+    block_len: usize,
+    a: Complex<T>,
+    w: Complex<T>,
+}
+
+struct Block<T: FftNum> {
+    j0: usize,
+    k0: usize,
+    czt: BluesteinsAlgorithm<T>,
+}
+
+impl<T: FftNum + Float> SpiralCzt<T> {
+    /// Splits the contour into blocks of at most `block_len` inputs and outputs. The block count
+    /// is not capped here; check [`is_needed`](Self::is_needed) first to stay within it.
+    pub fn new(
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        k0: isize,
+        length: ConvolutionLength,
+        fft_provider: &mut dyn FftProvider<T>,
+    ) -> Self {
+        let block_len = Self::block_len(w);
+
+        let mut blocks = Vec::new();
+        for j0 in (0..n).step_by(block_len) {
+            for k in (0..m).step_by(block_len) {
+                let czt = BluesteinsAlgorithm::new_segment(
+                    block_len.min(n - j0),
+                    block_len.min(m - k),
+                    a,
+                    w,
+                    j0,
+                    k0 + k as isize,
+                    length,
+                    fft_provider,
+                );
+                blocks.push(Block { j0, k0: k, czt });
+            }
+        }
+
+        // The first block's contour starts at bin k0 as well.
+        let a = blocks.first().map_or(a, |block| block.czt.params().a);
+        Self {
+            blocks,
+            n,
+            m,
+            block_len,
+            a,
+            w,
+        }
+    }
+
+    /// Folds `window` into each block's input chirp, like [`BluesteinsAlgorithm::with_window`].
+    pub fn with_window(mut self, window: &[T]) -> Self {
+        assert_eq!(window.len(), self.n);
+        self.blocks = self
+            .blocks
+            .into_iter()
+            .map(|block| {
+                let n = block.czt.get_input_len();
+                let czt = block.czt.with_window(&window[block.j0..block.j0 + n]);
+                Block { czt, ..block }
+            })
+            .collect();
+        self
+    }
+
+    /// Scales every block's output by `factor`, like [`BluesteinsAlgorithm::with_output_scale`].
+    pub fn with_output_scale(mut self, factor: T) -> Self {
+        self.blocks = self
+            .blocks
+            .into_iter()
+            .map(|block| Block {
+                czt: block.czt.with_output_scale(factor),
+                ..block
+            })
+            .collect();
+        self
+    }
+
+    /// Delays the input by `delay` samples, like [`BluesteinsAlgorithm::with_delay`]. Every
+    /// block continues the phase ramp from the contour's `a` to its own bins.
+    pub fn with_delay(mut self, delay: T) -> Self {
+        self.blocks = self
+            .blocks
+            .into_iter()
+            .map(|block| Block {
+                czt: block.czt.with_delay(delay),
+                ..block
+            })
+            .collect();
+        self
+    }
+
+    /// Whether a single Bluestein convolution of this size loses precision on the contour `w`,
+    /// and splitting it takes at most `MAX_BLOCKS` blocks. Contours that would need more are
+    /// left to a single convolution, trading their accuracy for bounded memory.
+    pub fn is_needed(n: usize, m: usize, w: Complex<T>) -> bool {
+        let block_len = Self::block_len(w);
+        let blocks = n.div_ceil(block_len).saturating_mul(m.div_ceil(block_len));
+        (n + m) / 2 > block_len && blocks <= MAX_BLOCKS
+    }
+
+    /// Longest input or output block keeping the kernel within `MAX_LOG_RANGE`.
+    fn block_len(w: Complex<T>) -> usize {
+        let block_len = Float::sqrt(2.0 * MAX_LOG_RANGE / log_radius(w).abs());
+        if block_len < usize::MAX as f64 {
+            (block_len as usize).max(1)
+        } else {
+            usize::MAX
+        }
+    }
+}
+
+impl<T: FftNum> SpiralCzt<T> {
+    /// Adds every block's transform into `accumulator`, running the block plans with `process`.
+    fn accumulate_blocks(
+        &self,
+        buffer: &[Complex<T>],
+        accumulator: &mut [Complex<T>],
+        block_buffer: &mut [Complex<T>],
+        mut process: impl FnMut(&BluesteinsAlgorithm<T>, &mut [Complex<T>]),
+    ) {
+        for block in &self.blocks {
+            let (n, m) = (block.czt.get_input_len(), block.czt.get_output_len());
+            let block_buffer = &mut block_buffer[..n.max(m)];
+            block_buffer[..n].copy_from_slice(&buffer[block.j0..block.j0 + n]);
+            block_buffer[n..].fill(Complex::zero());
+
+            process(&block.czt, block_buffer);
+            for (acc, &x) in accumulator[block.k0..block.k0 + m]
+                .iter_mut()
+                .zip(&*block_buffer)
+            {
+                *acc = *acc + x;
+            }
+        }
+    }
+}
+
+impl<T: FftNum> Czt<T> for SpiralCzt<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let (accumulator, scratch) = scratch.split_at_mut(self.m);
+        let (block_buffer, scratch) = scratch.split_at_mut(self.block_len.min(self.n.max(self.m)));
+        accumulator.fill(Complex::zero());
+        self.accumulate_blocks(buffer, accumulator, block_buffer, |czt, block_buffer| {
+            czt.process_with_scratch(block_buffer, &mut scratch[..czt.get_inplace_scratch_len()])
+        });
+        buffer[..self.m].copy_from_slice(accumulator);
+    }
+
+    fn process_with_uninit_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [MaybeUninit<Complex<T>>],
+    ) {
+        assert_eq!(buffer.len(), self.get_buffer_len());
+        assert_eq!(scratch.len(), self.get_inplace_scratch_len());
+
+        let (accumulator, scratch) = scratch.split_at_mut(self.m);
+        let (block_buffer, scratch) = scratch.split_at_mut(self.block_len.min(self.n.max(self.m)));
+        let accumulator = uninit::fill(accumulator, Complex::zero());
+        let block_buffer = uninit::fill(block_buffer, Complex::zero());
+        self.accumulate_blocks(buffer, accumulator, block_buffer, |czt, block_buffer| {
+            czt.process_with_uninit_scratch(
+                block_buffer,
+                &mut scratch[..czt.get_inplace_scratch_len()],
+            )
+        });
+        buffer[..self.m].copy_from_slice(accumulator);
+    }
+
+    /// Checks every block plan, so a blowup is attributed to the stage of the block it starts
+    /// in.
+    fn process_checked(&self, buffer: &mut [Complex<T>]) -> Result<(), CztError> {
+        let (expected, actual) = (self.get_buffer_len(), buffer.len());
+        if actual != expected {
+            return Err(CztError::BufferLength { expected, actual });
+        }
+        check_finite("input", &buffer[..self.n])?;
+
+        let mut accumulator = vec![Complex::zero(); self.m];
+        let mut block_buffer = vec![Complex::zero(); self.block_len.min(self.n.max(self.m))];
+        let mut result = Ok(());
+        self.accumulate_blocks(
+            buffer,
+            &mut accumulator,
+            &mut block_buffer,
+            |czt, block_buffer| {
+                if result.is_ok() {
+                    result = czt.process_checked(block_buffer);
+                }
+            },
+        );
+        result?;
+        check_finite("output", &accumulator)?;
+        buffer[..self.m].copy_from_slice(&accumulator);
+        Ok(())
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        let block_scratch = self
+            .blocks
+            .iter()
+            .map(|block| block.czt.get_inplace_scratch_len())
+            .max()
+            .unwrap_or(0);
+        self.m + self.block_len.min(self.n.max(self.m)) + block_scratch
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        // The blocks share one scratch buffer, so count only their tables and FFTs.
+        let size = core::mem::size_of::<Complex<T>>();
+        self.blocks
+            .iter()
+            .map(|block| block.czt.get_memory_usage() - block.czt.get_inplace_scratch_len() * size)
+            .sum::<usize>()
+            + self.get_inplace_scratch_len() * size
+    }
+
+    fn cost(&self) -> CostEstimate {
+        self.blocks
+            .iter()
+            .map(|block| block.czt.cost())
+            .fold(CostEstimate::default(), |acc, cost| acc + cost)
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.n
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.m
+    }
+
+    fn params(&self) -> CztParams<T> {
+        CztParams {
+            n: self.n,
+            m: self.m,
+            a: self.a,
+            w: self.w,
+        }
+    }
+
+    /// Like the default, against the sum of every block's
+    /// [`NaiveCzt`](crate::naive_czt::NaiveCzt) reference with the window, output scale and delay
+    /// folded into it.
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        let signal = verification_signal(self.n, self.get_buffer_len());
+        let mut expected = vec![Complex::zero(); self.m];
+        for block in &self.blocks {
+            let n = block.czt.get_input_len();
+            let bins = block.czt.naive_reference(&signal[block.j0..block.j0 + n]);
+            for (acc, x) in expected[block.k0..].iter_mut().zip(bins) {
+                *acc = *acc + x;
+            }
+        }
+        let mut actual = signal;
+        self.process(&mut actual);
+        agrees_within(&expected, &actual[..self.m], tolerance)
+    }
+}
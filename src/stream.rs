@@ -0,0 +1,168 @@
+use alloc::{sync::Arc, vec::Vec};
+use std::io::{self, Read};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero, FftNum};
+
+use crate::Czt;
+
+/// Layout of the samples in the byte stream, all little-endian.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SampleFormat {
+    /// Interleaved `re, im` pairs of `f32`.
+    ComplexF32,
+    /// Interleaved `re, im` pairs of `f64`.
+    ComplexF64,
+    /// Real `f32` samples.
+    RealF32,
+}
+
+impl SampleFormat {
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            SampleFormat::ComplexF32 => 8,
+            SampleFormat::ComplexF64 => 16,
+            SampleFormat::RealF32 => 4,
+        }
+    }
+
+    fn decode<T: FftNum>(&self, bytes: &[u8]) -> Complex<T> {
+        let f32_at = |i: usize| f32::from_le_bytes(bytes[i..i + 4].try_into().unwrap()) as f64;
+        let f64_at = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        let (re, im) = match self {
+            SampleFormat::ComplexF32 => (f32_at(0), f32_at(4)),
+            SampleFormat::ComplexF64 => (f64_at(0), f64_at(8)),
+            SampleFormat::RealF32 => (f32_at(0), 0.0),
+        };
+        Complex::new(T::from_f64(re).unwrap(), T::from_f64(im).unwrap())
+    }
+}
+
+/// Frames the samples of a [`Read`] every `hop` samples and runs a plan on each frame, yielding
+/// one spectrum of `get_output_len()` bins per frame.
+///
+/// Frames are `get_input_len()` samples long; a hop longer than that skips the samples between
+/// frames. The stream ends at the first frame the reader cannot fill, dropping the trailing
+/// samples as [`Spectrogram`](crate::spectrogram::Spectrogram) does. Read errors other than
+/// [`io::ErrorKind::Interrupted`] are yielded once and end the stream.
+pub struct CztStream<T: FftNum, R: Read> {
+    reader: R,
+    czt: Arc<dyn Czt<T>>,
+    format: SampleFormat,
+    hop: usize,
+    frame: Vec<Complex<T>>,
+    started: bool,
+    finished: bool,
+    bytes: Vec<u8>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T: FftNum, R: Read> CztStream<T, R> {
+    pub fn new(reader: R, czt: Arc<dyn Czt<T>>, format: SampleFormat, hop: usize) -> Self {
+        assert!(hop > 0, "hop size must be positive");
+        let n = czt.get_input_len();
+        Self {
+            reader,
+            format,
+            hop,
+            frame: vec![Complex::zero(); n],
+            started: false,
+            finished: false,
+            bytes: Vec::new(),
+            scratch: vec![Complex::zero(); czt.get_outofplace_scratch_len()],
+            czt,
+        }
+    }
+
+    pub fn frame_len(&self) -> usize {
+        self.frame.len()
+    }
+
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Number of frequency bins per frame.
+    pub fn bins(&self) -> usize {
+        self.czt.get_output_len()
+    }
+
+    /// Reads the next frame and writes its spectrum to `output` without allocating, returning
+    /// `false` once the reader cannot fill another frame.
+    pub fn next_into(&mut self, output: &mut [Complex<T>]) -> io::Result<bool> {
+        assert_eq!(output.len(), self.bins());
+        if self.finished {
+            return Ok(false);
+        }
+        let filled = self.advance();
+        if !matches!(filled, Ok(true)) {
+            self.finished = true;
+        }
+        if !filled? {
+            return Ok(false);
+        }
+        self.czt
+            .process_outofplace_with_scratch(&self.frame, output, &mut self.scratch);
+        Ok(true)
+    }
+
+    /// Slides the frame by `hop` samples, or fills it for the first frame.
+    fn advance(&mut self) -> io::Result<bool> {
+        let n = self.frame.len();
+        if !self.started {
+            self.started = true;
+            return self.read_samples(0);
+        }
+        if self.hop < n {
+            self.frame.copy_within(self.hop.., 0);
+            return self.read_samples(n - self.hop);
+        }
+        let mut skip = self.hop - n;
+        while skip > 0 {
+            let count = skip.min(n);
+            if !self.read_samples(n - count)? {
+                return Ok(false);
+            }
+            skip -= count;
+        }
+        self.read_samples(0)
+    }
+
+    /// Fills `frame[start..]` from the reader, returning `false` at end of stream.
+    fn read_samples(&mut self, start: usize) -> io::Result<bool> {
+        let size = self.format.bytes_per_sample();
+        self.bytes.resize((self.frame.len() - start) * size, 0);
+        let mut read = 0;
+        while read < self.bytes.len() {
+            match self.reader.read(&mut self.bytes[read..]) {
+                Ok(0) => return Ok(false),
+                Ok(count) => read += count,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        for (sample, bytes) in self.frame[start..]
+            .iter_mut()
+            .zip(self.bytes.chunks_exact(size))
+        {
+            *sample = self.format.decode(bytes);
+        }
+        Ok(true)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<T: FftNum, R: Read> Iterator for CztStream<T, R> {
+    type Item = io::Result<Vec<Complex<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut output = vec![Complex::zero(); self.bins()];
+        match self.next_into(&mut output) {
+            Ok(true) => Some(Ok(output)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use core::mem::MaybeUninit;
+
+/// Initializes every element to `value`.
+pub fn fill<T: Copy>(slice: &mut [MaybeUninit<T>], value: T) -> &mut [T] {
+    for x in slice.iter_mut() {
+        x.write(value);
+    }
+    // SAFETY: every element was just written.
+    unsafe { assume_init(slice) }
+}
+
+/// # Safety
+///
+/// Every element of `slice` must be initialized.
+pub unsafe fn assume_init<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
+}
@@ -0,0 +1,181 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{window::Window, zoom::ZoomFft, Czt, CztPlanner, FftProvider};
+
+/// Perceptual frequency scales.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrequencyScale {
+    /// HTK Mel scale, `2595 log10(1 + f / 700)`.
+    Mel,
+    /// Traunmüller's Bark scale, `26.81 f / (1960 + f) - 0.53`.
+    Bark,
+}
+
+impl FrequencyScale {
+    pub fn from_hz(self, hz: f64) -> f64 {
+        match self {
+            FrequencyScale::Mel => 2595.0 * Float::log10(1.0 + hz / 700.0),
+            FrequencyScale::Bark => 26.81 * hz / (1960.0 + hz) - 0.53,
+        }
+    }
+
+    pub fn to_hz(self, x: f64) -> f64 {
+        match self {
+            FrequencyScale::Mel => 700.0 * (Float::powf(10.0, x / 2595.0) - 1.0),
+            FrequencyScale::Bark => 1960.0 * (x + 0.53) / (26.28 - x),
+        }
+    }
+}
+
+/// Band energies of triangular filters equally spaced on a warped frequency scale.
+///
+/// Instead of one fine FFT grid, the span between adjacent band edges is covered by its own zoom
+/// segment of `bins_per_band` bins, so the resolution follows the bandwidth of the filters. Each
+/// bin's power is weighted by the filter and by its width in units of `fs / n`, so the energies
+/// approximate a filterbank applied to the length-`n` FFT power spectrum.
+pub struct WarpedSpectrum<T: FftNum> {
+    segments: Vec<Arc<ZoomFft<T>>>,
+    /// `(band, bin, weight)` with `bin` indexing the stitched segment outputs.
+    weights: Vec<(usize, usize, T)>,
+    centers: Vec<T>,
+}
+
+impl<T: FftNum + Float> WarpedSpectrum<T> {
+    /// Plans `bands` filters between `f_min` and `f_max` Hz for frames of `n` samples.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<P: FftProvider<T>>(
+        planner: &mut CztPlanner<T, P>,
+        n: usize,
+        sample_rate: T,
+        f_min: T,
+        f_max: T,
+        bands: usize,
+        scale: FrequencyScale,
+        window: Window<T>,
+    ) -> Self {
+        const BINS_PER_BAND: usize = 4;
+
+        assert!(bands > 0);
+        let fs = sample_rate.to_f64().unwrap();
+        let (f_min, f_max) = (f_min.to_f64().unwrap(), f_max.to_f64().unwrap());
+        assert!(0.0 <= f_min && f_min < f_max && f_max <= fs / 2.0);
+
+        let (lo, hi) = (scale.from_hz(f_min), scale.from_hz(f_max));
+        let edges: Vec<f64> = (0..bands + 2)
+            .map(|i| scale.to_hz(lo + (hi - lo) * i as f64 / (bands + 1) as f64))
+            .collect();
+
+        let mut segments = Vec::with_capacity(bands + 1);
+        let mut weights = Vec::new();
+        for (s, edge) in edges.windows(2).enumerate() {
+            let width = (edge[1] - edge[0]) / BINS_PER_BAND as f64;
+            // Bins sit at the midpoints of BINS_PER_BAND equal slices of the segment.
+            segments.push(planner.plan_zoom_fft_hz_windowed(
+                n,
+                BINS_PER_BAND,
+                sample_rate,
+                T::from_f64(edge[0] + width / 2.0).unwrap(),
+                T::from_f64(edge[1] - width / 2.0).unwrap(),
+                window,
+            ));
+
+            for i in 0..BINS_PER_BAND {
+                let f = edge[0] + width * (i as f64 + 0.5);
+                let bin = s * BINS_PER_BAND + i;
+                let bin_weight = width * n as f64 / fs;
+                // The segment lies on the rising slope of band s and the falling one of s - 1.
+                if s < bands {
+                    let rising = (f - edges[s]) / (edges[s + 1] - edges[s]);
+                    weights.push((s, bin, T::from_f64(rising * bin_weight).unwrap()));
+                }
+                if s > 0 {
+                    let falling = (edges[s + 1] - f) / (edges[s + 1] - edges[s]);
+                    weights.push((s - 1, bin, T::from_f64(falling * bin_weight).unwrap()));
+                }
+            }
+        }
+
+        let centers = edges[1..=bands]
+            .iter()
+            .map(|&f| T::from_f64(f).unwrap())
+            .collect();
+        Self {
+            segments,
+            weights,
+            centers,
+        }
+    }
+}
+
+impl<T: FftNum> WarpedSpectrum<T> {
+    pub fn bands(&self) -> usize {
+        self.centers.len()
+    }
+
+    /// Center frequency of every band in Hz.
+    pub fn center_freqs(&self) -> &[T] {
+        &self.centers
+    }
+
+    pub fn frame_len(&self) -> usize {
+        self.segments[0].get_input_len()
+    }
+
+    /// Frequencies of the stitched zoom bins.
+    pub fn bin_freqs(&self) -> Vec<T> {
+        self.segments.iter().flat_map(|zoom| zoom.freqs()).collect()
+    }
+
+    pub fn process(&self, frame: &[Complex<T>]) -> Vec<T> {
+        let mut energies = vec![T::zero(); self.bands()];
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.process_with_scratch(frame, &mut energies, &mut scratch);
+        energies
+    }
+
+    pub fn process_with_scratch(
+        &self,
+        frame: &[Complex<T>],
+        energies: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) {
+        assert_eq!(frame.len(), self.frame_len());
+        assert_eq!(energies.len(), self.bands());
+        assert_eq!(scratch.len(), self.get_scratch_len());
+
+        let bins: usize = self.segments.iter().map(|zoom| zoom.get_output_len()).sum();
+        let (spectrum, scratch) = scratch.split_at_mut(bins);
+        let mut offset = 0;
+        for zoom in &self.segments {
+            let m = zoom.get_output_len();
+            zoom.process_outofplace_with_scratch(
+                frame,
+                &mut spectrum[offset..offset + m],
+                &mut scratch[..zoom.get_outofplace_scratch_len()],
+            );
+            offset += m;
+        }
+
+        energies.fill(T::zero());
+        for &(band, bin, weight) in &self.weights {
+            energies[band] = energies[band] + spectrum[bin].norm_sqr() * weight;
+        }
+    }
+
+    pub fn get_scratch_len(&self) -> usize {
+        let bins: usize = self.segments.iter().map(|zoom| zoom.get_output_len()).sum();
+        let czt_scratch = self
+            .segments
+            .iter()
+            .map(|zoom| zoom.get_outofplace_scratch_len())
+            .max()
+            .unwrap_or(0);
+        bins + czt_scratch
+    }
+}
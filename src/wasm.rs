@@ -0,0 +1,62 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{num_complex::Complex, num_traits::Zero};
+use wasm_bindgen::prelude::*;
+
+use crate::{zoom::ZoomFft, Czt, CztPlanner};
+
+/// [`ZoomFft`] for JavaScript, exported as the class `ZoomFft`: real samples in a
+/// `Float32Array`, bins out as `re, im` pairs in another.
+///
+/// The buffer and scratch are allocated once with the plan and reused by every call.
+#[wasm_bindgen(js_name = ZoomFft)]
+pub struct WasmZoomFft {
+    zoom: Arc<ZoomFft<f32>>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+}
+
+#[wasm_bindgen(js_class = ZoomFft)]
+impl WasmZoomFft {
+    /// `n` samples at `sample_rate` to `m` bins from `f_start` to `f_end`, both included.
+    #[wasm_bindgen(constructor)]
+    pub fn new(n: usize, m: usize, sample_rate: f32, f_start: f32, f_end: f32) -> Self {
+        let zoom = CztPlanner::new().plan_zoom_fft_hz(n, m, sample_rate, f_start, f_end);
+        Self {
+            buffer: vec![Complex::zero(); zoom.get_buffer_len()],
+            scratch: vec![Complex::zero(); zoom.get_inplace_scratch_len()],
+            zoom,
+        }
+    }
+
+    #[wasm_bindgen(getter = inputLength)]
+    pub fn input_len(&self) -> usize {
+        self.zoom.get_input_len()
+    }
+
+    #[wasm_bindgen(getter = outputLength)]
+    pub fn output_len(&self) -> usize {
+        self.zoom.get_output_len()
+    }
+
+    /// Bin frequencies in Hz.
+    pub fn frequencies(&self) -> Vec<f32> {
+        self.zoom.freqs()
+    }
+
+    /// Transforms `inputLength` real samples into `2 * outputLength` interleaved values.
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        assert_eq!(samples.len(), self.zoom.get_input_len());
+        let (n, m) = (samples.len(), self.zoom.get_output_len());
+        for (x, &sample) in self.buffer.iter_mut().zip(samples) {
+            *x = Complex::new(sample, 0.0);
+        }
+        self.buffer[n..].fill(Complex::zero());
+        self.zoom
+            .process_with_scratch(&mut self.buffer, &mut self.scratch);
+        self.buffer[..m]
+            .iter()
+            .flat_map(|bin| [bin.re, bin.im])
+            .collect()
+    }
+}
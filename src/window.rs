@@ -0,0 +1,84 @@
+use alloc::vec::Vec;
+
+use crate::fft::num_traits::{Float, FromPrimitive};
+
+/// Window functions that can be fused into a plan's input coefficients.
+///
+/// All windows are symmetric, i.e. sample `0` and sample `n - 1` have equal weight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window<T> {
+    Rectangular,
+    Hann,
+    Hamming,
+    /// 4-term Blackman-Harris.
+    BlackmanHarris,
+    /// Kaiser window with shape parameter beta.
+    Kaiser(T),
+    FlatTop,
+}
+
+impl<T: Float + FromPrimitive> Window<T> {
+    pub fn coefficients(&self, n: usize) -> Vec<T> {
+        if n == 1 {
+            return vec![T::one()];
+        }
+
+        let two_pi = T::from_f64(core::f64::consts::PI * 2.0).unwrap();
+        let denominator = T::from_usize(n - 1).unwrap();
+        let cosine_sum = |weights: &[f64], k: usize| {
+            let x = two_pi * T::from_usize(k).unwrap() / denominator;
+            weights
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |acc, (i, &weight)| {
+                    let term = T::from_f64(weight).unwrap() * (x * T::from_usize(i).unwrap()).cos();
+                    if i % 2 == 0 {
+                        acc + term
+                    } else {
+                        acc - term
+                    }
+                })
+        };
+
+        (0..n)
+            .map(|k| match *self {
+                Window::Rectangular => T::one(),
+                Window::Hann => cosine_sum(&[0.5, 0.5], k),
+                Window::Hamming => cosine_sum(&[0.54, 0.46], k),
+                Window::BlackmanHarris => cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168], k),
+                Window::FlatTop => cosine_sum(
+                    &[
+                        0.21557895,
+                        0.41663158,
+                        0.277263158,
+                        0.083578947,
+                        0.006947368,
+                    ],
+                    k,
+                ),
+                Window::Kaiser(beta) => {
+                    let two = T::from_f64(2.0).unwrap();
+                    let ratio = two * T::from_usize(k).unwrap() / denominator - T::one();
+                    let arg = beta * (T::one() - ratio * ratio).max(T::zero()).sqrt();
+                    bessel_i0(arg) / bessel_i0(beta)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Modified Bessel function of the first kind, order zero, by its power series.
+fn bessel_i0<T: Float + FromPrimitive>(x: T) -> T {
+    let quarter_x_squared = x * x / T::from_f64(4.0).unwrap();
+    let mut term = T::one();
+    let mut sum = T::one();
+    for k in 1..500 {
+        let k = T::from_usize(k).unwrap();
+        term = term * quarter_x_squared / (k * k);
+        sum = sum + term;
+        if term < sum * T::epsilon() {
+            break;
+        }
+    }
+    sum
+}
@@ -0,0 +1,170 @@
+use alloc::{collections::BTreeMap, string::String};
+use core::fmt::Write;
+
+use crate::fft::{num_complex::Complex, num_traits::Float};
+
+use crate::ConvolutionLength;
+
+/// Algorithm a planner built for a contour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Algorithm {
+    Goertzel,
+    Naive,
+    Direct,
+    Bluestein,
+    Mixer,
+    Rotation,
+}
+
+impl Algorithm {
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Goertzel => "goertzel",
+            Algorithm::Naive => "naive",
+            Algorithm::Direct => "direct",
+            Algorithm::Bluestein => "bluestein",
+            Algorithm::Mixer => "mixer",
+            Algorithm::Rotation => "rotation",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Algorithm::Goertzel,
+            Algorithm::Naive,
+            Algorithm::Direct,
+            Algorithm::Bluestein,
+            Algorithm::Mixer,
+            Algorithm::Rotation,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.name() == name)
+    }
+}
+
+/// What was chosen for a contour, and how long one transform took if it was measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WisdomEntry {
+    pub algorithm: Algorithm,
+    pub length: ConvolutionLength,
+    pub nanos: Option<u64>,
+}
+
+/// Planning decisions keyed by `n`, `m` and the exact contour, which a planner in
+/// [`PlannerStrategy::Auto`](crate::PlannerStrategy::Auto) follows instead of its heuristics.
+///
+/// The text form starts with a `rustczt-wisdom 1` line followed by one line per entry:
+/// `n m a.re a.im w.re w.im algorithm length nanos`, the contour as the hexadecimal bits of
+/// `f64`s and `-` for an unmeasured entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Wisdom {
+    entries: BTreeMap<(usize, usize, [u64; 4]), WisdomEntry>,
+}
+
+const HEADER: &str = "rustczt-wisdom 1";
+
+fn key<T: Float>(n: usize, m: usize, a: Complex<T>, w: Complex<T>) -> (usize, usize, [u64; 4]) {
+    let bits = |x: T| x.to_f64().unwrap().to_bits();
+    (n, m, [bits(a.re), bits(a.im), bits(w.re), bits(w.im)])
+}
+
+impl Wisdom {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get<T: Float>(
+        &self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+    ) -> Option<WisdomEntry> {
+        self.entries.get(&key(n, m, a, w)).copied()
+    }
+
+    pub fn insert<T: Float>(
+        &mut self,
+        n: usize,
+        m: usize,
+        a: Complex<T>,
+        w: Complex<T>,
+        entry: WisdomEntry,
+    ) {
+        self.entries.insert(key(n, m, a, w), entry);
+    }
+
+    /// Adds the entries of `other`, replacing those for the same contour.
+    pub fn merge(&mut self, other: &Wisdom) {
+        self.entries
+            .extend(other.entries.iter().map(|(&key, &entry)| (key, entry)));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for (&(n, m, contour), entry) in &self.entries {
+            let length = match entry.length {
+                ConvolutionLength::Smooth => "smooth",
+                ConvolutionLength::PowerOfTwo => "pow2",
+            };
+            write!(text, "{n} {m}").unwrap();
+            for bits in contour {
+                write!(text, " {bits:016x}").unwrap();
+            }
+            write!(text, " {} {length} ", entry.algorithm.name()).unwrap();
+            match entry.nanos {
+                Some(nanos) => writeln!(text, "{nanos}").unwrap(),
+                None => text.push_str("-\n"),
+            }
+        }
+        text
+    }
+
+    /// Parses [`to_text`](Self::to_text), or returns `None` if `text` is malformed or of
+    /// another version.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()?.trim() != HEADER {
+            return None;
+        }
+        let mut wisdom = Self::new();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let n = fields.next()?.parse().ok()?;
+            let m = fields.next()?.parse().ok()?;
+            let mut contour = [0; 4];
+            for bits in &mut contour {
+                *bits = u64::from_str_radix(fields.next()?, 16).ok()?;
+            }
+            let algorithm = Algorithm::from_name(fields.next()?)?;
+            let length = match fields.next()? {
+                "smooth" => ConvolutionLength::Smooth,
+                "pow2" => ConvolutionLength::PowerOfTwo,
+                _ => return None,
+            };
+            let nanos = match fields.next()? {
+                "-" => None,
+                nanos => Some(nanos.parse().ok()?),
+            };
+            if fields.next().is_some() {
+                return None;
+            }
+            let entry = WisdomEntry {
+                algorithm,
+                length,
+                nanos,
+            };
+            wisdom.entries.insert((n, m, contour), entry);
+        }
+        Some(wisdom)
+    }
+}
@@ -0,0 +1,150 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::mem::MaybeUninit;
+
+use crate::fft::{num_complex::Complex, num_traits::Float, FftNum};
+
+use crate::{
+    cost::CostEstimate,
+    peak::{PeakEstimate, PeakInterpolator},
+    Czt, CztParams,
+};
+
+/// A CZT plan evaluating `m` equally spaced bins of the unit circle, together with its frequency
+/// grid.
+///
+/// Frequencies are reported in the units the plan was requested in: Hz for
+/// [`plan_zoom_fft_hz`](crate::CztPlanner::plan_zoom_fft_hz), cycles per sample otherwise.
+pub struct ZoomFft<T: FftNum> {
+    czt: Arc<dyn Czt<T>>,
+    start: T,
+    step: T,
+    sample_rate: T,
+}
+
+impl<T: FftNum> ZoomFft<T> {
+    /// `start` and `step` are normalized to cycles per sample.
+    pub fn new(czt: Arc<dyn Czt<T>>, start: T, step: T, sample_rate: T) -> Self {
+        Self {
+            czt,
+            start,
+            step,
+            sample_rate,
+        }
+    }
+
+    pub fn sample_rate(&self) -> T {
+        self.sample_rate
+    }
+
+    /// Spacing between adjacent bins.
+    pub fn bin_width(&self) -> T {
+        self.step * self.sample_rate
+    }
+
+    pub fn freq_of_bin(&self, k: usize) -> T {
+        (self.start + self.step * T::from_usize(k).unwrap()) * self.sample_rate
+    }
+
+    pub fn freqs(&self) -> Vec<T> {
+        (0..self.get_output_len())
+            .map(|k| self.freq_of_bin(k))
+            .collect()
+    }
+}
+
+impl<T: FftNum + Float> ZoomFft<T> {
+    /// Interpolates the peak at bin `k` of `spectrum`, an output of this plan. Peaks in the first
+    /// or last bin are returned at the bin itself.
+    pub fn interpolate_peak(
+        &self,
+        spectrum: &[Complex<T>],
+        k: usize,
+        interpolator: PeakInterpolator,
+    ) -> PeakEstimate<T> {
+        assert_eq!(spectrum.len(), self.get_output_len());
+        if 0 < k && k + 1 < spectrum.len() {
+            let bins = [spectrum[k - 1], spectrum[k], spectrum[k + 1]];
+            interpolator.interpolate(bins, self.freq_of_bin(k), self.bin_width())
+        } else {
+            PeakEstimate {
+                offset: T::zero(),
+                frequency: self.freq_of_bin(k),
+                magnitude: spectrum[k].norm(),
+            }
+        }
+    }
+}
+
+impl<T: FftNum> Czt<T> for ZoomFft<T> {
+    fn process_with_scratch(&self, buffer: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        self.czt.process_with_scratch(buffer, scratch);
+    }
+
+    fn process_outofplace_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) {
+        self.czt
+            .process_outofplace_with_scratch(input, output, scratch);
+    }
+
+    fn process_with_uninit_scratch(
+        &self,
+        buffer: &mut [Complex<T>],
+        scratch: &mut [MaybeUninit<Complex<T>>],
+    ) {
+        self.czt.process_with_uninit_scratch(buffer, scratch);
+    }
+
+    fn process_batch(&self, buffers: &mut [Complex<T>], stride: usize, count: usize) {
+        self.czt.process_batch(buffers, stride, count);
+    }
+
+    fn process_batch_with_scratch(
+        &self,
+        buffers: &mut [Complex<T>],
+        stride: usize,
+        count: usize,
+        scratch: &mut [Complex<T>],
+    ) {
+        self.czt
+            .process_batch_with_scratch(buffers, stride, count, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.czt.get_inplace_scratch_len()
+    }
+
+    fn get_outofplace_scratch_len(&self) -> usize {
+        self.czt.get_outofplace_scratch_len()
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        self.czt.get_memory_usage()
+    }
+
+    fn cost(&self) -> CostEstimate {
+        self.czt.cost()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.czt.get_input_len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.czt.get_output_len()
+    }
+
+    fn params(&self) -> CztParams<T> {
+        self.czt.params()
+    }
+
+    fn verify(&self, tolerance: T) -> bool
+    where
+        T: Float,
+    {
+        self.czt.verify(tolerance)
+    }
+}
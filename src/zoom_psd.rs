@@ -0,0 +1,113 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::fft::{
+    num_complex::Complex,
+    num_traits::{Float, Zero},
+    FftNum,
+};
+
+use crate::{zoom::ZoomFft, Czt};
+
+/// Welch power spectral density estimate over a zoom band.
+///
+/// The record is cut into segments of `segment_len` samples, `hop` samples apart; each windowed
+/// segment runs through the zoom plan and the periodograms are averaged. With the plan in Hz the
+/// result is in units²/Hz, matching `scipy.signal.welch(..., detrend=False)`. Segments are not
+/// detrended.
+pub struct ZoomPsd<T: FftNum> {
+    zoom: Arc<ZoomFft<T>>,
+    hop: usize,
+    /// `1 / (fs * sum(window^2))`
+    scale: T,
+}
+
+impl<T: FftNum + Float> ZoomPsd<T> {
+    /// `window` must be the window folded into `zoom`.
+    pub fn new(zoom: Arc<ZoomFft<T>>, hop: usize, window: &[T]) -> Self {
+        assert!(hop > 0, "hop size must be positive");
+        assert_eq!(window.len(), zoom.get_input_len());
+        let energy = window.iter().fold(T::zero(), |acc, &w| acc + w * w);
+        let scale = (zoom.sample_rate() * energy).recip();
+        Self { zoom, hop, scale }
+    }
+
+    pub fn segment_len(&self) -> usize {
+        self.zoom.get_input_len()
+    }
+
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    pub fn freqs(&self) -> Vec<T> {
+        self.zoom.freqs()
+    }
+
+    /// Number of segments averaged for a record of `len` samples.
+    pub fn num_segments(&self, len: usize) -> usize {
+        if len < self.segment_len() {
+            0
+        } else {
+            (len - self.segment_len()) / self.hop + 1
+        }
+    }
+
+    /// Two-sided density of a complex record.
+    pub fn process(&self, signal: &[Complex<T>]) -> Vec<T> {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        self.accumulate(signal.len(), &mut scratch, |start, buffer| {
+            buffer.copy_from_slice(&signal[start..start + buffer.len()])
+        })
+    }
+
+    /// One-sided density of a real record: bins strictly between 0 and Nyquist carry the power
+    /// of their negative-frequency mirror as well. The band should lie within `[0, fs/2]`.
+    pub fn process_real(&self, signal: &[T]) -> Vec<T> {
+        let mut scratch = vec![Complex::zero(); self.get_scratch_len()];
+        let mut psd = self.accumulate(signal.len(), &mut scratch, |start, buffer| {
+            for (y, &x) in buffer.iter_mut().zip(&signal[start..]) {
+                *y = Complex::new(x, T::zero());
+            }
+        });
+
+        let nyquist = self.zoom.sample_rate() / T::from_f64(2.0).unwrap();
+        let tolerance = self.zoom.bin_width().abs() / T::from_f64(2.0).unwrap();
+        for (p, f) in psd.iter_mut().zip(self.freqs()) {
+            if f.abs() > tolerance && (nyquist - f.abs()) > tolerance {
+                *p = *p + *p;
+            }
+        }
+        psd
+    }
+
+    fn accumulate(
+        &self,
+        len: usize,
+        scratch: &mut [Complex<T>],
+        mut load: impl FnMut(usize, &mut [Complex<T>]),
+    ) -> Vec<T> {
+        let segments = self.num_segments(len);
+        assert!(segments > 0, "record is shorter than one segment");
+
+        let (n, m) = (self.segment_len(), self.zoom.get_output_len());
+        let (buffer, scratch) = scratch.split_at_mut(n);
+        let (spectrum, scratch) = scratch.split_at_mut(m);
+        let mut psd = vec![T::zero(); m];
+        for i in 0..segments {
+            load(i * self.hop, buffer);
+            self.zoom
+                .process_outofplace_with_scratch(buffer, spectrum, scratch);
+            for (p, x) in psd.iter_mut().zip(&*spectrum) {
+                *p = *p + x.norm_sqr();
+            }
+        }
+
+        let factor = self.scale / T::from_usize(segments).unwrap();
+        psd.iter_mut().for_each(|p| *p = *p * factor);
+        psd
+    }
+
+    fn get_scratch_len(&self) -> usize {
+        self.segment_len() + self.zoom.get_output_len() + self.zoom.get_outofplace_scratch_len()
+    }
+}
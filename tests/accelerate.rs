@@ -0,0 +1,33 @@
+#![cfg(all(feature = "accelerate", target_vendor = "apple"))]
+
+use rustczt::{
+    accelerate::{AccelerateBluesteins, AccelerateProvider},
+    naive_czt::NaiveCzt,
+    Czt, CztPlanner, PlannerStrategy,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_accelerate_backend_accuracy() {
+    let signal: Vec<_> = (0..100)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let mut expected = signal.clone();
+    NaiveCzt::new(signal.len(), signal.len(), a, w).process(&mut expected);
+
+    let mut planner = CztPlanner::with_fft_provider(AccelerateProvider::new());
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let planned = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let split = AccelerateBluesteins::new(signal.len(), signal.len(), a, w);
+
+    for czt_obj in [planned.as_ref(), &split as &dyn Czt<f64>] {
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        for (ex, ac) in expected.iter().zip(&actual) {
+            assert!((ex - ac).norm() < 1e-8, "{ex} != {ac}");
+        }
+    }
+}
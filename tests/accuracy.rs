@@ -3,13 +3,58 @@ use rand::{
     rngs::StdRng,
     SeedableRng,
 };
-use rustczt::{naive_czt::NaiveCzt, Czt, CztPlanner};
+use rustczt::{
+    blocked::BlockedCzt,
+    bluesteins::BluesteinsAlgorithm,
+    compat::{matlab, scipy},
+    cost::{self, estimate_cost},
+    cqt::CqtPlanner,
+    czt_fft::CztFft,
+    dct::{DctPlanner, DctType},
+    dft::DftCzt,
+    direct::DirectCzt,
+    double_double::{DoubleDouble, DoubleDoubleCzt},
+    error_report::ErrorReport,
+    fixed_point::{to_complex_f64, FixedPoint, FixedPointCzt, Overflow},
+    freqz::{freqz_czt, Contour},
+    frft::FrftPlanner,
+    goertzel::GoertzelAlgorithm,
+    harmonics::analyze_harmonics,
+    interpolate::{at_times, interpolate},
+    iq::{decode_iq, read_iq, Endianness, IqFormat},
+    mixer::MixerCzt,
+    naive_czt::NaiveCzt,
+    ntt::{evaluate_poly_mod, NttCzt},
+    peak::PeakInterpolator,
+    pipeline::{PipelineBuilder, PipelineStats},
+    poly::evaluate_poly,
+    range_doppler::FmcwConfig,
+    realtime::channel,
+    refine::refine_frequency,
+    rotation::RotationCzt,
+    sliding::SlidingCzt,
+    spiral::SpiralCzt,
+    stream::{CztStream, SampleFormat},
+    warped::{FrequencyScale, WarpedSpectrum},
+    wisdom::{Algorithm, Wisdom, WisdomEntry},
+    ConvolutionLength, Czt, CztDirection, CztError, CztParams, CztPlanner, FftProvider,
+    Normalization, PlanError, PlannerStrategy, Window,
+};
 use rustfft::{
     num_complex::{Complex, ComplexFloat},
-    num_traits::Float,
-    FftNum, FftPlanner,
+    num_traits::{Float, Zero},
+    Fft, FftDirection, FftNum, FftPlanner,
+};
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    mem::MaybeUninit,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
 };
-use std::fmt::Display;
 
 const RNG_SEED: [u8; 32] = [
     1, 9, 1, 0, 1, 1, 4, 3, 1, 4, 9, 8, 4, 1, 4, 8, 2, 8, 1, 2, 2, 2, 6, 1, 2, 3, 4, 5, 6, 7, 8, 9,
@@ -20,27 +65,29 @@ const RNG_SEED: [u8; 32] = [
 /// Implementation is O(n^2) and is simply looping over the expression.
 fn naive_czt<T>(buffer: &[Complex<T>], a: &Complex<T>, w: &Complex<T>) -> Vec<Complex<T>>
 where
-    T: FftNum,
+    T: FftNum + Float,
 {
-    let czt_obj = NaiveCzt::new(buffer.len(), *a, *w);
+    let czt_obj = NaiveCzt::new(buffer.len(), buffer.len(), *a, *w);
     let mut out = Vec::from(buffer);
     czt_obj.process(&mut out);
     out
 }
 
-fn compare_float_vector<T>(expected: &[T], actual: &[T])
+/// Asserts that the largest error of `actual` is within `1e-5` of the largest expected value,
+/// or of 1 for spectra smaller than that.
+fn assert_spectra_match<T>(expected: &[Complex<T>], actual: &[Complex<T>])
 where
-    T: ComplexFloat + Display + std::fmt::Debug,
-    T::Real: From<f64>,
+    T: FftNum + Float + Display,
 {
-    let threshold = T::Real::try_from(0.00001).unwrap();
-
-    for (i, (&ex, &ac)) in expected.iter().zip(actual.iter()).enumerate() {
-        assert!(
-            Float::abs(ac.re() - ex.re()) < threshold || Float::abs(ac.im() - ex.im()) < threshold,
-            "Element {i} is not equal: {ex} != {ac}"
-        );
-    }
+    let report = ErrorReport::compare(expected, actual);
+    let peak = expected
+        .iter()
+        .map(|x| x.norm())
+        .fold(T::one(), |peak, norm| peak.max(norm));
+    assert!(
+        report.max_abs_error <= T::from_f64(1e-5).unwrap() * peak,
+        "{report}, relative to {peak}"
+    );
 }
 
 fn random_signal<T: FftNum + SampleUniform>(length: usize) -> Vec<Complex<T>> {
@@ -57,24 +104,32 @@ fn random_signal<T: FftNum + SampleUniform>(length: usize) -> Vec<Complex<T>> {
     sig
 }
 
+fn random_real_signal<T: FftNum + SampleUniform>(length: usize) -> Vec<T> {
+    random_signal::<T>(length).iter().map(|c| c.re).collect()
+}
+
+fn promote<T: FftNum>(signal: &[T]) -> Vec<Complex<T>> {
+    signal.iter().map(|&x| Complex::new(x, T::zero())).collect()
+}
+
 #[test]
 fn test_unit_circle_contour_czt_accuracy() {
     let signal = random_signal(64);
     let mut planner = CztPlanner::new();
     let a = Complex::from_polar(1.0, 5.0);
-    let w = Complex::from_polar(1.0, -1.0 * std::f64::consts::PI / signal.len() as f64);
+    let w = Complex::from_polar(1.0, -std::f64::consts::PI / signal.len() as f64);
     let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
 
     let mut actual = signal.clone();
     czt_obj.process(&mut actual);
     let expected = naive_czt(&signal, &a, &w);
-    compare_float_vector(&expected, &actual);
+    assert_spectra_match(&expected, &actual);
 }
 
 #[test]
 fn test_zoom_fft_accuracy() {
     let signal = random_signal(64);
-    let a = Complex::from_polar(1.0, -1.0 * std::f64::consts::PI);
+    let a = Complex::from_polar(1.0, -std::f64::consts::PI);
     let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / (signal.len() - 1) as f64);
 
     let expected = naive_czt(&signal, &a, &w);
@@ -84,13 +139,13 @@ fn test_zoom_fft_accuracy() {
     let mut actual = signal.clone();
     czt_obj.process(&mut actual);
 
-    compare_float_vector(&expected, &actual);
+    assert_spectra_match(&expected, &actual);
 }
 
 #[test]
 fn test_partial_zoom_fft_accuracy() {
     let signal = random_signal(64);
-    let a = Complex::from_polar(1.0, -1.0 * std::f64::consts::PI);
+    let a = Complex::from_polar(1.0, -std::f64::consts::PI);
     let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / (signal.len() - 1) as f64);
 
     let expected = naive_czt(&signal, &a, &w);
@@ -100,7 +155,29 @@ fn test_partial_zoom_fft_accuracy() {
     let mut actual = signal.clone();
     czt_obj.process(&mut actual);
 
-    compare_float_vector(&expected[..32], &actual[..32]);
+    assert_spectra_match(&expected[..32], &actual[..32]);
+}
+
+#[test]
+fn test_extended_zoom_fft_accuracy() {
+    let signal = random_signal(32);
+    let a = Complex::from_polar(1.0, -std::f64::consts::PI);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / (signal.len() - 1) as f64);
+
+    let mut padded = signal.clone();
+    padded.resize(96, Complex::new(0.0, 0.0));
+    let expected = naive_czt(&padded, &a, &w);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_zoom_fft_with_m(signal.len(), 96, -0.5, 0.5);
+    assert_eq!(czt_obj.get_input_len(), 32);
+    assert_eq!(czt_obj.get_output_len(), 96);
+    assert_eq!(czt_obj.get_buffer_len(), 96);
+
+    let mut actual = padded.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
 }
 
 #[test]
@@ -114,7 +191,7 @@ fn test_fft_like_czt_accuracy() {
     let mut actual = signal.clone();
     czt_obj.process(&mut actual);
     let expected = naive_czt(&signal, &a, &w);
-    compare_float_vector(&expected, &actual);
+    assert_spectra_match(&expected, &actual);
 }
 
 #[test]
@@ -129,7 +206,35 @@ fn test_partial_czt_accuracy() {
     czt_obj.process(&mut actual);
     let expected = naive_czt(&signal, &a, &w);
 
-    compare_float_vector(&expected[..32], &actual[..32]);
+    assert_spectra_match(&expected[..32], &actual[..32]);
+}
+
+#[test]
+fn test_extended_czt_accuracy() {
+    let signal = random_signal(48);
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(1.0, 0.25);
+    let w = Complex::from_polar(1.0, -std::f64::consts::PI / 100.0);
+    let czt_obj = planner.plan_czt_forward(signal.len(), 100, a, w);
+    assert_eq!(czt_obj.get_input_len(), 48);
+    assert_eq!(czt_obj.get_output_len(), 100);
+
+    let mut padded = signal.clone();
+    padded.resize(100, Complex::new(0.0, 0.0));
+    let mut actual = padded.clone();
+    czt_obj.process(&mut actual);
+    let expected = naive_czt(&padded, &a, &w);
+
+    assert_spectra_match(&expected, &actual);
+}
+
+#[test]
+fn test_partial_czt_buffer_len() {
+    let mut planner = CztPlanner::<f64>::new();
+    let czt_obj = planner.plan_czt_forward(64, 32, Complex::new(1.0, 0.0), Complex::new(0.0, 1.0));
+    assert_eq!(czt_obj.get_input_len(), 64);
+    assert_eq!(czt_obj.get_output_len(), 32);
+    assert_eq!(czt_obj.get_buffer_len(), 64);
 }
 
 #[test]
@@ -144,5 +249,3488 @@ fn test_naive_czt_accuracy() {
     let a = Complex::new(1.0, 0.0);
     let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / signal.len() as f64);
     let expected = naive_czt(&signal, &a, &w);
-    compare_float_vector(&expected, &actual);
+    assert_spectra_match(&expected, &actual);
+}
+
+/// Inverse chirp Z transform
+///
+/// Solves the Vandermonde system `X_k = sum_j x_j (a * w^-k)^-j` by Gaussian elimination.
+fn naive_iczt<T>(buffer: &[Complex<T>], a: &Complex<T>, w: &Complex<T>) -> Vec<Complex<T>>
+where
+    T: FftNum + Float,
+{
+    let n = buffer.len();
+    let mut matrix: Vec<Vec<Complex<T>>> = (0..n)
+        .map(|k| {
+            let z = a * w.powi(-(k as i32));
+            let mut row: Vec<_> = (0..n).map(|j| z.powi(-(j as i32))).collect();
+            row.push(buffer[k]);
+            row
+        })
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| {
+                matrix[i][col]
+                    .norm()
+                    .partial_cmp(&matrix[j][col].norm())
+                    .unwrap()
+            })
+            .unwrap();
+        matrix.swap(col, pivot);
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for row in lower.iter_mut() {
+            let factor = row[col] / pivot_row[col];
+            for (x, &p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                *x = *x - p * factor;
+            }
+        }
+    }
+
+    let mut out = vec![Complex::new(T::zero(), T::zero()); n];
+    for row in (0..n).rev() {
+        let mut acc = matrix[row][n];
+        for i in row + 1..n {
+            acc = acc - matrix[row][i] * out[i];
+        }
+        out[row] = acc / matrix[row][row];
+    }
+    out
+}
+
+#[test]
+fn test_inverse_czt_round_trip_accuracy() {
+    let signal = random_signal(64);
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / signal.len() as f64);
+    let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let iczt_obj = planner.plan_czt_inverse_exact(signal.len(), a, w);
+
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+    iczt_obj.process(&mut actual);
+    assert_spectra_match(&signal, &actual);
+}
+
+#[test]
+fn test_inverse_czt_round_trip_large_n() {
+    // The running products behind the Lagrange weights pass f64's range near n = 4500.
+    let mut planner = CztPlanner::new();
+    for (n, a) in [
+        (5000, Complex::new(1.0, 0.0)),
+        (5000, Complex::from_polar(1.0, 0.3)),
+        (20000, Complex::new(1.0, 0.0)),
+    ] {
+        let signal = random_signal(n);
+        let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / n as f64);
+        let czt_obj = planner.plan_czt_forward(n, n, a, w);
+        let iczt_obj = planner.plan_czt_inverse_exact(n, a, w);
+
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        iczt_obj.process(&mut actual);
+        assert_spectra_match(&signal, &actual);
+    }
+}
+
+#[test]
+fn test_inverse_czt_accuracy() {
+    let signal = random_signal(16);
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(1.05, 0.7);
+    let w = Complex::from_polar(0.995, -1.8 * std::f64::consts::PI / signal.len() as f64);
+    let iczt_obj = planner.plan_czt_inverse_exact(signal.len(), a, w);
+
+    let mut actual = signal.clone();
+    iczt_obj.process(&mut actual);
+    let expected = naive_iczt(&signal, &a, &w);
+    assert_spectra_match(&expected, &actual);
+}
+
+#[test]
+fn test_inverse_czt_verify_and_matrix() {
+    let n = 16;
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(1.05, 0.7);
+    let w = Complex::from_polar(0.995, -1.8 * std::f64::consts::PI / n as f64);
+    let iczt_obj = planner.plan_czt_inverse_exact(n, a, w);
+    assert!(iczt_obj.verify(1e-8));
+    // Its matrix inverts the forward one.
+    let forward = NaiveCzt::new(n, n, a, w).to_matrix();
+    let inverse = iczt_obj.to_matrix();
+    for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))) {
+        let product: Complex<f64> = (0..n)
+            .map(|k| inverse[i * n + k] * forward[k * n + j])
+            .sum();
+        let identity = if i == j { 1.0 } else { 0.0 };
+        assert!((product - identity).norm() < 1e-8, "({i}, {j}): {product}");
+    }
+}
+
+fn check_real_czt(n: usize, m: usize, a: Complex<f64>, w: Complex<f64>) {
+    let signal = random_real_signal(n);
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_real_czt_forward(n, m, a, w);
+    assert_eq!(czt_obj.get_input_len(), n);
+    assert_eq!(czt_obj.get_output_len(), m);
+
+    let mut actual = vec![Complex::new(0.0, 0.0); m];
+    czt_obj.process(&signal, &mut actual);
+
+    let mut padded = promote(&signal);
+    padded.resize(n.max(m), Complex::new(0.0, 0.0));
+    let expected = naive_czt(&padded, &a, &w);
+    assert_spectra_match(&expected[..m], &actual);
+}
+
+#[test]
+fn test_real_zoom_fft_accuracy() {
+    let a = Complex::from_polar(1.0, -0.2 * std::f64::consts::PI);
+    let w = Complex::from_polar(1.0, -0.4 * std::f64::consts::PI / 39.0);
+    check_real_czt(64, 40, a, w);
+    check_real_czt(63, 40, a, w);
+}
+
+#[test]
+fn test_real_fft_like_czt_accuracy() {
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / 50.0);
+    check_real_czt(50, 50, Complex::new(1.0, 0.0), w);
+}
+
+#[test]
+fn test_real_asymmetric_czt_accuracy() {
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(0.999, -0.01);
+    check_real_czt(64, 80, a, w);
+}
+
+#[test]
+fn test_batch_czt_accuracy() {
+    let (n, m, stride, count) = (40, 24, 48, 5);
+    let signals = random_signal(stride * count);
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(1.0, 0.1);
+    let w = Complex::from_polar(1.0, -0.02);
+    let czt_obj = planner.plan_czt_forward(n, m, a, w);
+
+    let mut actual = signals.clone();
+    czt_obj.process_batch(&mut actual, stride, count);
+
+    for (signal, actual) in signals.chunks(stride).zip(actual.chunks(stride)) {
+        let expected = naive_czt(&signal[..n], &a, &w);
+        assert_spectra_match(&expected[..m], &actual[..m]);
+        assert_eq!(signal[n..], actual[n..]);
+    }
+}
+
+#[test]
+fn test_batched_bluestein_accuracy() {
+    let (n, m, stride, count) = (40, 24, 48, 5);
+    let signals = random_signal(stride * count);
+    let a = Complex::from_polar(1.0, 0.1);
+    let w = Complex::from_polar(1.0, -0.02);
+    let czt_obj = BluesteinsAlgorithm::new(n, m, a, w, 0, &mut FftPlanner::new());
+
+    let mut batched = signals.clone();
+    czt_obj.process_batch(&mut batched, stride, count);
+    let mut sequential = signals.clone();
+    let mut scratch = vec![Complex::zero(); czt_obj.get_inplace_scratch_len()];
+    czt_obj.process_batch_with_scratch(&mut sequential, stride, count, &mut scratch);
+
+    for ((signal, batched), sequential) in signals
+        .chunks(stride)
+        .zip(batched.chunks(stride))
+        .zip(sequential.chunks(stride))
+    {
+        let expected = naive_czt(&signal[..n], &a, &w);
+        assert_spectra_match(&expected[..m], &batched[..m]);
+        assert_spectra_match(&expected[..m], &sequential[..m]);
+        assert_eq!(signal[n..], batched[n..]);
+    }
+}
+
+#[test]
+fn test_simd_planner_matches_portable_planner() {
+    let signal = random_signal(300);
+    let a = Complex::from_polar(1.0, 0.2);
+    let w = Complex::from_polar(1.0, -0.015);
+
+    let mut detected = CztPlanner::new();
+    let mut portable = CztPlanner::with_fft_provider(FftPlanner::new());
+    detected.set_strategy(PlannerStrategy::ForceBluestein);
+    portable.set_strategy(PlannerStrategy::ForceBluestein);
+
+    let mut expected = signal.clone();
+    portable
+        .plan_czt_forward(300, 300, a, w)
+        .process(&mut expected);
+    let mut actual = signal.clone();
+    detected
+        .plan_czt_forward(300, 300, a, w)
+        .process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
+    assert_spectra_match(&naive_czt(&signal, &a, &w), &actual);
+}
+
+#[test]
+fn test_pointwise_kernels_handle_partial_chunks() {
+    // The vector kernels take 4 (f64) or 8 (f32) products at a time; every remainder is hit.
+    fn check<T: FftNum + Float + Display + SampleUniform>() {
+        let a = Complex::from_polar(T::one(), T::from_f64(0.2).unwrap());
+        let w = Complex::from_polar(T::one(), T::from_f64(-0.15).unwrap());
+        for n in 1..=17 {
+            let signal = random_signal::<T>(n);
+            let mut actual = signal.clone();
+            BluesteinsAlgorithm::new(n, n, a, w, 0, &mut FftPlanner::new()).process(&mut actual);
+            assert_spectra_match(&naive_czt(&signal, &a, &w), &actual);
+        }
+    }
+    check::<f64>();
+    check::<f32>();
+}
+
+#[test]
+fn test_bluestein_coefficients_are_aligned() {
+    let czt_obj = BluesteinsAlgorithm::<f32>::new(
+        37,
+        11,
+        Complex::new(1.0, 0.0),
+        Complex::from_polar(1.0, -0.1),
+        0,
+        &mut FftPlanner::new(),
+    );
+    for coefficients in [
+        czt_obj.y_coefficients(),
+        czt_obj.v_coefficients(),
+        czt_obj.x_coefficients(),
+    ] {
+        assert_eq!(coefficients.as_ptr() as usize % 64, 0);
+    }
+}
+
+#[test]
+fn test_czt2_accuracy() {
+    let (rows, cols) = (12, 20);
+    let signal = random_signal(rows * cols);
+    let a_row = Complex::from_polar(1.0, 0.4);
+    let w_row = Complex::from_polar(1.0, -0.05);
+    let a_col = Complex::from_polar(1.0, -0.2);
+    let w_col = Complex::from_polar(1.0, -0.1);
+
+    let mut expected: Vec<_> = signal
+        .chunks(cols)
+        .flat_map(|row| naive_czt(row, &a_row, &w_row))
+        .collect();
+    for c in 0..cols {
+        let column: Vec<_> = (0..rows).map(|r| expected[r * cols + c]).collect();
+        for (r, x) in naive_czt(&column, &a_col, &w_col).into_iter().enumerate() {
+            expected[r * cols + c] = x;
+        }
+    }
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt2_forward(rows, cols, a_row, w_row, a_col, w_col);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
+}
+
+#[test]
+fn test_zoom_fft_hz_accuracy() {
+    let signal = random_signal(64);
+    let (sample_rate, f_start, f_end, m) = (48000.0, 1000.0, 3000.0, 41);
+    let a = Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * f_start / sample_rate);
+    let w = Complex::from_polar(
+        1.0,
+        -2.0 * std::f64::consts::PI * (f_end - f_start) / sample_rate / (m - 1) as f64,
+    );
+
+    let mut padded = signal.clone();
+    padded.resize(m.max(signal.len()), Complex::new(0.0, 0.0));
+    let expected = naive_czt(&padded, &a, &w);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_zoom_fft_hz(signal.len(), m, sample_rate, f_start, f_end);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected[..m], &actual[..m]);
+}
+
+#[test]
+#[should_panic(expected = "zoom band must lie within")]
+fn test_zoom_fft_hz_rejects_band_above_nyquist() {
+    let mut planner = CztPlanner::<f64>::new();
+    planner.plan_zoom_fft_hz(64, 32, 1000.0, 100.0, 600.0);
+}
+
+#[test]
+fn test_zoom_fft_freqs() {
+    let mut planner = CztPlanner::<f64>::new();
+    let zoom = planner.plan_zoom_fft_hz(64, 41, 48000.0, 1000.0, 3000.0);
+    let freqs = zoom.freqs();
+    assert_eq!(freqs.len(), 41);
+    assert!((freqs[0] - 1000.0).abs() < 1e-9);
+    assert!((freqs[40] - 3000.0).abs() < 1e-9);
+    assert!((zoom.freq_of_bin(20) - 2000.0).abs() < 1e-9);
+    assert!((zoom.bin_width() - 50.0).abs() < 1e-9);
+
+    let zoom = planner.plan_zoom_fft(65, -0.5, 0.5);
+    assert!((zoom.freq_of_bin(0) + 0.5).abs() < 1e-12);
+    assert!((zoom.freq_of_bin(32)).abs() < 1e-12);
+    assert!((zoom.freq_of_bin(64) - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_zoom_fft_freqs_match_tone() {
+    let (sample_rate, tone) = (1000.0, 123.4);
+    let signal: Vec<_> = (0..256)
+        .map(|i| {
+            Complex::from_polar(
+                1.0,
+                2.0 * std::f64::consts::PI * tone * i as f64 / sample_rate,
+            )
+        })
+        .collect();
+    let mut planner = CztPlanner::new();
+    let zoom = planner.plan_zoom_fft_hz(signal.len(), 201, sample_rate, 113.4, 133.4);
+    let mut spectrum = signal.clone();
+    zoom.process(&mut spectrum);
+
+    let peak = (0..201)
+        .max_by(|&i, &j| spectrum[i].norm().partial_cmp(&spectrum[j].norm()).unwrap())
+        .unwrap();
+    assert!((zoom.freq_of_bin(peak) - tone).abs() < 1e-6);
+}
+
+#[test]
+fn test_window_coefficients() {
+    let hann = Window::<f64>::Hann.coefficients(9);
+    assert!(hann[0].abs() < 1e-12 && hann[8].abs() < 1e-12);
+    assert!((hann[4] - 1.0).abs() < 1e-12);
+
+    let kaiser = Window::<f64>::Kaiser(8.6).coefficients(9);
+    assert!((kaiser[4] - 1.0).abs() < 1e-12);
+    assert!((kaiser[0] - kaiser[8]).abs() < 1e-12);
+    assert!((kaiser[0] - 0.00133251399790).abs() < 1e-12);
+
+    for window in [Window::Hamming, Window::BlackmanHarris, Window::FlatTop] {
+        let coefficients: Vec<f64> = window.coefficients(9);
+        assert!((coefficients[4] - 1.0).abs() < 1e-6, "{window:?}");
+    }
+}
+
+#[test]
+fn test_windowed_zoom_fft_accuracy() {
+    let signal = random_signal(64);
+    let a = Complex::from_polar(1.0, -0.2 * std::f64::consts::PI);
+    let w = Complex::from_polar(1.0, -0.4 * std::f64::consts::PI / (signal.len() - 1) as f64);
+
+    for window in [Window::Hann, Window::Kaiser(8.6), Window::FlatTop] {
+        let windowed: Vec<_> = signal
+            .iter()
+            .zip(window.coefficients(signal.len()))
+            .map(|(&x, weight)| x * weight)
+            .collect();
+        let expected = naive_czt(&windowed, &a, &w);
+
+        let mut planner = CztPlanner::new();
+        let czt_obj = planner.plan_zoom_fft_windowed(signal.len(), signal.len(), -0.1, 0.1, window);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+
+        assert_spectra_match(&expected, &actual);
+    }
+}
+
+#[test]
+fn test_normalized_czt_accuracy() {
+    let signal = random_signal(64);
+    let a = Complex::from_polar(1.0, 0.0);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / signal.len() as f64);
+    let unscaled = naive_czt(&signal, &a, &w);
+
+    let cases = [
+        (Normalization::None, 1.0),
+        (Normalization::OneOverN, 1.0 / 64.0),
+        (Normalization::OneOverSqrtN, 1.0 / 8.0),
+        (Normalization::Custom(2.5), 2.5),
+    ];
+    let mut planner = CztPlanner::new();
+    for (normalization, factor) in cases {
+        let czt_obj =
+            planner.plan_czt_forward_normalized(signal.len(), signal.len(), a, w, normalization);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+
+        let expected: Vec<_> = unscaled.iter().map(|&x| x * factor).collect();
+        assert_spectra_match(&expected, &actual);
+    }
+}
+
+#[test]
+fn test_normalized_spiral_contour() {
+    let (n, a, w) = (
+        500,
+        Complex::new(1.0, 0.0),
+        Complex::from_polar(0.995, -0.01),
+    );
+    let signal = random_signal::<f64>(n);
+    let mut expected = signal.clone();
+    NaiveCzt::new(n, n, a, w).process(&mut expected);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt_forward_normalized(n, n, a, w, Normalization::OneOverSqrtN);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+    let scale = (n as f64).sqrt().recip();
+    for (k, (ex, ac)) in expected.iter().zip(&actual).enumerate() {
+        let ex = ex * scale;
+        assert!(
+            (ex - ac).norm() < 1e-8 * ex.norm().max(1.0),
+            "bin {k}: {ex} != {ac}"
+        );
+    }
+}
+
+#[test]
+fn test_backward_czt_accuracy() {
+    let signal = random_signal(48);
+    let a = Complex::from_polar(1.02, 0.4);
+    let w = Complex::from_polar(0.999, -0.03);
+    let expected = naive_czt(&signal, &a.inv(), &w.inv());
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt(signal.len(), signal.len(), a, w, CztDirection::Inverse);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
+}
+
+#[test]
+fn test_forward_backward_dft_round_trip() {
+    let signal = random_signal(64);
+    let a = Complex::new(1.0, 0.0);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / signal.len() as f64);
+
+    let mut planner = CztPlanner::new();
+    let forward = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let backward = planner.plan_czt_backward(signal.len(), signal.len(), a, w);
+    let mut actual = signal.clone();
+    forward.process(&mut actual);
+    backward.process(&mut actual);
+
+    let expected: Vec<_> = signal.iter().map(|&x| x * signal.len() as f64).collect();
+    assert_spectra_match(&expected, &actual);
+    assert_eq!(
+        CztDirection::Forward.opposite_direction(),
+        CztDirection::Inverse
+    );
+}
+
+#[test]
+fn test_offset_czt_accuracy() {
+    let signal = random_signal(64);
+    let a = Complex::from_polar(1.0, 0.2);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / 200.0);
+    let mut padded = signal.clone();
+    padded.resize(200, Complex::new(0.0, 0.0));
+    let expected = naive_czt(&padded, &a, &w);
+
+    let mut planner = CztPlanner::new();
+    for (k0, m) in [(0, 64), (50, 40), (120, 80)] {
+        let czt_obj = planner.plan_czt_forward_with_offset(signal.len(), m, a, w, k0);
+        let mut actual = signal.clone();
+        actual.resize(m.max(signal.len()), Complex::new(0.0, 0.0));
+        czt_obj.process(&mut actual);
+
+        assert_spectra_match(&expected[k0 as usize..][..m], &actual[..m]);
+    }
+}
+
+#[test]
+fn test_negative_offset_czt_accuracy() {
+    let signal = random_signal(32);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / 32.0);
+    let a = Complex::new(1.0, 0.0);
+    let shifted_a = a * w.powi(16);
+    let expected = naive_czt(&signal, &shifted_a, &w);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt_forward_with_offset(signal.len(), signal.len(), a, w, -16);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
+}
+
+#[test]
+fn test_naive_czt_independent_output_len() {
+    let signal = random_signal(24);
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.05);
+
+    for m in [10, 24, 60] {
+        let mut padded = signal.clone();
+        padded.resize(m.max(signal.len()), Complex::new(0.0, 0.0));
+        let expected = naive_czt(&padded, &a, &w);
+
+        let naive = NaiveCzt::new(signal.len(), m, a, w);
+        assert_eq!(naive.get_inplace_scratch_len(), m);
+        let mut actual = padded.clone();
+        naive.process(&mut actual);
+        assert_spectra_match(&expected[..m], &actual[..m]);
+
+        let mut planner = CztPlanner::new();
+        let czt_obj = planner.plan_czt_forward(signal.len(), m, a, w);
+        let mut actual = padded.clone();
+        czt_obj.process(&mut actual);
+        assert_spectra_match(&expected[..m], &actual[..m]);
+    }
+}
+
+#[test]
+fn test_few_bins_czt_accuracy() {
+    let signal = random_signal(256);
+    let a = Complex::from_polar(1.01, 0.7);
+    let w = Complex::from_polar(0.999, -0.01);
+    let expected = naive_czt(&signal, &a, &w);
+
+    let mut planner = CztPlanner::new();
+    for m in 1..=8 {
+        let czt_obj = planner.plan_czt_forward(signal.len(), m, a, w);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        assert_spectra_match(&expected[..m], &actual[..m]);
+    }
+}
+
+#[test]
+fn test_goertzel_places_far_bins_exactly() {
+    // Powers of w by repeated multiplication drift by about k eps; far bins must not.
+    let (n, m) = (4, 20000);
+    let a = Complex::new(1.0f32, 0.0);
+    let w = Complex::from_polar(1.0f32, -1e-3);
+    let signal = random_signal::<f32>(n);
+    let mut actual = signal.clone();
+    actual.resize(m, Complex::zero());
+    GoertzelAlgorithm::new(n, m, a, w).process(&mut actual);
+
+    let up = |z: Complex<f32>| Complex::new(z.re as f64, z.im as f64);
+    let mut planner = CztPlanner::<f64>::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt = planner.plan_czt_forward(n, m, up(a), up(w));
+    let mut expected: Vec<_> = signal.iter().map(|&x| up(x)).collect();
+    expected.resize(czt.get_buffer_len(), Complex::zero());
+    czt.process(&mut expected);
+    let actual: Vec<_> = actual.iter().map(|&x| up(x)).collect();
+    let report = ErrorReport::compare(&expected[..m], &actual);
+    let scale: f64 = signal.iter().map(|&x| up(x).norm()).sum();
+    assert!(report.max_abs_error < 1e-5 * scale, "{report}");
+}
+
+#[test]
+fn test_planner_reuses_identical_plans() {
+    let mut planner = CztPlanner::<f64>::new();
+    let a = Complex::from_polar(1.0, 0.1);
+    let w = Complex::from_polar(1.0, -0.01);
+    let first = planner.plan_czt_forward(64, 32, a, w);
+    let second = planner.plan_czt_forward(64, 32, a, w);
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let other = planner.plan_czt_forward(64, 33, a, w);
+    assert!(!Arc::ptr_eq(&first, &other));
+    let other = planner.plan_czt_forward(64, 32, a, w.conj());
+    assert!(!Arc::ptr_eq(&first, &other));
+}
+
+#[test]
+fn test_planner_strategies_agree() {
+    let signal = random_signal(40);
+    let a = Complex::from_polar(1.0, -0.4);
+    let w = Complex::from_polar(1.0, -0.02);
+    let expected = naive_czt(&signal, &a, &w);
+
+    let mut planner = CztPlanner::new();
+    assert_eq!(planner.strategy(), PlannerStrategy::Auto);
+    for strategy in [
+        PlannerStrategy::Auto,
+        PlannerStrategy::ForceNaive,
+        PlannerStrategy::ForceBluestein,
+    ] {
+        planner.set_strategy(strategy);
+        for m in [3, 40] {
+            let czt_obj = planner.plan_czt_forward(signal.len(), m, a, w);
+            let mut actual = signal.clone();
+            czt_obj.process(&mut actual);
+            assert_spectra_match(&expected[..m], &actual[..m]);
+        }
+    }
+}
+
+#[test]
+fn test_planner_strategy_is_part_of_cache_key() {
+    let mut planner = CztPlanner::<f64>::new();
+    let a = Complex::new(1.0, 0.0);
+    let w = Complex::from_polar(1.0, -0.1);
+    let auto = planner.plan_czt_forward(64, 64, a, w);
+    planner.set_strategy(PlannerStrategy::ForceNaive);
+    let naive = planner.plan_czt_forward(64, 64, a, w);
+    assert!(!Arc::ptr_eq(&auto, &naive));
+}
+
+#[test]
+fn test_czt_fft_adapter_accuracy() {
+    let len = 97;
+    let signal = random_signal(2 * len);
+    let mut fft_planner = FftPlanner::new();
+    let mut czt_planner = CztPlanner::new();
+
+    for direction in [FftDirection::Forward, FftDirection::Inverse] {
+        let sign = match direction {
+            FftDirection::Forward => -1.0,
+            FftDirection::Inverse => 1.0,
+        };
+        let w = Complex::from_polar(1.0, sign * 2.0 * std::f64::consts::PI / len as f64);
+        let czt_obj = czt_planner.plan_czt_forward(len, len, Complex::new(1.0, 0.0), w);
+        let fft: Arc<dyn Fft<f64>> = Arc::new(CztFft::new(czt_obj, direction));
+        assert_eq!(fft.len(), len);
+        assert_eq!(fft.fft_direction(), direction);
+
+        let mut expected = signal.clone();
+        fft_planner.plan_fft(len, direction).process(&mut expected);
+        let mut actual = signal.clone();
+        fft.process(&mut actual);
+        assert_spectra_match(&expected, &actual);
+
+        let mut immutable = vec![Complex::zero(); signal.len()];
+        let mut scratch = vec![Complex::zero(); fft.get_immutable_scratch_len()];
+        fft.process_immutable_with_scratch(&signal, &mut immutable, &mut scratch);
+        assert_eq!(immutable, actual);
+    }
+}
+
+struct CountingFftProvider {
+    planner: FftPlanner<f64>,
+    lengths: Rc<RefCell<Vec<usize>>>,
+}
+
+impl FftProvider<f64> for CountingFftProvider {
+    fn plan_forward(&mut self, len: usize) -> Arc<dyn Fft<f64>> {
+        self.lengths.borrow_mut().push(len);
+        self.planner.plan_fft_forward(len)
+    }
+
+    fn plan_inverse(&mut self, len: usize) -> Arc<dyn Fft<f64>> {
+        self.lengths.borrow_mut().push(len);
+        self.planner.plan_fft_inverse(len)
+    }
+}
+
+#[test]
+fn test_custom_fft_provider() {
+    let signal = random_signal(64);
+    let a = Complex::from_polar(1.0, 0.5);
+    let w = Complex::from_polar(1.0, -0.03);
+    let expected = naive_czt(&signal, &a, &w);
+
+    let lengths = Rc::new(RefCell::new(Vec::new()));
+    let mut planner = CztPlanner::with_fft_provider(CountingFftProvider {
+        planner: FftPlanner::new(),
+        lengths: Rc::clone(&lengths),
+    });
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    assert_spectra_match(&expected, &actual);
+    assert_eq!(*lengths.borrow(), vec![128, 128]);
+}
+
+/// Relative RMS error of a fixed-point plan against the float reference on the quantized input.
+fn fixed_point_error<S: FixedPoint>(n: usize, m: usize) -> f64 {
+    let a = Complex::from_polar(1.0, 0.4);
+    let w = Complex::from_polar(1.0, -0.02);
+    let input: Vec<Complex<S>> = random_signal::<f64>(n)
+        .iter()
+        .map(|x| {
+            Complex::new(
+                S::from_f64(x.re / 10.0 - 0.5, Overflow::Saturate),
+                S::from_f64(x.im / 10.0 - 0.5, Overflow::Saturate),
+            )
+        })
+        .collect();
+
+    let mut expected = to_complex_f64(&input, 0);
+    expected.resize(n.max(m), Complex::zero());
+    NaiveCzt::new(n, m, a, w).process(&mut expected);
+
+    let czt_obj = FixedPointCzt::<S>::new(n, m, a, w);
+    let mut buffer = input.clone();
+    buffer.resize(n.max(m), Complex::new(S::default(), S::default()));
+    let exponent = czt_obj.process(&mut buffer);
+    let actual = to_complex_f64(&buffer[..m], exponent);
+
+    let error: f64 = expected
+        .iter()
+        .zip(&actual)
+        .map(|(ex, ac)| (ex - ac).norm_sqr())
+        .sum();
+    let power: f64 = expected[..m].iter().map(|ex| ex.norm_sqr()).sum();
+    (error / power).sqrt()
+}
+
+#[test]
+fn test_fixed_point_czt_accuracy() {
+    for (n, m) in [(100, 100), (256, 64), (37, 90)] {
+        let q15 = fixed_point_error::<i16>(n, m);
+        let q31 = fixed_point_error::<i32>(n, m);
+        assert!(q15 < 5e-3, "Q15 error {q15} for n = {n}, m = {m}");
+        assert!(q31 < 1e-7, "Q31 error {q31} for n = {n}, m = {m}");
+    }
+}
+
+#[test]
+fn test_fixed_point_overflow_policy() {
+    assert_eq!(i16::from_f64(1.5, Overflow::Saturate), i16::MAX);
+    assert_eq!(i16::from_f64(-1.5, Overflow::Saturate), i16::MIN);
+    assert_eq!(i16::from_f64(1.5, Overflow::Wrap), i16::MIN / 2);
+    assert_eq!(i32::from_f64(-0.25, Overflow::Wrap), i32::MIN / 4);
+}
+
+#[test]
+fn test_chirp_phase_is_reduced_exactly() {
+    // On the DFT contour the chirp phase -pi k^2 / n can be reduced exactly in integers.
+    let n = 5000;
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / n as f64);
+    let bluesteins =
+        BluesteinsAlgorithm::new(n, n, Complex::new(1.0, 0.0), w, 0, &mut FftPlanner::new());
+
+    // The output chirp also carries the 1/l of the inverse FFT.
+    let l = bluesteins.v_coefficients().len() as f64;
+    for (k, &x) in bluesteins.x_coefficients().iter().enumerate() {
+        let turns = (k * k % (2 * n)) as f64 / (2 * n) as f64;
+        let expected = Complex::from_polar(1.0 / l, -2.0 * std::f64::consts::PI * turns);
+        assert!(
+            (x - expected).norm() < 1e-12,
+            "Chirp {k}: {expected} != {x}"
+        );
+    }
+}
+
+#[test]
+fn test_large_dft_contour_matches_fft() {
+    // Past n = 46341, k^2 no longer fits an i32.
+    let n = 100_000;
+    let signal = random_signal::<f64>(n);
+    let w = Complex::from_polar(1.0, -2.0 * std::f64::consts::PI / n as f64);
+
+    let mut expected = signal.clone();
+    FftPlanner::new().plan_fft_forward(n).process(&mut expected);
+
+    let mut planner = CztPlanner::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(n, n, Complex::new(1.0, 0.0), w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    let scale = expected[0].norm();
+    for (k, (ex, ac)) in expected.iter().zip(&actual).enumerate() {
+        assert!((ex - ac).norm() < 1e-9 * scale, "Bin {k}: {ex} != {ac}");
+    }
+}
+
+#[test]
+fn test_decaying_spiral_contour() {
+    // A single convolution would need chirp magnitudes |w|^(+-k^2 / 2) spanning e^(+-1000).
+    for (n, m, k0, radius) in [
+        (1000, 100, 0, 0.998),
+        (1000, 100, 450, 0.998),
+        (300, 700, -20, 0.998),
+        (600, 600, 0, 1.001),
+    ] {
+        let signal = random_signal::<f64>(n);
+        let a = Complex::from_polar(1.01, 0.2);
+        let w = Complex::from_polar(radius, -0.01);
+
+        let mut expected = signal.clone();
+        expected.resize(n.max(m), Complex::zero());
+        NaiveCzt::new(n, m, a * w.powi(-k0), w).process(&mut expected);
+
+        let mut planner = CztPlanner::new();
+        planner.set_strategy(PlannerStrategy::ForceBluestein);
+        let czt_obj = planner.plan_czt_forward_with_offset(n, m, a, w, k0 as isize);
+        let mut actual = signal.clone();
+        actual.resize(n.max(m), Complex::zero());
+        czt_obj.process(&mut actual);
+
+        for (k, (ex, ac)) in expected.iter().zip(&actual).take(m).enumerate() {
+            assert!(
+                (ex - ac).norm() < 1e-8 * ex.norm().max(1.0),
+                "n = {n}, m = {m}, k0 = {k0}, |w| = {radius}, bin {k}: {ex} != {ac}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_smooth_convolution_length() {
+    for (min_len, expected) in [(1, 1), (127, 128), (599, 600), (1025, 1080), (9999, 10000)] {
+        assert_eq!(ConvolutionLength::Smooth.for_len(min_len), expected);
+    }
+    assert_eq!(ConvolutionLength::PowerOfTwo.for_len(599), 1024);
+
+    let signal = random_signal(300);
+    let a = Complex::from_polar(1.0, 0.5);
+    let w = Complex::from_polar(1.0, -0.01);
+    let expected = naive_czt(&signal, &a, &w);
+
+    for (length, l) in [
+        (ConvolutionLength::Smooth, 600),
+        (ConvolutionLength::PowerOfTwo, 1024),
+    ] {
+        let lengths = Rc::new(RefCell::new(Vec::new()));
+        let mut planner = CztPlanner::with_fft_provider(CountingFftProvider {
+            planner: FftPlanner::new(),
+            lengths: Rc::clone(&lengths),
+        });
+        planner.set_strategy(PlannerStrategy::ForceBluestein);
+        planner.set_convolution_length(length);
+        let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+
+        assert_spectra_match(&expected, &actual);
+        assert_eq!(*lengths.borrow(), vec![l, l]);
+    }
+}
+
+#[test]
+fn test_memory_usage() {
+    let size = std::mem::size_of::<Complex<f64>>();
+    let a = Complex::from_polar(1.0, 0.5);
+    let w = Complex::from_polar(1.0, -0.01);
+
+    let mut planner = CztPlanner::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    planner.set_convolution_length(ConvolutionLength::PowerOfTwo);
+    let czt_obj = planner.plan_czt_forward(300, 100, a, w);
+    // Tables y, v and x, two length-512 FFTs, and at least one length-512 scratch buffer
+    let lower_bound = (300 + 512 + 100 + 2 * 512 + 512) * size;
+    assert!(czt_obj.get_memory_usage() >= lower_bound);
+    assert!(czt_obj.get_memory_usage() >= czt_obj.get_inplace_scratch_len() * size);
+
+    let larger = planner.plan_czt_forward(3000, 100, a, w);
+    assert!(larger.get_memory_usage() > czt_obj.get_memory_usage());
+
+    let naive: NaiveCzt<f64> = NaiveCzt::new(300, 100, a, w);
+    assert_eq!(
+        naive.get_memory_usage(),
+        naive.get_inplace_scratch_len() * size
+    );
+}
+
+#[test]
+fn test_uninit_scratch_is_not_read() {
+    let signal = random_signal(300);
+    let a = Complex::from_polar(1.0, 0.5);
+
+    for (w, strategy) in [
+        (
+            Complex::from_polar(1.0, -0.01),
+            PlannerStrategy::ForceBluestein,
+        ),
+        (
+            Complex::from_polar(1.001, -0.01),
+            PlannerStrategy::ForceBluestein,
+        ),
+        (Complex::from_polar(1.0, -0.01), PlannerStrategy::ForceNaive),
+    ] {
+        let mut planner = CztPlanner::new();
+        planner.set_strategy(strategy);
+        let czt_obj = planner.plan_czt_forward(signal.len(), 200, a, w);
+
+        let mut expected = signal.clone();
+        czt_obj.process(&mut expected);
+
+        // Stale scratch contents must never reach the output.
+        let mut scratch = vec![
+            MaybeUninit::new(Complex::new(f64::NAN, f64::NAN));
+            czt_obj.get_inplace_scratch_len()
+        ];
+        let mut actual = signal.clone();
+        czt_obj.process_with_uninit_scratch(&mut actual, &mut scratch);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn test_outofplace_matches_inplace() {
+    let signal = random_signal(300);
+    let a = Complex::from_polar(1.0, 0.5);
+    let w = Complex::from_polar(1.0, -0.01);
+
+    for (m, strategy) in [
+        (200, PlannerStrategy::ForceBluestein),
+        (500, PlannerStrategy::ForceBluestein),
+        (200, PlannerStrategy::ForceNaive),
+    ] {
+        let mut planner = CztPlanner::new();
+        planner.set_strategy(strategy);
+        let czt_obj = planner.plan_czt_forward(signal.len(), m, a, w);
+
+        let mut expected = signal.clone();
+        expected.resize(czt_obj.get_buffer_len(), Complex::zero());
+        czt_obj.process(&mut expected);
+
+        let mut actual = vec![Complex::zero(); m];
+        let mut scratch = vec![Complex::zero(); czt_obj.get_outofplace_scratch_len()];
+        czt_obj.process_outofplace_with_scratch(&signal, &mut actual, &mut scratch);
+        assert_spectra_match(&expected[..m], &actual);
+    }
+
+    let czt_obj = BluesteinsAlgorithm::new(300, 200, a, w, 0, &mut FftPlanner::new());
+    assert_eq!(
+        czt_obj.get_outofplace_scratch_len(),
+        czt_obj.get_inplace_scratch_len()
+    );
+}
+
+#[test]
+fn test_plan_params() {
+    let a = Complex::from_polar(1.0, 0.5);
+    let w = Complex::from_polar(1.0, -0.01);
+
+    for strategy in [
+        PlannerStrategy::Auto,
+        PlannerStrategy::ForceBluestein,
+        PlannerStrategy::ForceNaive,
+    ] {
+        for (n, m) in [(300, 4), (300, 200)] {
+            let mut planner = CztPlanner::new();
+            planner.set_strategy(strategy);
+            let params = planner.plan_czt_forward(n, m, a, w).params();
+            assert_eq!(params, CztParams { n, m, a, w });
+        }
+    }
+
+    // Offset plans report the contour of their first bin.
+    let mut planner = CztPlanner::new();
+    for w in [w, Complex::from_polar(1.001, -0.01)] {
+        let params = planner
+            .plan_czt_forward_with_offset(300, 200, a, w, 25)
+            .params();
+        let expected = a * w.powi(-25);
+        assert!((params.a - expected).norm() < 1e-12);
+        assert_eq!((params.n, params.m, params.w), (300, 200, w));
+    }
+}
+
+#[test]
+fn test_retune_a_matches_fresh_plan() {
+    let signal = random_signal(300);
+    let w = Complex::from_polar(1.0, -0.01);
+    let window = Window::Hann.coefficients(signal.len());
+    let mut fft_planner = FftPlanner::new();
+
+    let mut czt_obj = BluesteinsAlgorithm::new(
+        300,
+        200,
+        Complex::from_polar(1.0, 0.5),
+        w,
+        7,
+        &mut fft_planner,
+    )
+    .with_window(&window);
+    for phase in [0.1, -0.3, 2.0] {
+        let a = Complex::from_polar(1.0, phase);
+        czt_obj.retune_a(a);
+        let fresh =
+            BluesteinsAlgorithm::new(300, 200, a, w, 7, &mut fft_planner).with_window(&window);
+        assert_eq!(czt_obj.params(), fresh.params());
+
+        let mut expected = signal.clone();
+        fresh.process(&mut expected);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn test_retune_a_keeps_a_segment_delay() {
+    // Bins 10.. of a contour crossing the negative real axis, half a sample late: the segment's
+    // ramp has to continue the one from the contour's start, before and after retuning.
+    let (n, m, k0, delay) = (64, 20, 10, 0.5);
+    let signal = random_signal(n);
+    let w = Complex::from_polar(1.0, -0.05);
+    let mut fft_planner = FftPlanner::new();
+    let mut segment = BluesteinsAlgorithm::new_segment(
+        n,
+        m,
+        Complex::from_polar(1.0, 1.0),
+        w,
+        0,
+        k0 as isize,
+        ConvolutionLength::default(),
+        &mut fft_planner,
+    )
+    .with_delay(delay);
+    for phase in [3.0, -2.9] {
+        let a = Complex::from_polar(1.0, phase);
+        segment.retune_a(a);
+        let whole =
+            BluesteinsAlgorithm::new(n, k0 + m, a, w, 0, &mut fft_planner).with_delay(delay);
+
+        let mut expected = signal.clone();
+        expected.resize(whole.get_buffer_len(), Complex::zero());
+        whole.process(&mut expected);
+        let mut actual = signal.clone();
+        segment.process(&mut actual);
+        assert_spectra_match(&expected[k0..k0 + m], &actual[..m]);
+    }
+}
+
+#[test]
+fn test_retune_matches_fresh_plan() {
+    let signal = random_signal(300);
+    let window = Window::Hann.coefficients(signal.len());
+    let mut fft_planner = FftPlanner::new();
+    let plan = |a, w, fft_planner: &mut FftPlanner<f64>| {
+        BluesteinsAlgorithm::new(300, 200, a, w, -20, fft_planner)
+            .with_window(&window)
+            .with_output_scale(0.5)
+    };
+
+    let mut czt_obj = plan(
+        Complex::from_polar(1.0, 0.5),
+        Complex::from_polar(1.0, -0.01),
+        &mut fft_planner,
+    );
+    let l = czt_obj.v_coefficients().len();
+    for (a, w) in [
+        (
+            Complex::from_polar(1.0, 0.1),
+            Complex::from_polar(1.0, 0.003),
+        ),
+        (
+            Complex::from_polar(0.9, -1.0),
+            Complex::from_polar(1.0001, -0.02),
+        ),
+        (
+            Complex::from_polar(1.0, 2.0),
+            Complex::from_polar(1.0, -0.01),
+        ),
+    ] {
+        czt_obj.retune(a, w);
+        let fresh = plan(a, w, &mut fft_planner);
+        assert_eq!(czt_obj.params(), fresh.params());
+        assert_eq!(czt_obj.v_coefficients().len(), l);
+
+        let mut expected = signal.clone();
+        fresh.process(&mut expected);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn test_spectrogram_matches_per_frame_zoom() {
+    let signal = random_signal(1000);
+    let (frame_len, hop, m) = (128, 48, 40);
+    let (sample_rate, f_start, f_end) = (8000.0f64, 500.0, 1500.0);
+
+    let mut planner = CztPlanner::new();
+    let spectrogram =
+        planner.plan_spectrogram(frame_len, hop, m, sample_rate, f_start, f_end, Window::Hann);
+    let zoom =
+        planner.plan_zoom_fft_hz_windowed(frame_len, m, sample_rate, f_start, f_end, Window::Hann);
+
+    let frames = spectrogram.num_frames(signal.len());
+    assert_eq!(frames, (1000 - 128) / 48 + 1);
+    assert_eq!(spectrogram.freqs(), zoom.freqs());
+    assert!(Float::abs(spectrogram.time_of_frame(2) - (96.0 + 63.5) / sample_rate) < 1e-15);
+
+    let actual = spectrogram.process(&signal);
+    assert_eq!(actual.len(), frames * m);
+    for (i, row) in actual.chunks(m).enumerate() {
+        let mut expected = signal[i * hop..i * hop + frame_len].to_vec();
+        zoom.process(&mut expected);
+        assert_spectra_match(&expected[..m], row);
+    }
+    assert!(spectrogram.process(&signal[..100]).is_empty());
+}
+
+#[test]
+fn test_zoom_psd_scaling() {
+    let (segment_len, sample_rate) = (64, 1000.0f64);
+    let signal = random_signal::<f64>(64 * 8);
+    let bin_width = sample_rate / segment_len as f64;
+    let mut planner = CztPlanner::new();
+
+    // On the full DFT grid with a rectangular window, the density integrates to the mean power.
+    let psd = planner.plan_zoom_psd(
+        segment_len,
+        0,
+        segment_len,
+        sample_rate,
+        -sample_rate / 2.0,
+        sample_rate / 2.0 - bin_width,
+        Window::Rectangular,
+    );
+    let power = psd.process(&signal).iter().sum::<f64>() * bin_width;
+    let mean_power = signal.iter().map(|x| x.norm_sqr()).sum::<f64>() / signal.len() as f64;
+    assert!(Float::abs(power - mean_power) < 1e-9 * mean_power);
+
+    // Welch with overlap and a window against a direct evaluation
+    let real_signal: Vec<f64> = signal.iter().map(|x| x.re).collect();
+    let (overlap, m, f_start, f_end) = (40, 25, 0.0, sample_rate / 2.0);
+    let psd = planner.plan_zoom_psd(
+        segment_len,
+        overlap,
+        m,
+        sample_rate,
+        f_start,
+        f_end,
+        Window::Hann,
+    );
+    let window = Window::<f64>::Hann.coefficients(segment_len);
+    let energy = window.iter().map(|w| w * w).sum::<f64>();
+    let segments = psd.num_segments(real_signal.len());
+    assert_eq!(segments, (512 - 64) / 24 + 1);
+
+    let actual = psd.process_real(&real_signal);
+    for (k, (&p, f)) in actual.iter().zip(psd.freqs()).enumerate() {
+        let mut expected = 0.0;
+        for s in 0..segments {
+            let segment = &real_signal[s * 24..s * 24 + segment_len];
+            let sum: Complex<f64> = segment
+                .iter()
+                .zip(&window)
+                .enumerate()
+                .map(|(j, (&x, &w))| {
+                    Complex::from_polar(
+                        x * w,
+                        -2.0 * std::f64::consts::PI * f * j as f64 / sample_rate,
+                    )
+                })
+                .sum();
+            expected += sum.norm_sqr();
+        }
+        expected /= segments as f64 * sample_rate * energy;
+        if k != 0 && k != m - 1 {
+            expected *= 2.0;
+        }
+        assert!(
+            Float::abs(p - expected) < 1e-9 * expected.max(1e-6),
+            "bin {k}: {expected} != {p}"
+        );
+    }
+}
+
+#[test]
+fn test_cqt_matches_direct_evaluation() {
+    let sample_rate = 8000.0f64;
+    let signal = random_signal::<f64>(3000);
+    let (f_min, bins_per_octave, bins, hop) = (100.0, 12, 40, 256);
+
+    let cqt = CqtPlanner::new().plan_cqt(sample_rate, f_min, bins_per_octave, bins, hop);
+    assert_eq!(cqt.bins(), bins);
+    let q = 1.0 / (2.0f64.powf(1.0 / 12.0) - 1.0);
+    assert_eq!(cqt.frame_len(), (q * sample_rate / f_min).ceil() as usize);
+    assert!(cqt.window_len(bins - 1) < cqt.window_len(0));
+
+    let frames = cqt.num_frames(signal.len());
+    assert!(frames > 0);
+    let actual = cqt.process(&signal);
+    assert_eq!(actual.len(), frames * bins);
+
+    for (i, row) in actual.chunks(bins).enumerate() {
+        let center = i * hop + cqt.frame_len() / 2;
+        for (k, (&ac, f)) in row.iter().zip(cqt.freqs()).enumerate() {
+            assert!(Float::abs(f - f_min * 2.0f64.powf(k as f64 / 12.0)) < 1e-9);
+            let len = cqt.window_len(k);
+            let window = Window::<f64>::Hann.coefficients(len);
+            let start = center - len / 2;
+            let ex: Complex<f64> = (0..len)
+                .map(|j| {
+                    signal[start + j]
+                        * window[j]
+                        * Complex::from_polar(
+                            1.0,
+                            -2.0 * std::f64::consts::PI * f * j as f64 / sample_rate,
+                        )
+                })
+                .sum::<Complex<f64>>()
+                / len as f64;
+            assert!((ex - ac).norm() < 1e-9, "frame {i}, bin {k}: {ex} != {ac}");
+        }
+    }
+}
+
+#[test]
+fn test_warped_spectrum_band_energies() {
+    for scale in [FrequencyScale::Mel, FrequencyScale::Bark] {
+        for hz in [0.0, 100.0, 1000.0, 7999.0] {
+            assert!(Float::abs(scale.to_hz(scale.from_hz(hz)) - hz) < 1e-9);
+        }
+    }
+
+    let (n, sample_rate, bands) = (512, 16000.0f64, 24);
+    let mut planner = CztPlanner::new();
+    let spectrum = WarpedSpectrum::new(
+        &mut planner,
+        n,
+        sample_rate,
+        50.0,
+        7000.0,
+        bands,
+        FrequencyScale::Mel,
+        Window::Rectangular,
+    );
+    assert_eq!(spectrum.bands(), bands);
+    let centers = spectrum.center_freqs().to_vec();
+    assert!(centers.windows(3).all(|c| c[2] - c[1] > c[1] - c[0]));
+
+    // An impulse has a flat unit power spectrum, so each band's energy is the area of its
+    // triangle in units of fs / n.
+    let mut impulse = vec![Complex::zero(); n];
+    impulse[0] = Complex::new(1.0, 0.0);
+    let energies = spectrum.process(&impulse);
+    let edges: Vec<f64> = [50.0]
+        .into_iter()
+        .chain(centers.iter().copied())
+        .chain([7000.0])
+        .collect();
+    for (b, &energy) in energies.iter().enumerate() {
+        let expected = (edges[b + 2] - edges[b]) / 2.0 * n as f64 / sample_rate;
+        assert!(
+            Float::abs(energy - expected) < 1e-9 * expected,
+            "band {b}: {expected} != {energy}"
+        );
+    }
+
+    // A tone lands in the band centered on it.
+    let tone: Vec<Complex<f64>> = (0..n)
+        .map(|j| {
+            Complex::from_polar(
+                1.0,
+                2.0 * std::f64::consts::PI * centers[10] * j as f64 / sample_rate,
+            )
+        })
+        .collect();
+    let energies = spectrum.process(&tone);
+    let loudest = (0..bands)
+        .max_by(|&i, &j| energies[i].partial_cmp(&energies[j]).unwrap())
+        .unwrap();
+    assert_eq!(loudest, 10);
+}
+
+#[test]
+fn test_harmonic_distortion() {
+    let (n, sample_rate, f0) = (4096, 48000.0f64, 1000.3);
+    let levels = [1.0, 0.01, 0.005, 0.002];
+    let signal: Vec<f64> = (0..n)
+        .map(|j| {
+            let t = j as f64 / sample_rate;
+            0.25 + levels
+                .iter()
+                .enumerate()
+                .map(|(h, &a)| {
+                    a * (2.0 * std::f64::consts::PI * (h + 1) as f64 * f0 * t + h as f64).sin()
+                })
+                .sum::<f64>()
+        })
+        .collect();
+
+    let mut planner = CztPlanner::new();
+    let analysis = analyze_harmonics(&mut planner, &signal, sample_rate, 1003.0, 5, Window::Hann);
+    assert_eq!(analysis.amplitudes.len(), 5);
+    for (h, (&a, &f)) in analysis
+        .amplitudes
+        .iter()
+        .zip(&analysis.frequencies)
+        .enumerate()
+    {
+        let expected = levels.get(h).copied().unwrap_or(0.0);
+        assert!(
+            Float::abs(a - expected) < 1e-4 * levels[0],
+            "harmonic {h}: {expected} != {a}"
+        );
+        if h < levels.len() {
+            assert!(
+                Float::abs(f - (h + 1) as f64 * f0) < 0.05,
+                "harmonic {h} at {f} Hz"
+            );
+        }
+    }
+
+    let thd = (levels[1..].iter().map(|a| a * a).sum::<f64>()).sqrt();
+    assert!(Float::abs(analysis.thd - thd) < 1e-2 * thd);
+    assert!(Float::abs(analysis.thd_n - thd) < 5e-2 * thd);
+}
+
+#[test]
+fn test_refine_frequency() {
+    let (n, sample_rate, f0) = (1000, 8000.0f64, 1234.5678);
+    let tone: Vec<Complex<f64>> = (0..n)
+        .map(|j| {
+            Complex::from_polar(
+                0.5,
+                2.0 * std::f64::consts::PI * f0 * j as f64 / sample_rate,
+            )
+        })
+        .collect();
+
+    let mut planner = CztPlanner::new();
+    let estimate = refine_frequency(&mut planner, &tone, sample_rate, 1240.0, 1e-4);
+    assert!(estimate.resolution <= 1e-4);
+    assert!(estimate.stages > 1);
+    assert!(
+        Float::abs(estimate.frequency - f0) <= estimate.resolution,
+        "{} != {f0}",
+        estimate.frequency
+    );
+    assert!(Float::abs(estimate.confidence - 1.0) < 1e-6);
+
+    let noisy: Vec<_> = tone
+        .iter()
+        .zip(random_signal::<f64>(n))
+        .map(|(&x, noise)| x + (noise - Complex::new(5.0, 5.0)) * 0.02)
+        .collect();
+    let estimate = refine_frequency(&mut planner, &noisy, sample_rate, 1240.0, 1e-2);
+    assert!(Float::abs(estimate.frequency - f0) < 0.5);
+    assert!(0.9 < estimate.confidence && estimate.confidence < 0.99);
+}
+
+#[test]
+fn test_peak_interpolation() {
+    let (n, sample_rate) = (256, 1000.0f64);
+    let mut planner = CztPlanner::new();
+    // Bins at the DFT spacing for Jacobsen and Quinn, eight times denser for the parabola.
+    let coarse = planner.plan_zoom_fft_hz(
+        n,
+        16,
+        sample_rate,
+        100.0,
+        100.0 + 15.0 * sample_rate / n as f64,
+    );
+    let fine = planner.plan_zoom_fft_hz(
+        n,
+        121,
+        sample_rate,
+        100.0,
+        100.0 + 15.0 * sample_rate / n as f64,
+    );
+    for f0 in [113.1, 117.77, 121.4] {
+        let tone: Vec<Complex<f64>> = (0..n)
+            .map(|j| {
+                Complex::from_polar(
+                    3.0,
+                    2.0 * std::f64::consts::PI * f0 * j as f64 / sample_rate,
+                )
+            })
+            .collect();
+        for (zoom, interpolator, tolerance) in [
+            (&coarse, PeakInterpolator::Jacobsen, 0.05),
+            (&coarse, PeakInterpolator::Quinn, 0.01),
+            (&fine, PeakInterpolator::Quadratic, 0.01),
+        ] {
+            let mut spectrum = tone.clone();
+            zoom.process(&mut spectrum);
+            spectrum.truncate(zoom.get_output_len());
+            let k = (0..spectrum.len())
+                .max_by(|&a, &b| spectrum[a].norm().total_cmp(&spectrum[b].norm()))
+                .unwrap();
+            let estimate = zoom.interpolate_peak(&spectrum, k, interpolator);
+            assert!(
+                Float::abs(estimate.frequency - f0) < tolerance * zoom.bin_width(),
+                "{interpolator:?}: {} != {f0}",
+                estimate.frequency
+            );
+            assert!(Float::abs(estimate.offset) <= 0.5 + tolerance);
+        }
+    }
+
+    let peak = PeakInterpolator::Quadratic.interpolate(
+        [
+            Complex::new(1.0, 0.0),
+            Complex::new(2.0, 0.0),
+            Complex::new(1.0, 0.0),
+        ],
+        50.0,
+        0.5,
+    );
+    assert_eq!(peak.frequency, 50.0);
+    assert_eq!(peak.magnitude, 2.0);
+}
+
+#[test]
+fn test_range_doppler_locates_target() {
+    let config = FmcwConfig {
+        samples_per_chirp: 64,
+        chirps: 32,
+        sample_rate: 10e6f64,
+        slope: 30e12,
+        chirp_interval: 50e-6,
+        carrier: 77e9,
+    };
+    let mut planner = CztPlanner::new();
+    let map = planner.plan_range_doppler(config, -5.0, 5.0, 41, Window::Hann);
+    let (ranges, velocities) = (map.ranges(), map.velocities());
+    assert_eq!((ranges.len(), velocities.len()), (64, 41));
+    assert!(Float::abs(velocities[0] + 5.0) < 1e-9 && Float::abs(velocities[40] - 5.0) < 1e-9);
+    assert!(Float::abs(map.velocity_resolution() - 0.25) < 1e-9);
+
+    let (range, velocity) = (ranges[9], velocities[27]);
+    let c = 299_792_458.0;
+    let beat = 2.0 * config.slope * range / c;
+    let doppler = 2.0 * velocity / config.wavelength();
+    let frame: Vec<Complex<f64>> = (0..config.chirps)
+        .flat_map(|chirp| {
+            (0..config.samples_per_chirp).map(move |j| {
+                let phase = beat * j as f64 / config.sample_rate
+                    + doppler * chirp as f64 * config.chirp_interval;
+                Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * phase)
+            })
+        })
+        .collect();
+
+    let output = map.process(&frame);
+    let peak = (0..output.len())
+        .max_by(|&a, &b| output[a].norm().total_cmp(&output[b].norm()))
+        .unwrap();
+    assert_eq!((peak / 41, peak % 41), (9, 27));
+
+    // The Doppler axis is a zoom over the range FFT's columns.
+    let mut fft = FftPlanner::new();
+    let fft = fft.plan_fft_forward(64);
+    let window = Window::<f64>::Hann.coefficients(64);
+    let mut cube: Vec<_> = frame
+        .chunks(64)
+        .flat_map(|chirp| chirp.iter().zip(&window).map(|(&x, &w)| x * w))
+        .collect();
+    fft.process(&mut cube);
+    let doppler_zoom = planner.plan_zoom_fft_hz_windowed(
+        32,
+        41,
+        1.0 / config.chirp_interval,
+        -5.0 * 2.0 / config.wavelength(),
+        5.0 * 2.0 / config.wavelength(),
+        Window::Hann,
+    );
+    let mut column: Vec<_> = (0..32).map(|chirp| cube[chirp * 64 + 9]).collect();
+    column.resize(41, Complex::zero());
+    doppler_zoom.process(&mut column);
+    for (expected, actual) in column.iter().zip(&output[9 * 41..10 * 41]) {
+        assert!((expected - actual).norm() < 1e-9);
+    }
+}
+
+/// Dense sampled FrFT kernel, valid away from multiples of pi.
+fn frft_matrix(n: usize, alpha: f64) -> Vec<Complex<f64>> {
+    let (cot, csc) = (alpha.cos() / alpha.sin(), 1.0 / alpha.sin());
+    let amplitude = Complex::new(1.0, -cot).sqrt() / (n as f64).sqrt();
+    let c = (n / 2) as f64;
+    let mut matrix = Vec::with_capacity(n * n);
+    for k in 0..n {
+        for j in 0..n {
+            let (u, x) = (k as f64 - c, j as f64 - c);
+            let phase =
+                std::f64::consts::PI * (cot * (u * u + x * x) - 2.0 * csc * u * x) / n as f64;
+            matrix.push(amplitude * Complex::from_polar(1.0, phase));
+        }
+    }
+    matrix
+}
+
+fn apply_matrix(matrix: &[Complex<f64>], x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    matrix
+        .chunks(x.len())
+        .map(|row| row.iter().zip(x).map(|(a, b)| a * b).sum())
+        .collect()
+}
+
+#[test]
+fn test_frft_matches_dense_reference() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    let assert_close = |expected: &[Complex<f64>], actual: &[Complex<f64>]| {
+        for (e, a) in expected.iter().zip(actual) {
+            assert!((e - a).norm() < 1e-9, "{e} != {a}");
+        }
+    };
+    let mut planner = FrftPlanner::new();
+    for n in [16, 31, 64] {
+        let signal = random_signal::<f64>(n);
+        for alpha in [0.9, 2.0, -1.2, 0.3, -0.1, 2.9, -3.0, 7.5] {
+            let frft = planner.plan_frft(n, alpha);
+            let mut actual = signal.clone();
+            frft.process(&mut actual);
+
+            // Angles near multiples of pi go through a DFT first.
+            let mut reduced = f64::rem_euclid(alpha, 2.0 * PI);
+            if reduced > PI {
+                reduced -= 2.0 * PI;
+            }
+            let expected = if Float::abs(reduced) < PI / 4.0 || Float::abs(reduced) > 3.0 * PI / 4.0
+            {
+                let step = FRAC_PI_2.copysign(reduced);
+                let first = apply_matrix(&frft_matrix(n, step), &signal);
+                apply_matrix(&frft_matrix(n, reduced - step), &first)
+            } else {
+                apply_matrix(&frft_matrix(n, reduced), &signal)
+            };
+            assert_close(&expected, &actual);
+        }
+
+        // A rotation by pi / 2 is the centered unitary DFT.
+        let dft = planner.plan_frft(n, FRAC_PI_2);
+        let mut actual = signal.clone();
+        dft.process(&mut actual);
+        let c = (n / 2) as f64;
+        let expected: Vec<Complex<f64>> = (0..n)
+            .map(|k| {
+                signal
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &x)| {
+                        let phase = -2.0 * PI * (k as f64 - c) * (j as f64 - c) / n as f64;
+                        x * Complex::from_polar(1.0 / (n as f64).sqrt(), phase)
+                    })
+                    .sum()
+            })
+            .collect();
+        assert_close(&expected, &actual);
+
+        let identity = planner.plan_frft(n, 0.0);
+        let mut actual = signal.clone();
+        identity.process(&mut actual);
+        assert_close(&signal, &actual);
+
+        // A rotation by pi reverses the signal about the center sample.
+        let reversal = planner.plan_frft(n, PI);
+        let mut actual = signal.clone();
+        reversal.process(&mut actual);
+        let expected: Vec<_> = (0..n).map(|j| signal[(n / 2 * 2 + n - j) % n]).collect();
+        assert_close(&expected, &actual);
+    }
+}
+
+#[test]
+fn test_dct_matches_direct_evaluation() {
+    use std::f64::consts::PI;
+
+    let mut planner = DctPlanner::new();
+    for n in [1, 2, 7, 13, 16, 31] {
+        let signal = random_real_signal::<f64>(n);
+        let nf = n as f64;
+        for kind in [DctType::Dct2, DctType::Dct3, DctType::Dst2, DctType::Dst3] {
+            let expected: Vec<f64> = (0..n)
+                .map(|k| {
+                    let k = k as f64;
+                    signal
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &x)| {
+                            let j_f = j as f64;
+                            match kind {
+                                DctType::Dct2 => {
+                                    x * (PI * k * (2.0 * j_f + 1.0) / (2.0 * nf)).cos()
+                                }
+                                DctType::Dct3 if j == 0 => x / 2.0,
+                                DctType::Dct3 => {
+                                    x * (PI * j_f * (2.0 * k + 1.0) / (2.0 * nf)).cos()
+                                }
+                                DctType::Dst2 => {
+                                    x * (PI * (k + 1.0) * (2.0 * j_f + 1.0) / (2.0 * nf)).sin()
+                                }
+                                DctType::Dst3 => {
+                                    let scale = if j == n - 1 { 0.5 } else { 1.0 };
+                                    scale
+                                        * x
+                                        * (PI * (j_f + 1.0) * (2.0 * k + 1.0) / (2.0 * nf)).sin()
+                                }
+                            }
+                        })
+                        .sum()
+                })
+                .collect();
+
+            let dct = planner.plan(n, kind);
+            assert_eq!(dct.kind(), kind);
+            let mut actual = signal.clone();
+            dct.process(&mut actual);
+            for (e, a) in expected.iter().zip(&actual) {
+                assert!(Float::abs(e - a) < 1e-9, "{kind:?} of {n}: {e} != {a}");
+            }
+        }
+
+        // Type III inverts type II up to N / 2.
+        let mut round_trip = signal.clone();
+        planner.plan_dct2(n).process(&mut round_trip);
+        planner.plan_dct3(n).process(&mut round_trip);
+        let mut sine_round_trip = signal.clone();
+        planner.plan_dst2(n).process(&mut sine_round_trip);
+        planner.plan_dst3(n).process(&mut sine_round_trip);
+        for ((&x, &c), &s) in signal.iter().zip(&round_trip).zip(&sine_round_trip) {
+            assert!(Float::abs(c * 2.0 / nf - x) < 1e-9);
+            assert!(Float::abs(s * 2.0 / nf - x) < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_plan_fft_via_czt_matches_rustfft() {
+    let mut fft_planner = FftPlanner::new();
+    let mut czt_planner = CztPlanner::new();
+    for len in [1, 7, 97, 1009, 1024] {
+        let signal = random_signal::<f64>(len);
+        for direction in [FftDirection::Forward, FftDirection::Inverse] {
+            let fft = czt_planner.plan_fft_via_czt(len, direction);
+            assert_eq!((fft.len(), fft.fft_direction()), (len, direction));
+
+            let mut expected = signal.clone();
+            fft_planner.plan_fft(len, direction).process(&mut expected);
+            let mut actual = signal.clone();
+            fft.process(&mut actual);
+            for (e, a) in expected.iter().zip(&actual) {
+                assert!((e - a).norm() < 1e-9 * len as f64, "{len}: {e} != {a}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_s_plane_line_matches_closed_form() {
+    // h_j = e^(-decay j T) has H(z) = (1 - (e^(-decay T) / z)^n) / (1 - e^(-decay T) / z).
+    let (n, sample_rate, decay) = (200, 1000.0f64, 40.0);
+    let period = 1.0 / sample_rate;
+    let response: Vec<Complex<f64>> = (0..n)
+        .map(|j| Complex::new((-decay * j as f64 * period).exp(), 0.0))
+        .collect();
+
+    let mut planner = CztPlanner::new();
+    for sigma in [-20.0, 0.0, 15.0] {
+        let line = planner.plan_s_plane_line(n, 33, sample_rate, sigma, -400.0, 400.0);
+        assert_eq!(line.sigma(), sigma);
+        assert_eq!(line.omega_step(), 25.0);
+        assert_eq!(line.omegas()[16], 0.0);
+
+        let mut actual = response.clone();
+        line.process(&mut actual);
+        for (k, &actual) in actual.iter().enumerate().take(33) {
+            let z = (line.s_of_bin(k) * period).exp();
+            let ratio = Complex::new((-decay * period).exp(), 0.0) / z;
+            let expected = (Complex::new(1.0, 0.0) - ratio.powu(n as u32)) / (1.0 - ratio);
+            assert!(
+                (expected - actual).norm() < 1e-9 * expected.norm(),
+                "sigma {sigma}, bin {k}: {expected} != {actual}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_freqz_czt_matches_direct_evaluation() {
+    let evaluate = |coefficients: &[f64], z: Complex<f64>| -> Complex<f64> {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Complex::zero(), |acc, &c| acc * z.inv() + c)
+    };
+
+    // A resonant biquad, a long FIR filter and a lone gain, on an arc and on a spiral.
+    let biquad = (vec![0.2, 0.4, 0.2], vec![1.0, -1.1, 0.6]);
+    let fir = (random_real_signal::<f64>(501), vec![1.0]);
+    let gain = (vec![3.0], vec![2.0]);
+    let contours = [
+        Contour::arc(200, 48000.0, 100.0, 12000.0),
+        Contour::new(
+            64,
+            Complex::from_polar(0.95, 0.3),
+            Complex::from_polar(1.002, -0.02),
+        ),
+    ];
+
+    let mut planner = CztPlanner::new();
+    for (b, a) in [biquad, fir, gain] {
+        for contour in contours {
+            let actual = freqz_czt(&mut planner, &b, &a, contour);
+            assert_eq!(actual.len(), contour.m);
+            for (k, actual) in actual.iter().enumerate() {
+                let z = contour.point(k);
+                let expected = evaluate(&b, z) / evaluate(&a, z);
+                assert!(
+                    (expected - actual).norm() < 1e-9 * expected.norm().max(1.0),
+                    "bin {k}: {expected} != {actual}"
+                );
+            }
+        }
+    }
+
+    let arc = Contour::<f64>::arc(3, 1000.0, 0.0, 500.0);
+    assert!((arc.point(2) - Complex::new(-1.0, 0.0)).norm() < 1e-12);
+}
+
+#[test]
+fn test_evaluate_poly_matches_horner() {
+    let mut planner = CztPlanner::new();
+    for (n, m) in [(1, 5), (20, 7), (33, 100)] {
+        let coeffs: Vec<Complex<f64>> = random_signal::<f64>(n).iter().map(|c| c / 10.0).collect();
+        let a = Complex::from_polar(1.01, 0.4);
+        let w = Complex::from_polar(0.999, 0.05);
+        let values = evaluate_poly(&mut planner, &coeffs, a, w, m);
+        assert_eq!(values.len(), m);
+        for (k, value) in values.iter().enumerate() {
+            let x = a * w.powi(-(k as i32));
+            let expected = coeffs
+                .iter()
+                .rev()
+                .fold(Complex::<f64>::zero(), |acc, &c| acc * x + c);
+            assert!(
+                (expected - value).norm() < 1e-9 * expected.norm().max(1.0),
+                "({n}, {m}) point {k}: {expected} != {value}"
+            );
+        }
+    }
+    assert_eq!(
+        evaluate_poly(
+            &mut planner,
+            &[],
+            Complex::new(1.0, 0.0),
+            Complex::new(1.0, 0.0),
+            3
+        ),
+        vec![Complex::zero(); 3]
+    );
+}
+
+#[test]
+fn test_to_matrix() {
+    let mut planner = CztPlanner::new();
+    let a = Complex::from_polar(0.98, 0.3);
+    let w = Complex::from_polar(1.01, -0.1);
+    for (n, m) in [(1, 1), (12, 5), (7, 30)] {
+        let czt_obj = planner.plan_czt_forward(n, m, a, w);
+        let matrix = czt_obj.to_matrix();
+        assert_eq!(matrix.len(), m * n);
+        for k in 0..m {
+            for j in 0..n {
+                let expected = (a * w.powi(-(k as i32))).powi(-(j as i32));
+                let actual = matrix[k * n + j];
+                assert!((expected - actual).norm() < 1e-9 * expected.norm());
+            }
+        }
+    }
+
+    // Windows folded into a plan show up in its matrix.
+    let zoom = planner.plan_zoom_fft_windowed(40, 9, 0.1, 0.2, Window::Hann);
+    let matrix = zoom.to_matrix();
+    let signal = random_signal::<f64>(40);
+    let mut expected = signal.clone();
+    zoom.process(&mut expected);
+    for (k, row) in matrix.chunks_exact(40).enumerate() {
+        let actual: Complex<f64> = row.iter().zip(&signal).map(|(a, x)| a * x).sum();
+        assert!((expected[k] - actual).norm() < 1e-9 * expected[k].norm());
+    }
+}
+
+#[test]
+fn test_ntt_czt_is_exact() {
+    let mul = |x: u64, y: u64, p: u64| (x as u128 * y as u128 % p as u128) as u64;
+    let pow = |x: u64, e: u64, p: u64| (0..e).fold(1, |acc, _| mul(acc, x, p));
+    let inverse = |x: u64, p: u64| {
+        // p is prime, so x^(p - 2) is the inverse; square-and-multiply keeps this quick.
+        let (mut base, mut e, mut result) = (x, p - 2, 1);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = mul(result, base, p);
+            }
+            base = mul(base, base, p);
+            e >>= 1;
+        }
+        result
+    };
+
+    // Two NTT-friendly primes and one that forces direct evaluation.
+    for (p, fast) in [
+        (998_244_353, true),
+        (29 << 57 | 1, true),
+        (1_000_000_007, false),
+    ] {
+        for (n, m) in [(1, 1), (10, 3), (17, 40)] {
+            let (a, w) = (123_456_789 % p, 987_654_321 % p);
+            let input: Vec<u64> = (0..n as u64)
+                .map(|j| (j * j * 7_654_321).wrapping_add(u64::MAX - j))
+                .collect();
+            let czt = NttCzt::new(p, n, m, a, w);
+            assert_eq!(czt.is_fast(), fast || n + m - 1 <= 2);
+
+            let a_inv = inverse(a, p);
+            let expected: Vec<u64> = (0..m as u64)
+                .map(|k| {
+                    input.iter().enumerate().fold(0, |acc, (j, &x)| {
+                        let j = j as u64;
+                        let term = mul(mul(x % p, pow(a_inv, j, p), p), pow(w, j * k, p), p);
+                        (acc + term) % p
+                    })
+                })
+                .collect();
+            assert_eq!(czt.process(&input), expected, "p = {p}, n = {n}, m = {m}");
+        }
+    }
+
+    // x^2 + 2x + 3 at 5 * 2^-k modulo 998244353.
+    let p = 998_244_353;
+    let values = evaluate_poly_mod(p, &[3, 2, 1], 5, 2, 3);
+    let half = inverse(2, p);
+    let expected: Vec<u64> = (0..3)
+        .map(|k| {
+            let x = mul(5, pow(half, k, p), p);
+            (mul(x, x, p) + mul(2, x, p) + 3) % p
+        })
+        .collect();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn test_double_double_czt_resolves_weak_bins() {
+    let (n, m) = (200, 64);
+    let bin = core::f64::consts::TAU / n as f64;
+    let a = Complex::from_polar(1.0, 20.0 * bin);
+    let w = Complex::from_polar(1.0, -bin);
+
+    // A tone on DFT bin 25 over a noise floor about 250 dB down.
+    let mut rng = StdRng::seed_from_u64(59);
+    let noise = Uniform::new(-1e-12, 1e-12);
+    let input: Vec<Complex<f64>> = (0..n)
+        .map(|j| {
+            Complex::from_polar(1.0, 25.0 * bin * j as f64)
+                + Complex::new(noise.sample(&mut rng), noise.sample(&mut rng))
+        })
+        .collect();
+
+    // Horner in u_k = a^-1 w^k.
+    let dd = |z: Complex<f64>| Complex::new(DoubleDouble::from(z.re), DoubleDouble::from(z.im));
+    let mut u = dd(a).inv();
+    let expected: Vec<Complex<DoubleDouble>> = (0..m)
+        .map(|_| {
+            let bin = input
+                .iter()
+                .rev()
+                .fold(Complex::zero(), |acc, &x| acc * u + dd(x));
+            u = u * dd(w);
+            bin
+        })
+        .collect();
+
+    let czt = DoubleDoubleCzt::new(n, m, a, w);
+    assert_eq!(czt.params(), CztParams { n, m, a, w });
+    let mut buffer: Vec<_> = input.iter().map(|&x| dd(x)).collect();
+    czt.process_double_double(&mut buffer);
+    let peak = expected
+        .iter()
+        .map(|x| x.re.to_f64().hypot(x.im.to_f64()))
+        .fold(0.0, f64::max);
+    for (ex, ac) in expected.iter().zip(&buffer) {
+        let diff = *ex - *ac;
+        let error = diff.re.to_f64().hypot(diff.im.to_f64());
+        assert!(error < 1e-28 * peak, "{error}");
+    }
+
+    // Every f64 bin is accurate relative to itself, not just to the peak.
+    let mut actual = input.clone();
+    czt.process(&mut actual);
+    let mut smallest = f64::INFINITY;
+    for (ex, ac) in expected.iter().zip(&actual) {
+        let ex = Complex::new(ex.re.to_f64(), ex.im.to_f64());
+        smallest = smallest.min(ex.norm());
+        assert!((ex - ac).norm() <= 1e-14 * ex.norm(), "{ex} != {ac}");
+    }
+    assert!(smallest < 1e-8 * peak);
+}
+
+#[test]
+fn test_resampler_band_limited() {
+    let n = 64;
+    let signal = |t: f64, high: f64| {
+        let phase = core::f64::consts::TAU * t / n as f64;
+        (3.0 * phase).cos() + 0.5 * (10.0 * phase + 0.3).sin() + high * (20.0 * phase).cos()
+    };
+    let input: Vec<_> = (0..n)
+        .map(|j| Complex::new(signal(j as f64, 0.25), 0.0))
+        .collect();
+    let mut planner = CztPlanner::<f64>::new();
+
+    // Up by an irrational ratio: every component is reproduced at the new times.
+    let output_rate = core::f64::consts::SQRT_2;
+    let resampler = planner.plan_resampler(n, 1.0, output_rate);
+    assert_eq!(resampler.get_output_len(), 91);
+    let mut output = vec![Complex::zero(); 91];
+    resampler.process(&input, &mut output);
+    for (p, y) in output.iter().enumerate() {
+        let expected = signal(p as f64 / output_rate, 0.25);
+        assert!((y - expected).norm() < 1e-10, "{p}: {y} != {expected}");
+    }
+
+    // Down to 0.5: 20 cycles per block lie above the new Nyquist frequency and are removed.
+    let resampler = planner.plan_resampler(n, 1.0, 0.5);
+    assert_eq!(resampler.get_output_len(), 32);
+    let mut output = vec![Complex::zero(); 32];
+    resampler.process(&input, &mut output);
+    for (p, y) in output.iter().enumerate() {
+        let expected = signal(p as f64 / 0.5, 0.0);
+        assert!((y - expected).norm() < 1e-10, "{p}: {y} != {expected}");
+    }
+}
+
+#[test]
+fn test_interpolate_and_at_times() {
+    let n = 48;
+    let signal = |t: f64| {
+        let phase = core::f64::consts::TAU * t / n as f64;
+        Complex::from_polar(1.0, 5.0 * phase) + Complex::new((2.0 * phase).sin(), 0.0)
+    };
+    let input: Vec<_> = (0..n).map(|j| signal(j as f64)).collect();
+    let mut planner = CztPlanner::<f64>::new();
+
+    let upsampled = interpolate(&mut planner, &input, 4.0);
+    assert_eq!(upsampled.len(), 4 * n);
+    for (p, y) in upsampled.iter().enumerate() {
+        let expected = signal(p as f64 / 4.0);
+        assert!((y - expected).norm() < 1e-10, "{p}: {y} != {expected}");
+    }
+
+    let times = [0.0, 0.3, 7.77, 20.5, 47.9, -3.25];
+    let values = at_times(&mut planner, &input, &times);
+    for (&t, y) in times.iter().zip(&values) {
+        let expected = signal(t);
+        assert!((y - expected).norm() < 1e-10, "{t}: {y} != {expected}");
+    }
+
+    // A sinc pulse peaking between samples: the interpolant finds the peak time.
+    let peak = 20.37;
+    let pulse: Vec<_> = (0..64)
+        .map(|j| {
+            let x = core::f64::consts::PI * (j as f64 - peak) / 2.0;
+            Complex::new(if x == 0.0 { 1.0 } else { x.sin() / x }, 0.0)
+        })
+        .collect();
+    let times: Vec<_> = (0..=200).map(|i| 19.0 + i as f64 * 0.01).collect();
+    let values = at_times(&mut planner, &pulse, &times);
+    let (best, _) = values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.re.total_cmp(&b.1.re))
+        .unwrap();
+    assert!((times[best] - peak).abs() <= 0.01, "{}", times[best]);
+}
+
+#[test]
+fn test_czt_forward_delayed() {
+    // Odd n and a DFT contour centered on DC, so the delay acts on the band-limited interpolant.
+    let n = 33;
+    let h = (n / 2) as f64;
+    let step = core::f64::consts::TAU / n as f64;
+    let a = Complex::from_polar(1.0, -h * step);
+    let w = Complex::from_polar(1.0, -step);
+    let delay = 2.35;
+    let signal: Vec<_> = (0..n)
+        .map(|j| {
+            let phase = j as f64 * step;
+            Complex::new(
+                (3.0 * phase).cos() + 0.4 * (7.0 * phase).sin(),
+                (2.0 * phase).cos(),
+            )
+        })
+        .collect();
+
+    let mut planner = CztPlanner::<f64>::new();
+    let mut spectrum = signal.clone();
+    planner
+        .plan_czt_forward_delayed(n, n, a, w, delay)
+        .process(&mut spectrum);
+    let times: Vec<_> = (0..n).map(|j| j as f64 - delay).collect();
+    let expected = at_times(&mut planner, &signal, &times);
+    for (j, ex) in expected.iter().enumerate() {
+        let y: Complex<f64> = spectrum
+            .iter()
+            .enumerate()
+            .map(|(k, &x)| x * Complex::from_polar(1.0, (k as f64 - h) * step * j as f64))
+            .sum::<Complex<f64>>()
+            / n as f64;
+        assert!((y - ex).norm() < 1e-10, "{j}: {y} != {ex}");
+    }
+
+    // The ramp is z_k^-delay on any contour, and retuning keeps it.
+    let (a2, w2) = (
+        Complex::from_polar(1.01, 0.4),
+        Complex::from_polar(0.999, -0.03),
+    );
+    let mut fft_planner = FftPlanner::new();
+    let mut retuned = BluesteinsAlgorithm::new(n, 20, a, w, 0, &mut fft_planner).with_delay(delay);
+    retuned.retune(a2, w2);
+    let mut actual = signal.clone();
+    retuned.process(&mut actual);
+    let mut reference = signal.clone();
+    NaiveCzt::new(n, 20, a2, w2).process(&mut reference);
+    for (k, (ac, ex)) in actual.iter().zip(&reference).take(20).enumerate() {
+        let z: Complex<f64> = a2 * w2.powi(-(k as i32));
+        let ex = ex * (-z.ln() * delay).exp();
+        assert!(
+            (ac - ex).norm() < 1e-9 * ex.norm().max(1.0),
+            "{k}: {ac} != {ex}"
+        );
+    }
+}
+
+#[test]
+fn test_delayed_spiral_contour() {
+    // The contour winds past -pi within the plan, and the ramp must not jump where it does.
+    let (n, delay) = (500, 2.35);
+    let (a, w) = (Complex::new(1.0, 0.0), Complex::from_polar(0.995, -0.01));
+    let signal = random_signal::<f64>(n);
+    let mut expected = signal.clone();
+    NaiveCzt::new(n, n, a, w).process(&mut expected);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt_forward_delayed(n, n, a, w, delay);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+    for (k, (ex, ac)) in expected.iter().zip(&actual).enumerate() {
+        let ex = ex * ((w.ln() * k as f64 - a.ln()) * delay).exp();
+        assert!(
+            (ex - ac).norm() < 1e-8 * ex.norm().max(1.0),
+            "bin {k}: {ex} != {ac}"
+        );
+    }
+}
+
+#[test]
+fn test_mixer_czt_accuracy_and_selection() {
+    let (n, m) = (1 << 17, 64);
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -1e-4);
+    let mut rng = StdRng::seed_from_u64(64);
+    let noise = Uniform::new(-1.0, 1.0);
+    let signal: Vec<Complex<f64>> = (0..n)
+        .map(|j| {
+            Complex::from_polar(1.0, 0.301 * j as f64)
+                + Complex::from_polar(0.5, 2.0 * j as f64)
+                + Complex::new(noise.sample(&mut rng), noise.sample(&mut rng))
+        })
+        .collect();
+    let l1: f64 = signal.iter().map(|x| x.norm()).sum();
+
+    let (decimation, taps) = MixerCzt::design(n, m, a, w).unwrap();
+    assert!(decimation > 100 && taps < n / 10, "{decimation} {taps}");
+    assert!(MixerCzt::<f64>::design(n, m, a * 1.001, w).is_none());
+
+    // Reference on the unit circle through the angles of a and w, in double-double.
+    let unit = |z: Complex<f64>| {
+        let z = Complex::new(DoubleDouble::from(z.re), DoubleDouble::from(z.im));
+        z.unscale((z.re * z.re + z.im * z.im).sqrt())
+    };
+    let mut expected = signal.clone();
+    DoubleDoubleCzt::with_double_double_contour(n, m, unit(a), unit(w)).process(&mut expected);
+    let max_error =
+        |actual: &[Complex<f64>]| ErrorReport::compare(&expected[..m], &actual[..m]).max_abs_error;
+
+    let mut planner = CztPlanner::<f64>::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let mut bluestein = signal.clone();
+    planner.plan_czt_forward(n, m, a, w).process(&mut bluestein);
+    planner.set_strategy(PlannerStrategy::ForceMixer);
+    let mut mixer = signal.clone();
+    planner.plan_czt_forward(n, m, a, w).process(&mut mixer);
+    let bound = max_error(&bluestein) + 1000.0 * f64::EPSILON * l1;
+    assert!(
+        max_error(&mixer) <= bound,
+        "{} > {bound}",
+        max_error(&mixer)
+    );
+
+    // Long input, narrow band: the cost model picks the mixer.
+    planner.set_strategy(PlannerStrategy::Auto);
+    let mut auto = signal.clone();
+    planner.plan_czt_forward(n, m, a, w).process(&mut auto);
+    assert_eq!(auto[..m], mixer[..m]);
+
+    // In single precision the filter's rounding would outweigh the savings: Auto must stay as
+    // accurate as Bluestein on the same (rounded) contour.
+    let to_f32 = |z: Complex<f64>| Complex::new(z.re as f32, z.im as f32);
+    let up = |z: Complex<f32>| Complex::new(z.re as f64, z.im as f64);
+    let (a32, w32) = (to_f32(a), to_f32(w));
+    assert!(MixerCzt::design(n, m, a32, w32).is_none());
+    let signal32: Vec<_> = signal.iter().map(|&x| to_f32(x)).collect();
+    let mut expected: Vec<_> = signal32.iter().map(|&x| up(x)).collect();
+    DoubleDoubleCzt::new(n, m, up(a32), up(w32)).process(&mut expected);
+    let mut planner32 = CztPlanner::<f32>::new();
+    let mut error = |strategy| {
+        planner32.set_strategy(strategy);
+        let mut actual = signal32.clone();
+        planner32
+            .plan_czt_forward(n, m, a32, w32)
+            .process(&mut actual);
+        let actual: Vec<_> = actual[..m].iter().map(|&x| up(x)).collect();
+        ErrorReport::compare(&expected[..m], &actual).max_abs_error
+    };
+    let (bluestein, auto) = (
+        error(PlannerStrategy::ForceBluestein),
+        error(PlannerStrategy::Auto),
+    );
+    assert!(auto <= 4.0 * bluestein, "{auto} > 4 * {bluestein}");
+
+    // Off the unit circle ForceMixer plans Bluestein.
+    let a = a * 1.001;
+    planner.set_strategy(PlannerStrategy::ForceMixer);
+    let mut mixer = signal[..4096].to_vec();
+    planner.plan_czt_forward(4096, m, a, w).process(&mut mixer);
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let mut bluestein = signal[..4096].to_vec();
+    planner
+        .plan_czt_forward(4096, m, a, w)
+        .process(&mut bluestein);
+    assert_eq!(mixer[..m], bluestein[..m]);
+}
+
+#[test]
+fn test_sliding_czt_tracks_window() {
+    let (n, m) = (40, 12);
+    let a = Complex::from_polar(1.0, 0.4);
+    let w = Complex::from_polar(0.999, -0.02);
+    let signal = random_signal::<f64>(300);
+    let naive = NaiveCzt::new(n, m, a, w);
+
+    let mut sliding = SlidingCzt::new(n, m, a, w);
+    for (t, &sample) in signal.iter().enumerate() {
+        sliding.push(sample);
+        if t % 37 != 0 && t != signal.len() - 1 {
+            continue;
+        }
+        // The zero-filled window before n samples have arrived.
+        let mut window = vec![Complex::zero(); n.saturating_sub(t + 1)];
+        window.extend_from_slice(&signal[(t + 1).saturating_sub(n)..=t]);
+        assert!(sliding.window().eq(window.iter()));
+        naive.process(&mut window);
+        for (ex, ac) in window.iter().zip(sliding.bins()) {
+            assert!((ex - ac).norm() < 1e-10, "t = {t}: {ex} != {ac}");
+        }
+    }
+
+    let drifted = sliding.bins().to_vec();
+    sliding.refresh();
+    let mut window: Vec<_> = signal[signal.len() - n..].to_vec();
+    naive.process(&mut window);
+    for ((ex, ac), before) in window.iter().zip(sliding.bins()).zip(&drifted) {
+        assert!((ex - ac).norm() < 1e-12, "{ex} != {ac}");
+        assert!((ac - before).norm() < 1e-10);
+    }
+
+    sliding.reset();
+    assert!(sliding.bins().iter().all(|bin| bin.is_zero()));
+    let bins = sliding.extend(&signal[..n]).to_vec();
+    let mut window = signal[..n].to_vec();
+    naive.process(&mut window);
+    for (ex, ac) in window.iter().zip(&bins) {
+        assert!((ex - ac).norm() < 1e-10, "{ex} != {ac}");
+    }
+}
+
+#[test]
+fn test_czt_stream_frames_reader() {
+    let (n, m) = (16, 10);
+    let czt: Arc<dyn Czt<f64>> = Arc::new(NaiveCzt::new(
+        n,
+        m,
+        Complex::from_polar(1.0, 0.3),
+        Complex::from_polar(1.0, -0.05),
+    ));
+    let signal: Vec<Complex<f64>> = random_signal::<f32>(101)
+        .iter()
+        .map(|x| Complex::new(x.re as f64, x.im as f64))
+        .collect();
+    let bytes: Vec<u8> = signal
+        .iter()
+        .flat_map(|x| [(x.re as f32).to_le_bytes(), (x.im as f32).to_le_bytes()])
+        .flatten()
+        .collect();
+
+    for hop in [5, 16, 23] {
+        // Small reads so samples straddle read calls.
+        let reader = bytes.chunks(7).fold(
+            Box::new(std::io::empty()) as Box<dyn std::io::Read>,
+            |reader, chunk| Box::new(std::io::Read::chain(reader, chunk)),
+        );
+        let stream = CztStream::new(reader, czt.clone(), SampleFormat::ComplexF32, hop);
+        let spectra: Vec<_> = stream.map(Result::unwrap).collect();
+        assert_eq!(spectra.len(), (signal.len() - n) / hop + 1);
+        for (i, spectrum) in spectra.iter().enumerate() {
+            let mut expected = signal[i * hop..i * hop + n].to_vec();
+            czt.process(&mut expected);
+            assert_eq!(spectrum[..], expected[..m], "hop = {hop}, frame {i}");
+        }
+    }
+
+    let real: Vec<u8> = signal
+        .iter()
+        .flat_map(|x| (x.re as f32).to_le_bytes())
+        .collect();
+    let mut stream = CztStream::new(&real[..], czt.clone(), SampleFormat::RealF32, n);
+    let mut spectrum = vec![Complex::zero(); stream.bins()];
+    assert!(stream.next_into(&mut spectrum).unwrap());
+    let mut expected: Vec<_> = signal[..n].iter().map(|x| Complex::from(x.re)).collect();
+    czt.process(&mut expected);
+    assert_eq!(spectrum, expected[..m]);
+
+    struct Failing;
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+    }
+    let mut stream = CztStream::new(Failing, czt, SampleFormat::ComplexF64, n);
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_realtime_channel_publishes_frames() {
+    let (n, m, hop) = (32, 8, 12);
+    let czt: Arc<dyn Czt<f64>> = Arc::new(NaiveCzt::new(
+        n,
+        m,
+        Complex::from_polar(1.0, 0.2),
+        Complex::from_polar(1.0, -0.03),
+    ));
+    let signal = random_signal::<f64>(500);
+    let frame = |i: usize| {
+        let mut expected = signal[i * hop..i * hop + n].to_vec();
+        czt.process(&mut expected);
+        expected.truncate(m);
+        expected
+    };
+
+    let (mut producer, mut worker, mut reader) = channel(czt.clone(), hop, 50);
+    assert!(reader.latest().iter().all(|bin| bin.is_zero()));
+    assert_eq!(producer.push(&signal[..n - 1]), n - 1);
+    assert_eq!(worker.process(), 0);
+    assert!(!reader.has_update());
+    assert_eq!(producer.push(&signal[n - 1..n + 2 * hop]), 2 * hop + 1);
+    assert_eq!(worker.process(), 3);
+    assert!(reader.has_update());
+    assert_eq!(reader.latest(), frame(2));
+    assert!(!reader.has_update());
+    // A full ring drops the excess.
+    assert_eq!(producer.free(), 50);
+    assert_eq!(producer.push(&signal[n + 2 * hop..]), 50);
+
+    let (mut producer, mut worker, mut reader) = channel(czt.clone(), hop, 64);
+    let frames = (signal.len() - n) / hop + 1;
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let mut pushed = 0;
+            while pushed < signal.len() {
+                let end = (pushed + 7).min(signal.len());
+                pushed += producer.push(&signal[pushed..end]);
+            }
+        });
+        let mut published = 0;
+        while published < frames {
+            published += worker.process();
+        }
+    });
+    assert_eq!(reader.latest(), frame(frames - 1));
+}
+
+#[test]
+fn test_pipeline_decimates_and_orders_frames() {
+    let (n, m, factor) = (32, 16, 3);
+    let czt: Arc<dyn Czt<f64>> = Arc::new(NaiveCzt::new(
+        n,
+        m,
+        Complex::from_polar(1.0, -0.2),
+        Complex::from_polar(1.0, -0.025),
+    ));
+    let taps = vec![0.1, 0.2, 0.4, 0.2, 0.1];
+    let signal = random_signal::<f64>(1000);
+
+    let filtered: Vec<_> = (0..signal.len())
+        .step_by(factor)
+        .map(|j| {
+            (0..taps.len().min(j + 1)).fold(Complex::zero(), |acc, l| acc + signal[j - l] * taps[l])
+        })
+        .collect();
+    let expected: Vec<_> = filtered
+        .chunks_exact(n)
+        .map(|frame| {
+            let mut frame = frame.to_vec();
+            czt.process(&mut frame);
+            frame.truncate(m);
+            frame
+        })
+        .collect();
+
+    let block_lens = [1, 40, 7, 100, 3];
+    let mut blocks: Vec<&[Complex<f64>]> = Vec::new();
+    let mut start = 0;
+    for len in block_lens.iter().cycle() {
+        if start == signal.len() {
+            break;
+        }
+        let end = (start + len).min(signal.len());
+        blocks.push(&signal[start..end]);
+        start = end;
+    }
+    for decimation_threads in [1, 3] {
+        let mut spectra = Vec::new();
+        let mut source = blocks.iter();
+        let stats = PipelineBuilder::new(czt.clone())
+            .decimate(factor, taps.clone())
+            .channel_capacity(2)
+            .decimation_threads(decimation_threads)
+            .czt_threads(3)
+            .run(
+                || source.next().map(|block| block.to_vec()),
+                |index, spectrum| {
+                    assert_eq!(index, spectra.len());
+                    spectra.push(spectrum.to_vec());
+                },
+            );
+        assert_eq!(
+            stats,
+            PipelineStats {
+                blocks: blocks.len(),
+                samples: signal.len(),
+                frames: expected.len(),
+            }
+        );
+        assert_eq!(spectra, expected);
+    }
+}
+
+/// Holds the first frame back until the other CZT threads have had time to run ahead.
+struct StallFirstFrame(NaiveCzt<f64>, AtomicBool);
+
+impl Czt<f64> for StallFirstFrame {
+    fn process_with_scratch(&self, buffer: &mut [Complex<f64>], scratch: &mut [Complex<f64>]) {
+        if !self.1.swap(true, Ordering::SeqCst) {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+        self.0.process_with_scratch(buffer, scratch);
+    }
+
+    fn get_inplace_scratch_len(&self) -> usize {
+        self.0.get_inplace_scratch_len()
+    }
+
+    fn get_input_len(&self) -> usize {
+        self.0.get_input_len()
+    }
+
+    fn get_output_len(&self) -> usize {
+        self.0.get_output_len()
+    }
+
+    fn params(&self) -> CztParams<f64> {
+        self.0.params()
+    }
+}
+
+#[test]
+fn test_pipeline_bounds_frames_in_flight() {
+    let n = 8;
+    let czt = StallFirstFrame(
+        NaiveCzt::new(n, n, Complex::new(1.0, 0.0), Complex::from_polar(1.0, -0.1)),
+        AtomicBool::new(false),
+    );
+    let produced = AtomicUsize::new(0);
+    let mut produced_at_first_frame = None;
+    let stats = PipelineBuilder::new(Arc::new(czt))
+        .channel_capacity(2)
+        .decimation_threads(2)
+        .czt_threads(3)
+        .run(
+            || {
+                (produced.fetch_add(1, Ordering::SeqCst) < 1000)
+                    .then(|| vec![Complex::new(1.0, 0.0); n])
+            },
+            |index, _| {
+                if index == 0 {
+                    produced_at_first_frame = Some(produced.load(Ordering::SeqCst));
+                }
+            },
+        );
+    assert_eq!(stats.frames, 1000);
+    // The credit windows, the channels and the blocks each stage holds, nowhere near all 1000.
+    assert!(produced_at_first_frame.unwrap() <= 24);
+}
+
+#[test]
+fn test_process_multichannel_matches_channels() {
+    let channels = 3;
+    let (a, w) = (
+        Complex::from_polar(1.0, 0.7),
+        Complex::from_polar(0.998, -0.05),
+    );
+    let mut planner = CztPlanner::new();
+    let mut plans = vec![(20, 13), (13, 20)]
+        .into_iter()
+        .map(|(n, m)| (n, m, planner.plan_czt_forward(n, m, a, w)))
+        .collect::<Vec<_>>();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    plans.push((64, 48, planner.plan_czt_forward(64, 48, a, w)));
+    for (n, m, czt) in plans {
+        let len = czt.get_buffer_len();
+        let mut interleaved = random_signal::<f64>(channels * len);
+        let expected: Vec<Vec<_>> = (0..channels)
+            .map(|c| {
+                let mut channel: Vec<_> =
+                    interleaved[c..].iter().step_by(channels).copied().collect();
+                channel[n..].fill(Complex::zero());
+                czt.process(&mut channel);
+                channel.truncate(m);
+                channel
+            })
+            .collect();
+
+        czt.process_multichannel(&mut interleaved, channels);
+        for (c, expected) in expected.iter().enumerate() {
+            let actual: Vec<_> = interleaved[c..]
+                .iter()
+                .step_by(channels)
+                .take(m)
+                .copied()
+                .collect();
+            assert_eq!(&actual, expected, "n = {n}, m = {m}, channel {c}");
+        }
+    }
+}
+
+#[test]
+fn test_process_split_matches_packed() {
+    let (n, m) = (17, 25);
+    let mut planner = CztPlanner::new();
+    // Bluestein takes less out-of-place scratch than packing needs.
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt = planner.plan_czt_forward(
+        n,
+        m,
+        Complex::from_polar(1.01, 0.3),
+        Complex::from_polar(1.0, -0.07),
+    );
+    let mut packed = random_signal::<f32>(czt.get_buffer_len());
+    let mut re: Vec<_> = packed.iter().map(|x| x.re).collect();
+    let mut im: Vec<_> = packed.iter().map(|x| x.im).collect();
+    czt.process(&mut packed);
+    czt.process_split(&mut re, &mut im);
+    for (k, x) in packed.iter().take(m).enumerate() {
+        assert_eq!((re[k], im[k]), (x.re, x.im));
+    }
+}
+
+#[test]
+fn test_process_strided_transforms_column() {
+    let (a, w) = (
+        Complex::from_polar(1.0, 0.2),
+        Complex::from_polar(1.0, -0.04),
+    );
+    let mut planner = CztPlanner::new();
+    let mut plans = vec![(24, 30, planner.plan_czt_forward(24, 30, a, w))];
+    // Bluestein and blocked plans take less out-of-place scratch than gathering needs.
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    plans.push((64, 64, planner.plan_czt_forward(64, 64, a, w)));
+    plans.push((64, 64, planner.plan_czt_forward_blocked(64, 64, a, w, 200)));
+
+    let (cols, column) = (5, 3);
+    for (rows, m, czt) in plans {
+        // Row-major, with enough rows for the bins to be written back in place.
+        let matrix = random_signal::<f64>(czt.get_buffer_len() * cols);
+        let mut expected: Vec<_> = matrix[column..].iter().step_by(cols).copied().collect();
+        expected[rows..].fill(Complex::zero());
+        czt.process(&mut expected);
+
+        let mut actual = matrix.clone();
+        czt.process_strided(&mut actual[column..], cols);
+        for (i, (a, x)) in actual.iter().zip(&matrix).enumerate() {
+            if i % cols == column && i / cols < m {
+                assert_eq!(*a, expected[i / cols], "rows = {rows}, element {i}");
+            } else {
+                assert_eq!(a, x, "rows = {rows}, element {i} outside the bins changed");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_wisdom_round_trips_and_steers_planning() {
+    let a = Complex::from_polar(1.0, 1.0);
+    let w = Complex::from_polar(1.0, -0.002);
+    let mut planner = CztPlanner::<f64>::new();
+    planner.plan_czt_forward(8, 4, a, w);
+    planner.plan_czt_forward(4096, 512, a, w);
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    planner.plan_czt_forward(100, 100, a, w);
+
+    // Only decisions made in Auto are recorded.
+    let wisdom = planner.wisdom().clone();
+    assert_eq!(wisdom.len(), 2);
+    let small = wisdom.get(8, 4, a, w).unwrap();
+    assert_eq!(small.algorithm, Algorithm::Goertzel);
+    assert_eq!(small.nanos, None);
+    assert_ne!(
+        wisdom.get(4096, 512, a, w).unwrap().algorithm,
+        Algorithm::Goertzel
+    );
+
+    let path = std::env::temp_dir().join(format!("rustczt-wisdom-{}", std::process::id()));
+    planner.save_wisdom(&path).unwrap();
+    let mut restored = CztPlanner::<f64>::new();
+    restored.load_wisdom(&path).unwrap();
+    assert_eq!(restored.wisdom(), &wisdom);
+    std::fs::write(&path, "rustczt-wisdom 1\n8 4 bogus\n").unwrap();
+    let error = restored.load_wisdom(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut text = wisdom.to_text();
+    assert_eq!(Wisdom::from_text(&text), Some(wisdom));
+    text = text.replacen("rustczt-wisdom 1", "rustczt-wisdom 2", 1);
+    assert_eq!(Wisdom::from_text(&text), None);
+
+    // Imported wisdom overrides the heuristics.
+    let (n, m) = (64, 48);
+    let mut forced = Wisdom::new();
+    forced.insert(
+        n,
+        m,
+        a,
+        w,
+        WisdomEntry {
+            algorithm: Algorithm::Naive,
+            length: ConvolutionLength::PowerOfTwo,
+            nanos: Some(1234),
+        },
+    );
+    let mut planner = CztPlanner::<f64>::new();
+    planner.import_wisdom(&forced);
+    let signal = random_signal::<f64>(n);
+    let mut actual = signal.clone();
+    planner.plan_czt_forward(n, m, a, w).process(&mut actual);
+    let mut expected = signal;
+    NaiveCzt::new(n, m, a, w).process(&mut expected);
+    assert_eq!(actual, expected);
+    assert_eq!(planner.wisdom().get(n, m, a, w).unwrap().nanos, Some(1234));
+}
+
+#[test]
+fn test_plan_czt_forward_measured() {
+    let (n, m) = (200, 50);
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.001);
+    let mut planner = CztPlanner::<f64>::new();
+    let heuristic = planner.plan_czt_forward(n, m, a, w);
+    assert_eq!(planner.wisdom().get(n, m, a, w).unwrap().nanos, None);
+
+    let measured = planner.plan_czt_forward_measured(n, m, a, w);
+    let entry = planner.wisdom().get(n, m, a, w).unwrap();
+    assert!(entry.nanos.is_some());
+    assert!(Arc::ptr_eq(
+        &measured,
+        &planner.plan_czt_forward_measured(n, m, a, w)
+    ));
+    // Auto plans now follow the measurement.
+    assert!(Arc::ptr_eq(
+        &measured,
+        &planner.plan_czt_forward(n, m, a, w)
+    ));
+
+    let signal = random_signal::<f64>(n);
+    let (mut expected, mut actual) = (signal.clone(), signal);
+    heuristic.process(&mut expected);
+    measured.process(&mut actual);
+    for (ex, ac) in expected.iter().zip(&actual).take(m) {
+        assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+}
+
+#[test]
+fn test_auto_keeps_bluestein_accuracy_in_single_precision() {
+    // Long inputs and few bins, where the direct algorithms and the mixer are cheapest.
+    for (n, m, step) in [(20000, 32, 1e-3), (100000, 64, 1e-4)] {
+        let a = Complex::from_polar(1.0f32, 0.3);
+        let w = Complex::from_polar(1.0f32, -step);
+        let signal = random_signal::<f32>(n);
+        let up = |z: Complex<f32>| Complex::new(z.re as f64, z.im as f64);
+        let mut planner = CztPlanner::<f64>::new();
+        planner.set_strategy(PlannerStrategy::ForceBluestein);
+        let mut expected: Vec<_> = signal.iter().map(|&x| up(x)).collect();
+        planner
+            .plan_czt_forward(n, m, up(a), up(w))
+            .process(&mut expected);
+
+        let mut planner = CztPlanner::<f32>::new();
+        let error = |czt: Arc<dyn Czt<f32>>| {
+            let mut actual = signal.clone();
+            czt.process(&mut actual);
+            let actual: Vec<_> = actual[..m].iter().map(|&x| up(x)).collect();
+            ErrorReport::compare(&expected[..m], &actual).max_abs_error
+        };
+        let auto = error(planner.plan_czt_forward(n, m, a, w));
+        let measured = error(planner.plan_czt_forward_measured(n, m, a, w));
+        planner.set_strategy(PlannerStrategy::ForceBluestein);
+        let bluestein = error(planner.plan_czt_forward(n, m, a, w));
+        assert!(auto <= 4.0 * bluestein, "n = {n}: {auto} > 4 * {bluestein}");
+        assert!(
+            measured <= 4.0 * bluestein,
+            "n = {n}: {measured} > 4 * {bluestein}"
+        );
+    }
+}
+
+#[test]
+fn test_auto_plans_direct_for_few_bins() {
+    let (n, m) = (1024, 16);
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -1e-3);
+    let mut planner = CztPlanner::<f64>::new();
+    let czt = planner.plan_czt_forward(n, m, a, w);
+    assert_eq!(
+        planner.wisdom().get(n, m, a, w).unwrap().algorithm,
+        Algorithm::Direct
+    );
+
+    let signal = random_signal(n);
+    let mut actual = signal.clone();
+    czt.process(&mut actual);
+    assert_spectra_match(&naive_czt(&signal, &a, &w)[..m], &actual[..m]);
+}
+
+#[test]
+fn test_cost_estimates() {
+    // Few bins are cheapest directly, many through Bluestein's convolution.
+    assert_eq!(
+        estimate_cost::<f64>(1000, 2),
+        cost::goertzel::<f64>(1000, 2)
+    );
+    assert_eq!(
+        estimate_cost::<f64>(1000, 1000),
+        cost::bluesteins::<f64>(1000, 1000, ConvolutionLength::default())
+    );
+    assert!(cost::naive::<f64>(100, 100).flops > cost::goertzel::<f64>(100, 100).flops);
+    assert_eq!(
+        cost::naive::<f32>(100, 100).bytes * 2,
+        cost::naive::<f64>(100, 100).bytes
+    );
+    // The direct kernel multiplies eight bins per f32 vector and four per f64 vector.
+    assert!(cost::direct::<f32>(1000, 16).flops < cost::direct::<f64>(1000, 16).flops);
+    assert!(cost::direct::<f64>(1000, 16).flops < cost::naive::<f64>(1000, 16).flops);
+
+    let (n, m) = (300, 200);
+    let a = Complex::from_polar(1.0, 0.1);
+    let w = Complex::from_polar(1.0, -0.01);
+    let naive = NaiveCzt::new(n, m, a, w);
+    assert_eq!(naive.cost(), cost::naive::<f64>(n, m));
+    assert_eq!(DirectCzt::new(n, m, a, w).cost(), cost::direct::<f64>(n, m));
+    let mut fft_planner = FftPlanner::new();
+    for length in [ConvolutionLength::Smooth, ConvolutionLength::PowerOfTwo] {
+        let bluestein =
+            BluesteinsAlgorithm::new_segment(n, m, a, w, 0, 0, length, &mut fft_planner);
+        assert_eq!(bluestein.cost(), cost::bluesteins::<f64>(n, m, length));
+        assert!(bluestein.cost().bytes > 0);
+    }
+}
+
+#[test]
+fn test_decode_iq_formats() {
+    let cases = [
+        (
+            IqFormat::I16(Endianness::Little),
+            vec![0x00, 0x40, 0x00, 0x80, 0xff, 0x7f, 0x00, 0x00, 0x01],
+            vec![
+                Complex::new(0.5, -1.0),
+                Complex::new(32767.0 / 32768.0, 0.0),
+            ],
+        ),
+        (
+            IqFormat::I16(Endianness::Big),
+            vec![0x40, 0x00, 0x80, 0x00],
+            vec![Complex::new(0.5, -1.0)],
+        ),
+        (
+            IqFormat::I8,
+            vec![0x40, 0x80, 0x7f, 0x00],
+            vec![Complex::new(0.5, -1.0), Complex::new(127.0 / 128.0, 0.0)],
+        ),
+        (
+            IqFormat::U8,
+            vec![0, 255, 127, 128, 7],
+            vec![
+                Complex::new(-1.0, 1.0),
+                Complex::new(-0.5 / 127.5, 0.5 / 127.5),
+            ],
+        ),
+    ];
+    for (format, bytes, expected) in cases {
+        assert_eq!(decode_iq(&bytes, format), expected, "{format:?}");
+        assert_eq!(read_iq(&bytes[..], format).unwrap(), expected);
+    }
+    assert_eq!(IqFormat::U8.bytes_per_sample(), 2);
+}
+
+#[test]
+fn test_scipy_compat_defaults_and_zoom() {
+    // With every default, `czt` is the DFT.
+    let signal = random_signal::<f64>(30);
+    let mut fft = signal.clone();
+    FftPlanner::new().plan_fft_forward(30).process(&mut fft);
+    let spectrum = scipy::czt(&signal, None, None, None);
+    assert_eq!(spectrum.len(), 30);
+    for (ex, ac) in fft.iter().zip(&spectrum) {
+        assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+
+    let (m, w, a) = (
+        12,
+        Complex::from_polar(1.01, -0.1),
+        Complex::from_polar(0.9, 0.4),
+    );
+    let plan = scipy::Czt::new(30, Some(m), Some(w), Some(a));
+    let mut expected = signal.clone();
+    NaiveCzt::new(30, m, a, w).process(&mut expected);
+    for (ex, ac) in expected.iter().zip(&plan.call(&signal)) {
+        assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+    let points = plan.points();
+    assert_eq!(points, scipy::czt_points(m, Some(w), Some(a)));
+    assert!((points[3] - a * w.powi(-3)).norm() < 1e-15);
+
+    // `ZoomFFT(30, [100, 200], m=5, fs=1000)` stops short of 200 Hz unless `endpoint`.
+    let zoom = scipy::ZoomFft::new(30, (100.0, 200.0), Some(5), 1000.0, false);
+    assert_eq!(zoom.freqs(), [100.0, 120.0, 140.0, 160.0, 180.0]);
+    let closed = scipy::ZoomFft::new(30, (100.0, 200.0), Some(5), 1000.0, true);
+    assert_eq!(closed.freqs(), [100.0, 125.0, 150.0, 175.0, 200.0]);
+    for (z, f) in closed.points().iter().zip(closed.freqs()) {
+        let expected = Complex::from_polar(1.0, std::f64::consts::TAU * f / 1000.0);
+        assert!((z - expected).norm() < 1e-12);
+    }
+    let spectrum = scipy::zoom_fft(&signal, (100.0, 200.0), Some(5), 1000.0, true);
+    assert_eq!(spectrum, closed.call(&signal));
+}
+
+#[test]
+fn test_matlab_czt_matches_scipy() {
+    let signal = random_signal::<f64>(40);
+    let (w, a) = (
+        Complex::from_polar(1.0, -0.02),
+        Complex::from_polar(1.0, 0.5),
+    );
+    for (m, w, a) in [
+        (None, None, None),
+        (Some(25), Some(w), Some(a)),
+        (Some(1), None, Some(a)),
+    ] {
+        let expected = scipy::czt(&signal, m, w, a);
+        // The second call runs the cached plan.
+        for _ in 0..2 {
+            assert_eq!(matlab::czt(&signal, m, w, a), expected);
+        }
+    }
+
+    let single: Vec<Complex<f32>> = signal
+        .iter()
+        .map(|x| Complex::new(x.re as f32, x.im as f32))
+        .collect();
+    let (expected, actual) = (
+        matlab::czt(&signal, Some(8), None, None),
+        matlab::czt(&single, Some(8), None, None),
+    );
+    for (ex, ac) in expected.iter().zip(&actual) {
+        assert!((ex - Complex::new(ac.re as f64, ac.im as f64)).norm() < 1e-4 * ex.norm());
+    }
+}
+
+#[test]
+fn test_try_process_reports_lengths() {
+    let (n, m) = (20, 30);
+    let czt = CztPlanner::<f64>::new().plan_czt_forward(
+        n,
+        m,
+        Complex::from_polar(1.0, 0.1),
+        Complex::from_polar(1.0, -0.01),
+    );
+    let signal = random_signal::<f64>(m);
+    let mut expected = signal.clone();
+    czt.process(&mut expected);
+
+    let mut buffer = signal.clone();
+    assert_eq!(czt.try_process(&mut buffer), Ok(()));
+    assert_eq!(buffer, expected);
+    let mut scratch = vec![Complex::zero(); czt.get_inplace_scratch_len()];
+    let mut buffer = signal.clone();
+    assert_eq!(
+        czt.try_process_with_scratch(&mut buffer, &mut scratch),
+        Ok(())
+    );
+    assert_eq!(buffer, expected);
+
+    let mut short = signal[..n].to_vec();
+    let error = czt.try_process(&mut short).unwrap_err();
+    assert_eq!(
+        error,
+        CztError::BufferLength {
+            expected: m,
+            actual: n
+        }
+    );
+    assert_eq!(short, signal[..n]);
+    assert_eq!(
+        error.to_string(),
+        "buffer holds 20 samples, the plan needs 30"
+    );
+    let mut buffer = signal;
+    assert_eq!(
+        czt.try_process_with_scratch(&mut buffer, &mut scratch[1..]),
+        Err(CztError::ScratchLength {
+            expected: scratch.len(),
+            actual: scratch.len() - 1
+        })
+    );
+}
+
+#[test]
+fn test_try_new_validates_parameters() {
+    let (a, w) = (
+        Complex::from_polar(1.0, 0.2),
+        Complex::from_polar(1.0, -0.05),
+    );
+    let zero = Complex::zero();
+    let nan = Complex::new(f64::NAN, 0.0);
+    let cases = [
+        ((0, 10, a, w), PlanError::ZeroLength { name: "n" }),
+        ((10, 0, a, w), PlanError::ZeroLength { name: "m" }),
+        ((10, 10, zero, w), PlanError::InvalidPoint { name: "a" }),
+        ((10, 10, a, zero), PlanError::InvalidPoint { name: "w" }),
+        ((10, 10, a, nan), PlanError::InvalidPoint { name: "w" }),
+    ];
+    let mut planner = CztPlanner::<f64>::new();
+    let mut fft_planner = FftPlanner::new();
+    for ((n, m, a, w), error) in cases {
+        assert_eq!(CztParams { n, m, a, w }.validate(), Err(error));
+        assert_eq!(planner.try_plan_czt_forward(n, m, a, w).err(), Some(error));
+        assert_eq!(
+            BluesteinsAlgorithm::try_new(n, m, a, w, 0, &mut fft_planner).err(),
+            Some(error)
+        );
+    }
+    assert_eq!(
+        PlanError::InvalidPoint { name: "w" }.to_string(),
+        "w must be finite and nonzero"
+    );
+
+    let czt = planner.try_plan_czt_forward(10, 12, a, w).unwrap();
+    assert_eq!(czt.params(), CztParams { n: 10, m: 12, a, w });
+    let bluestein = BluesteinsAlgorithm::try_new(10, 12, a, w, 0, &mut fft_planner).unwrap();
+    assert_eq!(
+        (bluestein.get_input_len(), bluestein.get_output_len()),
+        (10, 12)
+    );
+}
+
+#[test]
+fn test_process_checked_names_failing_stage() {
+    let (n, m) = (64, 48);
+    let a = Complex::from_polar(1.0f32, 0.3);
+    let w = Complex::from_polar(1.0f32, -0.01);
+    let bluestein = BluesteinsAlgorithm::new(n, m, a, w, 0, &mut FftPlanner::new());
+
+    let signal = random_signal::<f32>(n);
+    let (mut expected, mut actual) = (signal.clone(), signal.clone());
+    bluestein.process(&mut expected);
+    assert_eq!(bluestein.process_checked(&mut actual), Ok(()));
+    assert_eq!(actual, expected);
+
+    let mut poisoned = signal.clone();
+    poisoned[5].im = f32::NAN;
+    assert_eq!(
+        bluestein.process_checked(&mut poisoned),
+        Err(CztError::NonFinite {
+            stage: "input",
+            index: 5
+        })
+    );
+
+    // Finite samples whose sum overflows: the forward FFT is the first stage to blow up.
+    let mut huge = vec![Complex::new(f32::MAX / 4.0, 0.0); n];
+    let error = bluestein.process_checked(&mut huge).unwrap_err();
+    let stage = if cfg!(debug_assertions) {
+        "forward FFT"
+    } else {
+        "output"
+    };
+    assert!(
+        matches!(error, CztError::NonFinite { stage: s, .. } if s == stage),
+        "{error}"
+    );
+    if cfg!(debug_assertions) {
+        assert_eq!(huge, vec![Complex::new(f32::MAX / 4.0, 0.0); n]);
+    }
+
+    let (a, w) = (
+        Complex::from_polar(1.2, 0.1),
+        Complex::from_polar(1.001, -0.02),
+    );
+    let spiral = SpiralCzt::new(
+        400,
+        300,
+        a,
+        w,
+        0,
+        ConvolutionLength::default(),
+        &mut FftPlanner::new(),
+    );
+    let signal = random_signal::<f64>(400);
+    let (mut expected, mut actual) = (signal.clone(), signal.clone());
+    spiral.process(&mut expected);
+    assert_eq!(spiral.process_checked(&mut actual), Ok(()));
+    assert_eq!(actual, expected);
+    assert_eq!(
+        spiral.process_checked(&mut actual[1..]),
+        Err(CztError::BufferLength {
+            expected: 400,
+            actual: 399
+        })
+    );
+
+    // Here `|z_k|^-j` exceeds the range of f64: the first block's chirp overflows.
+    let w = Complex::from_polar(1.01, -0.02);
+    let spiral = SpiralCzt::new(
+        400,
+        300,
+        a,
+        w,
+        0,
+        ConvolutionLength::default(),
+        &mut FftPlanner::new(),
+    );
+    let mut actual = signal.clone();
+    let error = spiral.process_checked(&mut actual).unwrap_err();
+    let stage = if cfg!(debug_assertions) {
+        "chirp"
+    } else {
+        "output"
+    };
+    assert!(
+        matches!(error, CztError::NonFinite { stage: s, .. } if s == stage),
+        "{error}"
+    );
+    assert_eq!(actual, signal);
+}
+
+#[test]
+fn test_verify_against_naive() {
+    let mut planner = CztPlanner::new();
+    let (a, w) = (
+        Complex::from_polar(0.98, 0.3),
+        Complex::from_polar(1.0005, -0.01),
+    );
+    assert!(planner.plan_czt_forward(300, 200, a, w).verify(1e-10));
+    assert!(CztPlanner::<f32>::new()
+        .plan_czt_forward(
+            300,
+            200,
+            Complex::from_polar(1.0, 0.3),
+            Complex::from_polar(1.0, -0.01)
+        )
+        .verify(1e-3));
+    assert!(SpiralCzt::new(
+        400,
+        300,
+        Complex::from_polar(1.2, 0.1),
+        Complex::from_polar(1.001, -0.02),
+        0,
+        ConvolutionLength::default(),
+        &mut FftPlanner::new(),
+    )
+    .verify(1e-9));
+
+    // Folded windows, scaling and delays are part of the reference, on single convolutions and
+    // on spirals split into blocks.
+    let spiral = Complex::from_polar(1.001, -0.02);
+    assert!(SpiralCzt::is_needed(400, 300, spiral));
+    for (n, m, w) in [(300, 200, w), (400, 300, spiral)] {
+        let folded: [Arc<dyn Czt<f64>>; 4] = [
+            planner.plan_czt_forward_normalized(n, m, a, w, Normalization::OneOverN),
+            planner.plan_czt_forward_windowed(n, m, a, w, Window::Hann),
+            planner.plan_czt_forward_delayed(n, m, a, w, 2.5),
+            planner.plan_czt_forward_with_offset(n, m, a, w, -7),
+        ];
+        for czt in folded {
+            assert!(czt.verify(1e-9));
+        }
+    }
+    assert!(planner
+        .plan_zoom_fft_windowed(300, 200, 0.1, 0.2, Window::Hann)
+        .verify(1e-9));
+    let segment = BluesteinsAlgorithm::new_segment(
+        100,
+        80,
+        a,
+        w,
+        50,
+        10,
+        ConvolutionLength::default(),
+        &mut FftPlanner::new(),
+    )
+    .with_delay(0.5);
+    assert!(segment.verify(1e-9));
+    assert!(planner
+        .plan_czt_inverse_exact(
+            16,
+            Complex::from_polar(1.0, 0.3),
+            Complex::from_polar(1.0, -0.3)
+        )
+        .verify(1e-8));
+}
+
+#[test]
+fn test_error_report_finds_worst_bin() {
+    let expected = vec![Complex::new(1.0, 0.0); 4];
+    let mut actual = expected.clone();
+    actual[1].re += 0.3;
+    actual[2].im -= 0.4;
+    let report = ErrorReport::compare(&expected, &actual);
+    assert_eq!(report.worst_bin, 2);
+    assert!((report.max_abs_error - 0.4f64).abs() < 1e-12);
+    assert!((report.rms_error - (0.25f64 / 4.0).sqrt()).abs() < 1e-12);
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "max error {} at bin 2, rms error {}",
+            report.max_abs_error, report.rms_error
+        )
+    );
+
+    actual[3] = Complex::new(f64::NAN, 0.0);
+    let report = ErrorReport::compare(&expected, &actual);
+    assert_eq!(report.worst_bin, 3);
+    assert!(report.max_abs_error.is_nan() && report.rms_error.is_nan());
+
+    let report = ErrorReport::<f32>::compare(&[], &[]);
+    assert_eq!(
+        (report.max_abs_error, report.rms_error, report.worst_bin),
+        (0.0, 0.0, 0)
+    );
+
+    // Against NaiveCzt, a Bluestein plan stays within a few ulps of the peak.
+    let signal = random_signal::<f64>(200);
+    let (a, w) = (
+        Complex::from_polar(1.0, 0.2),
+        Complex::from_polar(1.0, -0.01),
+    );
+    let expected = naive_czt(&signal, &a, &w);
+    let mut actual = signal.clone();
+    BluesteinsAlgorithm::new(200, 200, a, w, 0, &mut FftPlanner::new()).process(&mut actual);
+    let peak = expected.iter().map(|x| x.norm()).fold(0.0, f64::max);
+    let report = ErrorReport::compare(&expected, &actual);
+    assert!(report.max_abs_error < 1e-11 * peak, "{report}");
+    assert!(report.rms_error <= report.max_abs_error);
+}
+
+#[test]
+fn test_naive_compensated_summation() {
+    let n = 100_000;
+    let signal = random_signal::<f32>(n);
+    // At `z = 1` every power is exact, leaving only the summation error.
+    let (a, w) = (Complex::new(1.0, 0.0), Complex::from_polar(1.0, -0.2));
+
+    let widen = |x: &Complex<f32>| Complex::new(x.re as f64, x.im as f64);
+    let mut expected: Vec<_> = signal.iter().map(widen).collect();
+    NaiveCzt::new(n, 1, widen(&a), widen(&w))
+        .with_compensated_summation()
+        .process(&mut expected);
+
+    let error = |czt: NaiveCzt<f32>| {
+        let mut actual = signal.clone();
+        czt.process(&mut actual);
+        let actual: Vec<_> = actual[..1].iter().map(widen).collect();
+        ErrorReport::compare(&expected[..1], &actual).max_abs_error
+    };
+    let plain = error(NaiveCzt::new(n, 1, a, w));
+    let compensated = error(NaiveCzt::new(n, 1, a, w).with_compensated_summation());
+    // The bin sums to about 5e5.
+    assert!(compensated < 0.2, "{compensated} {plain}");
+    assert!(compensated * 10.0 < plain, "{compensated} {plain}");
+}
+
+#[test]
+fn test_naive_recurrence_accuracy() {
+    let n = 100_000;
+    let signal = random_signal::<f32>(n);
+    let a = Complex::from_polar(1.0f32, 0.3);
+
+    // The f64 reference evaluates on the same point `a`.
+    let z_inv = a.inv();
+    let widen = |x: &Complex<f32>| Complex::new(x.re as f64, x.im as f64);
+    let mut expected: Vec<_> = signal.iter().map(widen).collect();
+    NaiveCzt::new(n, 1, widen(&a), Complex::new(1.0, 0.0))
+        .with_compensated_summation()
+        .process(&mut expected);
+    let mut actual = signal.clone();
+    NaiveCzt::new(n, 1, a, Complex::new(1.0, 0.0))
+        .with_compensated_summation()
+        .process(&mut actual);
+
+    // The terms `x_j z^-j` with `powi` powers, as NaiveCzt used to sum them.
+    let powi: Complex<f64> = signal
+        .iter()
+        .enumerate()
+        .fold(Complex::zero(), |sum, (j, x)| {
+            sum + widen(&(x * z_inv.powi(j as i32)))
+        });
+    let l1: f64 = signal.iter().map(|x| widen(x).norm()).sum();
+    let error = (widen(&actual[0]) - expected[0]).norm();
+    assert!(error < 1e-7 * l1, "{error}");
+    assert!(3.0 * error < (powi - expected[0]).norm());
+}
+
+#[test]
+fn test_direct_czt_matches_naive() {
+    for (n, m, a, w) in [
+        (1, 1, Complex::new(1.0, 0.0), Complex::from_polar(1.0, -0.3)),
+        (
+            37,
+            13,
+            Complex::from_polar(1.0, 0.4),
+            Complex::from_polar(1.0, -0.05),
+        ),
+        (
+            64,
+            64,
+            Complex::new(1.0, 0.0),
+            Complex::from_polar(1.0, -std::f64::consts::PI / 32.0),
+        ),
+        (
+            300,
+            517,
+            Complex::from_polar(0.995, 0.3),
+            Complex::from_polar(1.0001, -0.01),
+        ),
+    ] {
+        let signal = random_signal::<f64>(n.max(m));
+        let mut expected = signal.clone();
+        NaiveCzt::new(n, m, a, w).process(&mut expected);
+        let direct = DirectCzt::new(n, m, a, w);
+        assert_eq!(direct.params(), CztParams { n, m, a, w });
+        assert_eq!(direct.get_inplace_scratch_len(), n);
+        let mut actual = signal.clone();
+        direct.process(&mut actual);
+
+        let peak = expected[..m].iter().map(|x| x.norm()).fold(0.0, f64::max);
+        let report = ErrorReport::compare(&expected[..m], &actual[..m]);
+        assert!(report.max_abs_error < 1e-10 * peak, "{n} {m}: {report}");
+        assert_eq!(actual[m..], signal[m..]);
+    }
+
+    let (a, w) = (Complex::new(1.0f32, 0.0), Complex::from_polar(1.0, -0.01));
+    assert!(DirectCzt::new(100, 40, a, w).verify(1e-4));
+
+    let mut wisdom = Wisdom::new();
+    let entry = WisdomEntry {
+        algorithm: Algorithm::Direct,
+        length: ConvolutionLength::Smooth,
+        nanos: Some(1),
+    };
+    wisdom.insert(
+        64,
+        64,
+        Complex::new(1.0, 0.0),
+        Complex::from_polar(1.0, -0.1),
+        entry,
+    );
+    assert_eq!(Wisdom::from_text(&wisdom.to_text()), Some(wisdom));
+}
+
+#[test]
+fn test_dft_contours_plan_a_single_fft() {
+    let n = 1000;
+    let one = Complex::new(1.0, 0.0);
+    let w = Complex::from_polar(1.0, -std::f64::consts::TAU / n as f64);
+    assert_eq!(DftCzt::direction(n, n, one, w), Some(FftDirection::Forward));
+    assert_eq!(
+        DftCzt::direction(n, n, one, w.conj()),
+        Some(FftDirection::Inverse)
+    );
+    assert_eq!(DftCzt::direction(n, n - 1, one, w), None);
+    assert_eq!(DftCzt::direction(n, n, -one, w), None);
+    assert_eq!(DftCzt::direction(n + 1, n + 1, one, w), None);
+    // A point computed in f32 arithmetic is within the rounding of f32.
+    let w32 = Complex::from_polar(1.0f32, -std::f32::consts::TAU / n as f32);
+    assert_eq!(
+        DftCzt::direction(n, n, Complex::new(1.0, 0.0), w32),
+        Some(FftDirection::Forward)
+    );
+
+    let signal = random_signal::<f64>(n);
+    let mut planner = CztPlanner::new();
+    for (czt_obj, direction) in [
+        (
+            planner.plan_czt_forward(n, n, one, w),
+            FftDirection::Forward,
+        ),
+        (
+            planner.plan_czt_backward(n, n, one, w),
+            FftDirection::Inverse,
+        ),
+    ] {
+        assert_eq!(czt_obj.cost(), cost::fft::<f64>(n));
+        let mut expected = signal.clone();
+        FftPlanner::new()
+            .plan_fft(n, direction)
+            .process(&mut expected);
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        assert_eq!(actual, expected);
+    }
+    // Measuring has nothing to choose between, so the planned DFT is reused as is.
+    let measured = planner.plan_czt_forward_measured(n, n, one, w);
+    assert!(Arc::ptr_eq(
+        &measured,
+        &planner.plan_czt_forward(n, n, one, w)
+    ));
+    assert!(Arc::ptr_eq(
+        &measured,
+        &planner.plan_czt_forward_measured(n, n, one, w)
+    ));
+
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    assert_ne!(
+        planner.plan_czt_forward(n, n, one, w).cost(),
+        cost::fft::<f64>(n)
+    );
+}
+
+#[test]
+fn test_near_roots_of_unity_keep_bluestein_accuracy() {
+    // One ulp off a root of order 4096: snapping to the root moves the far bins by n m ulp.
+    let n = 2048;
+    let root = Complex::from_polar(1.0f64, -std::f64::consts::TAU * 7.0 / 4096.0);
+    let w = Complex::new(
+        root.re as f32,
+        f32::from_bits((root.im as f32).to_bits() + 1),
+    );
+    assert_eq!(RotationCzt::root(w, 1 << 12), None);
+
+    let a = Complex::new(1.0f32, 0.0);
+    let signal = random_signal::<f32>(n);
+    let to_f64 = |z: Complex<f32>| Complex::new(z.re as f64, z.im as f64);
+    let mut expected: Vec<_> = signal.iter().map(|&x| to_f64(x)).collect();
+    NaiveCzt::new(n, n, to_f64(a), to_f64(w)).process(&mut expected);
+    let mut planner = CztPlanner::new();
+    let error = |planner: &mut CztPlanner<f32>| {
+        let mut actual = signal.clone();
+        planner.plan_czt_forward(n, n, a, w).process(&mut actual);
+        let actual: Vec<_> = actual.iter().map(|&x| to_f64(x)).collect();
+        ErrorReport::compare(&expected, &actual).max_abs_error
+    };
+    let auto = error(&mut planner);
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let bluestein = error(&mut planner);
+    assert!(auto <= 4.0 * bluestein, "{auto} vs {bluestein}");
+}
+
+#[test]
+fn test_root_of_unity_contours_skip_the_convolution() {
+    let (n, m) = (1000, 300);
+    let w = Complex::from_polar(1.0, -std::f64::consts::TAU * 3.0 / 64.0);
+    assert_eq!(RotationCzt::root(w, 1 << 12), Some((64, 3)));
+    assert_eq!(RotationCzt::root(w.conj(), 1 << 12), Some((64, 61)));
+    assert_eq!(RotationCzt::root(w, 63), None);
+    assert_eq!(
+        RotationCzt::root(Complex::from_polar(1.0, -0.1), 1 << 12),
+        None
+    );
+    let w32 = Complex::new(w.re as f32, w.im as f32);
+    assert_eq!(RotationCzt::root(w32, 1 << 12), Some((64, 3)));
+
+    // A zoom off the unit circle, starting between FFT bins.
+    let a = Complex::from_polar(0.9995, 0.3);
+    let signal = random_signal::<f64>(n);
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt_forward(n, m, a, w);
+    assert_eq!(czt_obj.cost(), cost::rotation::<f64>(n, m, 64));
+    assert_eq!(czt_obj.params().w, w);
+
+    let mut expected = signal.clone();
+    NaiveCzt::new(n, m, a, w).process(&mut expected);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+    let peak = expected.iter().map(|x| x.norm()).fold(0.0, f64::max);
+    let report = ErrorReport::compare(&expected[..m], &actual[..m]);
+    assert!(report.max_abs_error <= 1e-10 * peak, "{report}");
+    assert!(czt_obj.verify(1e-10));
+}
+
+#[test]
+fn test_blocked_czt_bounds_scratch() {
+    let mut fft_planner = FftPlanner::new();
+    for (n, m, a, w) in [
+        (
+            5000,
+            1200,
+            Complex::from_polar(1.0, 0.2),
+            Complex::from_polar(1.0, -0.001),
+        ),
+        (
+            300,
+            2000,
+            Complex::from_polar(1.0, -1.0),
+            Complex::from_polar(1.0, 0.0007),
+        ),
+        (
+            3000,
+            500,
+            Complex::from_polar(0.9999, 0.5),
+            Complex::from_polar(1.00001, -0.002),
+        ),
+    ] {
+        let signal = random_signal::<f64>(n);
+        let mut expected = signal.clone();
+        expected.resize(n.max(m), Complex::zero());
+        BluesteinsAlgorithm::new(n, m, a, w, 0, &mut fft_planner).process(&mut expected);
+        let peak = expected[..m].iter().map(|x| x.norm()).fold(0.0, f64::max);
+
+        let czt_obj = BlockedCzt::new(
+            n,
+            m,
+            a,
+            w,
+            1000,
+            ConvolutionLength::default(),
+            &mut fft_planner,
+        );
+        let (input_block, output_block) = czt_obj.block_lens();
+        assert!(input_block < n && output_block < m);
+        assert!(czt_obj.get_outofplace_scratch_len() <= 1000);
+        assert_eq!(
+            czt_obj.get_inplace_scratch_len(),
+            m + czt_obj.get_outofplace_scratch_len()
+        );
+
+        let mut actual = signal.clone();
+        actual.resize(n.max(m), Complex::zero());
+        czt_obj.process(&mut actual);
+        let report = ErrorReport::compare(&expected[..m], &actual[..m]);
+        assert!(report.max_abs_error <= 1e-10 * peak, "{report}");
+
+        let mut output = vec![Complex::zero(); m];
+        let mut scratch = vec![Complex::zero(); czt_obj.get_outofplace_scratch_len()];
+        czt_obj.process_outofplace_with_scratch(&signal, &mut output, &mut scratch);
+        assert_eq!(output, actual[..m]);
+    }
+
+    // A budget for the whole convolution leaves a single pair of blocks.
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt_forward_blocked(
+        100,
+        50,
+        Complex::new(1.0, 0.0),
+        Complex::from_polar(1.0, -0.01),
+        usize::MAX,
+    );
+    let l = ConvolutionLength::default().for_len(149);
+    assert_eq!(czt_obj.cost(), cost::blocked::<f64>(100, 50, (100, 50), l));
+}
+
+#[test]
+fn test_windowed_zoom_spans_its_bins() {
+    // The first `n` bins span `[start, end]` whatever `m` is, as for `plan_zoom_fft_with_m`.
+    let mut planner = CztPlanner::<f64>::new();
+    let signal = random_signal::<f64>(64);
+    let windowed_signal: Vec<_> = signal
+        .iter()
+        .zip(Window::<f64>::Hann.coefficients(64))
+        .map(|(&x, weight)| x * weight)
+        .collect();
+    for m in [1, 9, 64, 200] {
+        let windowed = planner.plan_zoom_fft_windowed(64, m, 0.1, 0.2, Window::Hann);
+        let plain = planner.plan_zoom_fft_with_m(64, m, 0.1, 0.2);
+        assert_eq!(windowed.freqs(), plain.freqs());
+        assert!((plain.freq_of_bin(63) - 0.2).abs() < 1e-15);
+
+        let mut expected = windowed_signal.clone();
+        expected.resize(64.max(m), Complex::zero());
+        let mut actual = signal.clone();
+        actual.resize(64.max(m), Complex::zero());
+        plain.process(&mut expected);
+        windowed.process(&mut actual);
+        for (ex, ac) in expected.iter().zip(&actual).take(m) {
+            assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+        }
+    }
+
+    // A single sample has no spacing to span, so its bins are `end - start` apart.
+    let single = planner.plan_zoom_fft_with_m(1, 3, 0.1, 0.2);
+    assert!((single.freq_of_bin(2) - 0.3).abs() < 1e-15);
+    let mut buffer = vec![Complex::new(2.0, 0.0), Complex::zero(), Complex::zero()];
+    single.process(&mut buffer);
+    assert!(buffer
+        .iter()
+        .all(|x| (x - Complex::new(2.0, 0.0)).norm() < 1e-12));
+}
+
+#[test]
+fn test_windowed_spiral_contour() {
+    // A single convolution loses every digit here; the window has to reach the spiral blocks.
+    let a = Complex::new(1.0, 0.0);
+    let w = Complex::from_polar(0.995, -0.01);
+    for n in [500, 2000] {
+        assert!(SpiralCzt::is_needed(n, n, w));
+        let signal = random_signal::<f64>(n);
+        let mut expected: Vec<_> = signal
+            .iter()
+            .zip(Window::<f64>::Hann.coefficients(n))
+            .map(|(&x, weight)| x * weight)
+            .collect();
+        NaiveCzt::new(n, n, a, w).process(&mut expected);
+
+        let mut planner = CztPlanner::new();
+        let czt_obj = planner.plan_czt_forward_windowed(n, n, a, w, Window::Hann);
+        assert!(Arc::ptr_eq(
+            &czt_obj,
+            &planner.plan_czt_forward_windowed(n, n, a, w, Window::Hann)
+        ));
+        let mut actual = signal.clone();
+        czt_obj.process(&mut actual);
+        for (k, (ex, ac)) in expected.iter().zip(&actual).enumerate() {
+            assert!(
+                (ex - ac).norm() < 1e-8 * ex.norm().max(1.0),
+                "n = {n}, bin {k}: {ex} != {ac}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_spiral_caps_its_block_count() {
+    // Blocks of 6: 50 * 50 blocks are split, 167 * 167 fall back to a single convolution.
+    let (a, w) = (Complex::new(1.0, 0.0), Complex::from_polar(1.5, -0.01));
+    assert!(SpiralCzt::is_needed(300, 300, w));
+    assert!(!SpiralCzt::is_needed(1000, 1000, w));
+    assert!(!SpiralCzt::is_needed(usize::MAX / 2, usize::MAX / 2, w));
+
+    let mut planner = CztPlanner::<f64>::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(1000, 1000, a, w);
+    let single = BluesteinsAlgorithm::new(1000, 1000, a, w, 0, &mut FftPlanner::new());
+    assert_eq!(czt_obj.get_memory_usage(), single.get_memory_usage());
 }
@@ -0,0 +1,47 @@
+#![cfg(feature = "astro-float")]
+
+use rustczt::{
+    arbitrary_precision::{ArbitraryPrecisionCzt, BigComplex},
+    CztPlanner, PlannerStrategy,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_arbitrary_precision_reference() {
+    let signal: Vec<_> = (0..128)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.005);
+
+    let mut planner = CztPlanner::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(128, 48, a, w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+    actual.truncate(48);
+
+    let reference = ArbitraryPrecisionCzt::from_params(czt_obj.params(), 256);
+    let expected = reference.process_to_f64(&signal);
+    let scale = expected.iter().map(|x| x.norm()).fold(0.0, f64::max);
+    let error = reference.max_error(&signal, &actual);
+    assert!(error > 0.0 && error < 1e-12 * scale, "{error}");
+    for (ex, ac) in expected.iter().zip(&actual) {
+        assert!((ex - ac).norm() <= error * (1.0 + 1e-9), "{ex} != {ac}");
+    }
+
+    // Exact DFT roots of unity: a 4-point DFT of an impulse at j = 1 is (1, -i, -1, i).
+    let dft = ArbitraryPrecisionCzt::new(
+        4,
+        4,
+        BigComplex::from_turns(0, 1, 128),
+        BigComplex::from_turns(-1, 4, 128),
+        128,
+    );
+    let impulse = [0.0, 1.0, 0.0, 0.0].map(|x| Complex::new(x, 0.0));
+    let bins = dft.process_to_f64(&impulse);
+    let expected = [(1.0, 0.0), (0.0, -1.0), (-1.0, 0.0), (0.0, 1.0)];
+    for (bin, (re, im)) in bins.iter().zip(expected) {
+        assert!((bin - Complex::new(re, im)).norm() < 1e-30, "{bin}");
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "bytemuck")]
+
+use rustczt::{
+    bytes::{
+        complex_as_bytes, complex_as_bytes_mut, complex_from_bytes, process_bytes,
+        try_complex_from_bytes, try_complex_from_bytes_mut,
+    },
+    double_double::DoubleDouble,
+    CztPlanner,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_process_bytes_without_copies() {
+    let czt = CztPlanner::new().plan_czt_forward(
+        20,
+        20,
+        Complex::from_polar(1.0f32, 0.1),
+        Complex::from_polar(1.0, -0.2),
+    );
+    let mut samples: Vec<_> = (0..20)
+        .map(|i| Complex::new((i as f32).sin(), (i as f32 * 0.5).cos()))
+        .collect();
+    let mut expected = samples.clone();
+    czt.process(&mut expected);
+
+    // A packet viewed as bytes, as read from a socket into an aligned buffer.
+    let bytes = complex_as_bytes_mut(&mut samples);
+    assert_eq!(bytes.len(), 20 * 8);
+    process_bytes(czt.as_ref(), bytes);
+    assert_eq!(complex_from_bytes::<f32>(bytes), expected);
+    assert_eq!(samples, expected);
+
+    let bytes = complex_as_bytes(&samples);
+    assert!(try_complex_from_bytes::<f32>(&bytes[..bytes.len() - 4]).is_err());
+    assert!(try_complex_from_bytes::<f32>(&bytes[1..bytes.len() - 7]).is_err());
+
+    let mut words = [DoubleDouble::new(1.0, 1e-20); 4];
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+    let pairs = try_complex_from_bytes_mut::<DoubleDouble>(bytes).unwrap();
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[1].im, DoubleDouble::new(1.0, 1e-20));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "capi")]
+
+use rustczt::{
+    capi::{
+        rustczt_plan_buffer_len, rustczt_plan_create, rustczt_plan_destroy, rustczt_plan_process,
+        rustczt_plan_scratch_len, RUSTCZT_BAD_LENGTH, RUSTCZT_NULL_POINTER, RUSTCZT_OK,
+    },
+    naive_czt::NaiveCzt,
+    Czt,
+};
+use rustfft::{num_complex::Complex, num_traits::Zero};
+
+#[test]
+fn test_capi_matches_naive() {
+    let (n, m) = (40, 60);
+    let a = Complex::from_polar(0.9, 0.3);
+    let w = Complex::from_polar(1.001, -0.02);
+    let signal: Vec<_> = (0..n)
+        .map(|j| Complex::new((j as f64 * 0.7).sin(), (j as f64 * 0.3).cos()))
+        .collect();
+    let mut expected = signal.clone();
+    expected.resize(m, Complex::zero());
+    NaiveCzt::new(n, m, a, w).process(&mut expected);
+
+    let plan = rustczt_plan_create(n, m, a, w);
+    assert!(!plan.is_null());
+    unsafe {
+        let len = rustczt_plan_buffer_len(plan);
+        assert_eq!(len, m);
+        let mut scratch = vec![Complex::zero(); rustczt_plan_scratch_len(plan)];
+        for use_scratch in [true, false] {
+            let mut buffer = signal.clone();
+            buffer.resize(len, Complex::zero());
+            let (scratch_ptr, scratch_len) = if use_scratch {
+                (scratch.as_mut_ptr(), scratch.len())
+            } else {
+                (core::ptr::null_mut(), 0)
+            };
+            let status =
+                rustczt_plan_process(plan, buffer.as_mut_ptr(), len, scratch_ptr, scratch_len);
+            assert_eq!(status, RUSTCZT_OK);
+            for (ex, ac) in expected.iter().zip(&buffer) {
+                assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+            }
+        }
+
+        let mut short = vec![Complex::zero(); len - 1];
+        let status =
+            rustczt_plan_process(plan, short.as_mut_ptr(), len - 1, core::ptr::null_mut(), 0);
+        assert_eq!(status, RUSTCZT_BAD_LENGTH);
+        let status =
+            rustczt_plan_process(plan, core::ptr::null_mut(), len, core::ptr::null_mut(), 0);
+        assert_eq!(status, RUSTCZT_NULL_POINTER);
+        rustczt_plan_destroy(plan);
+
+        assert_eq!(rustczt_plan_scratch_len(core::ptr::null()), 0);
+        rustczt_plan_destroy(core::ptr::null_mut());
+    }
+    assert!(rustczt_plan_create(0, m, a, w).is_null());
+    assert!(rustczt_plan_create(n, m, a, Complex::zero()).is_null());
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "cli")]
+
+use std::{fs, path::PathBuf, process::Command};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustczt-cli-{}-{name}", std::process::id()))
+}
+
+fn rustczt(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rustczt"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Frequency of the loudest row of a `frequency,magnitude,phase` table.
+fn peak_frequency(csv: &str) -> f64 {
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("frequency,magnitude,phase"));
+    lines
+        .map(|line| {
+            let fields: Vec<f64> = line.split(',').map(|x| x.parse().unwrap()).collect();
+            (fields[0], fields[1])
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+        .0
+}
+
+#[test]
+fn test_cli_zooms_wav_and_csv() {
+    let (fs_hz, n, tone) = (8000u32, 800, -1250.0);
+    let samples: Vec<(f64, f64)> = (0..n)
+        .map(|j| {
+            let phase = std::f64::consts::TAU * tone * j as f64 / fs_hz as f64;
+            (0.5 * phase.cos(), 0.5 * phase.sin())
+        })
+        .collect();
+
+    // 16-bit stereo WAV, read as I/Q.
+    let mut wav = Vec::new();
+    let data_len = 4 * n as u32;
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    for field in [1u16, 2] {
+        wav.extend_from_slice(&field.to_le_bytes());
+    }
+    wav.extend_from_slice(&fs_hz.to_le_bytes());
+    wav.extend_from_slice(&(4 * fs_hz).to_le_bytes());
+    for field in [4u16, 16] {
+        wav.extend_from_slice(&field.to_le_bytes());
+    }
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for &(re, im) in &samples {
+        wav.extend_from_slice(&((re * 32767.0) as i16).to_le_bytes());
+        wav.extend_from_slice(&((im * 32767.0) as i16).to_le_bytes());
+    }
+    let wav_path = temp_path("tone.wav");
+    fs::write(&wav_path, wav).unwrap();
+    let wav_path = wav_path.to_str().unwrap();
+
+    let table = rustczt(&[
+        "--f-start",
+        "-1300",
+        "--f-end",
+        "-1200",
+        "--bins",
+        "101",
+        "--window",
+        "hann",
+        wav_path,
+    ]);
+    assert_eq!(table.lines().count(), 102);
+    assert_eq!(peak_frequency(&table), tone);
+
+    let json = rustczt(&[
+        wav_path,
+        "--f-start",
+        "-1300",
+        "--f-end",
+        "-1200",
+        "--bins",
+        "11",
+        "--format",
+        "json",
+    ]);
+    assert!(
+        json.starts_with("{\"sample_rate\":8000,\"samples\":800,\"bins\":[{\"frequency\":-1300,")
+    );
+    assert_eq!(json.matches("\"magnitude\"").count(), 11);
+
+    // CSV with a header, written to a file.
+    let csv_path = temp_path("tone.csv");
+    let mut csv = String::from("re,im\n");
+    for &(re, im) in &samples {
+        csv.push_str(&format!("{re},{im}\n"));
+    }
+    fs::write(&csv_path, csv).unwrap();
+    let out_path = temp_path("spectrum.csv");
+    let csv_path = csv_path.to_str().unwrap();
+    let out = out_path.to_str().unwrap();
+    assert_eq!(
+        rustczt(&[
+            "--fs",
+            "8000",
+            "--f-start",
+            "-1300",
+            "--f-end",
+            "-1200",
+            "--bins",
+            "101",
+            "-o",
+            out,
+            csv_path
+        ]),
+        ""
+    );
+    assert_eq!(
+        peak_frequency(&fs::read_to_string(&out_path).unwrap()),
+        tone
+    );
+
+    let failure = Command::new(env!("CARGO_BIN_EXE_rustczt"))
+        .arg(csv_path)
+        .output()
+        .unwrap();
+    assert_eq!(failure.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&failure.stderr).contains("--fs is required"));
+
+    for path in [wav_path, csv_path, out] {
+        fs::remove_file(path).unwrap();
+    }
+}
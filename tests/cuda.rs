@@ -0,0 +1,43 @@
+#![cfg(feature = "cuda")]
+
+use cudarc::driver::{sys, CudaContext};
+use rustczt::{cuda::CudaBluesteins, naive_czt::NaiveCzt, zoom::ZoomFft, Czt};
+use rustfft::num_complex::Complex;
+use std::{f64::consts::PI, sync::Arc};
+
+#[test]
+fn test_cuda_batched_zoom_accuracy() {
+    // Machines without a driver or a device have nothing to test.
+    if !unsafe { sys::is_culib_present() } {
+        return;
+    }
+    let Ok(context) = CudaContext::new(0) else {
+        return;
+    };
+
+    let (n, m, count) = (4096, 256, 16);
+    let signals: Vec<_> = (0..n * count)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let (start, step) = (0.05, 0.0002);
+    let a = Complex::from_polar(1.0, 2.0 * PI * start);
+    let w = Complex::from_polar(1.0, -2.0 * PI * step);
+
+    let czt_obj = ZoomFft::new(
+        Arc::new(CudaBluesteins::new(&context, n, m, a, w)),
+        start,
+        step,
+        1.0,
+    );
+    let mut actual = signals.clone();
+    czt_obj.process_batch(&mut actual, n, count);
+
+    let reference = NaiveCzt::new(n, m, a, w);
+    for (signal, actual) in signals.chunks(n).zip(actual.chunks(n)) {
+        let mut expected = signal.to_vec();
+        reference.process(&mut expected);
+        for (ex, ac) in expected[..m].iter().zip(&actual[..m]) {
+            assert!((ex - ac).norm() < 1e-6 * n as f64, "{ex} != {ac}");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "fftw")]
+
+use rustczt::{fftw_backend::FftwProvider, naive_czt::NaiveCzt, Czt, CztPlanner, PlannerStrategy};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_fftw_backend_accuracy() {
+    let signal: Vec<_> = (0..100)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let mut expected = signal.clone();
+    NaiveCzt::new(signal.len(), signal.len(), a, w).process(&mut expected);
+
+    let mut planner = CztPlanner::with_fft_provider(FftwProvider::new().with_threads(2));
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    for (ex, ac) in expected.iter().zip(&actual) {
+        assert!((ex - ac).norm() < 1e-8, "{ex} != {ac}");
+    }
+}
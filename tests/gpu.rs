@@ -0,0 +1,33 @@
+#![cfg(feature = "gpu")]
+
+use rustczt::{naive_czt::NaiveCzt, Czt, CztPlanner};
+use rustfft::{num_complex::Complex, FftPlanner};
+
+#[test]
+fn test_gpu_batched_accuracy() {
+    // Machines without an adapter have nothing to test.
+    let Some(mut planner) = CztPlanner::<f32>::with_gpu(FftPlanner::new()) else {
+        return;
+    };
+    assert!(CztPlanner::<f64>::with_gpu(FftPlanner::new()).is_none());
+
+    let (n, m, stride, count) = (100, 60, 128, 6);
+    let signals: Vec<_> = (0..stride * count)
+        .map(|i| Complex::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let czt_obj = planner.plan_czt_forward(n, m, a, w);
+    let mut actual = signals.clone();
+    czt_obj.process_batch(&mut actual, stride, count);
+
+    for (signal, actual) in signals.chunks(stride).zip(actual.chunks(stride)) {
+        let mut expected = signal[..n].to_vec();
+        NaiveCzt::new(n, m, a, w).process(&mut expected);
+        for (ex, ac) in expected[..m].iter().zip(&actual[..m]) {
+            assert!((ex - ac).norm() < 1e-3, "{ex} != {ac}");
+        }
+        assert_eq!(signal[n..], actual[n..]);
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "half")]
+
+use half::f16;
+use rustczt::{
+    half_precision::{narrow, widen, HalfCzt},
+    CztPlanner,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_half_czt_matches_f32_plan() {
+    let (n, m) = (96, 40);
+    let signal: Vec<_> = (0..n)
+        .map(|i| Complex::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+        .collect();
+    let mut packed = vec![f16::ZERO; 2 * n];
+    narrow(&signal, &mut packed);
+    let mut widened = vec![Complex::default(); n];
+    widen(&packed, &mut widened);
+    for (x, y) in signal.iter().zip(&widened) {
+        assert!((x - y).norm() < 1e-3, "{x} != {y}");
+    }
+
+    let mut planner = CztPlanner::new();
+    let czt = planner.plan_czt_forward(
+        n,
+        m,
+        Complex::from_polar(1.0, 0.3),
+        Complex::from_polar(1.0, -0.01),
+    );
+    let mut expected = widened.clone();
+    expected.resize(czt.get_buffer_len(), Complex::default());
+    czt.process(&mut expected);
+
+    let half_czt = HalfCzt::new(czt);
+    let mut output = vec![f16::ZERO; 2 * m];
+    half_czt.process(&packed, &mut output);
+    let mut actual = vec![Complex::default(); m];
+    widen(&output, &mut actual);
+    for (ex, ac) in expected.iter().zip(&actual) {
+        // f16 keeps 11 significant bits.
+        assert!((ex - ac).norm() < 1e-3 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+}
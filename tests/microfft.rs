@@ -0,0 +1,31 @@
+#![cfg(feature = "microfft")]
+
+use rustczt::{microfft_backend::MicroFftProvider, ConvolutionLength, CztPlanner, PlannerStrategy};
+use rustfft::{num_complex::Complex, FftPlanner};
+
+#[test]
+fn test_microfft_backend_matches_rustfft() {
+    let signal: Vec<_> = (0..256)
+        .map(|i| Complex::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.005);
+
+    let mut reference = CztPlanner::with_fft_provider(FftPlanner::new());
+    reference.set_strategy(PlannerStrategy::ForceBluestein);
+    let mut expected = signal.clone();
+    reference
+        .plan_czt_forward(256, 64, a, w)
+        .process(&mut expected);
+
+    let mut planner = CztPlanner::with_fft_provider(MicroFftProvider);
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    planner.set_convolution_length(ConvolutionLength::PowerOfTwo);
+    let czt_obj = planner.plan_czt_forward(256, 64, a, w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    for (ex, ac) in expected.iter().zip(&actual).take(64) {
+        assert!((ex - ac).norm() < 1e-4 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "mkl")]
+
+use rustczt::{mkl_backend::MklProvider, naive_czt::NaiveCzt, Czt, CztPlanner, PlannerStrategy};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_mkl_backend_accuracy() {
+    let signal: Vec<_> = (0..100)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let mut expected = signal.clone();
+    NaiveCzt::new(signal.len(), signal.len(), a, w).process(&mut expected);
+
+    let mut planner = CztPlanner::with_fft_provider(MklProvider::new());
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(signal.len(), signal.len(), a, w);
+    let mut actual = signal.clone();
+    czt_obj.process(&mut actual);
+
+    for (ex, ac) in expected.iter().zip(&actual) {
+        assert!((ex - ac).norm() < 1e-8, "{ex} != {ac}");
+    }
+}
+
+#[test]
+fn test_mkl_batched_accuracy() {
+    let (n, count) = (100, 8);
+    let signals: Vec<_> = (0..n * count)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let mut planner = CztPlanner::with_fft_provider(MklProvider::new());
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(n, n, a, w);
+    let mut actual = signals.clone();
+    czt_obj.process_batch(&mut actual, n, count);
+
+    for (signal, actual) in signals.chunks(n).zip(actual.chunks(n)) {
+        let mut expected = signal.to_vec();
+        NaiveCzt::new(n, n, a, w).process(&mut expected);
+        for (ex, ac) in expected.iter().zip(actual) {
+            assert!((ex - ac).norm() < 1e-8, "{ex} != {ac}");
+        }
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "uniffi")]
+
+use rustczt::{
+    mobile::{ZoomError, ZoomPlan},
+    Czt, CztPlanner,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_zoom_plan_round_trips_bytes() {
+    let (n, m, fs) = (256, 64, 8000.0);
+    let samples: Vec<f32> = (0..n)
+        .map(|j| (core::f32::consts::TAU * 1000.0 * j as f32 / fs).sin())
+        .collect();
+    let bytes: Vec<u8> = samples.iter().flat_map(|x| x.to_le_bytes()).collect();
+    let plan = ZoomPlan::new(n as u32, m as u32, fs, 900.0, 1100.0).unwrap();
+    assert_eq!((plan.input_len(), plan.output_len()), (n as u32, m as u32));
+
+    let expected_plan = CztPlanner::new().plan_zoom_fft_hz(n, m, fs, 900.0, 1100.0);
+    let mut expected: Vec<_> = samples.iter().map(|&x| Complex::new(x, 0.0)).collect();
+    expected.resize(expected_plan.get_buffer_len(), Complex::new(0.0, 0.0));
+    expected_plan.process(&mut expected);
+
+    // The plan's buffer must not carry anything over between calls.
+    for _ in 0..2 {
+        let bins = plan.process(bytes.clone()).unwrap();
+        assert_eq!(bins.len(), 8 * m);
+        let bins: Vec<f32> = bins
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        for (bin, pair) in expected.iter().zip(bins.chunks(2)) {
+            assert_eq!([bin.re, bin.im], pair);
+        }
+    }
+    assert_eq!(plan.frequencies(), expected_plan.freqs());
+
+    assert_eq!(
+        plan.process(bytes[..10].to_vec()),
+        Err(ZoomError::SampleBytes {
+            expected: n as u32,
+            actual: 10
+        })
+    );
+    assert!(matches!(
+        ZoomPlan::new(0, m as u32, fs, 900.0, 1100.0),
+        Err(ZoomError::InvalidPlan { .. })
+    ));
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "nalgebra")]
+
+use nalgebra::{DMatrix, DVector};
+use rustczt::{
+    nalgebra_ext::{CztMatrixExt, CztVectorExt},
+    CztPlanner,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_nalgebra_columns_match_process() {
+    let mut planner = CztPlanner::new();
+    let (rows, cols) = (12, 5);
+    let input = DMatrix::from_fn(rows, cols, |i, j| {
+        Complex::new(
+            (i as f64 * 0.7 + j as f64).sin(),
+            (i as f64 * 0.2 - j as f64).cos(),
+        )
+    });
+    for m in [7, 12, 20] {
+        let czt = planner.plan_czt_forward(
+            rows,
+            m,
+            Complex::from_polar(1.0, 0.3),
+            Complex::from_polar(0.999, -0.06),
+        );
+        let output = input.czt_columns(czt.as_ref());
+        assert_eq!(output.shape(), (m, cols));
+
+        for (column, bins) in input.column_iter().zip(output.column_iter()) {
+            let mut expected = column.iter().copied().collect::<Vec<_>>();
+            expected.resize(czt.get_buffer_len(), Complex::default());
+            czt.process(&mut expected);
+            for (actual, expected) in bins.iter().zip(&expected[..m]) {
+                assert!((actual - expected).norm() < 1e-12 * expected.norm().max(1.0));
+            }
+
+            let vector = DVector::from_iterator(rows, column.iter().copied());
+            assert_eq!(
+                vector.czt(czt.as_ref()),
+                DVector::from_column_slice(bins.as_slice())
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "ndarray")]
+
+use ndarray::{Array2, Axis};
+use rustczt::{
+    ndarray_axis::{czt_axis, czt_axis_inplace, zoom_axis},
+    CztPlanner,
+};
+use rustfft::num_complex::Complex;
+
+fn matrix(rows: usize, cols: usize) -> Array2<Complex<f64>> {
+    Array2::from_shape_fn((rows, cols), |(i, j)| {
+        Complex::new(
+            (i as f64 * 0.7 + j as f64).sin(),
+            (i as f64 * 0.2 - j as f64).cos(),
+        )
+    })
+}
+
+#[test]
+fn test_czt_axis_matches_lanes() {
+    let mut planner = CztPlanner::new();
+    let (rows, cols, m) = (12, 7, 9);
+    let czt = planner.plan_czt_forward(
+        rows,
+        m,
+        Complex::from_polar(1.0, 0.3),
+        Complex::from_polar(0.999, -0.06),
+    );
+    let input = matrix(rows, cols);
+
+    let output = czt_axis(czt.as_ref(), input.view(), Axis(0));
+    assert_eq!(output.dim(), (m, cols));
+    for (column, bins) in input.columns().into_iter().zip(output.columns()) {
+        let mut expected = column.to_vec();
+        expected.resize(czt.get_buffer_len(), Complex::default());
+        czt.process(&mut expected);
+        assert_eq!(bins.to_vec(), expected[..m]);
+    }
+
+    // Rows are contiguous, columns are not.
+    let transposed = input.t().to_owned();
+    let rows_output = czt_axis(czt.as_ref(), transposed.view(), Axis(1));
+    assert_eq!(rows_output, output.t());
+
+    let square = planner.plan_czt_forward(
+        rows,
+        rows,
+        Complex::from_polar(1.0, 0.3),
+        Complex::from_polar(1.0, -0.06),
+    );
+    let expected = czt_axis(square.as_ref(), input.view(), Axis(0));
+    for (mut array, axis) in [(input.clone(), Axis(0)), (transposed, Axis(1))] {
+        czt_axis_inplace(square.as_ref(), array.view_mut(), axis);
+        if axis == Axis(0) {
+            assert_eq!(array, expected);
+        } else {
+            assert_eq!(array, expected.t());
+        }
+    }
+}
+
+#[test]
+fn test_zoom_axis_reports_freqs() {
+    let zoom = CztPlanner::new().plan_zoom_fft_hz(32, 8, 1000.0, 100.0, 200.0);
+    let input = matrix(3, 32);
+    let (output, freqs) = zoom_axis(&zoom, input.view(), Axis(1));
+    assert_eq!(output.dim(), (3, 8));
+    assert_eq!(freqs, zoom.freqs());
+    assert_eq!(output, czt_axis(&*zoom, input.view(), Axis(1)));
+}
@@ -0,0 +1,44 @@
+use std::{path::Path, process::Command};
+
+/// Builds the library for `aarch64-unknown-none`, which has no `std` to fall back on, so any
+/// dependency needing it fails the build. Skipped when the target is not installed.
+fn build_bare_metal(features: &str) {
+    const TARGET: &str = "aarch64-unknown-none";
+    let libdir = Command::new("rustc")
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output()
+        .unwrap();
+    let libdir = String::from_utf8(libdir.stdout).unwrap();
+    if !Path::new(libdir.trim()).exists() {
+        eprintln!("skipping: {TARGET} is not installed");
+        return;
+    }
+
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            features,
+        ])
+        .args(["--target", TARGET, "--target-dir"])
+        .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "bare-metal build with features {features:?} failed"
+    );
+}
+
+#[test]
+fn test_builds_without_std() {
+    build_bare_metal("half ndarray nalgebra bytemuck serde rkyv reference");
+}
+
+#[test]
+fn test_builds_with_microfft_without_std() {
+    build_bare_metal("microfft");
+}
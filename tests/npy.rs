@@ -0,0 +1,127 @@
+#![cfg(feature = "npy")]
+
+use rustczt::{
+    naive_czt::NaiveCzt,
+    npy::{read_npy, read_npz, write_npy, write_npz, NpyArray},
+    Czt,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_npy_round_trips() {
+    let data: Vec<_> = (0..7)
+        .map(|j| Complex::new(j as f64 * 0.25, -(j as f64) / 3.0))
+        .collect();
+    let array = NpyArray::from_vec(data.clone());
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &array).unwrap();
+    assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    assert!((10 + header_len).is_multiple_of(64));
+    assert_eq!(bytes.len(), 10 + header_len + 7 * 16);
+    assert_eq!(read_npy::<f64, _>(&bytes[..]).unwrap(), array);
+
+    // Narrowing to f32 writes `c8`.
+    let single = NpyArray {
+        shape: vec![7, 1],
+        data: data
+            .iter()
+            .map(|x| Complex::new(x.re as f32, x.im as f32))
+            .collect(),
+    };
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &single).unwrap();
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+    assert!(header.contains("'descr': '<c8'") && header.contains("'shape': (7, 1)"));
+    assert_eq!(read_npy::<f32, _>(&bytes[..]).unwrap(), single);
+}
+
+#[test]
+fn test_npy_reads_numpy_headers() {
+    // As `numpy.save` writes `np.array([1.5, -2.0], dtype='>f8')`.
+    let mut bytes = b"\x93NUMPY\x01\x00\x76\x00".to_vec();
+    let mut header = "{'descr': '>f8', 'fortran_order': False, 'shape': (2,), }".to_string();
+    header.extend(std::iter::repeat_n(' ', 128 - 10 - header.len() - 1));
+    header.push('\n');
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(&1.5f64.to_be_bytes());
+    bytes.extend_from_slice(&(-2.0f64).to_be_bytes());
+    let array = read_npy::<f64, _>(&bytes[..]).unwrap();
+    assert_eq!(array.shape, [2]);
+    assert_eq!(
+        array.data,
+        [Complex::new(1.5, 0.0), Complex::new(-2.0, 0.0)]
+    );
+
+    bytes[20..23].copy_from_slice(b"i8'");
+    assert!(read_npy::<f64, _>(&bytes[..]).is_err());
+}
+
+#[test]
+fn test_npz_round_trips_czt() {
+    let (n, m) = (16, 24);
+    let a = Complex::from_polar(1.0, 0.2);
+    let w = Complex::from_polar(1.0, -0.05);
+    let input: Vec<_> = (0..n)
+        .map(|j| Complex::new((j as f64).cos(), (j as f64 * 0.5).sin()))
+        .collect();
+    let mut spectrum = input.clone();
+    spectrum.resize(m, Complex::new(0.0, 0.0));
+    NaiveCzt::new(n, m, a, w).process(&mut spectrum);
+
+    let (x, y) = (NpyArray::from_vec(input), NpyArray::from_vec(spectrum));
+    let mut archive = Vec::new();
+    write_npz(&mut archive, &[("x", &x), ("y", &y)]).unwrap();
+    let arrays = read_npz::<f64, _>(&archive[..]).unwrap();
+    assert_eq!(arrays, [("x".to_string(), x), ("y".to_string(), y)]);
+    assert!(read_npz::<f64, _>(&[][..]).is_err());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_round_trips_npy() {
+    let (n, m, fs) = (64, 33, 1000.0);
+    let input: Vec<_> = (0..n)
+        .map(|j| Complex::from_polar(1.0, 0.3 * j as f64))
+        .collect();
+    let dir = std::env::temp_dir();
+    let (input_path, output_path) = (
+        dir.join(format!("rustczt-npy-{}-x.npy", std::process::id())),
+        dir.join(format!("rustczt-npy-{}-y.npy", std::process::id())),
+    );
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &NpyArray::from_vec(input.clone())).unwrap();
+    std::fs::write(&input_path, bytes).unwrap();
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_rustczt"))
+        .args([
+            "--fs",
+            "1000",
+            "--f-start",
+            "-100",
+            "--f-end",
+            "100",
+            "--bins",
+            "33",
+        ])
+        .args(["--format", "npy", "-o"])
+        .args([&output_path, &input_path])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = read_npy::<f64, _>(&std::fs::read(&output_path).unwrap()[..]).unwrap();
+
+    let step = 200.0 / fs / (m - 1) as f64;
+    let a = Complex::from_polar(1.0, std::f64::consts::TAU * -100.0 / fs);
+    let w = Complex::from_polar(1.0, -std::f64::consts::TAU * step);
+    let mut expected = input;
+    NaiveCzt::new(n, m, a, w).process(&mut expected);
+    assert_eq!(output.shape, [m]);
+    for (ex, ac) in expected.iter().zip(&output.data) {
+        assert!((ex - ac).norm() < 1e-9 * ex.norm().max(1.0), "{ex} != {ac}");
+    }
+    for path in [input_path, output_path] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
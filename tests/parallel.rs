@@ -0,0 +1,83 @@
+#![cfg(feature = "rayon")]
+
+use rustczt::{naive_czt::NaiveCzt, Czt, CztPlanner, PlannerStrategy};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_parallel_batch_accuracy() {
+    let (n, count) = (120, 37);
+    let signals: Vec<Vec<_>> = (0..count)
+        .map(|s| {
+            (0..n)
+                .map(|i| Complex::new((i as f64 * 0.37 + s as f64).sin(), (i as f64 * 0.11).cos()))
+                .collect()
+        })
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.02);
+
+    let mut planner = CztPlanner::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    let czt_obj = planner.plan_czt_forward(n, n, a, w);
+    let mut actual = signals.clone();
+    let mut views: Vec<&mut [Complex<f64>]> = actual.iter_mut().map(|s| s.as_mut_slice()).collect();
+    czt_obj.process_batch_parallel(&mut views);
+
+    let reference = NaiveCzt::new(n, n, a, w);
+    for (signal, actual) in signals.iter().zip(&actual) {
+        let mut expected = signal.clone();
+        reference.process(&mut expected);
+        for (ex, ac) in expected.iter().zip(actual) {
+            assert!((ex - ac).norm() < 1e-8, "{ex} != {ac}");
+        }
+    }
+}
+
+#[test]
+fn test_parallel_czt2_matches_sequential() {
+    let (rows, cols) = (70, 45);
+    let signal: Vec<_> = (0..rows * cols)
+        .map(|i| Complex::new((i as f64 * 0.013).sin(), (i as f64 * 0.007).cos()))
+        .collect();
+    let a_row = Complex::from_polar(1.0, 0.4);
+    let w_row = Complex::from_polar(1.0, -0.05);
+    let a_col = Complex::from_polar(1.0, -0.2);
+    let w_col = Complex::from_polar(1.0, -0.1);
+
+    let mut planner = CztPlanner::new();
+    let czt_obj = planner.plan_czt2_forward(rows, cols, a_row, w_row, a_col, w_col);
+    let mut expected = signal.clone();
+    czt_obj.process(&mut expected);
+    let mut actual = signal.clone();
+    czt_obj.process_parallel(&mut actual);
+
+    for (ex, ac) in expected.iter().zip(&actual) {
+        assert!((ex - ac).norm() < 1e-9, "{ex} != {ac}");
+    }
+}
+
+#[test]
+fn test_parallel_naive_matches_bluestein() {
+    let (n, m) = (50_000, 400);
+    let signal: Vec<_> = (0..n)
+        .map(|i| Complex::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+        .collect();
+    let a = Complex::from_polar(1.0, 0.3);
+    let w = Complex::from_polar(1.0, -0.0005);
+
+    let mut expected = signal.clone();
+    let mut planner = CztPlanner::new();
+    planner.set_strategy(PlannerStrategy::ForceBluestein);
+    planner.plan_czt_forward(n, m, a, w).process(&mut expected);
+    let mut actual = signal.clone();
+    NaiveCzt::new(n, m, a, w).process(&mut actual);
+    // Both round the contour points, which perturbs the bins by about `n` ulps of the input sum.
+    for (ex, ac) in expected.iter().zip(&actual).take(m) {
+        assert!((ex - ac).norm() < 1e-10 * n as f64, "{ex} != {ac}");
+    }
+
+    // Too little work to split: the first bin is evaluated the same way on one thread.
+    let mut single = signal.clone();
+    NaiveCzt::new(n, 1, a, w).process(&mut single);
+    assert_eq!(single[0], actual[0]);
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "python")]
+
+use numpy::{PyArray1, PyArrayMethods};
+use pyo3::{prelude::*, types::PyTuple};
+use rustczt::{compat::scipy, python::extension_module};
+use rustfft::num_complex::Complex;
+
+fn signal(n: usize) -> Vec<Complex<f64>> {
+    (0..n)
+        .map(|j| Complex::new((j as f64 * 0.3).cos(), (j as f64 * 0.7).sin()))
+        .collect()
+}
+
+fn to_vec(array: Bound<'_, PyAny>) -> Vec<Complex<f64>> {
+    array
+        .cast_into::<PyArray1<Complex<f64>>>()
+        .unwrap()
+        .to_vec()
+        .unwrap()
+}
+
+#[test]
+fn test_python_module_matches_scipy_compat() {
+    Python::initialize();
+    Python::attach(|py| {
+        // The arrays crossing the module boundary need NumPy's C API.
+        if py.import("numpy").is_err() {
+            eprintln!("skipping: numpy is not installed");
+            return;
+        }
+        let module = PyModule::new(py, "_rustczt").unwrap();
+        extension_module(&module).unwrap();
+        let x = signal(48);
+        let array = PyArray1::from_slice(py, &x);
+        let w = Complex::from_polar(1.0, -0.05);
+
+        let bins = module
+            .getattr("czt")
+            .unwrap()
+            .call1((&array, 20, w))
+            .unwrap();
+        assert_eq!(to_vec(bins), scipy::czt(&x, Some(20), Some(w), None));
+
+        let band = PyTuple::new(py, [0.1, 0.3]).unwrap();
+        let zoom = module
+            .getattr("ZoomFFT")
+            .unwrap()
+            .call1((48, band, 16))
+            .unwrap();
+        let expected = scipy::ZoomFft::new(48, (0.1, 0.3), Some(16), 2.0, false);
+        assert_eq!(to_vec(zoom.call1((&array,)).unwrap()), expected.call(&x));
+        // The wrong length is a ValueError rather than a panic.
+        let short = PyArray1::from_slice(py, &x[..10]);
+        assert!(zoom
+            .call1((short,))
+            .unwrap_err()
+            .is_instance_of::<pyo3::exceptions::PyValueError>(py));
+
+        let planner = module.getattr("CztPlanner").unwrap().call0().unwrap();
+        let a = Complex::from_polar(1.0, 0.2);
+        let czt = planner
+            .call_method1("plan_czt_forward", (48, 20, a, w))
+            .unwrap();
+        assert_eq!(
+            czt.getattr("output_len")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap(),
+            20
+        );
+        let bins = to_vec(czt.call_method1("process", (&array,)).unwrap());
+        let expected = scipy::czt(&x, Some(20), Some(w), Some(a));
+        for (bin, expected) in bins.iter().zip(&expected) {
+            assert!((bin - expected).norm() < 1e-12 * expected.norm().max(1.0));
+        }
+    });
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "reference")]
+
+use rustczt::{
+    double_double::DoubleDoubleCzt,
+    error_report::ErrorReport,
+    naive_czt::NaiveCzt,
+    reference::{czt, ReferenceCzt},
+    Czt,
+};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_reference_matches_closed_form_and_double_double() {
+    // A constant input sums to the geometric series `(1 - z^-n) / (1 - z^-1)`.
+    let (n, m) = (500, 300);
+    let (a, w): (Complex<f64>, _) = (
+        Complex::from_polar(0.99, 0.4),
+        Complex::from_polar(1.0002, -0.013),
+    );
+    let ones = vec![Complex::new(1.0, 0.0); n];
+    let output = czt(&ones, m, a, w);
+    for (k, &x) in output.iter().enumerate() {
+        let z_inv = (a * w.powi(-(k as i32))).inv();
+        let expected = (Complex::new(1.0, 0.0) - z_inv.powi(n as i32)) / (1.0 - z_inv);
+        assert!((x - expected).norm() <= 1e-10 * expected.norm(), "bin {k}");
+    }
+
+    let signal: Vec<_> = (0..n)
+        .map(|j| Complex::new((j as f64 * 0.37).sin(), (j as f64 * 0.11).cos()))
+        .collect();
+    let mut expected = signal.clone();
+    DoubleDoubleCzt::new(n, m, a, w).process(&mut expected);
+    let mut actual = signal.clone();
+    ReferenceCzt::new(n, m, a, w).process(&mut actual);
+    let peak = expected.iter().map(|x| x.norm()).fold(0.0, f64::max);
+    let report = ErrorReport::compare(&expected[..m], &actual[..m]);
+    assert!(report.max_abs_error <= 1e-15 * peak, "{report}");
+}
+
+#[test]
+fn test_reference_beats_naive_in_single_precision() {
+    let (n, m) = (2000, 400);
+    let (a, w) = (
+        Complex::from_polar(1.0, 0.3),
+        Complex::from_polar(1.0, -0.003),
+    );
+    let signal: Vec<_> = (0..n)
+        .map(|j| Complex::new((j as f32 * 0.37).sin(), (j as f32 * 0.11).cos()))
+        .collect();
+    let widen = |z: Complex<f32>| Complex::new(z.re as f64, z.im as f64);
+    let mut exact: Vec<_> = signal.iter().map(|&x| widen(x)).collect();
+    ReferenceCzt::new(n, m, widen(a), widen(w)).process(&mut exact);
+
+    let error = |czt: &dyn Czt<f32>| {
+        let mut buffer = signal.clone();
+        czt.process(&mut buffer);
+        let widened: Vec<_> = buffer[..m].iter().map(|&x| widen(x)).collect();
+        ErrorReport::compare(&exact[..m], &widened).max_abs_error
+    };
+    let reference = error(&ReferenceCzt::new(n, m, a, w));
+    let naive = error(&NaiveCzt::new(n, m, a, w));
+    // The reference rounds once, to half an f32 ulp of each bin.
+    assert!(reference <= 1e-7 * exact.iter().map(|x| x.norm()).fold(0.0, f64::max));
+    assert!(reference * 10.0 < naive, "{reference} {naive}");
+}
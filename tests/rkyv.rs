@@ -0,0 +1,64 @@
+#![cfg(feature = "rkyv")]
+
+use rustczt::{
+    bluesteins::BluesteinsAlgorithm,
+    saved_plan::{access, to_bytes},
+    Czt,
+};
+use rustfft::{num_complex::Complex, FftPlanner};
+
+fn plan(k0: isize) -> BluesteinsAlgorithm<f32> {
+    let window: Vec<_> = (0..40).map(|j| 1.0 - j as f32 / 80.0).collect();
+    BluesteinsAlgorithm::new(
+        40,
+        25,
+        Complex::from_polar(1.01, 0.4),
+        Complex::from_polar(0.999, -0.03),
+        k0,
+        &mut FftPlanner::new(),
+    )
+    .with_window(&window)
+    .with_output_scale(0.5)
+    .with_delay(0.25)
+}
+
+fn transform(czt: &dyn Czt<f32>) -> Vec<Complex<f32>> {
+    let mut buffer: Vec<_> = (0..czt.get_buffer_len())
+        .map(|j| Complex::new((j as f32 * 0.3).cos(), (j as f32 * 0.7).sin()))
+        .collect();
+    czt.process(&mut buffer);
+    buffer
+}
+
+#[test]
+fn test_archived_plan_loads_in_place() {
+    for k0 in [0, 7] {
+        let original = plan(k0);
+        let expected = transform(&original);
+
+        let bytes = to_bytes(&original.to_saved(true));
+        let archived = access::<f32>(&bytes).unwrap();
+        assert!(archived.is_compatible());
+        let tables = archived.tables.as_ref().unwrap();
+        assert_eq!(&tables.y[..], original.y_coefficients());
+        let restored =
+            BluesteinsAlgorithm::from_archived(archived, &mut FftPlanner::new()).unwrap();
+        assert_eq!(restored.params(), original.params());
+        assert_eq!(transform(&restored), expected);
+
+        let bytes = to_bytes(&original.to_saved(false));
+        let archived = access::<f32>(&bytes).unwrap();
+        assert!(archived.tables.is_none());
+        let replanned =
+            BluesteinsAlgorithm::from_archived(archived, &mut FftPlanner::new()).unwrap();
+        for (ex, ac) in expected.iter().zip(transform(&replanned)) {
+            assert!((ex - ac).norm() < 1e-5 * ex.norm().max(1.0), "{ex} != {ac}");
+        }
+    }
+}
+
+#[test]
+fn test_access_rejects_corrupt_archives() {
+    let bytes = to_bytes(&plan(0).to_saved(true));
+    assert!(access::<f32>(&bytes[..bytes.len() / 2]).is_none());
+}
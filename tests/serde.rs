@@ -0,0 +1,121 @@
+#![cfg(feature = "serde")]
+
+use rustczt::{
+    bluesteins::BluesteinsAlgorithm,
+    saved_plan::{LoadError, SavedPlan, FORMAT_VERSION},
+    Czt, PlanError,
+};
+use rustfft::{num_complex::Complex, FftPlanner};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn is_serde<S: Serialize + DeserializeOwned>() {}
+
+fn plan(k0: isize) -> BluesteinsAlgorithm<f64> {
+    let window: Vec<_> = (0..40).map(|j| 1.0 - j as f64 / 80.0).collect();
+    BluesteinsAlgorithm::new(
+        40,
+        25,
+        Complex::from_polar(1.01, 0.4),
+        Complex::from_polar(0.999, -0.03),
+        k0,
+        &mut FftPlanner::new(),
+    )
+    .with_window(&window)
+    .with_output_scale(0.5)
+    .with_delay(0.25)
+}
+
+fn transform(czt: &dyn Czt<f64>) -> Vec<Complex<f64>> {
+    let mut buffer: Vec<_> = (0..czt.get_buffer_len())
+        .map(|j| Complex::new((j as f64 * 0.3).cos(), (j as f64 * 0.7).sin()))
+        .collect();
+    czt.process(&mut buffer);
+    buffer
+}
+
+#[test]
+fn test_saved_plan_restores() {
+    is_serde::<SavedPlan<f32>>();
+    is_serde::<SavedPlan<f64>>();
+
+    for k0 in [0, 7] {
+        let original = plan(k0);
+        let expected = transform(&original);
+
+        let saved = original.to_saved(true);
+        assert_eq!(saved.version, FORMAT_VERSION);
+        let restored = BluesteinsAlgorithm::from_saved(&saved, &mut FftPlanner::new()).unwrap();
+        assert_eq!(restored.y_coefficients(), original.y_coefficients());
+        assert_eq!(restored.params(), original.params());
+        assert_eq!(transform(&restored), expected);
+
+        let descriptor = saved.descriptor_only();
+        assert!(descriptor.tables.is_none());
+        let replanned =
+            BluesteinsAlgorithm::from_saved(&descriptor, &mut FftPlanner::new()).unwrap();
+        // Moving `a` to bin `k0` and back may round it.
+        let resaved = replanned.to_saved(false);
+        assert!((resaved.params.a - descriptor.params.a).norm() < 1e-15);
+        let resaved = SavedPlan {
+            params: descriptor.params,
+            ..resaved
+        };
+        assert_eq!(resaved, descriptor);
+        for (ex, ac) in expected.iter().zip(transform(&replanned)) {
+            assert!(
+                (ex - ac).norm() < 1e-12 * ex.norm().max(1.0),
+                "{ex} != {ac}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_saved_plan_rejects_invalid_plans() {
+    let load = |saved: &SavedPlan<f64>| {
+        BluesteinsAlgorithm::from_saved(saved, &mut FftPlanner::new()).map(|_| ())
+    };
+    let mut saved = plan(0).to_saved(false);
+    saved.version += 1;
+    assert!(!saved.is_compatible());
+    assert_eq!(
+        load(&saved),
+        Err(LoadError::Version {
+            found: FORMAT_VERSION + 1
+        })
+    );
+
+    let saved = plan(0).to_saved(true);
+    let mut empty = saved.clone();
+    (empty.params.n, empty.params.m) = (0, 0);
+    assert_eq!(
+        load(&empty),
+        Err(LoadError::Params(PlanError::ZeroLength { name: "n" }))
+    );
+
+    let short = SavedPlan {
+        convolution_len: 63,
+        ..saved.clone()
+    };
+    assert_eq!(
+        load(&short),
+        Err(LoadError::ConvolutionLength { len: 63, min: 64 })
+    );
+
+    let mut truncated = saved.clone();
+    truncated.tables.as_mut().unwrap().x.pop();
+    assert_eq!(
+        load(&truncated),
+        Err(LoadError::TableLength {
+            name: "x",
+            expected: 25,
+            actual: 24
+        })
+    );
+    let mut window = saved.descriptor_only();
+    window.window.as_mut().unwrap().push(1.0);
+    assert_eq!(
+        load(&window).unwrap_err().to_string(),
+        "saved window has length 41, expected 40"
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "tokio")]
+
+use std::{future::poll_fn, pin::Pin, sync::Arc};
+
+use futures_core::Stream;
+use rustczt::{
+    async_czt::{process_async, AsyncCztStream},
+    naive_czt::NaiveCzt,
+    stream::{CztStream, SampleFormat},
+    Czt,
+};
+use rustfft::num_complex::Complex;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+fn plan(n: usize, m: usize) -> Arc<dyn Czt<f64>> {
+    Arc::new(NaiveCzt::new(
+        n,
+        m,
+        Complex::from_polar(1.0, 0.1),
+        Complex::from_polar(1.0, -0.04),
+    ))
+}
+
+fn signal(len: usize) -> Vec<Complex<f64>> {
+    (0..len)
+        .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.17).cos()))
+        .collect()
+}
+
+#[test]
+fn test_process_async_matches_batch() {
+    let czt = plan(24, 30);
+    let buffers = signal(3 * czt.get_buffer_len());
+    let mut expected = buffers.clone();
+    czt.process_batch(&mut expected, czt.get_buffer_len(), 3);
+
+    let actual = runtime().block_on(process_async(czt, buffers));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_async_stream_yields_frames() {
+    let (n, hop) = (16, 10);
+    let czt = plan(n, 8);
+    let samples = signal(75);
+    let bytes: Vec<u8> = samples
+        .iter()
+        .flat_map(|x| [x.re.to_le_bytes(), x.im.to_le_bytes()])
+        .flatten()
+        .collect();
+    let expected: Vec<_> = CztStream::new(&bytes[..], czt.clone(), SampleFormat::ComplexF64, hop)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(expected.len(), 6);
+
+    let reader = std::io::Cursor::new(bytes);
+    let mut stream =
+        AsyncCztStream::new(CztStream::new(reader, czt, SampleFormat::ComplexF64, hop));
+    let actual = runtime().block_on(async {
+        let mut spectra = Vec::new();
+        while let Some(spectrum) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            spectra.push(spectrum.unwrap());
+        }
+        spectra
+    });
+    assert_eq!(actual, expected);
+}
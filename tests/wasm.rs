@@ -0,0 +1,29 @@
+#![cfg(feature = "wasm")]
+
+use rustczt::{wasm::WasmZoomFft, Czt, CztPlanner};
+use rustfft::num_complex::Complex;
+
+#[test]
+fn test_wasm_zoom_fft_interleaves_bins() {
+    let (n, m, fs) = (256, 64, 8000.0);
+    let samples: Vec<f32> = (0..n)
+        .map(|j| (core::f32::consts::TAU * 1000.0 * j as f32 / fs).sin())
+        .collect();
+    let mut zoom = WasmZoomFft::new(n, m, fs, 900.0, 1100.0);
+    assert_eq!((zoom.input_len(), zoom.output_len()), (n, m));
+
+    let expected_plan = CztPlanner::new().plan_zoom_fft_hz(n, m, fs, 900.0, 1100.0);
+    let mut expected: Vec<_> = samples.iter().map(|&x| Complex::new(x, 0.0)).collect();
+    expected.resize(expected_plan.get_buffer_len(), Complex::new(0.0, 0.0));
+    expected_plan.process(&mut expected);
+
+    // The reused buffer must not carry anything over between calls.
+    for _ in 0..2 {
+        let interleaved = zoom.process(&samples);
+        assert_eq!(interleaved.len(), 2 * m);
+        for (bin, pair) in expected.iter().zip(interleaved.chunks(2)) {
+            assert_eq!([bin.re, bin.im], pair);
+        }
+    }
+    assert_eq!(zoom.frequencies(), expected_plan.freqs());
+}